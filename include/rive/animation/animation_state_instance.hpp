@@ -21,6 +21,9 @@ public:
 
     void advance(float seconds, Span<SMIInput*>) override;
     void apply(float mix) override;
+    void collectApplied(float mix,
+                        size_t layer,
+                        std::vector<AppliedAnimation>& applied) const override;
 
     bool keepGoing() const override;
     void clearSpilledTime() override;
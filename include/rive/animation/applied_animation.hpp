@@ -0,0 +1,21 @@
+#ifndef _RIVE_APPLIED_ANIMATION_HPP_
+#define _RIVE_APPLIED_ANIMATION_HPP_
+
+#include <cstddef>
+
+namespace rive
+{
+class LinearAnimation;
+
+/// An animation a state machine applied during its last advance, at the
+/// time and mix it was applied with. The mix includes the mix of the layer's
+/// transition, so both sides of a transition in progress show up weighted.
+struct AppliedAnimation
+{
+    const LinearAnimation* animation;
+    float time;
+    float mix;
+    size_t layer;
+};
+} // namespace rive
+#endif
@@ -76,6 +76,19 @@ public:
         }
     }
 
+    void collectApplied(float mix,
+                        size_t layer,
+                        std::vector<AppliedAnimation>& applied) const override
+    {
+        for (auto& animation : m_AnimationInstances)
+        {
+            applied.push_back({animation.m_AnimationInstance.animation(),
+                               animation.m_AnimationInstance.time(),
+                               mix * animation.m_Mix,
+                               layer});
+        }
+    }
+
     // Find the animationInstance that corresponds to the blendAnimation.
     const LinearAnimationInstance* animationInstance(const BlendAnimation* blendAnimation) const
     {
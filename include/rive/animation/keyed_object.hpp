@@ -15,6 +15,11 @@ public:
     KeyedObject();
     ~KeyedObject() override;
     void addKeyedProperty(std::unique_ptr<KeyedProperty>);
+    size_t keyedPropertyCount() const { return m_KeyedProperties.size(); }
+    const KeyedProperty* keyedProperty(size_t index) const
+    {
+        return m_KeyedProperties[index].get();
+    }
 
     StatusCode onAddedDirty(CoreContext* context) override;
     StatusCode onAddedClean(CoreContext* context) override;
@@ -14,6 +14,8 @@ public:
     KeyedProperty();
     ~KeyedProperty() override;
     void addKeyFrame(std::unique_ptr<KeyFrame>);
+    size_t keyFrameCount() const { return m_KeyFrames.size(); }
+    const KeyFrame* keyFrame(size_t index) const { return m_KeyFrames[index].get(); }
     StatusCode onAddedClean(CoreContext* context) override;
     StatusCode onAddedDirty(CoreContext* context) override;
 
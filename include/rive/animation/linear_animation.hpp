@@ -21,6 +21,8 @@ public:
     StatusCode onAddedDirty(CoreContext* context) override;
     StatusCode onAddedClean(CoreContext* context) override;
     void addKeyedObject(std::unique_ptr<KeyedObject>);
+    size_t keyedObjectCount() const { return m_KeyedObjects.size(); }
+    const KeyedObject* keyedObject(size_t index) const { return m_KeyedObjects[index].get(); }
     void apply(Artboard* artboard, float time, float mix = 1.0f) const;
 
     Loop loop() const { return (Loop)loopValue(); }
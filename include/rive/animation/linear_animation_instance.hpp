@@ -81,6 +81,19 @@ public:
     // Override the animation's default loop
     void loopValue(int value);
 
+    // Restricts playback to the given range in seconds, overriding the work
+    // area. A range always repeats: it ping-pongs if the loop does, and loops
+    // otherwise. Returns false if the range had to be clamped, e.g. when it's
+    // narrower than a frame, or if the animation has no frames (0 fps), in
+    // which case no range is set.
+    bool playRange(float startSeconds, float endSeconds);
+    void clearPlayRange() { m_hasPlayRange = false; }
+    bool hasPlayRange() const { return m_hasPlayRange; }
+
+    // The bounds playback wraps at, either the play range or the work area.
+    float playStartSeconds() const;
+    float playEndSeconds() const;
+
     bool isTranslucent() const override;
     bool advanceAndApply(float seconds) override;
     std::string name() const override;
@@ -97,6 +110,9 @@ private:
     float m_direction;
     bool m_didLoop;
     int m_loopValue = -1;
+    bool m_hasPlayRange = false;
+    float m_playStart = 0.0f;
+    float m_playEnd = 0.0f;
 };
 } // namespace rive
 #endif
@@ -3,6 +3,8 @@
 
 #include <string>
 #include <stddef.h>
+#include <vector>
+#include "rive/animation/applied_animation.hpp"
 #include "rive/rive_types.hpp"
 #include "rive/span.hpp"
 
@@ -24,6 +26,12 @@ public:
     virtual void advance(float seconds, Span<SMIInput*> inputs) = 0;
     virtual void apply(float mix) = 0;
 
+    /// Appends the animations apply(mix) applies, for introspection.
+    virtual void collectApplied(float mix,
+                                size_t layer,
+                                std::vector<AppliedAnimation>& applied) const
+    {}
+
     /// Returns true when the State Machine needs to keep advancing this
     /// state.
     virtual bool keepGoing() const = 0;
@@ -4,6 +4,7 @@
 #include <string>
 #include <stddef.h>
 #include <vector>
+#include "rive/animation/applied_animation.hpp"
 #include "rive/animation/linear_animation_instance.hpp"
 #include "rive/listener_type.hpp"
 #include "rive/scene.hpp"
@@ -58,6 +59,21 @@ public:
     SMINumber* getNumber(const std::string& name) const override;
     SMITrigger* getTrigger(const std::string& name) const override;
 
+    size_t layerCount() const { return m_layerCount; }
+
+    // The state a layer is in, or null if the layer is out of range.
+    const LayerState* layerState(size_t layer) const;
+
+    // The animation played by a layer's state, if it's an animation state.
+    const LinearAnimationInstance* layerAnimation(size_t layer) const;
+
+    // Puts a layer straight into one of its states, dropping any transition in
+    // progress and without firing the states' events, and plays its animation
+    // from time if it's an animation state. Meant for restoring a saved state
+    // rather than driving the machine. Returns false if the state isn't one
+    // of the layer's.
+    bool restoreLayerState(size_t layer, const LayerState* state, float time);
+
     size_t currentAnimationCount() const;
     const LinearAnimationInstance* currentAnimationByIndex(size_t index) const;
 
@@ -76,6 +92,12 @@ public:
     void pointerDown(Vec2D position) override;
     void pointerUp(Vec2D position) override;
 
+    /// Whether a shape under position triggers a listener of the given type
+    /// whose conditions are met, e.g. to tell if a press starts a drag. Uses
+    /// the same hit area as the pointer events, and includes the listeners of
+    /// nested state machines.
+    bool hasListenerAt(Vec2D position, ListenerType type) const;
+
     float durationSeconds() const override { return -1; }
     Loop loop() const override { return Loop::oneShot; }
     bool isTranslucent() const override { return true; }
@@ -93,8 +115,20 @@ public:
     /// Gets a fired event at an index < firedEventCount().
     const Event* firedEventAt(std::size_t index) const;
 
+    /// The animations applied by the last advance, in the order they were
+    /// applied: per layer, the held and outgoing animations of a transition
+    /// first, then those of the current state. Blend states contribute one
+    /// entry per blended animation, including those mixed at 0. Nested state
+    /// machines aren't included.
+    const std::vector<AppliedAnimation>& appliedAnimations() const
+    {
+        return m_appliedAnimations;
+    }
+
 private:
     std::vector<Event*> m_firedEvents;
+    // Cleared rather than reallocated on every advance.
+    std::vector<AppliedAnimation> m_appliedAnimations;
     const StateMachine* m_machine;
     bool m_needsAdvance = false;
     std::vector<SMIInput*> m_inputInstances; // we own each pointer
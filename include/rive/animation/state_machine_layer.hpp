@@ -34,7 +34,8 @@ public:
     const EntryState* entryState() const { return m_Entry; }
     const ExitState* exitState() const { return m_Exit; }
 
-#ifdef TESTING
+    // Not only for tests: tools walk the states, e.g. to find the values
+    // transitions compare inputs against.
     size_t stateCount() const { return m_States.size(); }
     LayerState* state(size_t index) const
     {
@@ -44,7 +45,6 @@ public:
         }
         return nullptr;
     }
-#endif
 };
 } // namespace rive
 
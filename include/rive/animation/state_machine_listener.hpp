@@ -9,6 +9,7 @@ namespace rive
 class Shape;
 class StateMachineListenerImporter;
 class ListenerAction;
+class TransitionCondition;
 class StateMachineInstance;
 class StateMachineListener : public StateMachineListenerBase
 {
@@ -17,7 +18,9 @@ class StateMachineListener : public StateMachineListenerBase
 private:
     std::vector<uint32_t> m_HitShapesIds;
     std::vector<std::unique_ptr<ListenerAction>> m_Actions;
+    std::vector<std::unique_ptr<TransitionCondition>> m_Conditions;
     void addAction(std::unique_ptr<ListenerAction>);
+    void addCondition(std::unique_ptr<TransitionCondition>);
 
 public:
     StateMachineListener();
@@ -26,11 +29,18 @@ public:
     ListenerType listenerType() const { return (ListenerType)listenerTypeValue(); }
     size_t actionCount() const { return m_Actions.size(); }
 
+    size_t conditionCount() const { return m_Conditions.size(); }
+
     const ListenerAction* action(size_t index) const;
+    const TransitionCondition* condition(size_t index) const;
     StatusCode import(ImportStack& importStack) override;
+    StatusCode onAddedDirty(CoreContext* context) override;
     StatusCode onAddedClean(CoreContext* context) override;
 
     const std::vector<uint32_t>& hitShapeIds() const { return m_HitShapesIds; }
+    /// Returns true if every condition gating this listener passes with the
+    /// current input values of the state machine instance.
+    bool conditionsMet(const StateMachineInstance* stateMachineInstance) const;
     void performChanges(StateMachineInstance* stateMachineInstance, Vec2D position) const;
 };
 } // namespace rive
@@ -4,6 +4,7 @@
 #include "rive/animation/linear_animation.hpp"
 #include "rive/animation/state_machine.hpp"
 #include "rive/core_context.hpp"
+#include "rive/diagnostics.hpp"
 #include "rive/generated/artboard_base.hpp"
 #include "rive/hit_info.hpp"
 #include "rive/math/aabb.hpp"
@@ -11,6 +12,7 @@
 #include "rive/shapes/shape_paint_container.hpp"
 
 #include <queue>
+#include <unordered_set>
 #include <vector>
 
 namespace rive
@@ -52,6 +54,7 @@ private:
     Drawable* m_FirstDrawable = nullptr;
     bool m_IsInstance = false;
     bool m_FrameOrigin = true;
+    std::vector<NonFiniteValue> m_NonFiniteValues;
 
     void sortDependencies();
     void sortDrawOrder();
@@ -99,8 +102,18 @@ public:
         kHideFG,
     };
     void draw(Renderer* renderer, DrawOption = DrawOption::kNormal);
+    // Draws the drawables whose ids are in ids, in draw order, without the artboard's
+    // background, clip or origin.
+    void drawOnly(Renderer* renderer, const std::unordered_set<uint32_t>& ids);
     void addToRenderPath(RenderPath* path, const Mat2D& transform);
 
+    // Records a property of a component which turned NaN or infinite, once per component,
+    // property and stage. Only debug builds record them; release builds only fall back to
+    // finite values.
+    void reportNonFinite(const Component* component, const char* property, PipelineStage stage);
+    // In the order they were first caught, so the first one is usually the culprit.
+    const std::vector<NonFiniteValue>& nonFiniteValues() const { return m_NonFiniteValues; }
+
 #ifdef TESTING
     RenderPath* clipPath() const { return m_ClipPath.get(); }
     RenderPath* backgroundPath() const { return m_BackgroundPath.get(); }
@@ -0,0 +1,29 @@
+#ifndef _RIVE_DIAGNOSTICS_HPP_
+#define _RIVE_DIAGNOSTICS_HPP_
+
+namespace rive
+{
+class Component;
+
+// Where in the pipeline a non-finite value was caught.
+enum class PipelineStage : unsigned char
+{
+    // A local transform or opacity, right after animations were applied.
+    apply,
+    // A world transform, right after its constraints ran.
+    constrain,
+    // The world transform of a drawable about to be drawn.
+    render,
+};
+
+// A NaN or infinite value caught at the boundary of a pipeline stage. The value was replaced by
+// the last finite one, or the identity, so that the rest of the artboard still draws.
+struct NonFiniteValue
+{
+    const Component* component;
+    const char* property;
+    PipelineStage stage;
+};
+} // namespace rive
+
+#endif
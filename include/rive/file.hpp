@@ -5,6 +5,7 @@
 #include "rive/backboard.hpp"
 #include "rive/factory.hpp"
 #include "rive/file_asset_resolver.hpp"
+#include <cstdint>
 #include <vector>
 #include <set>
 
@@ -27,7 +28,48 @@ enum class ImportResult
     /// Indicates that the Rive file is not supported by this runtime.
     unsupportedVersion,
     /// Indicates that the there is a formatting problem in the file itself.
-    malformed
+    malformed,
+    /// Indicates that the file declares more than the LoadLimits allow.
+    limitExceeded
+};
+
+///
+/// Caps on what a file may declare. They're checked while the file is read,
+/// so an oversized file fails before the rest of it gets allocated.
+///
+struct LoadLimits
+{
+    enum class Limit
+    {
+        none,
+        objects,
+        keyFrames,
+        assetBytes,
+        nestingDepth,
+    };
+
+    /// Objects of any type, including ones this runtime doesn't know.
+    uint64_t maxObjects = 2000000;
+    /// Key frames across all animations.
+    uint64_t maxKeyFrames = 2000000;
+    /// In-band contents of all image and font assets.
+    uint64_t maxAssetBytes = 512 * 1024 * 1024;
+    /// Levels of artboards nested in one another. Cycles exceed any depth.
+    uint64_t maxNestingDepth = 32;
+
+    /// No limits at all, for trusted content.
+    static LoadLimits unlimited();
+    /// The name of the field setting limit, e.g. "maxObjects".
+    static const char* name(Limit limit);
+};
+
+///
+/// The limit an import exceeded and the count it had reached when it did.
+///
+struct LoadLimitReport
+{
+    LoadLimits::Limit limit = LoadLimits::Limit::none;
+    uint64_t observed = 0;
 };
 
 ///
@@ -76,6 +118,18 @@ public:
                                         ImportResult* result = nullptr,
                                         FileAssetResolver* assetResolver = nullptr);
 
+    ///
+    /// Imports a Rive file from a binary buffer, failing with
+    /// ImportResult::limitExceeded if it declares more than limits allow.
+    /// @param report is an optional description of the exceeded limit.
+    ///
+    static std::unique_ptr<File> import(Span<const uint8_t> data,
+                                        Factory*,
+                                        const LoadLimits& limits,
+                                        ImportResult* result = nullptr,
+                                        LoadLimitReport* report = nullptr,
+                                        FileAssetResolver* assetResolver = nullptr);
+
     /// @returns the file's backboard. All files have exactly one backboard.
     Backboard* backboard() const { return m_Backboard.get(); }
 
@@ -112,7 +166,10 @@ public:
 #endif
 
 private:
-    ImportResult read(BinaryReader&, const RuntimeHeader&);
+    ImportResult read(BinaryReader&,
+                      const RuntimeHeader&,
+                      const LoadLimits&,
+                      LoadLimitReport*);
 };
 } // namespace rive
 #endif
@@ -8,6 +8,7 @@ namespace rive
 class StateMachineListener;
 class StateMachine;
 class ListenerAction;
+class TransitionCondition;
 class StateMachineListenerImporter : public ImportStackObject
 {
 private:
@@ -17,6 +18,7 @@ public:
     StateMachineListenerImporter(StateMachineListener* listener);
     const StateMachineListener* stateMachineListener() const { return m_StateMachineListener; }
     void addAction(std::unique_ptr<ListenerAction>);
+    void addCondition(std::unique_ptr<TransitionCondition>);
     StatusCode resolve() override;
 };
 } // namespace rive
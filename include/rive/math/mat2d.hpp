@@ -58,6 +58,9 @@ public:
         return inverse;
     }
 
+    // False if any value is NaN or infinite.
+    bool isFinite() const;
+
     TransformComponents decompose() const;
     static Mat2D compose(const TransformComponents&);
     float findMaxScale() const;
@@ -29,6 +29,9 @@ public:
     void nest(Artboard* artboard);
 
     ArtboardInstance* artboard() { return m_Instance.get(); }
+    /// The artboard this one nests, which is the file's source artboard
+    /// until it's instanced.
+    const Artboard* nestedSource() const { return m_Artboard; }
 
     StatusCode import(ImportStack& importStack) override;
     Core* clone() const override;
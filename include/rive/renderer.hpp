@@ -25,7 +25,12 @@ namespace rive
 class Vec2D;
 
 // Helper that computes a matrix to "align" content (source) to fit inside frame (destination).
-Mat2D computeAlignment(Fit, Alignment, const AABB& frame, const AABB& content);
+// snapToPixels rounds the translation to whole frame units, halves up.
+Mat2D computeAlignment(Fit,
+                       Alignment,
+                       const AABB& frame,
+                       const AABB& content,
+                       bool snapToPixels = false);
 
 // A render buffer holds an immutable array of values
 class RenderBuffer : public RefCnt<RenderBuffer>
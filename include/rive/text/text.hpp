@@ -174,6 +174,10 @@ public:
     TextSizing sizing() const { return (TextSizing)sizingValue(); }
     TextOverflow overflow() const { return (TextOverflow)overflowValue(); }
     TextOrigin textOrigin() const { return (TextOrigin)originValue(); }
+    // Base direction of the paragraphs. Runtime only until the file format has
+    // a key for it, so files always start out with auto.
+    TextBaseDirection direction() const { return m_direction; }
+    void direction(TextBaseDirection value);
     void overflow(TextOverflow value) { return overflowValue((uint32_t)value); }
     void buildRenderStyles();
     const TextStyle* styleFromShaperId(uint16_t id) const;
@@ -197,6 +201,7 @@ public:
     const std::vector<OrderedLine>& orderedLines() const { return m_orderedLines; }
     const std::vector<TextModifierGroup*>& modifierGroups() const { return m_modifierGroups; }
     const SimpleArray<Paragraph>& shape() const { return m_shape; }
+    const SimpleArray<SimpleArray<GlyphLine>>& lines() const { return m_lines; }
     const std::vector<Unichar>& unichars() const { return m_styledText.unichars(); }
 #endif
 
@@ -211,6 +216,7 @@ protected:
     void originValueChanged() override;
 
 private:
+    TextBaseDirection m_direction = TextBaseDirection::automatic;
 #ifdef WITH_RIVE_TEXT
     void updateOriginWorldTransform();
     std::vector<TextValueRun*> m_runs;
@@ -30,7 +30,17 @@ enum class TextDirection : uint8_t
     rtl = 1
 };
 
-// The alignment of each word wrapped line in a paragraph.
+// Direction paragraphs are shaped with. Automatic picks each paragraph's from its first strongly
+// directional character, defaulting to LTR when there is none.
+enum class TextBaseDirection : uint8_t
+{
+    automatic = 0,
+    ltr = 1,
+    rtl = 2
+};
+
+// The alignment of each word wrapped line in a paragraph. Left and right align to the start and
+// end of the paragraph, so they swap sides in RTL paragraphs.
 enum class TextAlign : uint8_t
 {
     left = 0,
@@ -160,7 +170,9 @@ public:
     //
     virtual RawPath getPath(GlyphID) const = 0;
 
-    SimpleArray<Paragraph> shapeText(Span<const Unichar> text, Span<const TextRun> runs) const;
+    SimpleArray<Paragraph> shapeText(Span<const Unichar> text,
+                                     Span<const TextRun> runs,
+                                     TextBaseDirection dir = TextBaseDirection::automatic) const;
 
     // If the platform can supply fallback font(s), set this function pointer.
     // It will be called with a span of unichars, and the platform attempts to
@@ -174,7 +186,8 @@ protected:
     Font(const LineMetrics& lm) : m_lineMetrics(lm) {}
 
     virtual SimpleArray<Paragraph> onShapeText(Span<const Unichar> text,
-                                               Span<const TextRun> runs) const = 0;
+                                               Span<const TextRun> runs,
+                                               TextBaseDirection direction) const = 0;
 
 private:
     /// The font specified line metrics (automatic line metrics).
@@ -192,6 +205,8 @@ struct TextRun
     uint32_t script;
     uint16_t styleId;
     TextDirection dir;
+    // Embedding level resolved by the bidi algorithm, odd levels flow RTL.
+    uint8_t level = 0;
 };
 
 // The corresponding system generated run for the user provided TextRuns. GlyphRuns may not match
@@ -250,6 +265,10 @@ struct GlyphRun
 
     // The text direction (LTR = 0/RTL = 1)
     TextDirection dir;
+
+    // The bidi embedding level of the run, used to order runs visually within a line. Odd levels
+    // are RTL.
+    uint8_t level = 0;
 };
 
 } // namespace rive
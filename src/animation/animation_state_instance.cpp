@@ -30,5 +30,12 @@ void AnimationStateInstance::advance(float seconds, Span<SMIInput*>)
 
 void AnimationStateInstance::apply(float mix) { m_AnimationInstance.apply(mix); }
 
+void AnimationStateInstance::collectApplied(float mix,
+                                            size_t layer,
+                                            std::vector<AppliedAnimation>& applied) const
+{
+    applied.push_back({m_AnimationInstance.animation(), m_AnimationInstance.time(), mix, layer});
+}
+
 bool AnimationStateInstance::keepGoing() const { return m_KeepGoing; }
 void AnimationStateInstance::clearSpilledTime() { m_AnimationInstance.clearSpilledTime(); }
\ No newline at end of file
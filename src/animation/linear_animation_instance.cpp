@@ -2,6 +2,7 @@
 #include "rive/animation/linear_animation.hpp"
 #include "rive/animation/loop.hpp"
 #include "rive/rive_counter.hpp"
+#include <algorithm>
 #include <cmath>
 #include <cassert>
 
@@ -30,7 +31,10 @@ LinearAnimationInstance::LinearAnimationInstance(LinearAnimationInstance const&
     m_spilledTime(lhs.m_spilledTime),
     m_direction(lhs.m_direction),
     m_didLoop(lhs.m_didLoop),
-    m_loopValue(lhs.m_loopValue)
+    m_loopValue(lhs.m_loopValue),
+    m_hasPlayRange(lhs.m_hasPlayRange),
+    m_playStart(lhs.m_playStart),
+    m_playEnd(lhs.m_playEnd)
 {
     Counter::update(Counter::kLinearAnimationInstance, +1);
 }
@@ -71,9 +75,18 @@ bool LinearAnimationInstance::advance(float elapsedSeconds)
 
     int fps = animation.fps();
     float frames = m_time * fps;
-    int start = animation.enableWorkArea() ? animation.workStart() : 0;
-    int end = animation.enableWorkArea() ? animation.workEnd() : animation.duration();
-    int range = end - start;
+    float start, end;
+    if (m_hasPlayRange)
+    {
+        start = m_playStart * fps;
+        end = m_playEnd * fps;
+    }
+    else
+    {
+        start = (float)(animation.enableWorkArea() ? animation.workStart() : 0);
+        end = (float)(animation.enableWorkArea() ? animation.workEnd() : animation.duration());
+    }
+    float range = end - start;
 
     bool didLoop = false;
 
@@ -87,14 +100,14 @@ bool LinearAnimationInstance::advance(float elapsedSeconds)
             if (direction == 1 && frames > end)
             {
                 m_spilledTime = (frames - end) / fps;
-                frames = (float)end;
+                frames = end;
                 m_time = frames / fps;
                 didLoop = true;
             }
             else if (direction == -1 && frames < start)
             {
                 m_spilledTime = (start - frames) / fps;
-                frames = (float)start;
+                frames = start;
                 m_time = frames / fps;
                 didLoop = true;
             }
@@ -104,7 +117,7 @@ bool LinearAnimationInstance::advance(float elapsedSeconds)
             {
                 m_spilledTime = (frames - end) / fps;
                 frames = m_time * fps;
-                frames = start + std::fmod(frames - start, (float)range);
+                frames = start + std::fmod(frames - start, range);
                 m_time = frames / fps;
                 didLoop = true;
             }
@@ -112,7 +125,7 @@ bool LinearAnimationInstance::advance(float elapsedSeconds)
             {
                 m_spilledTime = (start - frames) / fps;
                 frames = m_time * fps;
-                frames = end - std::abs(std::fmod(start - frames, (float)range));
+                frames = end - std::abs(std::fmod(start - frames, range));
                 m_time = frames / fps;
                 didLoop = true;
             }
@@ -199,11 +212,13 @@ bool LinearAnimationInstance::isTranslucent() const
 // Returns either the animation's default or overridden loop values
 int LinearAnimationInstance::loopValue() const
 {
-    if (m_loopValue != -1)
+    int value = m_loopValue != -1 ? m_loopValue : m_animation->loopValue();
+    // A play range repeats even if the animation itself is a one shot.
+    if (m_hasPlayRange && value == static_cast<int>(Loop::oneShot))
     {
-        return m_loopValue;
+        return static_cast<int>(Loop::loop);
     }
-    return m_animation->loopValue();
+    return value;
 }
 
 // Override the animation's loop value
@@ -220,4 +235,44 @@ void LinearAnimationInstance::loopValue(int value)
     m_loopValue = value;
 }
 
+bool LinearAnimationInstance::playRange(float startSeconds, float endSeconds)
+{
+    // Without frames, there's no time to play a range of.
+    if (m_animation->fps() == 0)
+    {
+        return false;
+    }
+    float from = std::min(startSeconds, endSeconds);
+    float to = std::max(startSeconds, endSeconds);
+    float minimum = 1.0f / m_animation->fps();
+    float length = m_animation->duration() / (float)m_animation->fps();
+
+    float start = std::max(0.0f, std::min(from, length));
+    float end = std::max(0.0f, std::min(to, length));
+    if (end - start < minimum)
+    {
+        end = std::min(start + minimum, length);
+        start = std::max(0.0f, end - minimum);
+    }
+
+    m_hasPlayRange = true;
+    m_playStart = start;
+    m_playEnd = end;
+    if (m_time < start || m_time > end)
+    {
+        m_time = m_direction < 0 ? end : start;
+    }
+    return start == from && end == to;
+}
+
+float LinearAnimationInstance::playStartSeconds() const
+{
+    return m_hasPlayRange ? m_playStart : m_animation->startSeconds();
+}
+
+float LinearAnimationInstance::playEndSeconds() const
+{
+    return m_hasPlayRange ? m_playEnd : m_animation->endSeconds();
+}
+
 float LinearAnimationInstance::durationSeconds() const { return m_animation->durationSeconds(); }
@@ -23,6 +23,7 @@
 #include "rive/nested_artboard.hpp"
 #include "rive/rive_counter.hpp"
 #include "rive/shapes/shape.hpp"
+#include <cmath>
 #include <unordered_map>
 
 using namespace rive;
@@ -40,9 +41,13 @@ public:
 
     void init(StateMachineInstance* stateMachineInstance,
               const StateMachineLayer* layer,
-              ArtboardInstance* instance)
+              ArtboardInstance* instance,
+              size_t index,
+              std::vector<AppliedAnimation>* applied)
     {
         m_stateMachineInstance = stateMachineInstance;
+        m_index = index;
+        m_applied = applied;
         m_artboardInstance = instance;
         assert(m_layer == nullptr);
         m_anyStateInstance = layer->anyState()->makeInstance(instance).release();
@@ -93,7 +98,7 @@ public:
             }
         }
 
-        apply();
+        apply(true);
 
         m_currentState->clearSpilledTime();
 
@@ -241,11 +246,15 @@ public:
         return false;
     }
 
-    void apply(/*Artboard* artboard*/)
+    // Only the last apply of an advance records its states, as earlier ones
+    // are overridden by it. Held animations are only applied once, so they're
+    // always recorded.
+    void apply(bool record = false)
     {
         if (m_holdAnimation != nullptr)
         {
             m_holdAnimation->apply(m_artboardInstance, m_holdTime, m_mixFrom);
+            m_applied->push_back({m_holdAnimation, m_holdTime, m_mixFrom, m_index});
             m_holdAnimation = nullptr;
         }
 
@@ -259,14 +268,51 @@ public:
         {
             auto fromMix = cubic != nullptr ? cubic->transform(m_mixFrom) : m_mixFrom;
             m_stateFrom->apply(fromMix);
+            if (record)
+            {
+                m_stateFrom->collectApplied(fromMix, m_index, *m_applied);
+            }
         }
         if (m_currentState != nullptr)
         {
             auto mix = cubic != nullptr ? cubic->transform(m_mix) : m_mix;
             m_currentState->apply(mix);
+            if (record)
+            {
+                m_currentState->collectApplied(mix, m_index, *m_applied);
+            }
         }
     }
 
+    // Drops any transition in progress and puts the layer straight into
+    // stateTo, without firing events.
+    void restoreState(const LayerState* stateTo, float time)
+    {
+        if (m_stateFrom != m_anyStateInstance)
+        {
+            delete m_stateFrom;
+        }
+        m_stateFrom = nullptr;
+        delete m_currentState;
+        m_currentState = stateTo->makeInstance(m_artboardInstance).release();
+
+        m_transition = nullptr;
+        m_transitionCompleted = true;
+        m_holdAnimation = nullptr;
+        m_holdAnimationFrom = false;
+        m_mix = 1.0f;
+        m_mixFrom = 1.0f;
+        m_waitingForExit = false;
+        m_stateMachineChangedOnAdvance = false;
+
+        if (m_currentState->state()->is<AnimationState>())
+        {
+            static_cast<AnimationStateInstance*>(m_currentState)->animationInstance()->time(time);
+        }
+    }
+
+    const StateMachineLayer* layer() const { return m_layer; }
+
     bool stateChangedOnAdvance() const { return m_stateMachineChangedOnAdvance; }
 
     const LayerState* currentState()
@@ -287,6 +333,8 @@ private:
     static const int maxIterations = 100;
     StateMachineInstance* m_stateMachineInstance = nullptr;
     const StateMachineLayer* m_layer = nullptr;
+    size_t m_index = 0;
+    std::vector<AppliedAnimation>* m_applied = nullptr;
     ArtboardInstance* m_artboardInstance = nullptr;
 
     StateInstance* m_anyStateInstance = nullptr;
@@ -324,20 +372,33 @@ private:
 };
 } // namespace rive
 
-void StateMachineInstance::updateListeners(Vec2D position, ListenerType hitType)
+static IAABB hitAreaAt(const ArtboardInstance* artboard, Vec2D* position)
 {
-    if (m_artboardInstance->frameOrigin())
+    if (artboard->frameOrigin())
     {
-        position -= Vec2D(m_artboardInstance->originX() * m_artboardInstance->width(),
-                          m_artboardInstance->originY() * m_artboardInstance->height());
+        *position -= Vec2D(artboard->originX() * artboard->width(),
+                           artboard->originY() * artboard->height());
     }
 
     const float hitRadius = 2;
-    auto hitArea = AABB(position.x - hitRadius,
-                        position.y - hitRadius,
-                        position.x + hitRadius,
-                        position.y + hitRadius)
-                       .round();
+    return AABB(position->x - hitRadius,
+                position->y - hitRadius,
+                position->x + hitRadius,
+                position->y + hitRadius)
+        .round();
+}
+
+void StateMachineInstance::updateListeners(Vec2D position, ListenerType hitType)
+{
+    // Positions outside the artboard are fine, e.g. while a drag continues
+    // past the edge of the view; whether to clamp is up to each listener.
+    // Non-finite ones can't hit anything and would end up in aligned targets.
+    if (!std::isfinite(position.x) || !std::isfinite(position.y))
+    {
+        return;
+    }
+
+    auto hitArea = hitAreaAt(m_artboardInstance, &position);
 
     for (const auto& hitShape : m_hitShapes)
     {
@@ -352,22 +413,15 @@ void StateMachineInstance::updateListeners(Vec2D position, ListenerType hitType)
         // iterate all listeners associated with this hit shape
         for (auto listener : hitShape->listeners)
         {
-            // Always update hover states regardless of which specific listener type
-            // we're trying to trigger.
-            if (hoverChange)
-            {
-                if (isOver && listener->listenerType() == ListenerType::enter)
-                {
-                    listener->performChanges(this, position);
-                    markNeedsAdvance();
-                }
-                else if (!isOver && listener->listenerType() == ListenerType::exit)
-                {
-                    listener->performChanges(this, position);
-                    markNeedsAdvance();
-                }
-            }
-            if (isOver && hitType == listener->listenerType())
+            auto type = listener->listenerType();
+            bool fires = (isOver && hitType == type) ||
+                         (hoverChange && isOver && type == ListenerType::enter) ||
+                         (hoverChange && !isOver && type == ListenerType::exit);
+            // Hover is tracked above whatever the conditions evaluate to, so
+            // conditions only gate the actions. A gated listener behaves as if
+            // it didn't exist, leaving the event to whatever else is under the
+            // pointer.
+            if (fires && listener->conditionsMet(this))
             {
                 listener->performChanges(this, position);
                 markNeedsAdvance();
@@ -413,6 +467,51 @@ void StateMachineInstance::updateListeners(Vec2D position, ListenerType hitType)
     }
 }
 
+bool StateMachineInstance::hasListenerAt(Vec2D position, ListenerType type) const
+{
+    if (!std::isfinite(position.x) || !std::isfinite(position.y))
+    {
+        return false;
+    }
+
+    auto hitArea = hitAreaAt(m_artboardInstance, &position);
+    for (const auto& hitShape : m_hitShapes)
+    {
+        for (auto listener : hitShape->listeners)
+        {
+            if (listener->listenerType() == type && listener->conditionsMet(this) &&
+                hitShape->shape()->hitTest(hitArea))
+            {
+                return true;
+            }
+        }
+    }
+
+    // Listeners of nested state machines are gated by their own inputs.
+    for (auto nestedArtboard : m_hitNestedArtboards)
+    {
+        Vec2D nestedPosition;
+        if (!nestedArtboard->worldToLocal(position, &nestedPosition))
+        {
+            continue;
+        }
+        for (auto nestedAnimation : nestedArtboard->nestedAnimations())
+        {
+            if (!nestedAnimation->is<NestedStateMachine>())
+            {
+                continue;
+            }
+            auto nestedInstance =
+                nestedAnimation->as<NestedStateMachine>()->stateMachineInstance();
+            if (nestedInstance != nullptr && nestedInstance->hasListenerAt(nestedPosition, type))
+            {
+                return true;
+            }
+        }
+    }
+    return false;
+}
+
 void StateMachineInstance::pointerMove(Vec2D position)
 {
     updateListeners(position, ListenerType::move);
@@ -462,7 +561,7 @@ StateMachineInstance::StateMachineInstance(const StateMachine* machine,
     m_layers = new StateMachineLayerInstance[m_layerCount];
     for (size_t i = 0; i < m_layerCount; i++)
     {
-        m_layers[i].init(this, machine->layer(i), m_artboardInstance);
+        m_layers[i].init(this, machine->layer(i), m_artboardInstance, i, &m_appliedAnimations);
     }
 
     // Initialize listeners. Store a lookup table of shape id to hit shape
@@ -525,6 +624,7 @@ StateMachineInstance::~StateMachineInstance()
 bool StateMachineInstance::advance(float seconds)
 {
     m_firedEvents.clear();
+    m_appliedAnimations.clear();
     m_needsAdvance = false;
     for (size_t i = 0; i < m_layerCount; i++)
     {
@@ -620,6 +720,36 @@ const LayerState* StateMachineInstance::stateChangedByIndex(size_t index) const
     return nullptr;
 }
 
+const LayerState* StateMachineInstance::layerState(size_t layer) const
+{
+    return layer < m_layerCount ? m_layers[layer].currentState() : nullptr;
+}
+
+const LinearAnimationInstance* StateMachineInstance::layerAnimation(size_t layer) const
+{
+    return layer < m_layerCount ? m_layers[layer].currentAnimation() : nullptr;
+}
+
+bool StateMachineInstance::restoreLayerState(size_t layer, const LayerState* state, float time)
+{
+    if (layer >= m_layerCount || state == nullptr)
+    {
+        return false;
+    }
+
+    auto machineLayer = m_layers[layer].layer();
+    for (size_t i = 0; i < machineLayer->stateCount(); i++)
+    {
+        if (machineLayer->state(i) == state)
+        {
+            m_layers[layer].restoreState(state, time);
+            markNeedsAdvance();
+            return true;
+        }
+    }
+    return false;
+}
+
 size_t StateMachineInstance::currentAnimationCount() const
 {
     size_t count = 0;
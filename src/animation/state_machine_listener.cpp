@@ -6,6 +6,7 @@
 #include "rive/shapes/shape.hpp"
 #include "rive/animation/state_machine_instance.hpp"
 #include "rive/animation/listener_input_change.hpp"
+#include "rive/animation/transition_condition.hpp"
 
 using namespace rive;
 
@@ -29,6 +30,11 @@ StatusCode StateMachineListener::import(ImportStack& importStack)
     return Super::import(importStack);
 }
 
+void StateMachineListener::addCondition(std::unique_ptr<TransitionCondition> condition)
+{
+    m_Conditions.push_back(std::move(condition));
+}
+
 const ListenerAction* StateMachineListener::action(size_t index) const
 {
     if (index < m_Actions.size())
@@ -38,8 +44,39 @@ const ListenerAction* StateMachineListener::action(size_t index) const
     return nullptr;
 }
 
+const TransitionCondition* StateMachineListener::condition(size_t index) const
+{
+    if (index < m_Conditions.size())
+    {
+        return m_Conditions[index].get();
+    }
+    return nullptr;
+}
+
+StatusCode StateMachineListener::onAddedDirty(CoreContext* context)
+{
+    StatusCode code;
+    for (auto& condition : m_Conditions)
+    {
+        if ((code = condition->onAddedDirty(context)) != StatusCode::Ok)
+        {
+            return code;
+        }
+    }
+    return Super::onAddedDirty(context);
+}
+
 StatusCode StateMachineListener::onAddedClean(CoreContext* context)
 {
+    StatusCode code;
+    for (auto& condition : m_Conditions)
+    {
+        if ((code = condition->onAddedClean(context)) != StatusCode::Ok)
+        {
+            return code;
+        }
+    }
+
     auto artboard = static_cast<Artboard*>(context);
     auto target = artboard->resolve(targetId());
 
@@ -74,6 +111,18 @@ StatusCode StateMachineListener::onAddedClean(CoreContext* context)
     return Super::onAddedClean(context);
 }
 
+bool StateMachineListener::conditionsMet(const StateMachineInstance* stateMachineInstance) const
+{
+    for (auto& condition : m_Conditions)
+    {
+        if (!condition->evaluate(stateMachineInstance->input((size_t)condition->inputId())))
+        {
+            return false;
+        }
+    }
+    return true;
+}
+
 void StateMachineListener::performChanges(StateMachineInstance* stateMachineInstance,
                                           Vec2D position) const
 {
@@ -2,7 +2,9 @@
 #include "rive/animation/state_transition.hpp"
 #include "rive/importers/state_transition_importer.hpp"
 #include "rive/importers/state_machine_importer.hpp"
+#include "rive/importers/state_machine_listener_importer.hpp"
 #include "rive/animation/state_machine.hpp"
+#include "rive/animation/state_machine_listener.hpp"
 
 using namespace rive;
 
@@ -29,10 +31,20 @@ StatusCode TransitionCondition::import(ImportStack& importStack)
     }
 
     auto transitionImporter = importStack.latest<StateTransitionImporter>(StateTransition::typeKey);
-    if (transitionImporter == nullptr)
+    if (transitionImporter != nullptr)
+    {
+        transitionImporter->addCondition(this);
+        return Super::import(importStack);
+    }
+
+    // Listeners are exported after all the layers, so a condition with no
+    // transition to attach to gates the latest listener instead.
+    auto listenerImporter =
+        importStack.latest<StateMachineListenerImporter>(StateMachineListener::typeKey);
+    if (listenerImporter == nullptr)
     {
         return StatusCode::MissingObject;
     }
-    transitionImporter->addCondition(this);
+    listenerImporter->addCondition(std::unique_ptr<TransitionCondition>(this));
     return Super::import(importStack);
 }
@@ -580,6 +580,11 @@ void Artboard::draw(Renderer* renderer, DrawOption option)
             {
                 continue;
             }
+            if (!drawable->worldTransform().isFinite())
+            {
+                reportNonFinite(drawable, "worldTransform", PipelineStage::render);
+                continue;
+            }
             drawable->draw(renderer);
         }
     }
@@ -587,6 +592,52 @@ void Artboard::draw(Renderer* renderer, DrawOption option)
     renderer->restore();
 }
 
+void Artboard::drawOnly(Renderer* renderer, const std::unordered_set<uint32_t>& ids)
+{
+    std::unordered_set<const Drawable*> included;
+    for (auto id : ids)
+    {
+        if (id < m_Objects.size() && m_Objects[id] != nullptr && m_Objects[id]->is<Drawable>())
+        {
+            included.insert(m_Objects[id]->as<Drawable>());
+        }
+    }
+
+    for (auto drawable = m_FirstDrawable; drawable != nullptr; drawable = drawable->prev)
+    {
+        if (included.count(drawable) == 0 || drawable->isHidden())
+        {
+            continue;
+        }
+        if (!drawable->worldTransform().isFinite())
+        {
+            reportNonFinite(drawable, "worldTransform", PipelineStage::render);
+            continue;
+        }
+        drawable->draw(renderer);
+    }
+}
+
+void Artboard::reportNonFinite(const Component* component,
+                               const char* property,
+                               PipelineStage stage)
+{
+#ifdef DEBUG
+    for (const auto& value : m_NonFiniteValues)
+    {
+        if (value.component == component && value.property == property && value.stage == stage)
+        {
+            return;
+        }
+    }
+    fprintf(stderr,
+            "Non-finite %s on \"%s\", falling back to the last finite value.\n",
+            property,
+            component->name().c_str());
+    m_NonFiniteValues.push_back({component, property, stage});
+#endif
+}
+
 void Artboard::addToRenderPath(RenderPath* path, const Mat2D& transform)
 {
     for (auto drawable = m_FirstDrawable; drawable != nullptr; drawable = drawable->prev)
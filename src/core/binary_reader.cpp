@@ -49,8 +49,10 @@ uint64_t BinaryReader::readVarUint64()
 std::string BinaryReader::readString()
 {
     uint64_t length = readVarUint64();
-    if (didOverflow())
+    // Check the declared length before allocating for it.
+    if (didOverflow() || length > (uint64_t)(m_Bytes.end() - m_Position))
     {
+        overflow();
         return std::string();
     }
 
@@ -68,8 +70,9 @@ std::string BinaryReader::readString()
 Span<const uint8_t> BinaryReader::readBytes()
 {
     uint64_t length = readVarUint64();
-    if (didOverflow())
+    if (didOverflow() || length > (uint64_t)(m_Bytes.end() - m_Position))
     {
+        overflow();
         return Span<const uint8_t>(m_Position, 0);
     }
 
@@ -29,6 +29,10 @@
 #include "rive/animation/blend_state_direct.hpp"
 #include "rive/assets/file_asset.hpp"
 #include "rive/assets/file_asset_contents.hpp"
+#include "rive/animation/keyframe.hpp"
+#include "rive/nested_artboard.hpp"
+#include <algorithm>
+#include <unordered_map>
 
 // Default namespace for Rive Cpp code
 using namespace rive;
@@ -131,9 +135,103 @@ File::File(Factory* factory, FileAssetResolver* assetResolver) :
 
 File::~File() { Counter::update(Counter::kFile, -1); }
 
+LoadLimits LoadLimits::unlimited()
+{
+    LoadLimits limits;
+    limits.maxObjects = UINT64_MAX;
+    limits.maxKeyFrames = UINT64_MAX;
+    limits.maxAssetBytes = UINT64_MAX;
+    limits.maxNestingDepth = UINT64_MAX;
+    return limits;
+}
+
+const char* LoadLimits::name(Limit limit)
+{
+    switch (limit)
+    {
+        case Limit::none:
+            return "none";
+        case Limit::objects:
+            return "maxObjects";
+        case Limit::keyFrames:
+            return "maxKeyFrames";
+        case Limit::assetBytes:
+            return "maxAssetBytes";
+        case Limit::nestingDepth:
+            return "maxNestingDepth";
+    }
+    return "unknown";
+}
+
+// Levels of artboards nested below artboard, saturating at cap. Anything
+// deeper than cap, including cycles, is reported as cap so that neither the
+// recursion nor the memo grows with the file.
+static uint64_t nestingDepth(const Artboard* artboard,
+                             uint64_t level,
+                             uint64_t cap,
+                             std::unordered_map<const Artboard*, uint64_t>& depths)
+{
+    auto itr = depths.find(artboard);
+    if (itr != depths.end())
+    {
+        return itr->second;
+    }
+    if (level >= cap)
+    {
+        return cap;
+    }
+
+    // Marks the artboard as in progress; reaching it again is a cycle.
+    depths[artboard] = cap;
+    uint64_t depth = 0;
+    for (auto nested : artboard->nestedArtboards())
+    {
+        auto source = nested->nestedSource();
+        if (source == nullptr)
+        {
+            continue;
+        }
+        auto nestedDepth = nestingDepth(source, level + 1, cap, depths);
+        depth = std::max(depth, nestedDepth >= cap ? cap : nestedDepth + 1);
+    }
+    depths[artboard] = depth;
+    return depth;
+}
+
+static bool exceeds(uint64_t observed,
+                    uint64_t max,
+                    LoadLimits::Limit limit,
+                    LoadLimitReport* report)
+{
+    if (observed <= max)
+    {
+        return false;
+    }
+    fprintf(stderr,
+            "File exceeds the %s load limit with " RIVE_FMT_U64 ".\n",
+            LoadLimits::name(limit),
+            observed);
+    if (report)
+    {
+        report->limit = limit;
+        report->observed = observed;
+    }
+    return true;
+}
+
+std::unique_ptr<File> File::import(Span<const uint8_t> bytes,
+                                   Factory* factory,
+                                   ImportResult* result,
+                                   FileAssetResolver* assetResolver)
+{
+    return import(bytes, factory, LoadLimits(), result, nullptr, assetResolver);
+}
+
 std::unique_ptr<File> File::import(Span<const uint8_t> bytes,
                                    Factory* factory,
+                                   const LoadLimits& limits,
                                    ImportResult* result,
+                                   LoadLimitReport* report,
                                    FileAssetResolver* assetResolver)
 {
     BinaryReader reader(bytes);
@@ -162,29 +260,59 @@ std::unique_ptr<File> File::import(Span<const uint8_t> bytes,
         return nullptr;
     }
     auto file = std::unique_ptr<File>(new File(factory, assetResolver));
-    auto readResult = file->read(reader, header);
+    auto readResult = file->read(reader, header, limits, report);
     if (readResult != ImportResult::success)
     {
         file.reset(nullptr);
     }
     if (result)
     {
-        *result = ImportResult::success;
+        *result = readResult;
     }
     return file;
 }
 
-ImportResult File::read(BinaryReader& reader, const RuntimeHeader& header)
+ImportResult File::read(BinaryReader& reader,
+                        const RuntimeHeader& header,
+                        const LoadLimits& limits,
+                        LoadLimitReport* report)
 {
+    uint64_t objectCount = 0;
+    uint64_t keyFrameCount = 0;
+    uint64_t assetByteCount = 0;
+
     ImportStack importStack;
     while (!reader.reachedEnd())
     {
         auto object = readRuntimeObject(reader, header);
+        if (exceeds(++objectCount, limits.maxObjects, LoadLimits::Limit::objects, report))
+        {
+            delete object;
+            return ImportResult::limitExceeded;
+        }
         if (object == nullptr)
         {
             importStack.readNullObject();
             continue;
         }
+        if (object->is<KeyFrame>() &&
+            exceeds(++keyFrameCount, limits.maxKeyFrames, LoadLimits::Limit::keyFrames, report))
+        {
+            delete object;
+            return ImportResult::limitExceeded;
+        }
+        if (object->is<FileAssetContents>())
+        {
+            assetByteCount += object->as<FileAssetContents>()->bytes().size();
+            if (exceeds(assetByteCount,
+                        limits.maxAssetBytes,
+                        LoadLimits::Limit::assetBytes,
+                        report))
+            {
+                delete object;
+                return ImportResult::limitExceeded;
+            }
+        }
         if (object->import(importStack) == StatusCode::Ok)
         {
             switch (object->coreType())
@@ -275,6 +403,12 @@ ImportResult File::read(BinaryReader& reader, const RuntimeHeader& header)
                 break;
             case StateMachineListener::typeKey:
                 stackObject = new StateMachineListenerImporter(object->as<StateMachineListener>());
+                // Conditions following a listener belong to it, not to the
+                // last transition of the last layer.
+                if (importStack.makeLatest(StateTransition::typeKey, nullptr) != StatusCode::Ok)
+                {
+                    return ImportResult::malformed;
+                }
                 break;
             case ImageAsset::typeKey:
             case FontAsset::typeKey:
@@ -297,8 +431,27 @@ ImportResult File::read(BinaryReader& reader, const RuntimeHeader& header)
         }
     }
 
-    return !reader.hasError() && importStack.resolve() == StatusCode::Ok ? ImportResult::success
-                                                                         : ImportResult::malformed;
+    if (reader.hasError() || importStack.resolve() != StatusCode::Ok)
+    {
+        return ImportResult::malformed;
+    }
+
+    // Nested artboards are only linked to their sources once everything
+    // resolved.
+    auto cap = limits.maxNestingDepth == UINT64_MAX ? UINT64_MAX : limits.maxNestingDepth + 1;
+    std::unordered_map<const Artboard*, uint64_t> depths;
+    for (const auto& artboard : m_Artboards)
+    {
+        if (exceeds(nestingDepth(artboard.get(), 0, cap, depths),
+                    limits.maxNestingDepth,
+                    LoadLimits::Limit::nestingDepth,
+                    report))
+        {
+            return ImportResult::limitExceeded;
+        }
+    }
+
+    return ImportResult::success;
 }
 
 Artboard* File::artboard(std::string name) const
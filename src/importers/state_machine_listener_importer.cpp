@@ -1,4 +1,5 @@
 #include "rive/animation/listener_action.hpp"
+#include "rive/animation/transition_condition.hpp"
 #include "rive/importers/state_machine_listener_importer.hpp"
 #include "rive/animation/state_machine_listener.hpp"
 
@@ -13,4 +14,9 @@ void StateMachineListenerImporter::addAction(std::unique_ptr<ListenerAction> act
     m_StateMachineListener->addAction(std::move(action));
 }
 
+void StateMachineListenerImporter::addCondition(std::unique_ptr<TransitionCondition> condition)
+{
+    m_StateMachineListener->addCondition(std::move(condition));
+}
+
 StatusCode StateMachineListenerImporter::resolve() { return StatusCode::Ok; }
\ No newline at end of file
@@ -74,9 +74,16 @@ static ContourMeasure::PosTan eval_cubic(const Vec2D pts[], float t)
     auto b = two(eval.b);
     auto c = eval.c;
 
+    auto tangent = (a * t + b) * t + c;
+    if (tangent == Vec2D())
+    {
+        // Control points coinciding with an end point cancel the derivative there.
+        tangent = pts[3] - pts[0];
+    }
+
     return {
         eval(t),
-        ((a * t + b) * t + c).normalized(),
+        tangent.normalized(),
     };
 }
 
@@ -143,7 +150,13 @@ ContourMeasure::PosTan ContourMeasure::getPosTan(float distance) const
     {
         size_t N = m_points.size();
         assert(N > 1);
-        return {m_points[N - 1], (m_points[N - 1] - m_points[N - 2]).normalized()};
+        auto tangent = m_points[N - 1] - m_points[N - 2];
+        if (tangent != Vec2D())
+        {
+            return {m_points[N - 1], tangent.normalized()};
+        }
+        // The last control point sits on the end point, let the segment compute the tangent.
+        distance = m_length;
     }
 
     if (distance < 0)
@@ -295,6 +308,24 @@ static inline unsigned toDot30(float x)
     return (unsigned)(x * (1 << 30));
 }
 
+// Removes the segments in [begin, end) which don't add any length, returning the new end.
+// They'd make distance lookups divide by zero.
+static ContourMeasure::Segment* drop_empty_segments(ContourMeasure::Segment* begin,
+                                                    ContourMeasure::Segment* end,
+                                                    float prevDistance)
+{
+    auto out = begin;
+    for (auto seg = begin; seg != end; ++seg)
+    {
+        if (seg->m_distance > prevDistance)
+        {
+            prevDistance = seg->m_distance;
+            *out++ = *seg;
+        }
+    }
+    return out;
+}
+
 // These add[SegmentType]Segs routines append intermediate segments for the curve.
 // They assume the caller has set the initial segment (with t == 0), so they only
 // add intermediates.
@@ -451,23 +482,31 @@ rcp<ContourMeasure> ContourMeasureIter::tryNext()
             case PathVerb::move:
                 RIVE_UNREACHABLE();
             case PathVerb::line:
-                distance += (it.linePts()[1] - it.linePts()[0]).length();
-                *nextSeg++ = {distance, ptIndex, kMaxDot30, SegmentType::kLine};
+            {
+                float length = (it.linePts()[1] - it.linePts()[0]).length();
+                if (length > 0)
+                {
+                    distance += length;
+                    *nextSeg++ = {distance, ptIndex, kMaxDot30, SegmentType::kLine};
+                }
                 ++ptIndex;
                 break;
+            }
             case PathVerb::quad:
             {
                 const uint32_t n = *nextSegCount++;
+                float prevDistance = distance;
                 distance = addQuadSegs(nextSeg, it.quadPts(), n, ptIndex, distance);
-                nextSeg += n;
+                nextSeg = drop_empty_segments(nextSeg, nextSeg + n, prevDistance);
                 ptIndex += 2;
                 break;
             }
             case PathVerb::cubic:
             {
                 const uint32_t n = *nextSegCount++;
+                float prevDistance = distance;
                 distance = addCubicSegs(nextSeg, it.cubicPts(), n, ptIndex, distance);
-                nextSeg += n;
+                nextSeg = drop_empty_segments(nextSeg, nextSeg + n, prevDistance);
                 ptIndex += 3;
                 break;
             }
@@ -482,7 +521,8 @@ rcp<ContourMeasure> ContourMeasureIter::tryNext()
                 assert(isClosed);
         }
     }
-    assert(nextSeg == segs.data() + segs.size());
+    assert(nextSeg <= segs.data() + segs.size());
+    segs.resize(nextSeg - segs.data());
 
     // Copy out points.
     std::vector<Vec2D> pts;
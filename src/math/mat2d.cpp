@@ -6,6 +6,18 @@
 
 using namespace rive;
 
+bool Mat2D::isFinite() const
+{
+    for (float value : m_Buffer)
+    {
+        if (!std::isfinite(value))
+        {
+            return false;
+        }
+    }
+    return true;
+}
+
 Mat2D Mat2D::fromRotation(float rad)
 {
     float s = 0, c = 1;
@@ -5,12 +5,28 @@
 
 using namespace rive;
 
-Mat2D rive::computeAlignment(Fit fit, Alignment alignment, const AABB& frame, const AABB& content)
+// The ratio scaling content to frame along the side which fits it, i.e. the smaller ratio, or the
+// larger one for cover. Products of floats are exact in double, so the side is picked exactly
+// and the ratio is rounded once, rather than comparing two separately rounded ratios.
+static float fitRatio(const AABB& frame, float contentWidth, float contentHeight, bool larger)
+{
+    double widthCross = (double)frame.width() * contentHeight;
+    double heightCross = (double)frame.height() * contentWidth;
+    if ((widthCross <= heightCross) != larger)
+    {
+        return (float)((double)frame.width() / contentWidth);
+    }
+    return (float)((double)frame.height() / contentHeight);
+}
+
+Mat2D rive::computeAlignment(Fit fit,
+                             Alignment alignment,
+                             const AABB& frame,
+                             const AABB& content,
+                             bool snapToPixels)
 {
     float contentWidth = content.width();
     float contentHeight = content.height();
-    float x = -content.left() - contentWidth * 0.5f - (alignment.x() * contentWidth * 0.5f);
-    float y = -content.top() - contentHeight * 0.5f - (alignment.y() * contentHeight * 0.5f);
 
     float scaleX = 1.0f, scaleY = 1.0f;
 
@@ -24,16 +40,12 @@ Mat2D rive::computeAlignment(Fit fit, Alignment alignment, const AABB& frame, co
         }
         case Fit::contain:
         {
-            float minScale =
-                std::fmin(frame.width() / contentWidth, frame.height() / contentHeight);
-            scaleX = scaleY = minScale;
+            scaleX = scaleY = fitRatio(frame, contentWidth, contentHeight, false);
             break;
         }
         case Fit::cover:
         {
-            float maxScale =
-                std::fmax(frame.width() / contentWidth, frame.height() / contentHeight);
-            scaleX = scaleY = maxScale;
+            scaleX = scaleY = fitRatio(frame, contentWidth, contentHeight, true);
             break;
         }
         case Fit::fitHeight:
@@ -55,18 +67,32 @@ Mat2D rive::computeAlignment(Fit fit, Alignment alignment, const AABB& frame, co
         }
         case Fit::scaleDown:
         {
-            float minScale =
-                std::fmin(frame.width() / contentWidth, frame.height() / contentHeight);
+            float minScale = fitRatio(frame, contentWidth, contentHeight, false);
             scaleX = scaleY = minScale < 1.0f ? minScale : 1.0f;
             break;
         }
     }
 
-    Mat2D translation;
-    translation[4] = frame.left() + frame.width() * 0.5f + (alignment.x() * frame.width() * 0.5f);
-    translation[5] = frame.top() + frame.height() * 0.5f + (alignment.y() * frame.height() * 0.5f);
+    // Place the scaled content's top left corner within the frame, rather than scaling an
+    // offset from the content's center, so that it only moves when the frame's size does.
+    float left = frame.left() + (frame.width() - contentWidth * scaleX) *
+                                    (alignment.x() + 1.0f) * 0.5f;
+    float top = frame.top() + (frame.height() - contentHeight * scaleY) *
+                                  (alignment.y() + 1.0f) * 0.5f;
+    if (snapToPixels)
+    {
+        // Halves always round up, wherever the content is, so that growing and shrinking the
+        // frame by a pixel moves it back and forth by the same amount.
+        left = std::floor(left + 0.5f);
+        top = std::floor(top + 0.5f);
+    }
 
-    return translation * Mat2D::fromScale(scaleX, scaleY) * Mat2D::fromTranslate(x, y);
+    return Mat2D(scaleX,
+                 0.0f,
+                 0.0f,
+                 scaleY,
+                 left - content.left() * scaleX,
+                 top - content.top() * scaleY);
 }
 
 void Renderer::translate(float tx, float ty) { this->transform(Mat2D(1, 0, 0, 1, tx, ty)); }
@@ -102,7 +128,9 @@ RenderPath::~RenderPath() { Counter::update(Counter::kPath, -1); }
 
 bool rive::isWhiteSpace(Unichar c) { return c <= ' ' || c == 0x2028; }
 
-SimpleArray<Paragraph> Font::shapeText(Span<const Unichar> text, Span<const TextRun> runs) const
+SimpleArray<Paragraph> Font::shapeText(Span<const Unichar> text,
+                                       Span<const TextRun> runs,
+                                       TextBaseDirection direction) const
 {
 #ifdef DEBUG
     size_t count = 0;
@@ -114,7 +142,7 @@ SimpleArray<Paragraph> Font::shapeText(Span<const Unichar> text, Span<const Text
     assert(count <= text.size());
 #endif
 
-    SimpleArray<Paragraph> paragraphs = onShapeText(text, runs);
+    SimpleArray<Paragraph> paragraphs = onShapeText(text, runs, direction);
     bool wantWhiteSpace = false;
     GlyphRun* lastRun = nullptr;
     size_t reserveSize = text.size() / 4;
@@ -348,6 +348,7 @@ static rive::GlyphRun shape_run(const rive::Unichar text[],
     gr.letterSpacing = tr.letterSpacing;
     gr.styleId = tr.styleId;
     gr.dir = tr.dir;
+    gr.level = tr.level;
 
     const float scale = tr.size / kStdScale;
     for (unsigned int i = 0; i < glyph_count; i++)
@@ -377,6 +378,7 @@ static rive::GlyphRun extract_subset(const rive::GlyphRun& orig, size_t start, s
     subset.lineHeight = orig.lineHeight;
     subset.letterSpacing = orig.letterSpacing;
     subset.dir = orig.dir;
+    subset.level = orig.level;
     subset.xpos.back() = 0; // since we're now the end of a run
     subset.styleId = orig.styleId;
 
@@ -413,6 +415,7 @@ static void perform_fallback(rive::rcp<rive::Font> fallbackFont,
                 origTextRun.script,
                 orig.styleId,
                 orig.dir,
+                orig.level,
             };
             auto gr = shape_run(&text[textStart], tr, textStart);
             if (gr.glyphs.size() > 0)
@@ -432,8 +435,22 @@ static void perform_fallback(rive::rcp<rive::Font> fallbackFont,
     }
 }
 
+static SBLevel base_level(rive::TextBaseDirection direction)
+{
+    switch (direction)
+    {
+        case rive::TextBaseDirection::ltr:
+            return 0;
+        case rive::TextBaseDirection::rtl:
+            return 1;
+        default:
+            return SBLevelDefaultLTR;
+    }
+}
+
 rive::SimpleArray<rive::Paragraph> HBFont::onShapeText(rive::Span<const rive::Unichar> text,
-                                                       rive::Span<const rive::TextRun> truns) const
+                                                       rive::Span<const rive::TextRun> truns,
+                                                       rive::TextBaseDirection direction) const
 {
 
     rive::SimpleArrayBuilder<rive::Paragraph> paragraphs;
@@ -451,13 +468,14 @@ rive::SimpleArray<rive::Paragraph> HBFont::onShapeText(rive::Span<const rive::Un
     SBUInteger paragraphStart = 0;
 
     SBAlgorithmRef bidiAlgorithm = SBAlgorithmCreate(&codepointSequence);
+    SBLevel baseLevel = base_level(direction);
     uint32_t unicharIndex = 0;
     uint32_t runTextIndex = 0;
 
     while (paragraphStart < text.size())
     {
         SBParagraphRef paragraph =
-            SBAlgorithmCreateParagraph(bidiAlgorithm, paragraphStart, INT32_MAX, SBLevelDefaultLTR);
+            SBAlgorithmCreateParagraph(bidiAlgorithm, paragraphStart, INT32_MAX, baseLevel);
         SBUInteger paragraphLength = SBParagraphGetLength(paragraph);
         // Next iteration reads the next paragraph (if any remain).
         paragraphStart += paragraphLength;
@@ -483,6 +501,7 @@ rive::SimpleArray<rive::Paragraph> HBFont::onShapeText(rive::Span<const rive::Un
                 (uint32_t)lastScript,
                 tr.styleId,
                 lastLevel & 1 ? rive::TextDirection::rtl : rive::TextDirection::ltr,
+                lastLevel,
             };
 
             runStartTextIndex = textIndex;
@@ -521,6 +540,7 @@ rive::SimpleArray<rive::Paragraph> HBFont::onShapeText(rive::Span<const rive::Un
                         (uint32_t)script,
                         back.styleId,
                         lastLevel & 1 ? rive::TextDirection::rtl : rive::TextDirection::ltr,
+                        lastLevel,
                     };
                     runStartTextIndex = textIndex;
                     bidiRuns.push_back(backRun);
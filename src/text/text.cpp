@@ -55,41 +55,40 @@ OrderedLine::OrderedLine(const Paragraph& paragraph,
         }
     }
 
-    // Now sort the runs visually.
-    if (paragraph.baseDirection == TextDirection::ltr || logicalRuns.empty())
-    {
-        m_runs = std::move(logicalRuns);
+    // Now sort the runs visually, reversing each sequence of runs at or above
+    // a level from the highest level down to the lowest odd one (rule L2 of
+    // the bidi algorithm).
+    uint8_t highestLevel = 0;
+    uint8_t lowestOddLevel = UINT8_MAX;
+    for (const GlyphRun* run : logicalRuns)
+    {
+        highestLevel = std::max(highestLevel, run->level);
+        if (run->level & 1)
+        {
+            lowestOddLevel = std::min(lowestOddLevel, run->level);
+        }
     }
-    else
+    for (uint8_t level = highestLevel; level >= lowestOddLevel; level--)
     {
-        std::vector<const GlyphRun*> visualRuns;
-        visualRuns.reserve(logicalRuns.size());
-
-        auto itr = logicalRuns.rbegin();
-        auto end = logicalRuns.rend();
-        const GlyphRun* first = *itr;
-        visualRuns.push_back(first);
-        size_t ltrIndex = 0;
-        TextDirection previousDirection = first->dir;
-        while (++itr != end)
+        auto itr = logicalRuns.begin();
+        auto end = logicalRuns.end();
+        while (itr != end)
         {
-            const GlyphRun* run = *itr;
-            if (run->dir == TextDirection::ltr && previousDirection == run->dir)
+            if ((*itr)->level < level)
             {
-                visualRuns.insert(visualRuns.begin() + ltrIndex, run);
+                itr++;
+                continue;
             }
-            else
+            auto sequenceEnd = itr;
+            while (sequenceEnd != end && (*sequenceEnd)->level >= level)
             {
-                if (run->dir == TextDirection::ltr)
-                {
-                    ltrIndex = visualRuns.size();
-                }
-                visualRuns.push_back(run);
+                sequenceEnd++;
             }
-            previousDirection = run->dir;
+            std::reverse(itr, sequenceEnd);
+            itr = sequenceEnd;
         }
-        m_runs = std::move(visualRuns);
     }
+    m_runs = std::move(logicalRuns);
 }
 
 static void appendUnicode(std::vector<rive::Unichar>& unichars, const char text[])
@@ -171,8 +170,14 @@ bool OrderedLine::buildEllipsisRuns(std::vector<const GlyphRun*>& logicalRuns,
                                        run.lineHeight,
                                        run.letterSpacing,
                                        (uint32_t)ellipsisCodePoints.size()}};
+            // Shape it in the paragraph's direction so it lands at the
+            // paragraph's end.
             auto nextEllipsisShape =
-                ellipsisFont->shapeText(ellipsisCodePoints, Span<TextRun>(ellipsisRuns, 1));
+                ellipsisFont->shapeText(ellipsisCodePoints,
+                                        Span<TextRun>(ellipsisRuns, 1),
+                                        paragraph.baseDirection == TextDirection::rtl
+                                            ? TextBaseDirection::rtl
+                                            : TextBaseDirection::ltr);
 
             // Hard assumption one run and para
             const Paragraph& para = nextEllipsisShape[0];
@@ -507,6 +512,16 @@ void Text::markPaintDirty() { addDirt(ComponentDirt::Paint); }
 
 void Text::alignValueChanged() { markShapeDirty(); }
 
+void Text::direction(TextBaseDirection value)
+{
+    if (m_direction == value)
+    {
+        return;
+    }
+    m_direction = value;
+    markShapeDirty();
+}
+
 void Text::sizingValueChanged() { markShapeDirty(); }
 
 void Text::overflowValueChanged()
@@ -590,6 +605,25 @@ bool Text::makeStyled(StyledText& styledText, bool withModifiers) const
     return !styledText.empty();
 }
 
+// Left and right align to the start and end of the paragraph, so they swap for
+// RTL paragraphs.
+static TextAlign paragraphAlign(TextAlign align, TextDirection direction)
+{
+    if (direction == TextDirection::ltr)
+    {
+        return align;
+    }
+    switch (align)
+    {
+        case TextAlign::left:
+            return TextAlign::right;
+        case TextAlign::right:
+            return TextAlign::left;
+        default:
+            return align;
+    }
+}
+
 static SimpleArray<SimpleArray<GlyphLine>> breakLines(const SimpleArray<Paragraph>& paragraphs,
                                                       float width,
                                                       TextAlign align)
@@ -617,7 +651,7 @@ static SimpleArray<SimpleArray<GlyphLine>> breakLines(const SimpleArray<Paragrap
                                       lines[paragraphIndex],
                                       para.runs,
                                       paragraphWidth,
-                                      align);
+                                      paragraphAlign(align, para.baseDirection));
         paragraphIndex++;
     }
     return lines;
@@ -648,7 +682,8 @@ void Text::update(ComponentDirt value)
         {
             makeStyled(m_modifierStyledText, false);
             auto runs = m_modifierStyledText.runs();
-            m_modifierShape = runs[0].font->shapeText(m_modifierStyledText.unichars(), runs);
+            m_modifierShape =
+                runs[0].font->shapeText(m_modifierStyledText.unichars(), runs, direction());
             m_modifierLines = breakLines(m_modifierShape,
                                          sizing() == TextSizing::autoWidth ? -1.0f : width(),
                                          (TextAlign)alignValue());
@@ -666,7 +701,7 @@ void Text::update(ComponentDirt value)
         if (makeStyled(m_styledText))
         {
             auto runs = m_styledText.runs();
-            m_shape = runs[0].font->shapeText(m_styledText.unichars(), runs);
+            m_shape = runs[0].font->shapeText(m_styledText.unichars(), runs, direction());
             m_lines = breakLines(m_shape,
                                  sizing() == TextSizing::autoWidth ? -1.0f : width(),
                                  (TextAlign)alignValue());
@@ -751,6 +786,7 @@ void Text::addModifierGroup(TextModifierGroup* group) {}
 void Text::markShapeDirty() {}
 void Text::update(ComponentDirt value) {}
 void Text::alignValueChanged() {}
+void Text::direction(TextBaseDirection value) { m_direction = value; }
 void Text::sizingValueChanged() {}
 void Text::overflowValueChanged() {}
 void Text::widthChanged() {}
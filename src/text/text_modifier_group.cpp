@@ -179,6 +179,7 @@ static TextRun copyRun(const TextRun& source, uint32_t unicharCount)
         source.script,
         source.styleId,
         source.dir,
+        source.level,
     };
 }
 
@@ -216,6 +217,7 @@ TextRun TextModifierGroup::modifyShape(const Text& text, TextRun run, float stre
             run.script,
             run.styleId,
             run.dir,
+            run.level,
         };
     }
     else
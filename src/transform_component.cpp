@@ -3,6 +3,8 @@
 #include "rive/shapes/clipping_shape.hpp"
 #include "rive/math/vec2d.hpp"
 #include "rive/constraints/constraint.hpp"
+#include "rive/artboard.hpp"
+#include <cmath>
 
 using namespace rive;
 
@@ -33,16 +35,24 @@ void TransformComponent::markTransformDirty()
 
 void TransformComponent::updateTransform()
 {
-    m_Transform = Mat2D::fromRotation(rotation());
-    m_Transform[4] = x();
-    m_Transform[5] = y();
-    m_Transform.scaleByValues(scaleX(), scaleY());
+    Mat2D transform = Mat2D::fromRotation(rotation());
+    transform[4] = x();
+    transform[5] = y();
+    transform.scaleByValues(scaleX(), scaleY());
+    // Keep the last finite transform so that one bad key doesn't blank the whole artboard.
+    if (!transform.isFinite())
+    {
+        artboard()->reportNonFinite(this, "transform", PipelineStage::apply);
+        return;
+    }
+    m_Transform = transform;
 }
 
 AABB TransformComponent::localBounds() const { return AABB(); }
 
 void TransformComponent::updateWorldTransform()
 {
+    Mat2D lastWorldTransform = m_WorldTransform;
     if (m_ParentTransformComponent != nullptr)
     {
         m_WorldTransform = m_ParentTransformComponent->m_WorldTransform * m_Transform;
@@ -51,11 +61,29 @@ void TransformComponent::updateWorldTransform()
     {
         m_WorldTransform = m_Transform;
     }
+    if (!m_WorldTransform.isFinite())
+    {
+        artboard()->reportNonFinite(this, "worldTransform", PipelineStage::apply);
+        m_WorldTransform = lastWorldTransform.isFinite() ? lastWorldTransform : Mat2D();
+        lastWorldTransform = m_WorldTransform;
+    }
 
     for (auto constraint : m_Constraints)
     {
         constraint->constrain(this);
     }
+    if (!m_WorldTransform.isFinite())
+    {
+        // Falls back to the transform before constraints, which is known to be finite.
+        artboard()->reportNonFinite(this, "worldTransform", PipelineStage::constrain);
+        m_WorldTransform = m_ParentTransformComponent != nullptr
+                               ? m_ParentTransformComponent->m_WorldTransform * m_Transform
+                               : m_Transform;
+        if (!m_WorldTransform.isFinite())
+        {
+            m_WorldTransform = lastWorldTransform;
+        }
+    }
 }
 
 void TransformComponent::update(ComponentDirt value)
@@ -70,10 +98,18 @@ void TransformComponent::update(ComponentDirt value)
     }
     if (hasDirt(value, ComponentDirt::RenderOpacity))
     {
-        m_RenderOpacity = opacity();
+        float renderOpacity = opacity();
         if (m_ParentTransformComponent != nullptr)
         {
-            m_RenderOpacity *= m_ParentTransformComponent->childOpacity();
+            renderOpacity *= m_ParentTransformComponent->childOpacity();
+        }
+        if (std::isfinite(renderOpacity))
+        {
+            m_RenderOpacity = renderOpacity;
+        }
+        else
+        {
+            artboard()->reportNonFinite(this, "opacity", PipelineStage::apply);
         }
     }
 }
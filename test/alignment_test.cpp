@@ -0,0 +1,81 @@
+#include <catch.hpp>
+#include "rive/renderer.hpp"
+#include <cmath>
+
+using namespace rive;
+
+TEST_CASE("contain picks the limiting side exactly", "[alignment]")
+{
+    AABB content(0, 0, 500, 500);
+
+    auto wide = computeAlignment(Fit::contain, Alignment::center, AABB(0, 0, 800, 600), content);
+    REQUIRE(wide[0] == 1.2f);
+    REQUIRE(wide[3] == 1.2f);
+    REQUIRE(wide[4] == 100.0f);
+    REQUIRE(wide[5] == 0.0f);
+
+    // Either side fits a square frame.
+    auto square =
+        computeAlignment(Fit::contain, Alignment::center, AABB(0, 0, 1000, 1000), content);
+    REQUIRE(square[0] == 2.0f);
+    REQUIRE(square[4] == 0.0f);
+    REQUIRE(square[5] == 0.0f);
+}
+
+TEST_CASE("alignment places content by its slack in the frame", "[alignment]")
+{
+    AABB content(-20, 10, 400, 210);
+    Alignment alignments[] = {Alignment::topLeft, Alignment::center, Alignment::bottomRight};
+
+    for (auto fit : {Fit::fill, Fit::contain, Fit::cover, Fit::none, Fit::scaleDown})
+    {
+        for (auto alignment : alignments)
+        {
+            AABB frame(0, 0, 801, 601);
+            auto transform = computeAlignment(fit, alignment, frame, content);
+
+            // The content's corners land where the alignment puts them.
+            Vec2D topLeft = transform * Vec2D(content.left(), content.top());
+            Vec2D bottomRight = transform * Vec2D(content.right(), content.bottom());
+            float slackX = frame.width() - (bottomRight.x - topLeft.x);
+            float slackY = frame.height() - (bottomRight.y - topLeft.y);
+            CHECK(topLeft.x == Approx(slackX * (alignment.x() + 1) * 0.5f).margin(1e-3));
+            CHECK(topLeft.y == Approx(slackY * (alignment.y() + 1) * 0.5f).margin(1e-3));
+        }
+    }
+}
+
+TEST_CASE("snapped alignment only moves by whole pixels as the frame resizes", "[alignment]")
+{
+    AABB content(0, 0, 400, 200);
+    float width = 401, height = 301;
+    float previousTop = 0;
+    unsigned seed = 7;
+
+    for (int i = 0; i < 500; i++)
+    {
+        // A fixed sequence of one pixel steps either way.
+        seed = seed * 1103515245 + 12345;
+        height += (seed >> 16) & 1 ? 1 : -1;
+        seed = seed * 1103515245 + 12345;
+        width += (seed >> 16) & 1 ? 1 : -1;
+
+        AABB frame(0, 0, width, height);
+        auto snapped = computeAlignment(Fit::contain, Alignment::center, frame, content, true);
+        auto exact = computeAlignment(Fit::contain, Alignment::center, frame, content);
+
+        REQUIRE(snapped[0] == exact[0]);
+        REQUIRE(snapped[4] == std::floor(snapped[4]));
+        REQUIRE(snapped[5] == std::floor(snapped[5]));
+        // Halves round up, so snapping never moves content by more than half a pixel.
+        REQUIRE(snapped[4] - exact[4] <= 0.5f);
+        REQUIRE(snapped[4] - exact[4] > -0.5f);
+        REQUIRE(snapped[5] - exact[5] <= 0.5f);
+        REQUIRE(snapped[5] - exact[5] > -0.5f);
+        if (i > 0)
+        {
+            REQUIRE(std::abs(snapped[5] - previousTop) <= 1.0f);
+        }
+        previousTop = snapped[5];
+    }
+}
@@ -152,3 +152,36 @@ TEST_CASE("bad contour", "[contourmeasure]")
     auto machine = artboard->defaultStateMachine();
     machine->advanceAndApply(0.0f);
 }
+
+TEST_CASE("zero-length segments", "[contourmeasure]")
+{
+    RawPath path;
+    path.moveTo(0, 0);
+    path.lineTo(10, 0);
+    // Collapsed to a point.
+    path.cubicTo(10, 0, 10, 0, 10, 0);
+    path.lineTo(10, 0);
+    // Control points on the end points.
+    path.cubicTo(10, 0, 10, 10, 10, 10);
+
+    ContourMeasureIter iter(path, false);
+    auto cm = iter.next();
+    REQUIRE(cm);
+    REQUIRE(nearly_eq(cm->length(), 20, 0.001f));
+
+    for (float distance : {0.0f, 5.0f, 10.0f, 10.001f, 15.0f, 20.0f})
+    {
+        auto posTan = cm->getPosTan(distance);
+        REQUIRE(std::isfinite(posTan.pos.x));
+        REQUIRE(std::isfinite(posTan.pos.y));
+        REQUIRE(nearly_eq(posTan.tan.length(), 1, 0.001f));
+    }
+
+    // The end tangent comes from the segment rather than the coincident control point.
+    auto end = cm->getPosTan(20);
+    REQUIRE(nearly_eq(end.tan, Vec2D(0, 1), 0.001f));
+
+    RawPath segment;
+    cm->getSegment(5, 15, &segment, true);
+    REQUIRE(!segment.empty());
+}
@@ -37,3 +37,36 @@ TEST_CASE("draw rules load and sort correctly", "[draw rules]")
         artboard->draw(&renderer);
     }
 }
+
+class CountingRenderer : public rive::NoOpRenderer
+{
+public:
+    int drawn = 0;
+    void drawPath(rive::RenderPath*, rive::RenderPaint*) override { drawn++; }
+};
+
+TEST_CASE("drawOnly draws the given drawables alone", "[draw rules]")
+{
+    auto file = ReadRiveFile("../../test/assets/draw_rule_cycle.riv");
+    auto artboard = file->artboardDefault();
+    artboard->advance(0.0f);
+
+    std::unordered_set<uint32_t> all;
+    for (size_t id = 0; id < artboard->objects().size(); id++)
+    {
+        all.insert((uint32_t)id);
+    }
+    auto blue = artboard->find<rive::Shape>("Blue");
+    REQUIRE(blue != nullptr);
+
+    CountingRenderer full, none, everything, alone;
+    artboard->draw(&full, rive::Artboard::DrawOption::kHideBG);
+    artboard->drawOnly(&none, {});
+    artboard->drawOnly(&everything, all);
+    artboard->drawOnly(&alone, {artboard->idOf(blue)});
+
+    CHECK(none.drawn == 0);
+    CHECK(everything.drawn == full.drawn);
+    CHECK(alone.drawn > 0);
+    CHECK(alone.drawn < full.drawn);
+}
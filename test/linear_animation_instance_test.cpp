@@ -408,3 +408,104 @@ TEST_CASE("LinearAnimationInstance override loop", "[animation]")
     delete linearAnimationInstance;
     delete linearAnimation;
 }
+
+TEST_CASE("LinearAnimationInstance play range", "[animation]")
+{
+    rive::NoOpFactory emptyFactory;
+    rive::Artboard ab(&emptyFactory);
+    auto abi = ab.instance();
+
+    rive::LinearAnimation* linearAnimation = new rive::LinearAnimation();
+    // duration in seconds is 5
+    linearAnimation->duration(10);
+    linearAnimation->fps(2);
+    linearAnimation->loopValue(static_cast<int>(rive::Loop::oneShot));
+
+    rive::LinearAnimationInstance* linearAnimationInstance =
+        new rive::LinearAnimationInstance(linearAnimation, abi.get());
+
+    // The range excludes the authored end, and moves the time into it
+    REQUIRE(linearAnimationInstance->playRange(1.0, 3.0) == true);
+    REQUIRE(linearAnimationInstance->time() == 1.0);
+    REQUIRE(linearAnimationInstance->loop() == rive::Loop::loop);
+
+    bool continuePlaying = linearAnimationInstance->advance(1.5);
+    REQUIRE(continuePlaying == true);
+    REQUIRE(linearAnimationInstance->time() == 2.5);
+    REQUIRE(linearAnimationInstance->didLoop() == false);
+
+    // wraps at the end of the range instead of stopping at the end
+    continuePlaying = linearAnimationInstance->advance(1.0);
+    REQUIRE(continuePlaying == true);
+    REQUIRE(linearAnimationInstance->time() == 1.5);
+    REQUIRE(linearAnimationInstance->didLoop() == true);
+
+    // ping-pong bounces off the end of the range
+    linearAnimationInstance->loopValue(static_cast<int>(rive::Loop::pingPong));
+    continuePlaying = linearAnimationInstance->advance(2.0);
+    REQUIRE(continuePlaying == true);
+    REQUIRE(linearAnimationInstance->time() == 2.5);
+    REQUIRE(linearAnimationInstance->direction() == -1);
+    REQUIRE(linearAnimationInstance->didLoop() == true);
+
+    // clearing the range restores the animation's bounds
+    linearAnimationInstance->clearPlayRange();
+    REQUIRE(linearAnimationInstance->hasPlayRange() == false);
+    REQUIRE(linearAnimationInstance->playStartSeconds() == 0.0);
+    REQUIRE(linearAnimationInstance->playEndSeconds() == 5.0);
+
+    delete linearAnimationInstance;
+    delete linearAnimation;
+}
+
+TEST_CASE("LinearAnimationInstance play range narrower than a frame", "[animation]")
+{
+    rive::NoOpFactory emptyFactory;
+    rive::Artboard ab(&emptyFactory);
+    auto abi = ab.instance();
+
+    rive::LinearAnimation* linearAnimation = new rive::LinearAnimation();
+    // duration in seconds is 5, a frame is half a second
+    linearAnimation->duration(10);
+    linearAnimation->fps(2);
+    linearAnimation->loopValue(static_cast<int>(rive::Loop::loop));
+
+    rive::LinearAnimationInstance* linearAnimationInstance =
+        new rive::LinearAnimationInstance(linearAnimation, abi.get());
+
+    // reversed and too narrow, so it's widened to a frame
+    REQUIRE(linearAnimationInstance->playRange(2.25, 2.0) == false);
+    REQUIRE(linearAnimationInstance->playStartSeconds() == 2.0);
+    REQUIRE(linearAnimationInstance->playEndSeconds() == 2.5);
+
+    // at the end of the animation, it's widened backwards
+    REQUIRE(linearAnimationInstance->playRange(5.0, 5.0) == false);
+    REQUIRE(linearAnimationInstance->playStartSeconds() == 4.5);
+    REQUIRE(linearAnimationInstance->playEndSeconds() == 5.0);
+
+    delete linearAnimationInstance;
+    delete linearAnimation;
+}
+
+TEST_CASE("LinearAnimationInstance play range without frames", "[animation]")
+{
+    rive::NoOpFactory emptyFactory;
+    rive::Artboard ab(&emptyFactory);
+    auto abi = ab.instance();
+
+    rive::LinearAnimation* linearAnimation = new rive::LinearAnimation();
+    linearAnimation->duration(10);
+    linearAnimation->fps(0);
+    linearAnimation->loopValue(static_cast<int>(rive::Loop::loop));
+
+    rive::LinearAnimationInstance* linearAnimationInstance =
+        new rive::LinearAnimationInstance(linearAnimation, abi.get());
+
+    // a frame would last forever, so there's no range to play
+    REQUIRE(linearAnimationInstance->playRange(1.0, 3.0) == false);
+    REQUIRE(linearAnimationInstance->playRange(2.0, 2.0) == false);
+    REQUIRE(linearAnimationInstance->hasPlayRange() == false);
+
+    delete linearAnimationInstance;
+    delete linearAnimation;
+}
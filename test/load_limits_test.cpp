@@ -0,0 +1,199 @@
+#include <rive/file.hpp>
+#include <rive/animation/keyframe_double.hpp>
+#include <rive/assets/file_asset_contents.hpp>
+#include <rive/nested_artboard.hpp>
+#include <rive/node.hpp>
+#include "rive_file_reader.hpp"
+#include <catch.hpp>
+#include <cstdio>
+
+// Builds .riv files object by object so that oversized inputs don't need to
+// be checked in.
+class RivWriter
+{
+    std::vector<uint8_t> m_Bytes;
+
+public:
+    RivWriter()
+    {
+        m_Bytes = {'R', 'I', 'V', 'E'};
+        varUint(rive::File::majorVersion);
+        varUint(rive::File::minorVersion);
+        varUint(0); // file id
+        varUint(0); // empty property ToC
+        object(rive::BackboardBase::typeKey);
+    }
+
+    RivWriter& varUint(uint64_t value)
+    {
+        while (value >= 0x80)
+        {
+            m_Bytes.push_back(static_cast<uint8_t>(0x80 | (value & 0x7F)));
+            value >>= 7;
+        }
+        m_Bytes.push_back(static_cast<uint8_t>(value));
+        return *this;
+    }
+
+    // An object without properties.
+    RivWriter& object(uint16_t typeKey) { return varUint(typeKey).varUint(0); }
+
+    RivWriter& nestedArtboard(uint64_t artboardId)
+    {
+        return varUint(rive::NestedArtboardBase::typeKey)
+            .varUint(rive::ComponentBase::parentIdPropertyKey)
+            .varUint(0)
+            .varUint(rive::NestedArtboardBase::artboardIdPropertyKey)
+            .varUint(artboardId)
+            .varUint(0);
+    }
+
+    rive::Span<const uint8_t> bytes() const { return m_Bytes; }
+};
+
+static std::unique_ptr<rive::File> import(const RivWriter& writer,
+                                          const rive::LoadLimits& limits,
+                                          rive::ImportResult* result,
+                                          rive::LoadLimitReport* report)
+{
+    return rive::File::import(writer.bytes(), &gNoOpFactory, limits, result, report);
+}
+
+TEST_CASE("object count limit rejects before reading the rest", "[load_limits]")
+{
+    RivWriter writer;
+    writer.object(rive::ArtboardBase::typeKey);
+    for (int i = 0; i < 1000000; i++)
+    {
+        writer.object(rive::NodeBase::typeKey);
+    }
+
+    rive::LoadLimits limits;
+    limits.maxObjects = 1000;
+    rive::ImportResult result;
+    rive::LoadLimitReport report;
+    auto file = import(writer, limits, &result, &report);
+
+    REQUIRE(file == nullptr);
+    REQUIRE(result == rive::ImportResult::limitExceeded);
+    REQUIRE(report.limit == rive::LoadLimits::Limit::objects);
+    REQUIRE(report.observed == 1001);
+}
+
+TEST_CASE("key frame limit counts key frames of every animation", "[load_limits]")
+{
+    RivWriter writer;
+    writer.object(rive::ArtboardBase::typeKey);
+    for (int i = 0; i < 100; i++)
+    {
+        writer.object(rive::KeyFrameDoubleBase::typeKey);
+    }
+
+    rive::LoadLimits limits;
+    limits.maxKeyFrames = 10;
+    rive::ImportResult result;
+    rive::LoadLimitReport report;
+    import(writer, limits, &result, &report);
+
+    REQUIRE(result == rive::ImportResult::limitExceeded);
+    REQUIRE(report.limit == rive::LoadLimits::Limit::keyFrames);
+    REQUIRE(report.observed == 11);
+}
+
+TEST_CASE("asset byte limit sums in-band contents", "[load_limits]")
+{
+    RivWriter writer;
+    writer.varUint(rive::FileAssetContentsBase::typeKey)
+        .varUint(rive::FileAssetContentsBase::bytesPropertyKey)
+        .varUint(64);
+    for (int i = 0; i < 64; i++)
+    {
+        writer.varUint(0x7F);
+    }
+    writer.varUint(0);
+
+    rive::LoadLimits limits;
+    limits.maxAssetBytes = 32;
+    rive::ImportResult result;
+    rive::LoadLimitReport report;
+    import(writer, limits, &result, &report);
+
+    REQUIRE(result == rive::ImportResult::limitExceeded);
+    REQUIRE(report.limit == rive::LoadLimits::Limit::assetBytes);
+    REQUIRE(report.observed == 64);
+}
+
+TEST_CASE("nesting depth limit follows nested artboards", "[load_limits]")
+{
+    RivWriter writer;
+    for (int i = 0; i < 4; i++)
+    {
+        writer.object(rive::ArtboardBase::typeKey);
+        if (i < 3)
+        {
+            writer.nestedArtboard(i + 1);
+        }
+    }
+
+    rive::ImportResult result;
+    rive::LoadLimitReport report;
+
+    rive::LoadLimits limits;
+    limits.maxNestingDepth = 3;
+    REQUIRE(import(writer, limits, &result, &report) != nullptr);
+    REQUIRE(result == rive::ImportResult::success);
+
+    limits.maxNestingDepth = 2;
+    REQUIRE(import(writer, limits, &result, &report) == nullptr);
+    REQUIRE(result == rive::ImportResult::limitExceeded);
+    REQUIRE(report.limit == rive::LoadLimits::Limit::nestingDepth);
+    REQUIRE(report.observed == 3);
+}
+
+TEST_CASE("artboards nesting themselves exceed any depth", "[load_limits]")
+{
+    RivWriter writer;
+    writer.object(rive::ArtboardBase::typeKey);
+    writer.nestedArtboard(0);
+
+    rive::ImportResult result;
+    rive::LoadLimitReport report;
+    import(writer, rive::LoadLimits(), &result, &report);
+
+    REQUIRE(result == rive::ImportResult::limitExceeded);
+    REQUIRE(report.limit == rive::LoadLimits::Limit::nestingDepth);
+}
+
+TEST_CASE("unlimited loads accept what defaults reject", "[load_limits]")
+{
+    RivWriter writer;
+    writer.object(rive::ArtboardBase::typeKey);
+    for (int i = 0; i < 100; i++)
+    {
+        writer.object(rive::NodeBase::typeKey);
+    }
+
+    rive::LoadLimits limits;
+    limits.maxObjects = 10;
+    rive::ImportResult result;
+    REQUIRE(import(writer, limits, &result, nullptr) == nullptr);
+    REQUIRE(import(writer, rive::LoadLimits::unlimited(), &result, nullptr) != nullptr);
+    REQUIRE(result == rive::ImportResult::success);
+}
+
+TEST_CASE("declared string lengths past the end are rejected", "[load_limits]")
+{
+    RivWriter writer;
+    writer.object(rive::ArtboardBase::typeKey);
+    // A name claiming a terabyte must fail without allocating it.
+    writer.varUint(rive::NodeBase::typeKey)
+        .varUint(rive::ComponentBase::namePropertyKey)
+        .varUint(uint64_t(1) << 40)
+        .varUint(0);
+
+    rive::ImportResult result;
+    auto file = import(writer, rive::LoadLimits(), &result, nullptr);
+
+    REQUIRE(file == nullptr);
+    REQUIRE(result == rive::ImportResult::malformed);
+}
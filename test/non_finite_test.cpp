@@ -0,0 +1,92 @@
+#include <rive/artboard.hpp>
+#include <rive/constraints/scale_constraint.hpp>
+#include <rive/node.hpp>
+#include "utils/no_op_factory.hpp"
+#include <catch.hpp>
+#include <cmath>
+
+TEST_CASE("broken constraints don't blank their component", "[constraints]")
+{
+    rive::NoOpFactory emptyFactory;
+    rive::Artboard artboard(&emptyFactory);
+    rive::Node* target = new rive::Node();
+    rive::Node* constrained = new rive::Node();
+    rive::ScaleConstraint* constraint = new rive::ScaleConstraint();
+    rive::Node* sibling = new rive::Node();
+
+    constrained->x(10.0f);
+    constrained->y(20.0f);
+    // Scales by NaN, like a divide by zero in the file's data would.
+    constraint->copyFactor(std::nanf(""));
+    sibling->x(30.0f);
+
+    artboard.addObject(&artboard);
+    artboard.addObject(target);
+    artboard.addObject(constrained);
+    artboard.addObject(constraint);
+    artboard.addObject(sibling);
+    target->parentId(0);
+    constrained->parentId(0);
+    constraint->parentId(2);
+    constraint->targetId(1);
+    sibling->parentId(0);
+
+    REQUIRE(artboard.initialize() == rive::StatusCode::Ok);
+
+    artboard.advance(0.0f);
+
+    // The constrained node falls back to its unconstrained transform.
+    const rive::Mat2D& world = constrained->worldTransform();
+    REQUIRE(world.isFinite());
+    REQUIRE(world[4] == 10.0f);
+    REQUIRE(world[5] == 20.0f);
+    REQUIRE(sibling->worldTransform()[4] == 30.0f);
+
+#ifdef DEBUG
+    const auto& values = artboard.nonFiniteValues();
+    REQUIRE(values.size() == 1);
+    REQUIRE(values[0].component == constrained);
+    REQUIRE(std::string(values[0].property) == "worldTransform");
+    REQUIRE(values[0].stage == rive::PipelineStage::constrain);
+#else
+    REQUIRE(artboard.nonFiniteValues().empty());
+#endif
+
+    // Reported once, however many times it's caught.
+    constrained->x(11.0f);
+    artboard.advance(0.0f);
+    REQUIRE(constrained->worldTransform()[4] == 11.0f);
+#ifdef DEBUG
+    REQUIRE(artboard.nonFiniteValues().size() == 1);
+#endif
+}
+
+TEST_CASE("non-finite keys keep the last finite transform", "[node]")
+{
+    rive::NoOpFactory emptyFactory;
+    rive::Artboard artboard(&emptyFactory);
+    rive::Node* node = new rive::Node();
+    node->x(5.0f);
+
+    artboard.addObject(&artboard);
+    artboard.addObject(node);
+    node->parentId(0);
+
+    REQUIRE(artboard.initialize() == rive::StatusCode::Ok);
+    artboard.advance(0.0f);
+    REQUIRE(node->worldTransform()[4] == 5.0f);
+
+    node->scaleX(INFINITY);
+    node->y(std::nanf(""));
+    artboard.advance(0.0f);
+    REQUIRE(node->transform().isFinite());
+    REQUIRE(node->worldTransform()[4] == 5.0f);
+    REQUIRE(node->worldTransform()[0] == 1.0f);
+
+#ifdef DEBUG
+    const auto& values = artboard.nonFiniteValues();
+    REQUIRE(values.size() == 1);
+    REQUIRE(std::string(values[0].property) == "transform");
+    REQUIRE(values[0].stage == rive::PipelineStage::apply);
+#endif
+}
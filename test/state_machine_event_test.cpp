@@ -17,6 +17,8 @@
 #include "rive/animation/listener_input_change.hpp"
 #include "rive/animation/listener_fire_event.hpp"
 #include "rive/animation/entry_state.hpp"
+#include "rive/animation/nested_state_machine.hpp"
+#include "rive/nested_artboard.hpp"
 #include "rive/node.hpp"
 #include "catch.hpp"
 #include "rive_file_reader.hpp"
@@ -91,6 +93,47 @@ TEST_CASE("hit testing via a state machine works", "[file]")
     REQUIRE(trigger->didFire());
 }
 
+TEST_CASE("listeners can be looked up under a position", "[file]")
+{
+    auto file = ReadRiveFile("../../test/assets/bullet_man.riv");
+
+    auto artboard = file->artboard("Bullet Man")->instance();
+    auto stateMachine = artboard->stateMachineAt(0);
+    artboard->advance(0.0f);
+    stateMachine->advance(0.0f);
+
+    REQUIRE(stateMachine->hasListenerAt(rive::Vec2D(71.0f, 263.0f), rive::ListenerType::down));
+    REQUIRE(!stateMachine->hasListenerAt(rive::Vec2D(71.0f, 263.0f), rive::ListenerType::move));
+    REQUIRE(!stateMachine->hasListenerAt(rive::Vec2D(-500.0f, -500.0f), rive::ListenerType::down));
+    REQUIRE(!stateMachine->hasListenerAt(rive::Vec2D(NAN, 263.0f), rive::ListenerType::down));
+}
+
+TEST_CASE("pointers outside the artboard are accepted", "[file]")
+{
+    auto file = ReadRiveFile("../../test/assets/bullet_man.riv");
+
+    auto artboard = file->artboard("Bullet Man")->instance();
+    auto stateMachine = artboard->stateMachineAt(0);
+    artboard->advance(0.0f);
+    stateMachine->advance(0.0f);
+
+    auto trigger = stateMachine->getTrigger("Light");
+    REQUIRE(trigger != nullptr);
+
+    // A drag continuing past the view's edges, then a release far outside.
+    stateMachine->pointerDown(rive::Vec2D(71.0f, 263.0f));
+    REQUIRE(trigger->didFire());
+    stateMachine->advance(0.0f);
+    stateMachine->pointerMove(rive::Vec2D(-1e6f, 263.0f));
+    stateMachine->pointerMove(rive::Vec2D(INFINITY, NAN));
+    stateMachine->pointerUp(rive::Vec2D(1e6f, 1e6f));
+    REQUIRE(!trigger->didFire());
+
+    // Non-finite presses are dropped rather than hitting anything.
+    stateMachine->pointerDown(rive::Vec2D(NAN, NAN));
+    REQUIRE(!trigger->didFire());
+}
+
 TEST_CASE("hit a toggle boolean listener", "[file]")
 {
     auto file = ReadRiveFile("../../test/assets/light_switch.riv");
@@ -120,6 +163,100 @@ TEST_CASE("hit a toggle boolean listener", "[file]")
     REQUIRE(switchButton->value() == true);
 }
 
+// listener_conditions.riv has a "front" square on top of a "back" one, with
+// press listeners on both and an enter listener on the front. The front's
+// listeners only fire while Enabled is set. Its nested "child" artboard has a
+// button whose press listener only fires while the child's own Armed is set.
+static std::unique_ptr<rive::StateMachineInstance> listenerConditions(
+    rive::ArtboardInstance* artboard)
+{
+    auto stateMachine = artboard->stateMachineAt(0);
+    REQUIRE(stateMachine != nullptr);
+    artboard->advance(0.0f);
+    stateMachine->advance(0.0f);
+    return stateMachine;
+}
+
+static void press(rive::StateMachineInstance* stateMachine, rive::Vec2D position)
+{
+    stateMachine->pointerDown(position);
+    stateMachine->pointerUp(position);
+}
+
+TEST_CASE("listener conditions gate pointer events", "[file]")
+{
+    auto file = ReadRiveFile("../../test/assets/listener_conditions.riv");
+    auto artboard = file->artboard("Main")->instance();
+    auto stateMachine = listenerConditions(artboard.get());
+
+    auto listener = stateMachine->stateMachine()->listener(0);
+    REQUIRE(listener->conditionCount() == 1);
+    REQUIRE(stateMachine->stateMachine()->listener(1)->conditionCount() == 0);
+
+    auto enabled = stateMachine->getBool("Enabled");
+    auto frontPressed = stateMachine->getBool("FrontPressed");
+    auto backPressed = stateMachine->getBool("BackPressed");
+
+    enabled->value(false);
+    press(stateMachine.get(), rive::Vec2D(150.0f, 150.0f));
+    REQUIRE(frontPressed->value() == false);
+    // The gated front doesn't stop the press from reaching the back under it.
+    REQUIRE(backPressed->value() == true);
+
+    enabled->value(true);
+    backPressed->value(false);
+    press(stateMachine.get(), rive::Vec2D(150.0f, 150.0f));
+    REQUIRE(frontPressed->value() == true);
+    REQUIRE(backPressed->value() == true);
+}
+
+TEST_CASE("listener conditions don't hold back hover changes", "[file]")
+{
+    auto file = ReadRiveFile("../../test/assets/listener_conditions.riv");
+    auto artboard = file->artboard("Main")->instance();
+    auto stateMachine = listenerConditions(artboard.get());
+
+    auto enabled = stateMachine->getBool("Enabled");
+    auto entered = stateMachine->getBool("Entered");
+
+    // Entering while disabled doesn't toggle, and still counts as hovering, so
+    // moving on the front once enabled doesn't enter it again.
+    enabled->value(false);
+    stateMachine->pointerMove(rive::Vec2D(150.0f, 150.0f));
+    enabled->value(true);
+    stateMachine->pointerMove(rive::Vec2D(160.0f, 160.0f));
+    REQUIRE(entered->value() == false);
+
+    stateMachine->pointerMove(rive::Vec2D(250.0f, 250.0f));
+    stateMachine->pointerMove(rive::Vec2D(150.0f, 150.0f));
+    REQUIRE(entered->value() == true);
+}
+
+TEST_CASE("nested listener conditions use the nested inputs", "[file]")
+{
+    auto file = ReadRiveFile("../../test/assets/listener_conditions.riv");
+    auto artboard = file->artboard("Main")->instance();
+    auto stateMachine = listenerConditions(artboard.get());
+
+    auto nestedArtboard = artboard->find<rive::NestedArtboard>("child");
+    REQUIRE(nestedArtboard != nullptr);
+    auto nestedAnimation = nestedArtboard->nestedAnimations()[0];
+    auto nested = nestedAnimation->as<rive::NestedStateMachine>()->stateMachineInstance();
+    auto armed = nested->getBool("Armed");
+    auto pressed = nested->getBool("Pressed");
+
+    // The parent's Enabled has no say over the nested listener.
+    stateMachine->getBool("Enabled")->value(true);
+    REQUIRE(!stateMachine->hasListenerAt(rive::Vec2D(400.0f, 400.0f), rive::ListenerType::down));
+    press(stateMachine.get(), rive::Vec2D(400.0f, 400.0f));
+    REQUIRE(pressed->value() == false);
+
+    armed->value(true);
+    REQUIRE(stateMachine->hasListenerAt(rive::Vec2D(400.0f, 400.0f), rive::ListenerType::down));
+    press(stateMachine.get(), rive::Vec2D(400.0f, 400.0f));
+    REQUIRE(pressed->value() == true);
+}
+
 TEST_CASE("events load correctly on a listener", "[events]")
 {
     auto file = ReadRiveFile("../../test/assets/event_on_listener.riv");
@@ -197,3 +197,100 @@ TEST_CASE("1D blend state keeps keepsGoing true even when animations themselves
 
     delete stateMachineInstance;
 }
+
+static float appliedMix(const rive::StateMachineInstance& machine, const char name[])
+{
+    for (auto& applied : machine.appliedAnimations())
+    {
+        if (applied.animation->name() == name)
+        {
+            return applied.mix;
+        }
+    }
+    return -1.0f;
+}
+
+TEST_CASE("applied animations report 1D blend weights and times", "[state_machine]")
+{
+    auto file = ReadRiveFile("../../test/assets/blend_test.riv");
+
+    auto artboard = file->artboard();
+    auto abi = artboard->instance();
+    rive::StateMachineInstance machine(artboard->stateMachine("blend"), abi.get());
+
+    // The first advance only leaves the entry state.
+    machine.advance(0.0f);
+    machine.advance(0.25f);
+
+    // verticality is 66.63: vertical sits at 100, horizontal and rotate at 0.
+    auto& applied = machine.appliedAnimations();
+    REQUIRE(applied.size() == 3);
+    for (auto& animation : applied)
+    {
+        REQUIRE(animation.layer == 0);
+        REQUIRE(animation.time == Approx(0.25f));
+    }
+    REQUIRE(appliedMix(machine, "vertical") == Approx(0.666278f));
+    REQUIRE(appliedMix(machine, "horizontal") == Approx(0.333722f));
+    REQUIRE(appliedMix(machine, "rotate") == Approx(0.333722f));
+
+    machine.getNumber("verticality")->value(100.0f);
+    machine.advance(0.0f);
+    REQUIRE(machine.appliedAnimations().size() == 3);
+    REQUIRE(appliedMix(machine, "vertical") == Approx(1.0f));
+    REQUIRE(appliedMix(machine, "horizontal") == Approx(0.0f));
+}
+
+TEST_CASE("applied animations report direct blend weights", "[state_machine]")
+{
+    auto file = ReadRiveFile("../../test/assets/blend_test.riv");
+
+    auto artboard = file->artboard();
+    auto abi = artboard->instance();
+    rive::StateMachineInstance machine(artboard->stateMachine("State Machine 1"), abi.get());
+
+    machine.advance(0.0f);
+    machine.advance(0.0f);
+
+    REQUIRE(machine.appliedAnimations().size() == 2);
+    REQUIRE(appliedMix(machine, "vertical") == Approx(0.278433f));
+    REQUIRE(appliedMix(machine, "horizontal") == Approx(0.249160f));
+
+    // The collection is reused rather than appended to.
+    machine.advance(0.0f);
+    REQUIRE(machine.appliedAnimations().size() == 2);
+}
+
+TEST_CASE("layer states can be restored", "[state_machine]")
+{
+    auto file = ReadRiveFile("../../test/assets/rocket.riv");
+
+    auto artboard = file->artboard();
+    auto played = artboard->instance();
+    rive::StateMachineInstance machine(artboard->stateMachine("Button"), played.get());
+    machine.advance(0.0f);
+    machine.advance(0.3f);
+
+    auto idle = machine.layerState(0);
+    REQUIRE(idle != nullptr);
+    REQUIRE(idle->is<rive::AnimationState>());
+    REQUIRE(machine.layerAnimation(0)->time() == Approx(0.3f));
+    REQUIRE(machine.layerState(1) == nullptr);
+
+    auto restored = artboard->instance();
+    rive::StateMachineInstance restoredMachine(artboard->stateMachine("Button"), restored.get());
+    REQUIRE(restoredMachine.layerState(0) != idle);
+
+    REQUIRE(restoredMachine.restoreLayerState(0, idle, 0.3f));
+    REQUIRE(restoredMachine.layerState(0) == idle);
+    REQUIRE(restoredMachine.layerAnimation(0)->time() == Approx(0.3f));
+
+    // Advancing carries on from the restored state rather than from the entry.
+    restoredMachine.advance(0.1f);
+    REQUIRE(restoredMachine.stateChangedCount() == 0);
+    REQUIRE(restoredMachine.layerState(0) == idle);
+    REQUIRE(restoredMachine.layerAnimation(0)->time() == Approx(0.4f));
+
+    REQUIRE(!restoredMachine.restoreLayerState(1, idle, 0.0f));
+    REQUIRE(!restoredMachine.restoreLayerState(0, nullptr, 0.0f));
+}
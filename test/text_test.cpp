@@ -207,3 +207,85 @@ TEST_CASE("run modifier ranges select runs", "[text]")
         }
     }
 }
+
+// Logical index of the first character each run of the line shows, in visual
+// order.
+static std::vector<uint32_t> visualRunStarts(const rive::OrderedLine& line)
+{
+    std::vector<uint32_t> starts;
+    for (const rive::GlyphRun* run : line.runs())
+    {
+        starts.push_back(*std::min_element(run->textIndices.begin(), run->textIndices.end()));
+    }
+    return starts;
+}
+
+TEST_CASE("mixed direction runs are ordered by bidi level", "[text]")
+{
+    auto file = ReadRiveFile("../../test/assets/hello_world.riv");
+    auto artboard = file->artboard();
+    auto text = artboard->find<rive::Text>()[0];
+    auto run = artboard->find<rive::TextValueRun>()[0];
+
+    // A Latin brand name inside an Arabic sentence.
+    run->text("مرحبا Rive عالم");
+    artboard->advance(0.0f);
+
+    REQUIRE(text->shape().size() == 1);
+    REQUIRE(text->shape()[0].baseDirection == rive::TextDirection::rtl);
+    REQUIRE(text->orderedLines().size() == 1);
+    const rive::OrderedLine& rtlLine = text->orderedLines()[0];
+    auto starts = visualRunStarts(rtlLine);
+    REQUIRE(starts.size() >= 3);
+    REQUIRE(std::is_sorted(starts.rbegin(), starts.rend()));
+    REQUIRE(starts.front() > 6);
+    REQUIRE(starts.back() == 0);
+
+    // The brand name keeps reading left to right, one level above the
+    // paragraph.
+    auto brand = std::find(starts.begin(), starts.end(), 6u);
+    REQUIRE(brand != starts.end());
+    const rive::GlyphRun* brandRun = rtlLine.runs()[brand - starts.begin()];
+    REQUIRE(brandRun->dir == rive::TextDirection::ltr);
+    REQUIRE(brandRun->level == 2);
+
+    // Forcing LTR keeps the logical order of the runs and only flips the
+    // Arabic words.
+    text->direction(rive::TextBaseDirection::ltr);
+    artboard->advance(0.0f);
+
+    REQUIRE(text->shape()[0].baseDirection == rive::TextDirection::ltr);
+    REQUIRE(text->orderedLines().size() == 1);
+    const rive::OrderedLine& ltrLine = text->orderedLines()[0];
+    starts = visualRunStarts(ltrLine);
+    REQUIRE(std::is_sorted(starts.begin(), starts.end()));
+    REQUIRE(ltrLine.runs().front()->dir == rive::TextDirection::rtl);
+    REQUIRE(ltrLine.runs().front()->level == 1);
+}
+
+TEST_CASE("alignment flips for rtl paragraphs", "[text]")
+{
+    auto file = ReadRiveFile("../../test/assets/hello_world.riv");
+    auto artboard = file->artboard();
+    auto text = artboard->find<rive::Text>()[0];
+    auto run = artboard->find<rive::TextValueRun>()[0];
+
+    run->text("مرحبا");
+    text->sizingValue((uint32_t)rive::TextSizing::fixed);
+    text->width(1000.0f);
+    text->alignValue((uint32_t)rive::TextAlign::left);
+    artboard->advance(0.0f);
+
+    // Left aligns to the start, the right side of an RTL paragraph.
+    REQUIRE(text->lines().size() == 1);
+    REQUIRE(text->lines()[0].size() == 1);
+    REQUIRE(text->lines()[0][0].startX > 0.0f);
+
+    text->alignValue((uint32_t)rive::TextAlign::right);
+    artboard->advance(0.0f);
+    REQUIRE(text->lines()[0][0].startX == 0.0f);
+
+    text->direction(rive::TextBaseDirection::ltr);
+    artboard->advance(0.0f);
+    REQUIRE(text->lines()[0][0].startX > 0.0f);
+}
@@ -0,0 +1,134 @@
+use std::{
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use walkdir::WalkDir;
+
+const CHECKOUT_DIRECTORY: &str = "target";
+
+struct Checkout {
+    path: PathBuf,
+}
+
+impl Checkout {
+    pub fn new(repo: &str, tag: &str) -> Self {
+        let name = repo.rsplit_once('/').expect("URL format invalid").1;
+        let mut path = PathBuf::from(CHECKOUT_DIRECTORY);
+
+        path.push(name);
+
+        if !path.is_dir() {
+            Command::new("git")
+                .args([
+                    "clone",
+                    "-b",
+                    tag,
+                    repo,
+                    &path.as_os_str().to_string_lossy(),
+                ])
+                .output()
+                .unwrap_or_else(|_| panic!("failed to clone {}; is git CLI available?", name));
+        }
+
+        Self { path }
+    }
+
+    pub fn join<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        self.path.join(path)
+    }
+}
+
+fn all_files_with_extension<P: AsRef<Path>>(
+    path: P,
+    extension: &str,
+) -> impl Iterator<Item = PathBuf> + '_ {
+    WalkDir::new(path).into_iter().filter_map(move |entry| {
+        entry
+            .ok()
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension() == Some(&OsString::from(extension)))
+    })
+}
+
+fn absolute<P: AsRef<Path>>(path: P) -> String {
+    fs::canonicalize(path)
+        .expect("failed to resolve path")
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/file.cpp");
+    println!("cargo:rerun-if-changed=src/scene.cpp");
+
+    let text = env::var_os("CARGO_FEATURE_TEXT").is_some();
+
+    let mut rive = cc::Build::new();
+    rive.compiler("clang")
+        .cpp(true)
+        .files(all_files_with_extension("../../src", "cpp"))
+        .file("../../utils/no_op_factory.cpp")
+        .file("src/file.cpp")
+        .file("src/scene.cpp")
+        .include("../../include")
+        .flag("-std=c++14")
+        .flag("-Wno-everything");
+
+    // Dependents compiling C++ against the runtime get these as `DEP_RIVE_*`.
+    println!("cargo:include={}", absolute("../../include"));
+
+    if text {
+        let harfbuzz = Checkout::new("https://github.com/harfbuzz/harfbuzz", "8.1.1");
+        let sheen_bidi = Checkout::new("https://github.com/Tehreer/SheenBidi", "v2.6");
+
+        let target = env::var("TARGET").unwrap();
+        let profile = env::var("PROFILE").unwrap();
+
+        let mut cfg = cc::Build::new();
+        cfg.compiler("clang")
+            .cpp(true)
+            .flag_if_supported("-std=c++11") // for unix
+            .warnings(false)
+            .file(harfbuzz.join("src/harfbuzz.cc"));
+
+        if !target.contains("windows") {
+            cfg.define("HAVE_PTHREAD", "1");
+        }
+
+        if target.contains("apple") && profile.contains("release") {
+            cfg.define("HAVE_CORETEXT", "1");
+        }
+
+        if target.contains("windows") {
+            cfg.define("HAVE_DIRECTWRITE", "1");
+        }
+
+        if target.contains("windows-gnu") {
+            cfg.flag("-Wa,-mbig-obj");
+        }
+
+        cfg.compile("harfbuzz");
+
+        cc::Build::new()
+            .compiler("clang")
+            .files(all_files_with_extension(sheen_bidi.join("Source"), "c"))
+            .include(sheen_bidi.join("Headers"))
+            .compile("sheenbidi");
+
+        rive.include(harfbuzz.join("src"))
+            .include(sheen_bidi.join("Headers"))
+            .define("WITH_RIVE_TEXT", None);
+
+        println!("cargo:harfbuzz_include={}", absolute(harfbuzz.join("src")));
+        println!(
+            "cargo:sheenbidi_include={}",
+            absolute(sheen_bidi.join("Headers"))
+        );
+    }
+
+    rive.compile("rive");
+}
@@ -0,0 +1,405 @@
+use std::{
+    ffi::{c_char, c_void},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use crate::{to_string, File, RawFile};
+
+#[derive(Debug)]
+pub(crate) enum RawAnimation {}
+#[derive(Debug)]
+enum RawKeyedObject {}
+#[derive(Debug)]
+enum RawKeyedProperty {}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum InterpolatorKind {
+    None,
+    Ease,
+    Value,
+}
+
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum KeyFrameValueKind {
+    Number,
+    Color,
+    Boolean,
+    Id,
+    String,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+struct RawKeyFrame {
+    frame: u32,
+    seconds: f32,
+    interpolation_type: u32,
+    interpolator: InterpolatorKind,
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+    value_kind: KeyFrameValueKind,
+    number: f32,
+    integer: u32,
+    string: *const c_char,
+}
+
+#[repr(C)]
+#[derive(Debug, Default)]
+struct RawAnimationInfo {
+    fps: u32,
+    duration_frames: u32,
+    work_start: u32,
+    work_end: u32,
+    loop_value: u32,
+    speed: f32,
+}
+
+extern "C" {
+    fn rive_file_object_name(
+        raw_file: Option<NonNull<RawFile>>,
+        artboard: usize,
+        id: u32,
+    ) -> *const c_char;
+    fn rive_animation_fps(raw_animation: NonNull<RawAnimation>) -> u32;
+    fn rive_animation_info(raw_animation: NonNull<RawAnimation>, info: *mut RawAnimationInfo);
+    fn rive_animation_keyed_object_count(raw_animation: NonNull<RawAnimation>) -> usize;
+    fn rive_animation_keyed_object(
+        raw_animation: NonNull<RawAnimation>,
+        index: usize,
+    ) -> NonNull<RawKeyedObject>;
+    fn rive_keyed_object_id(raw_object: NonNull<RawKeyedObject>) -> u32;
+    fn rive_keyed_object_property_count(raw_object: NonNull<RawKeyedObject>) -> usize;
+    fn rive_keyed_object_property(
+        raw_object: NonNull<RawKeyedObject>,
+        index: usize,
+    ) -> NonNull<RawKeyedProperty>;
+    fn rive_keyed_property_key(raw_property: NonNull<RawKeyedProperty>) -> u32;
+    fn rive_keyed_property_key_frame_count(raw_property: NonNull<RawKeyedProperty>) -> usize;
+    fn rive_keyed_property_key_frame(
+        raw_property: NonNull<RawKeyedProperty>,
+        index: usize,
+        key_frame: *mut RawKeyFrame,
+    );
+}
+
+/// What happens when playback reaches the end of the work area.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoopMode {
+    OneShot,
+    Loop,
+    PingPong,
+}
+
+impl LoopMode {
+    /// Unknown values play once, like the runtime does.
+    pub fn from_value(value: u32) -> Self {
+        match value {
+            1 => Self::Loop,
+            2 => Self::PingPong,
+            _ => Self::OneShot,
+        }
+    }
+}
+
+/// The timing of a linear animation as authored, in frames.
+///
+/// The runtime works in seconds, which it derives as `frame as f32 / fps as f32`. Playback is
+/// bounded by `work_start..=work_end`: a time of 0 seconds is frame 0, not `work_start`, and
+/// times outside the work area are clamped or wrapped into it according to `loop_mode`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnimationInfo {
+    pub fps: u32,
+    pub duration_frames: u32,
+    /// First frame of the work area, or 0 when the animation has none.
+    pub work_start: u32,
+    /// Last frame of the work area, or `duration_frames` when the animation has none.
+    pub work_end: u32,
+    pub loop_mode: LoopMode,
+    /// Multiplies elapsed time. Negative speeds play backwards.
+    pub speed: f32,
+}
+
+impl AnimationInfo {
+    /// Reads the timing of a linear animation handed out by C++ code compiled against this
+    /// crate's runtime, e.g. through the headers in `DEP_RIVE_INCLUDE`.
+    ///
+    /// # Safety
+    ///
+    /// `animation` has to point to a live `rive::LinearAnimation`.
+    pub unsafe fn from_raw(animation: NonNull<c_void>) -> Self {
+        let mut raw = RawAnimationInfo::default();
+        rive_animation_info(animation.cast(), &mut raw);
+
+        Self {
+            fps: raw.fps,
+            duration_frames: raw.duration_frames,
+            work_start: raw.work_start,
+            work_end: raw.work_end,
+            loop_mode: LoopMode::from_value(raw.loop_value),
+            speed: raw.speed,
+        }
+    }
+
+    /// A zero fps would make every time infinite, so it's treated as 1.
+    fn fps(&self) -> f32 {
+        self.fps.max(1) as f32
+    }
+
+    pub fn start_seconds(&self) -> f32 {
+        self.time_at_frame(self.work_start)
+    }
+
+    pub fn end_seconds(&self) -> f32 {
+        self.time_at_frame(self.work_end)
+    }
+
+    /// Length of one pass over the work area at a speed of 1.
+    pub fn duration_seconds(&self) -> f32 {
+        (self.end_seconds() - self.start_seconds()).abs()
+    }
+
+    /// The time at which `frame` is shown, exactly as key frames compute theirs.
+    pub fn time_at_frame(&self, frame: u32) -> f32 {
+        frame as f32 / self.fps()
+    }
+
+    /// The frame nearest to `seconds`, rounding halves up, so that
+    /// `frame_at_time(time_at_frame(frame)) == frame` for every frame. Negative times map
+    /// to frame 0.
+    pub fn frame_at_time(&self, seconds: f32) -> u32 {
+        (seconds * self.fps()).round().max(0.0) as u32
+    }
+}
+
+/// Animated properties with well-known meaning. Anything else is kept as its raw key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Property {
+    X,
+    Y,
+    /// In radians.
+    Rotation,
+    ScaleX,
+    ScaleY,
+    Opacity,
+    Width,
+    Height,
+    /// 0xAARRGGBB.
+    Color,
+    StrokeThickness,
+    TrimStart,
+    TrimEnd,
+    TrimOffset,
+    Other(u16),
+}
+
+impl Property {
+    pub fn from_key(key: u16) -> Self {
+        match key {
+            13 => Self::X,
+            14 => Self::Y,
+            15 => Self::Rotation,
+            16 => Self::ScaleX,
+            17 => Self::ScaleY,
+            18 => Self::Opacity,
+            20 => Self::Width,
+            21 => Self::Height,
+            37 => Self::Color,
+            47 => Self::StrokeThickness,
+            114 => Self::TrimStart,
+            115 => Self::TrimEnd,
+            116 => Self::TrimOffset,
+            key => Self::Other(key),
+        }
+    }
+
+    pub fn key(self) -> u16 {
+        match self {
+            Self::X => 13,
+            Self::Y => 14,
+            Self::Rotation => 15,
+            Self::ScaleX => 16,
+            Self::ScaleY => 17,
+            Self::Opacity => 18,
+            Self::Width => 20,
+            Self::Height => 21,
+            Self::Color => 37,
+            Self::StrokeThickness => 47,
+            Self::TrimStart => 114,
+            Self::TrimEnd => 115,
+            Self::TrimOffset => 116,
+            Self::Other(key) => key,
+        }
+    }
+}
+
+/// How the value moves from a key frame to the next one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    Hold,
+    Linear,
+    /// Cubic bezier easing of time, with control points in normalized time and progress.
+    Cubic {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    },
+    /// Cubic bezier over the values themselves rather than over time.
+    CubicValue {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeyFrameValue {
+    Number(f32),
+    /// 0xAARRGGBB.
+    Color(u32),
+    Bool(bool),
+    Id(u32),
+    String(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyFrameInfo {
+    pub frame: u32,
+    /// `frame / fps`, exactly as the runtime computes it.
+    pub seconds: f32,
+    pub value: KeyFrameValue,
+    pub interpolation: Interpolation,
+}
+
+/// One animated property of one object, with all of its key frames in time order.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyedPropertyInfo {
+    /// Index of the target in the artboard's objects.
+    pub object_id: u32,
+    /// Empty for objects without a name.
+    pub object_name: String,
+    pub property: Property,
+    pub property_key: u16,
+    pub key_frames: Vec<KeyFrameInfo>,
+}
+
+/// A linear animation of one of a `File`'s artboards.
+#[derive(Debug)]
+pub struct Animation<'f> {
+    raw_file: Option<NonNull<RawFile>>,
+    artboard: usize,
+    raw_animation: NonNull<RawAnimation>,
+    _file: PhantomData<&'f File>,
+}
+
+impl<'f> Animation<'f> {
+    pub(crate) fn new(
+        file: &'f File,
+        artboard: usize,
+        raw_animation: NonNull<RawAnimation>,
+    ) -> Self {
+        Self {
+            raw_file: file.raw_file,
+            artboard,
+            raw_animation,
+            _file: PhantomData,
+        }
+    }
+
+    pub fn fps(&self) -> u32 {
+        unsafe { rive_animation_fps(self.raw_animation) }
+    }
+
+    pub fn info(&self) -> AnimationInfo {
+        unsafe { AnimationInfo::from_raw(self.raw_animation.cast()) }
+    }
+
+    /// The raw curves of the animation. Values are in the units the runtime applies them in,
+    /// e.g. radians for rotations, and times are in seconds, i.e. frames divided by `fps`.
+    pub fn keyed_properties(&self) -> Vec<KeyedPropertyInfo> {
+        let mut properties = Vec::new();
+
+        unsafe {
+            for i in 0..rive_animation_keyed_object_count(self.raw_animation) {
+                let object = rive_animation_keyed_object(self.raw_animation, i);
+                let object_id = rive_keyed_object_id(object);
+                let object_name = to_string(rive_file_object_name(
+                    self.raw_file,
+                    self.artboard,
+                    object_id,
+                ));
+
+                for j in 0..rive_keyed_object_property_count(object) {
+                    let property = rive_keyed_object_property(object, j);
+                    let property_key = rive_keyed_property_key(property) as u16;
+
+                    properties.push(KeyedPropertyInfo {
+                        object_id,
+                        object_name: object_name.clone(),
+                        property: Property::from_key(property_key),
+                        property_key,
+                        key_frames: (0..rive_keyed_property_key_frame_count(property))
+                            .map(|k| key_frame(property, k))
+                            .collect(),
+                    });
+                }
+            }
+        }
+
+        properties
+    }
+}
+
+unsafe fn key_frame(property: NonNull<RawKeyedProperty>, index: usize) -> KeyFrameInfo {
+    let mut raw = RawKeyFrame {
+        frame: 0,
+        seconds: 0.0,
+        interpolation_type: 0,
+        interpolator: InterpolatorKind::None,
+        x1: 0.0,
+        y1: 0.0,
+        x2: 0.0,
+        y2: 0.0,
+        value_kind: KeyFrameValueKind::Number,
+        number: 0.0,
+        integer: 0,
+        string: std::ptr::null(),
+    };
+    rive_keyed_property_key_frame(property, index, &mut raw);
+
+    let (x1, y1, x2, y2) = (raw.x1, raw.y1, raw.x2, raw.y2);
+    let interpolation = match (raw.interpolation_type, raw.interpolator) {
+        (0, _) => Interpolation::Hold,
+        (_, InterpolatorKind::Ease) => Interpolation::Cubic { x1, y1, x2, y2 },
+        (_, InterpolatorKind::Value) => Interpolation::CubicValue { x1, y1, x2, y2 },
+        (_, InterpolatorKind::None) => Interpolation::Linear,
+    };
+
+    let value = match raw.value_kind {
+        KeyFrameValueKind::Number => KeyFrameValue::Number(raw.number),
+        KeyFrameValueKind::Color => KeyFrameValue::Color(raw.integer),
+        KeyFrameValueKind::Boolean => KeyFrameValue::Bool(raw.integer != 0),
+        KeyFrameValueKind::Id => KeyFrameValue::Id(raw.integer),
+        KeyFrameValueKind::String => KeyFrameValue::String(to_string(raw.string)),
+    };
+
+    KeyFrameInfo {
+        frame: raw.frame,
+        seconds: raw.seconds,
+        value,
+        interpolation,
+    }
+}
@@ -0,0 +1,268 @@
+use std::{env, ffi::OsString, fs, process::ExitCode};
+
+use rive_core::File;
+
+#[cfg(feature = "serde")]
+mod dump {
+    use rive_core::{ArtboardSummary, File, KeyedPropertyInfo};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct AnimationCurves {
+        name: String,
+        fps: u32,
+        keyed_properties: Vec<KeyedPropertyInfo>,
+    }
+
+    #[derive(Serialize)]
+    struct ArtboardDump {
+        #[serde(flatten)]
+        summary: ArtboardSummary,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        curves: Option<Vec<AnimationCurves>>,
+    }
+
+    #[derive(Serialize)]
+    struct FileDump<'p> {
+        path: &'p str,
+        artboards: Vec<ArtboardDump>,
+    }
+
+    pub fn print(path: &str, file: &File, curves: bool) {
+        let artboards = file
+            .artboards()
+            .into_iter()
+            .enumerate()
+            .map(|(artboard, summary)| {
+                let curves = curves.then(|| {
+                    summary
+                        .animations
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, name)| {
+                            let animation = file.animation(artboard, index)?;
+
+                            Some(AnimationCurves {
+                                name: name.clone(),
+                                fps: animation.fps(),
+                                keyed_properties: animation.keyed_properties(),
+                            })
+                        })
+                        .collect()
+                });
+
+                ArtboardDump { summary, curves }
+            })
+            .collect();
+
+        let dump = FileDump { path, artboards };
+        println!("{}", serde_json::to_string_pretty(&dump).unwrap());
+    }
+}
+
+#[cfg(feature = "serde")]
+mod contract {
+    use std::fs;
+
+    use rive_core::{File, InteractionContract};
+
+    pub fn read(path: &str) -> Result<InteractionContract, String> {
+        let text = fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?;
+        serde_json::from_str(&text).map_err(|error| format!("{path} isn't a contract: {error}"))
+    }
+
+    /// Checks the first artboard with its default state machine, or its first one, printing
+    /// what's missing, mismatched or extra. Returns whether the contract is satisfied.
+    pub fn check(file: &File, contract: &InteractionContract) -> Result<bool, String> {
+        let state_machine = file.default_state_machine(0).unwrap_or(0);
+        let scene = file
+            .scene(0, state_machine)
+            .ok_or("the first artboard has no state machine to check")?;
+
+        let report = scene.check_contract(contract);
+        print!("{report}");
+        Ok(report.is_satisfied())
+    }
+}
+
+fn summarize(path: &str, file: &File) {
+    println!("{path}");
+
+    for artboard in file.artboards() {
+        println!("  artboard: {}", artboard.name);
+
+        for animation in &artboard.animations {
+            println!("    animation: {animation}");
+        }
+
+        for state_machine in &artboard.state_machines {
+            println!("    state machine: {state_machine}");
+        }
+    }
+}
+
+const USAGE: &str =
+    "usage: rive-summary [--dump-json [--curves]] [--contract <contract.json>] <file.riv>...";
+
+/// What the command line asks for.
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    dump_json: bool,
+    curves: bool,
+    contract: Option<String>,
+    paths: Vec<OsString>,
+}
+
+impl Args {
+    /// Parses the arguments after the program name. Flags can come anywhere, and `--contract`
+    /// takes its path as `--contract <path>` or `--contract=<path>`. Everything after `--` is a
+    /// path.
+    fn parse(args: impl IntoIterator<Item = OsString>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if !arg.to_string_lossy().starts_with("--") {
+                parsed.paths.push(arg);
+                continue;
+            }
+            let flag = arg.to_string_lossy();
+
+            match flag.split_once('=') {
+                _ if flag == "--" => {
+                    parsed.paths.extend(args);
+                    break;
+                }
+                None if flag == "--dump-json" => parsed.dump_json = true,
+                None if flag == "--curves" => parsed.curves = true,
+                None if flag == "--contract" => {
+                    let path = args.next().ok_or("--contract needs a path")?;
+                    parsed.contract = Some(path.to_string_lossy().into_owned());
+                }
+                Some(("--contract", path)) if !path.is_empty() => {
+                    parsed.contract = Some(path.to_string());
+                }
+                _ => return Err(format!("unknown flag {flag}")),
+            }
+        }
+
+        if parsed.paths.is_empty() {
+            return Err("no files to summarize".to_string());
+        }
+
+        Ok(parsed)
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args_os().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("rive-summary: {error}\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    #[cfg(feature = "serde")]
+    let (dump_json, curves) = (args.dump_json, args.curves);
+
+    #[cfg(feature = "serde")]
+    let contract = match args.contract.as_deref().map(contract::read).transpose() {
+        Ok(read) => args.contract.clone().zip(read),
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    #[cfg(not(feature = "serde"))]
+    if args.dump_json || args.curves || args.contract.is_some() {
+        eprintln!("rive-summary was built without the serde feature, which flags need");
+        return ExitCode::FAILURE;
+    }
+
+    let mut status = ExitCode::SUCCESS;
+
+    for path in args.paths {
+        let path = path.to_string_lossy();
+        let file = fs::read(&*path)
+            .map_err(|error| error.to_string())
+            .and_then(|data| File::new(&data).map_err(|error| error.to_string()));
+
+        let file = match file {
+            Ok(file) => file,
+            Err(error) => {
+                eprintln!("{path}: {error}");
+                status = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        #[cfg(feature = "serde")]
+        if dump_json {
+            dump::print(&path, &file, curves);
+        } else {
+            summarize(&path, &file);
+        }
+        #[cfg(not(feature = "serde"))]
+        summarize(&path, &file);
+
+        #[cfg(feature = "serde")]
+        if let Some((contract_path, contract)) = &contract {
+            match contract::check(&file, contract) {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("{path} breaks {contract_path}");
+                    status = ExitCode::FAILURE;
+                }
+                Err(error) => {
+                    eprintln!("{path}: {error}");
+                    status = ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn contracts_take_separate_or_joined_paths() {
+        let expected = Args {
+            contract: Some("contract.json".to_string()),
+            paths: vec![OsString::from("file.riv")],
+            ..Args::default()
+        };
+
+        assert_eq!(
+            parse(&["--contract", "contract.json", "file.riv"]),
+            Ok(expected)
+        );
+        let joined = parse(&["file.riv", "--contract=contract.json"]).unwrap();
+        assert_eq!(joined.contract.as_deref(), Some("contract.json"));
+        assert!(parse(&["file.riv", "--contract"]).is_err());
+        assert!(parse(&["file.riv", "--contract="]).is_err());
+    }
+
+    #[test]
+    fn unknown_flags_are_rejected() {
+        assert_eq!(
+            parse(&["--dump-jsn", "file.riv"]),
+            Err("unknown flag --dump-jsn".to_string())
+        );
+        assert!(parse(&["--curves=yes", "file.riv"]).is_err());
+        assert!(parse(&["--dump-json"]).is_err());
+
+        let args = parse(&["--dump-json", "--", "--curves"]).unwrap();
+        assert!(args.dump_json && !args.curves);
+        assert_eq!(args.paths, [OsString::from("--curves")]);
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+
+use crate::InputType;
+
+/// What a host relies on in an artboard and state machine of a file, checked with
+/// `Scene::check_contract` so that renames in the editor are caught before they break the
+/// host. With the `serde` feature, serialize it with any serde format, e.g. as JSON next to the
+/// code using it.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct InteractionContract {
+    pub inputs: Vec<InputContract>,
+    /// Events the host listens for.
+    pub events: Vec<String>,
+    /// Text runs the host sets, by path, e.g. `card/title` for a run of a nested artboard.
+    pub text_runs: Vec<String>,
+    /// Nested artboards the host reaches into, by path like `text_runs`.
+    pub nested_paths: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputContract {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub input_type: InputType,
+    /// Lowest and highest values the host sets a number input to.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub range: Option<[f32; 2]>,
+}
+
+/// An input of the file, as checked against a contract.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileInput {
+    pub name: String,
+    pub input_type: InputType,
+    /// The value numbers start with, then the values the state machine compares them against.
+    pub values: Vec<f32>,
+}
+
+/// What the file offers a host, as checked against a contract. `Scene::interface` builds it
+/// for a state machine; renderers with scenes of their own build it from those.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileInterface {
+    pub inputs: Vec<FileInput>,
+    pub events: Vec<String>,
+    pub text_runs: Vec<String>,
+    pub nested_paths: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeMismatch {
+    pub name: String,
+    pub expected: InputType,
+    pub found: InputType,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeMismatch {
+    pub name: String,
+    pub range: [f32; 2],
+    /// Values outside the range which the input starts with or the state machine compares it
+    /// against, so states the file expects to reach are out of the host's reach.
+    pub values: Vec<f32>,
+}
+
+/// How a file measures up to an `InteractionContract`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContractReport {
+    pub missing_inputs: Vec<String>,
+    pub type_mismatches: Vec<TypeMismatch>,
+    pub range_mismatches: Vec<RangeMismatch>,
+    pub missing_events: Vec<String>,
+    pub missing_text_runs: Vec<String>,
+    pub missing_nested_paths: Vec<String>,
+    /// Inputs of the file the contract doesn't mention. They don't break the contract, but
+    /// are often what a missing input was renamed to.
+    pub extra_inputs: Vec<String>,
+    /// Events of the file the contract doesn't mention, like `extra_inputs`.
+    pub extra_events: Vec<String>,
+}
+
+impl ContractReport {
+    pub fn check(contract: &InteractionContract, file: &FileInterface) -> Self {
+        let mut report = Self::default();
+
+        for expected in &contract.inputs {
+            let Some(input) = file.inputs.iter().find(|input| input.name == expected.name) else {
+                report.missing_inputs.push(expected.name.clone());
+                continue;
+            };
+            if input.input_type != expected.input_type {
+                report.type_mismatches.push(TypeMismatch {
+                    name: expected.name.clone(),
+                    expected: expected.input_type,
+                    found: input.input_type,
+                });
+                continue;
+            }
+            if let Some([min, max]) = expected.range {
+                let mut values: Vec<f32> = input
+                    .values
+                    .iter()
+                    .copied()
+                    .filter(|value| !(min..=max).contains(value))
+                    .collect();
+                values.dedup();
+                if !values.is_empty() {
+                    report.range_mismatches.push(RangeMismatch {
+                        name: expected.name.clone(),
+                        range: [min, max],
+                        values,
+                    });
+                }
+            }
+        }
+        report.extra_inputs = file
+            .inputs
+            .iter()
+            .map(|input| &input.name)
+            .filter(|name| !contract.inputs.iter().any(|input| &input.name == *name))
+            .cloned()
+            .collect();
+
+        report.missing_events = missing(&contract.events, &file.events);
+        report.extra_events = missing(&file.events, &contract.events);
+        report.missing_text_runs = missing(&contract.text_runs, &file.text_runs);
+        report.missing_nested_paths = missing(&contract.nested_paths, &file.nested_paths);
+
+        report
+    }
+
+    /// Whether nothing the contract relies on is missing or mismatched. Extras are allowed.
+    pub fn is_satisfied(&self) -> bool {
+        self.missing_inputs.is_empty()
+            && self.type_mismatches.is_empty()
+            && self.range_mismatches.is_empty()
+            && self.missing_events.is_empty()
+            && self.missing_text_runs.is_empty()
+            && self.missing_nested_paths.is_empty()
+    }
+}
+
+/// Names of `expected` which aren't in `found`, without repeats.
+fn missing(expected: &[String], found: &[String]) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for name in expected {
+        if !found.contains(name) && !missing.contains(name) {
+            missing.push(name.clone());
+        }
+    }
+    missing
+}
+
+/// One line per problem, then one per extra.
+impl fmt::Display for ContractReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in &self.missing_inputs {
+            writeln!(f, "missing input \"{name}\"")?;
+        }
+        for mismatch in &self.type_mismatches {
+            writeln!(
+                f,
+                "input \"{}\" is a {}, expected a {}",
+                mismatch.name, mismatch.found, mismatch.expected
+            )?;
+        }
+        for mismatch in &self.range_mismatches {
+            let values: Vec<String> = mismatch.values.iter().map(f32::to_string).collect();
+            writeln!(
+                f,
+                "input \"{}\" uses {} outside {}..{}",
+                mismatch.name,
+                values.join(", "),
+                mismatch.range[0],
+                mismatch.range[1]
+            )?;
+        }
+        for name in &self.missing_events {
+            writeln!(f, "missing event \"{name}\"")?;
+        }
+        for path in &self.missing_text_runs {
+            writeln!(f, "missing text run \"{path}\"")?;
+        }
+        for path in &self.missing_nested_paths {
+            writeln!(f, "missing nested artboard \"{path}\"")?;
+        }
+        for name in &self.extra_inputs {
+            writeln!(f, "extra input \"{name}\"")?;
+        }
+        for name in &self.extra_events {
+            writeln!(f, "extra event \"{name}\"")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str, input_type: InputType, values: &[f32]) -> FileInput {
+        FileInput {
+            name: name.to_string(),
+            input_type,
+            values: values.to_vec(),
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn contract() -> InteractionContract {
+        InteractionContract {
+            inputs: vec![
+                InputContract {
+                    name: "hover".to_string(),
+                    input_type: InputType::Bool,
+                    range: None,
+                },
+                InputContract {
+                    name: "progress".to_string(),
+                    input_type: InputType::Number,
+                    range: Some([0.0, 100.0]),
+                },
+            ],
+            events: names(&["done"]),
+            text_runs: names(&["card/title"]),
+            nested_paths: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn matching_files_satisfy_contracts() {
+        let file = FileInterface {
+            inputs: vec![
+                input("hover", InputType::Bool, &[0.0]),
+                input("progress", InputType::Number, &[0.0, 50.0, 100.0]),
+            ],
+            events: names(&["done"]),
+            text_runs: names(&["card/title", "card/body"]),
+            nested_paths: names(&["card"]),
+        };
+
+        let report = ContractReport::check(&contract(), &file);
+        assert!(report.is_satisfied(), "{report}");
+        assert_eq!(report, ContractReport::default());
+    }
+
+    #[test]
+    fn renames_are_reported_as_missing_and_extra() {
+        let file = FileInterface {
+            inputs: vec![
+                input("isHovered", InputType::Bool, &[0.0]),
+                input("progress", InputType::Number, &[0.0, 150.0, 150.0]),
+            ],
+            events: names(&["finished"]),
+            ..Default::default()
+        };
+
+        let report = ContractReport::check(&contract(), &file);
+        assert!(!report.is_satisfied());
+        assert_eq!(report.missing_inputs, names(&["hover"]));
+        assert_eq!(report.extra_inputs, names(&["isHovered"]));
+        assert_eq!(report.range_mismatches[0].values, [150.0]);
+        assert_eq!(report.missing_events, names(&["done"]));
+        assert_eq!(report.extra_events, names(&["finished"]));
+        assert_eq!(report.missing_text_runs, names(&["card/title"]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn contracts_round_trip_through_json() {
+        let contract: InteractionContract = serde_json::from_str(
+            r#"{
+                "inputs": [
+                    { "name": "hover", "type": "bool" },
+                    { "name": "progress", "type": "number", "range": [0, 100] }
+                ],
+                "events": ["done"],
+                "text_runs": ["card/title"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(contract, self::contract());
+
+        let text = serde_json::to_string(&contract).unwrap();
+        assert!(!text.contains("null"), "{text}");
+        assert_eq!(
+            serde_json::from_str::<InteractionContract>(&text).unwrap(),
+            contract
+        );
+    }
+}
@@ -0,0 +1,238 @@
+#include "rive/animation/cubic_interpolator.hpp"
+#include "rive/animation/cubic_value_interpolator.hpp"
+#include "rive/animation/keyed_object.hpp"
+#include "rive/animation/keyed_property.hpp"
+#include "rive/animation/keyframe_bool.hpp"
+#include "rive/animation/keyframe_color.hpp"
+#include "rive/animation/keyframe_double.hpp"
+#include "rive/animation/keyframe_id.hpp"
+#include "rive/animation/keyframe_string.hpp"
+#include "rive/animation/linear_animation.hpp"
+#include "rive/animation/state_machine.hpp"
+#include "rive/artboard.hpp"
+#include "rive/component.hpp"
+#include "rive/file.hpp"
+#include "utils/no_op_factory.hpp"
+
+using namespace rive;
+
+static NoOpFactory factory;
+
+enum class InterpolatorKind : uint32_t
+{
+    none,
+    ease,
+    value,
+};
+
+enum class KeyFrameValueKind : uint32_t
+{
+    number,
+    color,
+    boolean,
+    id,
+    string,
+};
+
+struct KeyFrameInfo
+{
+    uint32_t frame;
+    float seconds;
+    uint32_t interpolationType;
+    InterpolatorKind interpolator;
+    float x1, y1, x2, y2;
+    KeyFrameValueKind valueKind;
+    float number;
+    uint32_t integer;
+    const char* string;
+};
+
+// Work area bounds are the effective ones: 0 and the duration when the work
+// area is disabled.
+struct AnimationInfo
+{
+    uint32_t fps;
+    uint32_t durationFrames;
+    uint32_t workStart;
+    uint32_t workEnd;
+    uint32_t loop;
+    float speed;
+};
+
+extern "C"
+{
+    typedef void* RawFile;
+
+    const RawFile rive_file_new(const uint8_t* data,
+                                size_t len,
+                                const LoadLimits* limits,
+                                ImportResult* result,
+                                LoadLimitReport* report)
+    {
+        return static_cast<void*>(
+            File::import(Span<const uint8_t>(data, len), &factory, *limits, result, report)
+                .release());
+    }
+
+    void rive_file_release(const RawFile file)
+    {
+        std::unique_ptr<File> val(std::move(static_cast<File*>(file)));
+    }
+
+    size_t rive_file_artboard_count(const RawFile file)
+    {
+        return static_cast<File*>(file)->artboardCount();
+    }
+
+    const char* rive_file_artboard_name(const RawFile file, size_t index)
+    {
+        return static_cast<File*>(file)->artboard(index)->name().c_str();
+    }
+
+    size_t rive_file_animation_count(const RawFile file, size_t artboard)
+    {
+        return static_cast<File*>(file)->artboard(artboard)->animationCount();
+    }
+
+    const char* rive_file_animation_name(const RawFile file, size_t artboard, size_t index)
+    {
+        return static_cast<File*>(file)->artboard(artboard)->animation(index)->name().c_str();
+    }
+
+    size_t rive_file_state_machine_count(const RawFile file, size_t artboard)
+    {
+        return static_cast<File*>(file)->artboard(artboard)->stateMachineCount();
+    }
+
+    // Returns -1 if the artboard has no default state machine.
+    ptrdiff_t rive_file_default_state_machine(const RawFile file, size_t artboard)
+    {
+        return static_cast<File*>(file)->artboard(artboard)->defaultStateMachineIndex();
+    }
+
+    const char* rive_file_state_machine_name(const RawFile file, size_t artboard, size_t index)
+    {
+        return static_cast<File*>(file)->artboard(artboard)->stateMachine(index)->name().c_str();
+    }
+
+    typedef const void* RawAnimation;
+    typedef const void* RawKeyedObject;
+    typedef const void* RawKeyedProperty;
+
+    RawAnimation rive_file_animation(const RawFile file, size_t artboard, size_t index)
+    {
+        return static_cast<File*>(file)->artboard(artboard)->animation(index);
+    }
+
+    // Returns an empty name for objects which aren't components, e.g. the artboard's
+    // own keyed properties.
+    const char* rive_file_object_name(const RawFile file, size_t artboard, uint32_t id)
+    {
+        auto object = static_cast<File*>(file)->artboard(artboard)->resolve(id);
+        if (object == nullptr || !object->is<Component>())
+        {
+            return "";
+        }
+        return object->as<Component>()->name().c_str();
+    }
+
+    uint32_t rive_animation_fps(RawAnimation animation)
+    {
+        return static_cast<const LinearAnimation*>(animation)->fps();
+    }
+
+    void rive_animation_info(RawAnimation animation, AnimationInfo* info)
+    {
+        auto linear = static_cast<const LinearAnimation*>(animation);
+        bool workArea = linear->enableWorkArea();
+
+        info->fps = linear->fps();
+        info->durationFrames = linear->duration();
+        info->workStart = workArea ? linear->workStart() : 0;
+        info->workEnd = workArea ? linear->workEnd() : linear->duration();
+        info->loop = linear->loopValue();
+        info->speed = linear->speed();
+    }
+
+    size_t rive_animation_keyed_object_count(RawAnimation animation)
+    {
+        return static_cast<const LinearAnimation*>(animation)->keyedObjectCount();
+    }
+
+    RawKeyedObject rive_animation_keyed_object(RawAnimation animation, size_t index)
+    {
+        return static_cast<const LinearAnimation*>(animation)->keyedObject(index);
+    }
+
+    uint32_t rive_keyed_object_id(RawKeyedObject object)
+    {
+        return static_cast<const KeyedObject*>(object)->objectId();
+    }
+
+    size_t rive_keyed_object_property_count(RawKeyedObject object)
+    {
+        return static_cast<const KeyedObject*>(object)->keyedPropertyCount();
+    }
+
+    RawKeyedProperty rive_keyed_object_property(RawKeyedObject object, size_t index)
+    {
+        return static_cast<const KeyedObject*>(object)->keyedProperty(index);
+    }
+
+    uint32_t rive_keyed_property_key(RawKeyedProperty property)
+    {
+        return static_cast<const KeyedProperty*>(property)->propertyKey();
+    }
+
+    size_t rive_keyed_property_key_frame_count(RawKeyedProperty property)
+    {
+        return static_cast<const KeyedProperty*>(property)->keyFrameCount();
+    }
+
+    void rive_keyed_property_key_frame(RawKeyedProperty property, size_t index, KeyFrameInfo* info)
+    {
+        auto keyFrame = static_cast<const KeyedProperty*>(property)->keyFrame(index);
+
+        *info = {};
+        info->frame = keyFrame->frame();
+        info->seconds = keyFrame->seconds();
+        info->interpolationType = keyFrame->interpolationType();
+
+        if (auto interpolator = keyFrame->interpolator())
+        {
+            info->interpolator = interpolator->is<CubicValueInterpolator>()
+                                     ? InterpolatorKind::value
+                                     : InterpolatorKind::ease;
+            info->x1 = interpolator->x1();
+            info->y1 = interpolator->y1();
+            info->x2 = interpolator->x2();
+            info->y2 = interpolator->y2();
+        }
+
+        if (keyFrame->is<KeyFrameDouble>())
+        {
+            info->valueKind = KeyFrameValueKind::number;
+            info->number = keyFrame->as<KeyFrameDouble>()->value();
+        }
+        else if (keyFrame->is<KeyFrameColor>())
+        {
+            info->valueKind = KeyFrameValueKind::color;
+            info->integer = keyFrame->as<KeyFrameColor>()->value();
+        }
+        else if (keyFrame->is<KeyFrameBool>())
+        {
+            info->valueKind = KeyFrameValueKind::boolean;
+            info->integer = keyFrame->as<KeyFrameBool>()->value();
+        }
+        else if (keyFrame->is<KeyFrameId>())
+        {
+            info->valueKind = KeyFrameValueKind::id;
+            info->integer = keyFrame->as<KeyFrameId>()->value();
+        }
+        else if (keyFrame->is<KeyFrameString>())
+        {
+            info->valueKind = KeyFrameValueKind::string;
+            info->string = keyFrame->as<KeyFrameString>()->value().c_str();
+        }
+    }
+}
@@ -0,0 +1,437 @@
+//! Rive runtime without any graphics dependencies: parsing and introspection of `.riv` files,
+//! and `Scene`s which advance state machines, take their inputs and report their events.
+//! `InteractionContract`s check what a scene offers against what a host relies on.
+//! Rendering lives in `rive-vello`, which builds on top of this crate.
+
+use std::{
+    ffi::{c_char, CStr},
+    fmt,
+    ptr::NonNull,
+};
+
+mod animation;
+mod contract;
+mod scene;
+pub mod writer;
+
+pub use animation::{
+    Animation, AnimationInfo, Interpolation, KeyFrameInfo, KeyFrameValue, KeyedPropertyInfo,
+    LoopMode, Property,
+};
+pub use contract::{
+    ContractReport, FileInput, FileInterface, InputContract, InteractionContract, RangeMismatch,
+    TypeMismatch,
+};
+pub use scene::{InputDescriptor, InputType, Scene};
+#[doc(hidden)]
+pub use scene::{InputIndices, RawInputInfo};
+
+use animation::RawAnimation;
+
+#[derive(Debug)]
+enum RawFile {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ImportResult {
+    Success,
+    UnsupportedVersion,
+    Malformed,
+    LimitExceeded,
+}
+
+/// Caps on what a file may declare, checked while it's parsed so that oversized or adversarial
+/// files fail before their contents are allocated. The defaults fit anything the editor
+/// exports; use `unlimited` only for trusted content.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LoadLimits {
+    /// Objects of any type, including ones the runtime doesn't know.
+    pub max_objects: u64,
+    /// Key frames across all animations.
+    pub max_key_frames: u64,
+    /// In-band contents of all image and font assets.
+    pub max_asset_bytes: u64,
+    /// Levels of artboards nested in one another. Cycles exceed any depth.
+    pub max_nesting_depth: u64,
+}
+
+impl LoadLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_objects: u64::MAX,
+            max_key_frames: u64::MAX,
+            max_asset_bytes: u64::MAX,
+            max_nesting_depth: u64::MAX,
+        }
+    }
+}
+
+impl Default for LoadLimits {
+    fn default() -> Self {
+        Self {
+            max_objects: 2_000_000,
+            max_key_frames: 2_000_000,
+            max_asset_bytes: 512 * 1024 * 1024,
+            max_nesting_depth: 32,
+        }
+    }
+}
+
+/// One of the `LoadLimits`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoadLimit {
+    None,
+    Objects,
+    KeyFrames,
+    AssetBytes,
+    NestingDepth,
+}
+
+impl fmt::Display for LoadLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Objects => "max_objects",
+            Self::KeyFrames => "max_key_frames",
+            Self::AssetBytes => "max_asset_bytes",
+            Self::NestingDepth => "max_nesting_depth",
+        })
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+struct LoadLimitReport {
+    limit: LoadLimit,
+    observed: u64,
+}
+
+extern "C" {
+    fn rive_file_new(
+        data: *const u8,
+        len: usize,
+        limits: *const LoadLimits,
+        result: *mut ImportResult,
+        report: *mut LoadLimitReport,
+    ) -> Option<NonNull<RawFile>>;
+    fn rive_file_release(raw_file: Option<NonNull<RawFile>>);
+    fn rive_file_artboard_count(raw_file: Option<NonNull<RawFile>>) -> usize;
+    fn rive_file_artboard_name(raw_file: Option<NonNull<RawFile>>, index: usize) -> *const c_char;
+    fn rive_file_animation_count(raw_file: Option<NonNull<RawFile>>, artboard: usize) -> usize;
+    fn rive_file_animation_name(
+        raw_file: Option<NonNull<RawFile>>,
+        artboard: usize,
+        index: usize,
+    ) -> *const c_char;
+    fn rive_file_state_machine_count(raw_file: Option<NonNull<RawFile>>, artboard: usize) -> usize;
+    fn rive_file_default_state_machine(
+        raw_file: Option<NonNull<RawFile>>,
+        artboard: usize,
+    ) -> isize;
+    fn rive_file_state_machine_name(
+        raw_file: Option<NonNull<RawFile>>,
+        artboard: usize,
+        index: usize,
+    ) -> *const c_char;
+    fn rive_file_animation(
+        raw_file: Option<NonNull<RawFile>>,
+        artboard: usize,
+        index: usize,
+    ) -> NonNull<RawAnimation>;
+}
+
+unsafe fn to_string(c_str: *const c_char) -> String {
+    CStr::from_ptr(c_str).to_string_lossy().into_owned()
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImportError {
+    /// The file was exported for a runtime version this one doesn't support.
+    UnsupportedVersion,
+    /// The data is not a well-formed `.riv` file.
+    Malformed,
+    /// The file declares more than `limit` allows. `observed` is the count parsing had reached
+    /// when it stopped, not the file's total.
+    LimitExceeded { limit: LoadLimit, observed: u64 },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion => write!(f, "unsupported .riv version"),
+            Self::Malformed => write!(f, "malformed .riv file"),
+            Self::LimitExceeded { limit, observed } => {
+                write!(
+                    f,
+                    ".riv file exceeds the {limit} load limit with {observed}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArtboardSummary {
+    pub name: String,
+    pub animations: Vec<String>,
+    pub state_machines: Vec<String>,
+}
+
+/// An imported `.riv` file. Assets are resolved but never decoded for rendering.
+#[derive(Debug)]
+pub struct File {
+    raw_file: Option<NonNull<RawFile>>,
+}
+
+impl File {
+    /// Parses `data` with the default `LoadLimits`.
+    pub fn new(data: &[u8]) -> Result<Self, ImportError> {
+        Self::with_limits(data, &LoadLimits::default())
+    }
+
+    pub fn with_limits(data: &[u8], limits: &LoadLimits) -> Result<Self, ImportError> {
+        let mut result = ImportResult::Malformed;
+        let mut report = LoadLimitReport {
+            limit: LoadLimit::None,
+            observed: 0,
+        };
+        let raw_file =
+            unsafe { rive_file_new(data.as_ptr(), data.len(), limits, &mut result, &mut report) };
+
+        match (raw_file, result) {
+            (Some(raw_file), ImportResult::Success) => Ok(Self {
+                raw_file: Some(raw_file),
+            }),
+            (_, ImportResult::UnsupportedVersion) => Err(ImportError::UnsupportedVersion),
+            (_, ImportResult::LimitExceeded) => Err(ImportError::LimitExceeded {
+                limit: report.limit,
+                observed: report.observed,
+            }),
+            _ => Err(ImportError::Malformed),
+        }
+    }
+
+    pub fn artboard_count(&self) -> usize {
+        unsafe { rive_file_artboard_count(self.raw_file) }
+    }
+
+    /// `None` if either index is out of range. The artboard is checked first since the runtime
+    /// can't count the animations of an artboard that doesn't exist.
+    pub fn animation(&self, artboard: usize, index: usize) -> Option<Animation<'_>> {
+        if artboard >= self.artboard_count() {
+            return None;
+        }
+        let count = unsafe { rive_file_animation_count(self.raw_file, artboard) };
+        (index < count).then(|| {
+            Animation::new(self, artboard, unsafe {
+                rive_file_animation(self.raw_file, artboard, index)
+            })
+        })
+    }
+
+    /// Instances artboard `artboard` with its state machine `state_machine`. `None` if either
+    /// index is out of range.
+    pub fn scene(&self, artboard: usize, state_machine: usize) -> Option<Scene<'_>> {
+        if artboard >= self.artboard_count() {
+            return None;
+        }
+        Scene::new(self, artboard, state_machine)
+    }
+
+    /// Index of the state machine artboard `artboard` plays by default, if it's set.
+    pub fn default_state_machine(&self, artboard: usize) -> Option<usize> {
+        if artboard >= self.artboard_count() {
+            return None;
+        }
+        usize::try_from(unsafe { rive_file_default_state_machine(self.raw_file, artboard) }).ok()
+    }
+
+    pub fn artboards(&self) -> Vec<ArtboardSummary> {
+        (0..self.artboard_count())
+            .map(|artboard| unsafe {
+                ArtboardSummary {
+                    name: to_string(rive_file_artboard_name(self.raw_file, artboard)),
+                    animations: (0..rive_file_animation_count(self.raw_file, artboard))
+                        .map(|i| to_string(rive_file_animation_name(self.raw_file, artboard, i)))
+                        .collect(),
+                    state_machines: (0..rive_file_state_machine_count(self.raw_file, artboard))
+                        .map(|i| {
+                            to_string(rive_file_state_machine_name(self.raw_file, artboard, i))
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        unsafe {
+            rive_file_release(self.raw_file);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::writer::{RivWriter, Value};
+
+    #[test]
+    fn lists_artboards() {
+        let data = fs::read("../../test/assets/two_artboards.riv").unwrap();
+        let file = File::new(&data).unwrap();
+
+        assert_eq!(file.artboard_count(), 2);
+        assert_eq!(file.artboards().len(), 2);
+    }
+
+    #[test]
+    fn out_of_range_animations_are_none() {
+        let data = fs::read("../../test/assets/two_artboards.riv").unwrap();
+        let file = File::new(&data).unwrap();
+
+        assert!(file.animation(2, 0).is_none());
+        assert!(file.animation(usize::MAX, 0).is_none());
+        let count = file.artboards()[0].animations.len();
+        assert!(file.animation(0, count).is_none());
+    }
+
+    #[test]
+    fn exposes_raw_curves() {
+        let data = fs::read("../../test/assets/cubic_value_test.riv").unwrap();
+        let file = File::new(&data).unwrap();
+
+        let index = file.artboards()[0]
+            .animations
+            .iter()
+            .position(|name| name == "Timeline 1")
+            .unwrap();
+        let animation = file.animation(0, index).unwrap();
+        let fps = animation.fps() as f32;
+
+        let x = animation
+            .keyed_properties()
+            .into_iter()
+            .find(|property| {
+                property.object_name == "grey_rectangle" && property.property == Property::X
+            })
+            .unwrap();
+
+        assert_eq!(x.property_key, Property::X.key());
+        assert!(x
+            .key_frames
+            .iter()
+            .all(|key_frame| key_frame.seconds == key_frame.frame as f32 / fps));
+        assert!(x
+            .key_frames
+            .iter()
+            .any(|key_frame| matches!(key_frame.interpolation, Interpolation::CubicValue { .. })));
+    }
+
+    /// A single animation authored at `fps` over `duration` frames, with an optional work area.
+    fn authored(fps: u32, duration: u32, work_area: Option<(u32, u32)>) -> Vec<u8> {
+        let mut properties = vec![
+            (RivWriter::ANIMATION_NAME, Value::String("authored")),
+            (RivWriter::ANIMATION_FPS, Value::Uint(fps as u64)),
+            (RivWriter::ANIMATION_DURATION, Value::Uint(duration as u64)),
+            (RivWriter::ANIMATION_LOOP, Value::Uint(1)),
+        ];
+        if let Some((start, end)) = work_area {
+            properties.extend([
+                (RivWriter::ANIMATION_WORK_START, Value::Uint(start as u64)),
+                (RivWriter::ANIMATION_WORK_END, Value::Uint(end as u64)),
+                (RivWriter::ANIMATION_ENABLE_WORK_AREA, Value::Uint(1)),
+            ]);
+        }
+
+        RivWriter::new()
+            .object(RivWriter::ARTBOARD, &[])
+            .object(RivWriter::LINEAR_ANIMATION, &properties)
+            .finish()
+    }
+
+    #[test]
+    fn exposes_authored_timing() {
+        for (fps, duration, seconds) in [(24, 48, 2.0), (30, 45, 1.5), (60, 90, 1.5)] {
+            let file = File::new(&authored(fps, duration, None)).unwrap();
+            let info = file.animation(0, 0).unwrap().info();
+
+            assert_eq!(info.fps, fps);
+            assert_eq!(info.duration_frames, duration);
+            assert_eq!((info.work_start, info.work_end), (0, duration));
+            assert_eq!(info.loop_mode, LoopMode::Loop);
+            assert_eq!(info.speed, 1.0);
+            assert_eq!(info.duration_seconds(), seconds);
+        }
+    }
+
+    #[test]
+    fn work_area_bounds_duration() {
+        let file = File::new(&authored(30, 90, Some((15, 60)))).unwrap();
+        let info = file.animation(0, 0).unwrap().info();
+
+        assert_eq!((info.work_start, info.work_end), (15, 60));
+        assert_eq!(info.start_seconds(), 0.5);
+        assert_eq!(info.end_seconds(), 2.0);
+        assert_eq!(info.duration_seconds(), 1.5);
+    }
+
+    #[test]
+    fn frames_round_trip_through_seconds() {
+        for fps in [24, 30, 60] {
+            let file = File::new(&authored(fps, fps * 10, None)).unwrap();
+            let info = file.animation(0, 0).unwrap().info();
+
+            for frame in 0..=info.duration_frames {
+                assert_eq!(info.frame_at_time(info.time_at_frame(frame)), frame);
+            }
+
+            // Times between frames go to the nearest one.
+            let frame = 1.0 / fps as f32;
+            assert_eq!(info.frame_at_time(frame * 2.4), 2);
+            assert_eq!(info.frame_at_time(frame * 2.6), 3);
+            assert_eq!(info.frame_at_time(-frame), 0);
+        }
+    }
+
+    #[test]
+    fn stops_at_object_limit() {
+        let data = (0..1_000_000)
+            .fold(
+                RivWriter::new().object(RivWriter::ARTBOARD, &[]),
+                |writer, _| writer.object(RivWriter::NODE, &[]),
+            )
+            .finish();
+        let limits = LoadLimits {
+            max_objects: 100,
+            ..LoadLimits::default()
+        };
+
+        assert_eq!(
+            File::with_limits(&data, &limits).unwrap_err(),
+            ImportError::LimitExceeded {
+                limit: LoadLimit::Objects,
+                observed: 101,
+            }
+        );
+        assert!(File::with_limits(&data, &LoadLimits::unlimited()).is_ok());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(
+            File::new(b"definitely not a .riv file").unwrap_err(),
+            ImportError::Malformed
+        );
+    }
+}
@@ -0,0 +1,283 @@
+#include "rive/animation/blend_animation_1d.hpp"
+#include "rive/animation/blend_state_1d.hpp"
+#include "rive/animation/layer_state.hpp"
+#include "rive/animation/state_machine_bool.hpp"
+#include "rive/animation/state_machine_input_instance.hpp"
+#include "rive/animation/state_machine_instance.hpp"
+#include "rive/animation/state_machine_layer.hpp"
+#include "rive/animation/state_machine_listener.hpp"
+#include "rive/animation/state_machine_number.hpp"
+#include "rive/animation/state_transition.hpp"
+#include "rive/animation/transition_number_condition.hpp"
+#include "rive/artboard.hpp"
+#include "rive/event.hpp"
+#include "rive/file.hpp"
+#include "rive/nested_artboard.hpp"
+#include "rive/text/text_value_run.hpp"
+
+#include <algorithm>
+#include <string>
+#include <vector>
+
+using namespace rive;
+
+// An artboard instance with one of its state machines, which refers to it, and what the
+// pair offers a host.
+struct MachineScene
+{
+    std::unique_ptr<ArtboardInstance> artboard;
+    std::unique_ptr<StateMachineInstance> machine;
+    // Values each number input is compared against for equality or blended at, by input
+    // index. Inputs also used in ordered comparisons are continuous and have none.
+    std::vector<std::vector<float>> discreteValues;
+    std::vector<std::string> events;
+    // Named text runs and nested artboards, prefixed with the nested artboards leading to
+    // them, e.g. "card/title".
+    std::vector<std::string> textRuns;
+    std::vector<std::string> nestedPaths;
+};
+
+static void loadDiscreteValues(MachineScene* scene)
+{
+    auto machine = scene->machine->stateMachine();
+    auto& values = scene->discreteValues;
+    values.resize(machine->inputCount());
+    std::vector<bool> continuous(machine->inputCount(), false);
+
+    auto addCondition = [&](const TransitionCondition* condition) {
+        if (condition == nullptr || !condition->is<TransitionNumberCondition>() ||
+            condition->inputId() >= values.size())
+        {
+            return;
+        }
+        auto number = condition->as<TransitionNumberCondition>();
+        switch (number->op())
+        {
+            case TransitionConditionOp::equal:
+            case TransitionConditionOp::notEqual:
+                values[number->inputId()].push_back(number->value());
+                break;
+            default:
+                continuous[number->inputId()] = true;
+                break;
+        }
+    };
+
+    for (size_t i = 0; i < machine->layerCount(); i++)
+    {
+        auto layer = machine->layer(i);
+        for (size_t j = 0; j < layer->stateCount(); j++)
+        {
+            auto state = layer->state(j);
+            for (size_t k = 0; k < state->transitionCount(); k++)
+            {
+                auto transition = state->transition(k);
+                for (size_t l = 0; l < transition->conditionCount(); l++)
+                {
+                    addCondition(transition->condition(l));
+                }
+            }
+
+            if (state->is<BlendState1D>())
+            {
+                auto blendState = state->as<BlendState1D>();
+                if (blendState->inputId() < values.size())
+                {
+                    for (auto animation : blendState->animations())
+                    {
+                        values[blendState->inputId()].push_back(
+                            animation->as<BlendAnimation1D>()->value());
+                    }
+                }
+            }
+        }
+    }
+    for (size_t i = 0; i < machine->listenerCount(); i++)
+    {
+        auto listener = machine->listener(i);
+        for (size_t j = 0; j < listener->conditionCount(); j++)
+        {
+            addCondition(listener->condition(j));
+        }
+    }
+
+    for (size_t i = 0; i < values.size(); i++)
+    {
+        auto& inputValues = values[i];
+        if (continuous[i] || !machine->input(i)->is<StateMachineNumber>())
+        {
+            inputValues.clear();
+            continue;
+        }
+        std::sort(inputValues.begin(), inputValues.end());
+        inputValues.erase(std::unique(inputValues.begin(), inputValues.end()),
+                          inputValues.end());
+    }
+}
+
+static void loadPaths(MachineScene* scene, ArtboardInstance* artboard, const std::string& prefix)
+{
+    for (auto object : artboard->objects())
+    {
+        if (object != nullptr && object->is<TextValueRun>() &&
+            !object->as<TextValueRun>()->name().empty())
+        {
+            scene->textRuns.push_back(prefix + object->as<TextValueRun>()->name());
+        }
+    }
+    for (auto nested : artboard->nestedArtboards())
+    {
+        if (!nested->name().empty())
+        {
+            scene->nestedPaths.push_back(prefix + nested->name());
+        }
+        if (auto instance = nested->artboard())
+        {
+            loadPaths(scene, instance, prefix + nested->name() + "/");
+        }
+    }
+}
+
+// name is owned by the scene's state machine instance.
+struct InputInfo
+{
+    const char* name;
+    // 0 for numbers, 1 for booleans and 2 for triggers, which have no value.
+    int type;
+    float value;
+};
+
+static StateMachineInstance* machine(void* scene)
+{
+    return static_cast<MachineScene*>(scene)->machine.get();
+}
+
+extern "C"
+{
+    typedef void* RawFile;
+    typedef void* RawScene;
+
+    // Returns null if either index is out of range.
+    RawScene rive_scene_new(const RawFile file, size_t artboard, size_t stateMachine)
+    {
+        auto artboardInstance = static_cast<File*>(file)->artboardAt(artboard);
+        if (artboardInstance == nullptr || stateMachine >= artboardInstance->stateMachineCount())
+        {
+            return nullptr;
+        }
+
+        auto scene = new MachineScene();
+        scene->machine = artboardInstance->stateMachineAt(stateMachine);
+        scene->artboard = std::move(artboardInstance);
+
+        loadDiscreteValues(scene);
+        for (auto object : scene->artboard->objects())
+        {
+            if (object != nullptr && object->is<Event>() && !object->as<Event>()->name().empty())
+            {
+                scene->events.push_back(object->as<Event>()->name());
+            }
+        }
+        loadPaths(scene, scene->artboard.get(), "");
+        return scene;
+    }
+
+    void rive_scene_release(const RawScene scene)
+    {
+        std::unique_ptr<MachineScene> val(static_cast<MachineScene*>(scene));
+    }
+
+    bool rive_scene_advance(const RawScene scene, float seconds)
+    {
+        return machine(scene)->advanceAndApply(seconds);
+    }
+
+    size_t rive_scene_input_count(const RawScene scene)
+    {
+        return machine(scene)->inputCount();
+    }
+
+    void rive_scene_input(const RawScene scene, size_t index, InputInfo* info)
+    {
+        auto input = machine(scene)->input(index);
+        info->name = input->name().c_str();
+        if (input->input()->is<StateMachineNumber>())
+        {
+            info->type = 0;
+            info->value = static_cast<SMINumber*>(input)->value();
+        }
+        else if (input->input()->is<StateMachineBool>())
+        {
+            info->type = 1;
+            info->value = static_cast<SMIBool*>(input)->value() ? 1.0f : 0.0f;
+        }
+        else
+        {
+            info->type = 2;
+            info->value = 0.0f;
+        }
+    }
+
+    void rive_scene_set_number(const RawScene scene, size_t index, float value)
+    {
+        static_cast<SMINumber*>(machine(scene)->input(index))->value(value);
+    }
+
+    void rive_scene_set_boolean(const RawScene scene, size_t index, bool value)
+    {
+        static_cast<SMIBool*>(machine(scene)->input(index))->value(value);
+    }
+
+    void rive_scene_fire_trigger(const RawScene scene, size_t index)
+    {
+        static_cast<SMITrigger*>(machine(scene)->input(index))->fire();
+    }
+
+    size_t rive_scene_fired_event_count(const RawScene scene)
+    {
+        return machine(scene)->firedEventCount();
+    }
+
+    const char* rive_scene_fired_event_name(const RawScene scene, size_t index)
+    {
+        return machine(scene)->firedEventAt(index)->name().c_str();
+    }
+
+    // Returns the number of values, which are owned by the scene.
+    size_t rive_scene_discrete_values(const RawScene scene, size_t index, const float** values)
+    {
+        auto& inputValues = static_cast<MachineScene*>(scene)->discreteValues[index];
+        *values = inputValues.data();
+        return inputValues.size();
+    }
+
+    size_t rive_scene_event_count(const RawScene scene)
+    {
+        return static_cast<MachineScene*>(scene)->events.size();
+    }
+
+    const char* rive_scene_event_name(const RawScene scene, size_t index)
+    {
+        return static_cast<MachineScene*>(scene)->events[index].c_str();
+    }
+
+    size_t rive_scene_text_run_count(const RawScene scene)
+    {
+        return static_cast<MachineScene*>(scene)->textRuns.size();
+    }
+
+    const char* rive_scene_text_run_path(const RawScene scene, size_t index)
+    {
+        return static_cast<MachineScene*>(scene)->textRuns[index].c_str();
+    }
+
+    size_t rive_scene_nested_artboard_count(const RawScene scene)
+    {
+        return static_cast<MachineScene*>(scene)->nestedPaths.size();
+    }
+
+    const char* rive_scene_nested_artboard_path(const RawScene scene, size_t index)
+    {
+        return static_cast<MachineScene*>(scene)->nestedPaths[index].c_str();
+    }
+}
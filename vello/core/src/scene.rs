@@ -0,0 +1,446 @@
+use std::{collections::HashMap, ffi::c_char, fmt, marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    contract::{ContractReport, FileInput, FileInterface, InteractionContract},
+    to_string, File, RawFile,
+};
+
+#[derive(Debug)]
+pub(crate) enum RawScene {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum InputType {
+    Number,
+    Bool,
+    Trigger,
+}
+
+impl InputType {
+    /// The type of a C++ `InputInfo`. `None` for input types which this crate doesn't know.
+    #[doc(hidden)]
+    pub fn from_raw(input_type: i32) -> Option<Self> {
+        match input_type {
+            0 => Some(Self::Number),
+            1 => Some(Self::Bool),
+            2 => Some(Self::Trigger),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number => write!(f, "number"),
+            Self::Bool => write!(f, "boolean"),
+            Self::Trigger => write!(f, "trigger"),
+        }
+    }
+}
+
+/// An input of a state machine, as listed by `Scene::inputs`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputDescriptor {
+    pub name: String,
+    pub input_type: InputType,
+}
+
+/// Mirrors the C++ `InputInfo`, which `rive-vello`'s viewer fills in too.
+#[doc(hidden)]
+#[repr(C)]
+#[derive(Debug)]
+pub struct RawInputInfo {
+    pub name: *const c_char,
+    pub input_type: i32,
+    pub value: f32,
+}
+
+impl Default for RawInputInfo {
+    fn default() -> Self {
+        Self {
+            name: std::ptr::null(),
+            input_type: 0,
+            value: 0.0,
+        }
+    }
+}
+
+impl RawInputInfo {
+    /// `None` for input types which this crate doesn't know.
+    ///
+    /// # Safety
+    ///
+    /// `name` has to point to a nul-terminated string, as the runtime fills it in.
+    pub unsafe fn descriptor(&self) -> Option<InputDescriptor> {
+        Some(InputDescriptor {
+            input_type: InputType::from_raw(self.input_type)?,
+            name: to_string(self.name),
+        })
+    }
+
+    /// The input as an interface lists it: numbers with the value they start with, then
+    /// `discrete_values`.
+    ///
+    /// # Safety
+    ///
+    /// Same as `descriptor`.
+    pub unsafe fn file_input(&self, discrete_values: &[f32]) -> Option<FileInput> {
+        let InputDescriptor { name, input_type } = self.descriptor()?;
+        let mut values = Vec::new();
+        if input_type == InputType::Number {
+            values.push(self.value);
+            values.extend_from_slice(discrete_values);
+        }
+
+        Some(FileInput {
+            name,
+            input_type,
+            values,
+        })
+    }
+}
+
+/// Runtime indices of a state machine's inputs by name, so that hosts setting an input every
+/// frame don't compare names every frame.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct InputIndices(HashMap<String, Vec<(InputType, usize)>>);
+
+impl InputIndices {
+    pub fn new(inputs: impl IntoIterator<Item = (usize, InputDescriptor)>) -> Self {
+        let mut indices: HashMap<String, Vec<(InputType, usize)>> = HashMap::new();
+        for (index, input) in inputs {
+            indices
+                .entry(input.name)
+                .or_default()
+                .push((input.input_type, index));
+        }
+
+        Self(indices)
+    }
+
+    /// Index of the first input called `name` of type `input_type`.
+    pub fn get(&self, name: &str, input_type: InputType) -> Option<usize> {
+        self.0
+            .get(name)?
+            .iter()
+            .find(|(found, _)| *found == input_type)
+            .map(|(_, index)| *index)
+    }
+}
+
+extern "C" {
+    fn rive_scene_new(
+        raw_file: Option<NonNull<RawFile>>,
+        artboard: usize,
+        state_machine: usize,
+    ) -> Option<NonNull<RawScene>>;
+    fn rive_scene_release(raw_scene: NonNull<RawScene>);
+    fn rive_scene_advance(raw_scene: NonNull<RawScene>, seconds: f32) -> bool;
+    fn rive_scene_input_count(raw_scene: NonNull<RawScene>) -> usize;
+    fn rive_scene_input(raw_scene: NonNull<RawScene>, index: usize, info: *mut RawInputInfo);
+    fn rive_scene_set_number(raw_scene: NonNull<RawScene>, index: usize, value: f32);
+    fn rive_scene_set_boolean(raw_scene: NonNull<RawScene>, index: usize, value: bool);
+    fn rive_scene_fire_trigger(raw_scene: NonNull<RawScene>, index: usize);
+    fn rive_scene_fired_event_count(raw_scene: NonNull<RawScene>) -> usize;
+    fn rive_scene_fired_event_name(raw_scene: NonNull<RawScene>, index: usize) -> *const c_char;
+    fn rive_scene_discrete_values(
+        raw_scene: NonNull<RawScene>,
+        index: usize,
+        values: *mut *const f32,
+    ) -> usize;
+    fn rive_scene_event_count(raw_scene: NonNull<RawScene>) -> usize;
+    fn rive_scene_event_name(raw_scene: NonNull<RawScene>, index: usize) -> *const c_char;
+    fn rive_scene_text_run_count(raw_scene: NonNull<RawScene>) -> usize;
+    fn rive_scene_text_run_path(raw_scene: NonNull<RawScene>, index: usize) -> *const c_char;
+    fn rive_scene_nested_artboard_count(raw_scene: NonNull<RawScene>) -> usize;
+    fn rive_scene_nested_artboard_path(raw_scene: NonNull<RawScene>, index: usize)
+        -> *const c_char;
+}
+
+/// An instance of one of a `File`'s artboards, driven by one of its state machines. Nothing is
+/// drawn, so it suits hosts which only need the machine's logic, e.g. to test how a file reacts
+/// to its inputs.
+#[derive(Debug)]
+pub struct Scene<'f> {
+    raw_scene: NonNull<RawScene>,
+    /// Listed once, since a scene's state machine and so its inputs never change.
+    input_indices: InputIndices,
+    _file: PhantomData<&'f File>,
+}
+
+impl<'f> Scene<'f> {
+    pub(crate) fn new(file: &'f File, artboard: usize, state_machine: usize) -> Option<Self> {
+        let raw_scene = unsafe { rive_scene_new(file.raw_file, artboard, state_machine) }?;
+
+        let mut scene = Self {
+            raw_scene,
+            input_indices: InputIndices::default(),
+            _file: PhantomData,
+        };
+        scene.input_indices = InputIndices::new(scene.indexed_inputs());
+
+        Some(scene)
+    }
+
+    /// Advances the state machine by `elapsed` seconds and applies it to the artboard. The
+    /// first advance, usually by 0, takes the transitions out of the entry state. Returns
+    /// whether the machine keeps animating.
+    pub fn advance(&mut self, elapsed: f32) -> bool {
+        unsafe { rive_scene_advance(self.raw_scene, elapsed) }
+    }
+
+    fn input_count(&self) -> usize {
+        unsafe { rive_scene_input_count(self.raw_scene) }
+    }
+
+    fn raw_input(&self, index: usize) -> RawInputInfo {
+        let mut info = RawInputInfo::default();
+        unsafe {
+            rive_scene_input(self.raw_scene, index, &mut info);
+        }
+        info
+    }
+
+    /// Inputs of the state machine with their runtime indices, skipping those of types this
+    /// crate doesn't know.
+    fn indexed_inputs(&self) -> impl Iterator<Item = (usize, InputDescriptor)> + '_ {
+        (0..self.input_count())
+            .filter_map(|index| Some((index, unsafe { self.raw_input(index).descriptor() }?)))
+    }
+
+    /// Runtime index of the first input called `name` of type `input_type`.
+    fn find(&self, name: &str, input_type: InputType) -> Option<usize> {
+        self.input_indices.get(name, input_type)
+    }
+
+    /// Inputs of the state machine, in file order.
+    pub fn inputs(&self) -> Vec<InputDescriptor> {
+        self.indexed_inputs().map(|(_, input)| input).collect()
+    }
+
+    pub fn number(&self, name: &str) -> Option<f32> {
+        let index = self.find(name, InputType::Number)?;
+        Some(self.raw_input(index).value)
+    }
+
+    pub fn boolean(&self, name: &str) -> Option<bool> {
+        let index = self.find(name, InputType::Bool)?;
+        Some(self.raw_input(index).value != 0.0)
+    }
+
+    /// Returns whether there is a number input called `name`. Takes effect on the next
+    /// `advance`, like all inputs.
+    pub fn set_number(&mut self, name: &str, value: f32) -> bool {
+        self.find(name, InputType::Number)
+            .map(|index| unsafe { rive_scene_set_number(self.raw_scene, index, value) })
+            .is_some()
+    }
+
+    /// Returns whether there is a boolean input called `name`.
+    pub fn set_boolean(&mut self, name: &str, value: bool) -> bool {
+        self.find(name, InputType::Bool)
+            .map(|index| unsafe { rive_scene_set_boolean(self.raw_scene, index, value) })
+            .is_some()
+    }
+
+    /// Returns whether there is a trigger called `name`.
+    pub fn fire_trigger(&mut self, name: &str) -> bool {
+        self.find(name, InputType::Trigger)
+            .map(|index| unsafe { rive_scene_fire_trigger(self.raw_scene, index) })
+            .is_some()
+    }
+
+    /// Names of the events fired during the last `advance`, in the order they fired.
+    pub fn fired_events(&self) -> Vec<String> {
+        unsafe {
+            (0..rive_scene_fired_event_count(self.raw_scene))
+                .map(|index| to_string(rive_scene_fired_event_name(self.raw_scene, index)))
+                .collect()
+        }
+    }
+
+    fn strings(
+        &self,
+        count: unsafe extern "C" fn(NonNull<RawScene>) -> usize,
+        string: unsafe extern "C" fn(NonNull<RawScene>, usize) -> *const c_char,
+    ) -> Vec<String> {
+        unsafe {
+            (0..count(self.raw_scene))
+                .map(|index| to_string(string(self.raw_scene, index)))
+                .collect()
+        }
+    }
+
+    /// What the artboard and its state machine offer a host. Number inputs list the value they
+    /// start with, then the values the state machine compares them against for equality or
+    /// blends at, unless it also compares them in order.
+    pub fn interface(&self) -> FileInterface {
+        let inputs = (0..self.input_count())
+            .filter_map(|index| unsafe {
+                let mut discrete = std::ptr::null();
+                let len = rive_scene_discrete_values(self.raw_scene, index, &mut discrete);
+                let discrete = if len == 0 {
+                    &[][..]
+                } else {
+                    std::slice::from_raw_parts(discrete, len)
+                };
+
+                self.raw_input(index).file_input(discrete)
+            })
+            .collect();
+
+        FileInterface {
+            inputs,
+            events: self.strings(rive_scene_event_count, rive_scene_event_name),
+            text_runs: self.strings(rive_scene_text_run_count, rive_scene_text_run_path),
+            nested_paths: self.strings(
+                rive_scene_nested_artboard_count,
+                rive_scene_nested_artboard_path,
+            ),
+        }
+    }
+
+    /// Checks the inputs of the state machine and the events, text runs and nested artboards
+    /// of the artboard against what `contract` expects.
+    pub fn check_contract(&self, contract: &InteractionContract) -> ContractReport {
+        ContractReport::check(contract, &self.interface())
+    }
+}
+
+impl Drop for Scene<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            rive_scene_release(self.raw_scene);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn out_of_range_scenes_are_none() {
+        let data = fs::read("../../test/assets/death_knight.riv").unwrap();
+        let file = File::new(&data).unwrap();
+
+        assert!(file.scene(0, 0).is_some());
+        assert!(file.scene(0, 1).is_none());
+        assert!(file.scene(file.artboard_count(), 0).is_none());
+
+        // "Death Up" doesn't set one.
+        assert_eq!(file.default_state_machine(0), Some(0));
+        assert_eq!(file.default_state_machine(3), None);
+        assert_eq!(file.default_state_machine(file.artboard_count()), None);
+    }
+
+    #[test]
+    fn sets_inputs_by_name_and_type() {
+        let data = fs::read("../../test/assets/death_knight.riv").unwrap();
+        let file = File::new(&data).unwrap();
+        let mut scene = file.scene(0, 0).unwrap();
+        scene.advance(0.0);
+
+        let descriptor = |name: &str, input_type| InputDescriptor {
+            name: name.to_string(),
+            input_type,
+        };
+        assert_eq!(
+            scene.inputs(),
+            [
+                descriptor("Direction", InputType::Number),
+                descriptor("Attack", InputType::Trigger),
+                descriptor("Death", InputType::Trigger),
+                descriptor("isMoving", InputType::Bool),
+            ]
+        );
+
+        assert_eq!(scene.number("Direction"), Some(1.0));
+        assert!(scene.set_number("Direction", 3.0));
+        assert_eq!(scene.number("Direction"), Some(3.0));
+
+        assert_eq!(scene.boolean("isMoving"), Some(false));
+        assert!(scene.set_boolean("isMoving", true));
+        assert_eq!(scene.boolean("isMoving"), Some(true));
+
+        assert!(scene.fire_trigger("Attack"));
+        scene.advance(1.0 / 60.0);
+
+        // Names only match inputs of the right type.
+        assert!(!scene.fire_trigger("Direction"));
+        assert!(!scene.set_boolean("Attack", true));
+        assert_eq!(scene.number("isMoving"), None);
+        assert!(!scene.set_number("Missing", 1.0));
+    }
+
+    #[test]
+    fn reports_events_of_the_last_advance() {
+        let data = fs::read("../../test/assets/events_on_states.riv").unwrap();
+        let file = File::new(&data).unwrap();
+        let mut scene = file.scene(0, 0).unwrap();
+
+        scene.advance(0.0);
+        assert_eq!(scene.fired_events(), ["First"]);
+
+        // The first state is left after 2 seconds, firing an event on its way out and one on
+        // the transition taken.
+        scene.advance(1.0);
+        assert!(scene.fired_events().is_empty());
+        scene.advance(1.0);
+        assert_eq!(scene.fired_events(), ["Second", "Third"]);
+        scene.advance(1.0);
+        assert_eq!(scene.fired_events(), ["Fourth"]);
+    }
+
+    #[test]
+    fn lists_what_the_file_offers() {
+        let data = fs::read("../../test/assets/death_knight.riv").unwrap();
+        let file = File::new(&data).unwrap();
+        let interface = file.scene(0, 0).unwrap().interface();
+
+        let direction = &interface.inputs[0];
+        assert_eq!(direction.name, "Direction");
+        assert_eq!(direction.values, [1.0, 1.0, 2.0, 3.0, 4.0]);
+        assert!(interface.inputs[1..]
+            .iter()
+            .all(|input| input.values.is_empty()));
+        assert_eq!(
+            interface.nested_paths,
+            ["Death Left", "Death Down", "Death Up", "Death Right"]
+        );
+
+        let data = fs::read("../../test/assets/events_on_states.riv").unwrap();
+        let file = File::new(&data).unwrap();
+        assert_eq!(
+            file.scene(0, 0).unwrap().interface().events,
+            ["Fourth", "Second", "First", "Third"]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn checks_contracts() {
+        let data = fs::read("../../test/assets/death_knight.riv").unwrap();
+        let file = File::new(&data).unwrap();
+        let text = fs::read_to_string("../../test/assets/death_knight_contract.json").unwrap();
+        let contract: InteractionContract = serde_json::from_str(&text).unwrap();
+
+        let report = file.scene(0, 0).unwrap().check_contract(&contract);
+        assert!(!report.is_satisfied());
+        assert_eq!(report.missing_inputs, ["Jump"]);
+        assert_eq!(report.type_mismatches[0].found, InputType::Bool);
+        assert_eq!(report.range_mismatches[0].values, [4.0]);
+        assert_eq!(report.missing_events, ["done"]);
+        assert_eq!(report.missing_text_runs, ["name"]);
+        assert_eq!(report.missing_nested_paths, ["Death Sideways"]);
+        assert_eq!(report.extra_inputs, ["Death"]);
+    }
+}
@@ -0,0 +1,154 @@
+//! Builds `.riv` files object by object, so tests can generate the inputs they need instead of
+//! checking them in, and apps can ship small files without a binary blob.
+
+/// A property value, encoded as its key's field type expects.
+pub enum Value<'s> {
+    Uint(u64),
+    Double(f32),
+    String(&'s str),
+    /// An ARGB color.
+    Color(u32),
+}
+
+/// Writes objects in order, leaving it to the caller to make a file the runtime imports.
+pub struct RivWriter {
+    bytes: Vec<u8>,
+}
+
+impl RivWriter {
+    // Type keys.
+    pub const BACKBOARD: u64 = 23;
+    pub const ARTBOARD: u64 = 1;
+    pub const NODE: u64 = 2;
+    pub const SHAPE: u64 = 3;
+    pub const ELLIPSE: u64 = 4;
+    pub const STRAIGHT_VERTEX: u64 = 5;
+    pub const POINTS_PATH: u64 = 16;
+    pub const SOLID_COLOR: u64 = 18;
+    pub const FILL: u64 = 20;
+    pub const STROKE: u64 = 24;
+    pub const KEYED_OBJECT: u64 = 25;
+    pub const KEYED_PROPERTY: u64 = 26;
+    pub const CUBIC_EASE_INTERPOLATOR: u64 = 28;
+    pub const KEY_FRAME_DOUBLE: u64 = 30;
+    pub const LINEAR_ANIMATION: u64 = 31;
+    pub const KEY_FRAME_COLOR: u64 = 37;
+    pub const STATE_MACHINE: u64 = 53;
+    pub const STATE_MACHINE_LAYER: u64 = 57;
+    pub const STATE_MACHINE_BOOL: u64 = 59;
+    pub const ANIMATION_STATE: u64 = 61;
+    pub const ANY_STATE: u64 = 62;
+    pub const ENTRY_STATE: u64 = 63;
+    pub const EXIT_STATE: u64 = 64;
+    pub const STATE_TRANSITION: u64 = 65;
+    pub const TRANSITION_BOOL_CONDITION: u64 = 71;
+    pub const STATE_MACHINE_LISTENER: u64 = 114;
+    pub const LISTENER_BOOL_CHANGE: u64 = 117;
+
+    // Property keys, named after the type which declares them.
+    pub const COMPONENT_NAME: u64 = 4;
+    pub const COMPONENT_PARENT_ID: u64 = 5;
+    pub const ARTBOARD_WIDTH: u64 = 7;
+    pub const ARTBOARD_HEIGHT: u64 = 8;
+    pub const NODE_X: u64 = 13;
+    pub const NODE_Y: u64 = 14;
+    pub const TRANSFORM_SCALE_X: u64 = 16;
+    pub const TRANSFORM_SCALE_Y: u64 = 17;
+    pub const PARAMETRIC_PATH_WIDTH: u64 = 20;
+    pub const PARAMETRIC_PATH_HEIGHT: u64 = 21;
+    pub const VERTEX_X: u64 = 24;
+    pub const VERTEX_Y: u64 = 25;
+    pub const POINTS_PATH_IS_CLOSED: u64 = 32;
+    pub const SOLID_COLOR_VALUE: u64 = 37;
+    pub const FILL_RULE: u64 = 40;
+    pub const STROKE_THICKNESS: u64 = 47;
+    pub const STROKE_CAP: u64 = 48;
+    pub const STROKE_JOIN: u64 = 49;
+    pub const KEYED_OBJECT_ID: u64 = 51;
+    pub const KEYED_PROPERTY_KEY: u64 = 53;
+    pub const ANIMATION_NAME: u64 = 55;
+    pub const ANIMATION_FPS: u64 = 56;
+    pub const ANIMATION_DURATION: u64 = 57;
+    pub const ANIMATION_LOOP: u64 = 59;
+    pub const ANIMATION_WORK_START: u64 = 60;
+    pub const ANIMATION_WORK_END: u64 = 61;
+    pub const ANIMATION_ENABLE_WORK_AREA: u64 = 62;
+    pub const CUBIC_X1: u64 = 63;
+    pub const CUBIC_Y1: u64 = 64;
+    pub const CUBIC_X2: u64 = 65;
+    pub const CUBIC_Y2: u64 = 66;
+    pub const KEY_FRAME_FRAME: u64 = 67;
+    pub const KEY_FRAME_INTERPOLATION_TYPE: u64 = 68;
+    pub const KEY_FRAME_INTERPOLATOR_ID: u64 = 69;
+    pub const KEY_FRAME_DOUBLE_VALUE: u64 = 70;
+    pub const KEY_FRAME_COLOR_VALUE: u64 = 88;
+    pub const STATE_MACHINE_COMPONENT_NAME: u64 = 138;
+    pub const ANIMATION_STATE_ANIMATION_ID: u64 = 149;
+    pub const STATE_TRANSITION_STATE_TO_ID: u64 = 151;
+    pub const TRANSITION_CONDITION_INPUT_ID: u64 = 155;
+    pub const TRANSITION_VALUE_CONDITION_OP: u64 = 156;
+    pub const STATE_TRANSITION_DURATION: u64 = 158;
+    pub const STATE_MACHINE_LISTENER_TARGET_ID: u64 = 224;
+    pub const STATE_MACHINE_LISTENER_TYPE: u64 = 225;
+    pub const LISTENER_INPUT_CHANGE_INPUT_ID: u64 = 227;
+    pub const LISTENER_BOOL_CHANGE_VALUE: u64 = 228;
+    pub const ARTBOARD_DEFAULT_STATE_MACHINE_ID: u64 = 236;
+
+    /// A version 7.0 header with an empty property ToC, followed by the backboard.
+    pub fn new() -> Self {
+        let mut writer = Self {
+            bytes: b"RIVE".to_vec(),
+        };
+        for value in [7, 0, 0, 0] {
+            writer.var_uint(value);
+        }
+
+        writer.object(Self::BACKBOARD, &[])
+    }
+
+    fn var_uint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push(0x80 | (value & 0x7f) as u8);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    /// Appends an object of type `type_key` with properties keyed by property key. Property
+    /// keys must be ones the runtime knows, since the ToC is empty.
+    pub fn object(mut self, type_key: u64, properties: &[(u64, Value<'_>)]) -> Self {
+        self.push(type_key, properties);
+        self
+    }
+
+    /// Appends an object like `object`, for writers kept in a variable.
+    pub fn push(&mut self, type_key: u64, properties: &[(u64, Value<'_>)]) {
+        self.var_uint(type_key);
+
+        for (key, value) in properties {
+            self.var_uint(*key);
+
+            match value {
+                Value::Uint(value) => self.var_uint(*value),
+                Value::Double(value) => self.bytes.extend(value.to_le_bytes()),
+                Value::String(value) => {
+                    self.var_uint(value.len() as u64);
+                    self.bytes.extend(value.as_bytes());
+                }
+                Value::Color(value) => self.bytes.extend(value.to_le_bytes()),
+            }
+        }
+
+        self.var_uint(0);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl Default for RivWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
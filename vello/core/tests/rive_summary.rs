@@ -0,0 +1,42 @@
+#![cfg(feature = "serde")]
+
+use std::process::Command;
+
+#[test]
+fn contracts_list_each_violation() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rive-summary"))
+        .arg("--contract=../../test/assets/death_knight_contract.json")
+        .arg("../../test/assets/death_knight.riv")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("breaks"));
+    for line in [
+        "  state machine: State Machine 1",
+        "missing input \"Jump\"",
+        "input \"isMoving\" is a boolean, expected a number",
+        "input \"Direction\" uses 4 outside 1..3",
+        "missing event \"done\"",
+        "missing text run \"name\"",
+        "missing nested artboard \"Death Sideways\"",
+        "extra input \"Death\"",
+    ] {
+        assert!(stdout.lines().any(|l| l == line), "{line:?} in\n{stdout}");
+    }
+    assert!(!stdout.contains("\"Attack\""));
+}
+
+#[test]
+fn unreadable_contracts_fail_before_any_file() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rive-summary"))
+        .arg("--contract=../../test/assets/death_knight.riv")
+        .arg("../../test/assets/death_knight.riv")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("isn't a contract"));
+}
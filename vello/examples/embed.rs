@@ -0,0 +1,110 @@
+//! Renders a `.riv` file into a texture on a device owned by the application, then samples
+//! that texture in the application's own render pass.
+//!
+//! ```bash
+//! $ cargo run --example embed -- file.riv
+//! ```
+
+use std::{sync::Arc, time::Instant};
+
+use rive_vello::{Blitter, TextureTarget, Viewer, ViewerContent};
+use winit::{
+    event::{Event, WindowEvent},
+    event_loop::{ControlFlow, EventLoop},
+    window::WindowBuilder,
+};
+
+fn main() {
+    let path = std::env::args().nth(1).expect("usage: embed <file.riv>");
+
+    let event_loop = EventLoop::new();
+    let window = WindowBuilder::new()
+        .with_title("Rive embedded in a wgpu application")
+        .build(&event_loop)
+        .unwrap();
+
+    // Everything below up to the `Viewer` is the application's own wgpu setup.
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+    let surface = unsafe { instance.create_surface(&window) }.unwrap();
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        compatible_surface: Some(&surface),
+        force_fallback_adapter: false,
+    }))
+    .expect("no suitable adapter");
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: Some("application"),
+            features: rive_vello::required_features(),
+            limits: rive_vello::required_limits(),
+        },
+        None,
+    ))
+    .unwrap();
+    let (device, queue) = (Arc::new(device), Arc::new(queue));
+
+    let format = surface.get_capabilities(&adapter).formats[0];
+    let size = window.inner_size();
+    let mut config = wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format,
+        width: size.width,
+        height: size.height,
+        present_mode: wgpu::PresentMode::Fifo,
+        alpha_mode: wgpu::CompositeAlphaMode::Auto,
+        view_formats: vec![],
+    };
+    surface.configure(&device, &config);
+
+    let mut viewer = Viewer::new(device.clone(), queue.clone(), None).unwrap();
+    let mut target =
+        TextureTarget::new(device.clone(), queue.clone(), size.width, size.height).unwrap();
+    let blitter = Blitter::new(&device, format);
+
+    viewer.set_content(ViewerContent::new(&path));
+    viewer.resize(size.width, size.height);
+
+    let mut last_frame = Instant::now();
+
+    event_loop.run(move |event, _, control_flow| {
+        *control_flow = ControlFlow::Poll;
+
+        match event {
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => *control_flow = ControlFlow::Exit,
+            Event::WindowEvent {
+                event: WindowEvent::Resized(size),
+                ..
+            } => {
+                config.width = size.width.max(1);
+                config.height = size.height.max(1);
+                surface.configure(&device, &config);
+
+                target.resize(size.width, size.height);
+                viewer.resize(size.width, size.height);
+            }
+            Event::MainEventsCleared => window.request_redraw(),
+            Event::RedrawRequested(_) => {
+                let elapsed = last_frame.elapsed().as_secs_f64();
+                last_frame = Instant::now();
+
+                viewer
+                    .render_to_texture(&mut target, elapsed)
+                    .expect("failed to render");
+
+                let frame = surface.get_current_texture().unwrap();
+                let view = frame
+                    .texture
+                    .create_view(&wgpu::TextureViewDescriptor::default());
+
+                // The application's pass, sampling the texture Rive rendered to.
+                blitter.blit(&device, &queue, target.view(), &view);
+
+                frame.present();
+            }
+            _ => {}
+        }
+    });
+}
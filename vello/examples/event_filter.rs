@@ -0,0 +1,83 @@
+//! Counts the allocations made while advancing many instances which fire events, once with
+//! every event built for `events_this_frame` and once with the events filtered out and read
+//! through a subscription instead.
+//!
+//! ```bash
+//! $ cargo run --release --example event_filter -- [file.riv]
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use rive_vello::ViewerContent;
+
+const INSTANCES: usize = 200;
+const FRAMES: usize = 600;
+/// Instances are restarted this often so that their state machines keep firing events. The
+/// default file fires all of its events within 3 seconds.
+const RESTART_FRAMES: usize = 200;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "../test/assets/events_on_states.riv".to_string());
+
+    for filtered in [false, true] {
+        let mut contents: Vec<_> = (0..INSTANCES)
+            .map(|_| {
+                let mut content = ViewerContent::new(&path).expect("failed to open file");
+                if filtered {
+                    content.set_event_filter(&[]);
+                    content.subscribe_event("Third", |event| {
+                        black_box(event.number("Volume"));
+                    });
+                }
+                content
+            })
+            .collect();
+        let artboard = contents[0].artboard_names().remove(0);
+
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        let mut events = 0;
+
+        for frame in 0..FRAMES {
+            for content in &mut contents {
+                if frame % RESTART_FRAMES == 0 {
+                    content.select(Some(&artboard), None).unwrap();
+                }
+                content.advance(1.0 / 60.0).unwrap();
+                events += content.events_this_frame().len() + content.skipped_event_count();
+            }
+        }
+
+        println!(
+            "{}: {events} events, {} allocations, {:.2?}",
+            if filtered { "filtered" } else { "built" },
+            ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            start.elapsed()
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Renders one frame of a `.riv` file to a PNG without a window, e.g. to produce a golden
+//! image. Works on the software fallback adapter when there's no GPU.
+//!
+//! ```bash
+//! $ cargo run --no-default-features --example render_frame -- file.riv 1.5 out.png [512x512]
+//! ```
+
+use std::process::ExitCode;
+
+use rive_vello::{HeadlessRenderer, ViewerContent};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [path, time, out, rest @ ..] = args.as_slice() else {
+        eprintln!("usage: render_frame FILE.riv SECONDS OUT.png [WIDTHxHEIGHT]");
+        return ExitCode::FAILURE;
+    };
+
+    match run(path, time, out, rest.first().map(String::as_str)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("render_frame: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(path: &str, time: &str, out: &str, size: Option<&str>) -> Result<(), String> {
+    let time: f64 = time
+        .parse()
+        .map_err(|_| format!("expected a time in seconds, got \"{time}\""))?;
+    let (width, height) = match size {
+        Some(size) => size
+            .split_once('x')
+            .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+            .ok_or_else(|| format!("expected a size like 512x512, got \"{size}\""))?,
+        None => (512, 512),
+    };
+
+    let mut content = ViewerContent::new(path).ok_or_else(|| format!("failed to open {path}"))?;
+    let mut renderer = HeadlessRenderer::new().map_err(|error| error.to_string())?;
+    let image = renderer
+        .render(&mut content, width, height, time)
+        .map_err(|error| error.to_string())?;
+    image
+        .save(out)
+        .map_err(|error| format!("failed to save {out}: {error}"))?;
+
+    println!(
+        "Rendered {path} at {time}s to {out} on a {:?} device",
+        renderer.tier()
+    );
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use crate::status::SceneStatus;
+
+/// Reported for missing assets whose bytes no loader was asked for.
+pub(crate) const NOT_IN_FILE: &str = "not embedded in the file, or not decodable";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AssetKind {
+    Image,
+    Font,
+}
+
+/// What is drawn in place of a missing asset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AssetFallback {
+    /// Images are drawn over their authored size in the renderer's `MissingAssetStyle`.
+    Placeholder,
+    /// Texts are shaped with the runtime's registered fallback font.
+    FallbackFont,
+    /// No fallback font covers the texts, which are boxed instead.
+    BoundingBoxes,
+}
+
+/// An image or font the file refers to which couldn't be loaded, as listed by
+/// `ViewerContent::missing_assets`. The runtime has no audio assets, so there are no audio
+/// events to skip.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MissingAsset {
+    /// The id to pass to `ViewerContent::rebind_asset`.
+    pub id: u32,
+    pub name: String,
+    pub kind: AssetKind,
+    pub fallback: AssetFallback,
+    /// Why the asset is missing: the loader's error, or why its bytes couldn't be used.
+    pub error: String,
+}
+
+impl MissingAsset {
+    /// `None` for asset types and fallbacks which this crate doesn't know.
+    pub(crate) fn from_raw(
+        id: u32,
+        name: String,
+        asset_type: i32,
+        fallback: i32,
+        error: String,
+    ) -> Option<Self> {
+        let kind = match asset_type {
+            0 => AssetKind::Image,
+            1 => AssetKind::Font,
+            _ => return None,
+        };
+        let fallback = match fallback {
+            1 => AssetFallback::Placeholder,
+            2 => AssetFallback::FallbackFont,
+            3 => AssetFallback::BoundingBoxes,
+            _ => return None,
+        };
+
+        Some(Self {
+            id,
+            name,
+            kind,
+            fallback,
+            error,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RebindError {
+    Unavailable(SceneStatus),
+    /// No missing asset has this id, e.g. because it was already rebound.
+    NotMissing(u32),
+    /// The bytes aren't an image or font the runtime can decode. The asset keeps its fallback.
+    Undecodable(u32),
+}
+
+impl fmt::Display for RebindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(status) => write!(f, "scene is {status}"),
+            Self::NotMissing(id) => write!(f, "asset {id} isn't missing"),
+            Self::Undecodable(id) => write!(f, "bytes for asset {id} couldn't be decoded"),
+        }
+    }
+}
+
+impl std::error::Error for RebindError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_raw_values_are_skipped() {
+        let raw = |asset_type, fallback| {
+            MissingAsset::from_raw(7, "a.png".to_string(), asset_type, fallback, String::new())
+        };
+
+        assert_eq!(raw(0, 1).unwrap().kind, AssetKind::Image);
+        assert_eq!(raw(1, 3).unwrap().fallback, AssetFallback::BoundingBoxes);
+        assert!(raw(2, 1).is_none());
+        assert!(raw(0, 0).is_none());
+    }
+}
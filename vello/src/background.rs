@@ -0,0 +1,240 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+/// Longest step content is advanced by at once. Longer elapsed times are split so that state
+/// machines see every transition and physics-like animations don't jump.
+pub const MAX_STEP: f64 = 0.1;
+
+/// What content does while its window is minimized or fully occluded. Hidden windows never
+/// render either way.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BackgroundAdvance {
+    /// Stop advancing and carry on from the same pose when shown again, as if no time passed.
+    #[default]
+    Pause,
+    /// Keep advancing, this many times per second, so that content stays in step with the wall
+    /// clock, e.g. a state machine counting down.
+    Tick(f64),
+}
+
+impl FromStr for BackgroundAdvance {
+    type Err = String;
+
+    /// Parses `pause` or a rate such as `5hz`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        if s == "pause" {
+            return Ok(Self::Pause);
+        }
+
+        match s.strip_suffix("hz").map(|hz| hz.trim().parse::<f64>()) {
+            Some(Ok(hz)) if interval(hz).is_some() => Ok(Self::Tick(hz)),
+            _ => Err(format!("expected `pause` or a rate like `5hz`, got `{s}`")),
+        }
+    }
+}
+
+/// Time between two ticks at `hz`, or `None` for rates which don't tick, e.g. so slowly that
+/// the interval overflows a `Duration` or so fast that it rounds to zero.
+fn interval(hz: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(1.0 / hz)
+        .ok()
+        .filter(|interval| !interval.is_zero())
+}
+
+impl fmt::Display for BackgroundAdvance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pause => write!(f, "paused"),
+            Self::Tick(hz) => write!(f, "advancing at {hz} Hz"),
+        }
+    }
+}
+
+/// `elapsed` seconds split in equal steps of at most `MAX_STEP`.
+#[derive(Clone, Debug)]
+pub struct Substeps {
+    step: f64,
+    remaining: usize,
+}
+
+impl Substeps {
+    pub fn new(elapsed: f64) -> Self {
+        if !elapsed.is_finite() || elapsed <= 0.0 {
+            return Self::none();
+        }
+
+        let count = (elapsed / MAX_STEP).ceil().max(1.0);
+
+        Self {
+            step: elapsed / count,
+            remaining: count as usize,
+        }
+    }
+
+    fn none() -> Self {
+        Self {
+            step: 0.0,
+            remaining: 0,
+        }
+    }
+}
+
+impl Iterator for Substeps {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        Some(self.step)
+    }
+}
+
+/// Tracks whether the window is hidden and how much of the wall-clock time content should be
+/// advanced by meanwhile. Elapsed times are measured by the caller, since the last frame or
+/// tick, and applied in `Substeps`.
+#[derive(Debug, Default)]
+pub struct Throttle {
+    background: BackgroundAdvance,
+    hidden: bool,
+}
+
+impl Throttle {
+    pub fn new(background: BackgroundAdvance) -> Self {
+        Self {
+            background,
+            hidden: false,
+        }
+    }
+
+    pub fn background(&self) -> BackgroundAdvance {
+        self.background
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Stops rendering. Time since the last frame is left to the first tick, or dropped when
+    /// pausing.
+    pub fn hide(&mut self) {
+        self.hidden = true;
+    }
+
+    /// Time between two ticks while hidden, or `None` when there's nothing to do until shown.
+    /// Rates `BackgroundAdvance::from_str` rejects never tick, so their time is all caught up on
+    /// by `show`.
+    pub fn tick_interval(&self) -> Option<Duration> {
+        match self.background {
+            BackgroundAdvance::Tick(hz) if self.hidden => interval(hz),
+            _ => None,
+        }
+    }
+
+    /// What to advance by `elapsed` seconds after the last tick while hidden.
+    pub fn tick(&mut self, elapsed: f64) -> Substeps {
+        match self.background {
+            BackgroundAdvance::Tick(_) if self.hidden => Substeps::new(elapsed),
+            _ => Substeps::none(),
+        }
+    }
+
+    /// Resumes rendering `elapsed` seconds after the last frame or tick. Returns what to catch up
+    /// on before the next frame: the time since the last tick when ticking, nothing when paused.
+    pub fn show(&mut self, elapsed: f64) -> Substeps {
+        if !std::mem::take(&mut self.hidden) {
+            return Substeps::none();
+        }
+
+        match self.background {
+            BackgroundAdvance::Pause => Substeps::none(),
+            BackgroundAdvance::Tick(_) => Substeps::new(elapsed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hides `throttle` for `hidden` seconds, ticking as asked, and returns every step applied,
+    /// including the catch-up when shown.
+    fn steps_after(throttle: &mut Throttle, hidden: f64) -> Vec<f64> {
+        let mut steps = Vec::new();
+        let mut since_tick = 0.0;
+        let mut clock = 0.0;
+
+        throttle.hide();
+        if let Some(interval) = throttle.tick_interval() {
+            let interval = interval.as_secs_f64();
+            while clock + interval <= hidden + 1e-9 {
+                clock += interval;
+                steps.extend(throttle.tick(interval));
+            }
+            since_tick = hidden - clock;
+        }
+        steps.extend(throttle.show(since_tick.max(0.0)));
+
+        steps
+    }
+
+    #[test]
+    fn pausing_applies_nothing() {
+        let mut throttle = Throttle::default();
+
+        assert_eq!(throttle.tick_interval(), None);
+        assert!(steps_after(&mut throttle, 10.0).is_empty());
+        assert!(!throttle.is_hidden());
+    }
+
+    #[test]
+    fn ticking_keeps_wall_clock_time() {
+        for (hidden, hz) in [(10.0, 5.0), (10.1, 5.0), (3.0, 0.5), (0.05, 5.0)] {
+            let mut throttle = Throttle::new(BackgroundAdvance::Tick(hz));
+            let steps = steps_after(&mut throttle, hidden);
+
+            let applied: f64 = steps.iter().sum();
+            assert!((applied - hidden).abs() < 1e-6, "{applied} after {hidden}s");
+            assert!(steps.iter().all(|&step| step <= MAX_STEP + 1e-9));
+        }
+    }
+
+    #[test]
+    fn splits_long_steps() {
+        assert_eq!(Substeps::new(0.25).count(), 3);
+        assert_eq!(Substeps::new(0.1).count(), 1);
+        assert_eq!(Substeps::new(0.0).count(), 0);
+        assert_eq!(Substeps::new(f64::NAN).count(), 0);
+    }
+
+    #[test]
+    fn parses_rates() {
+        assert_eq!("pause".parse(), Ok(BackgroundAdvance::Pause));
+        assert_eq!("5hz".parse(), Ok(BackgroundAdvance::Tick(5.0)));
+        assert_eq!("0.5 Hz".parse(), Ok(BackgroundAdvance::Tick(0.5)));
+        assert!("0hz".parse::<BackgroundAdvance>().is_err());
+        assert!("fast".parse::<BackgroundAdvance>().is_err());
+
+        // Rates whose interval a `Duration` can't hold, or holds as zero.
+        assert!("1e-300hz".parse::<BackgroundAdvance>().is_err());
+        assert!("1e300hz".parse::<BackgroundAdvance>().is_err());
+        assert!("infhz".parse::<BackgroundAdvance>().is_err());
+    }
+
+    #[test]
+    fn unrepresentable_rates_catch_up_when_shown() {
+        for hz in [
+            1e-300,
+            f64::MIN_POSITIVE,
+            1e300,
+            f64::INFINITY,
+            f64::NAN,
+            -5.0,
+        ] {
+            let mut throttle = Throttle::new(BackgroundAdvance::Tick(hz));
+            throttle.hide();
+
+            assert_eq!(throttle.tick_interval(), None, "{hz}");
+            let applied: f64 = throttle.show(0.25).sum();
+            assert!((applied - 0.25).abs() < 1e-9, "{hz}");
+        }
+    }
+}
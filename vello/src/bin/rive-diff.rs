@@ -0,0 +1,187 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use rive_vello::{
+    match_files, request_device, review_file, FileReview, Rasterizer, ReviewOutcome, ReviewReport,
+};
+
+/// How often a worker is checked on.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Parser)]
+#[command(about = "Reports what changed visually between two directories of .riv files")]
+struct Args {
+    old_dir: PathBuf,
+    new_dir: PathBuf,
+    /// Directory the HTML report and its images are written to.
+    #[arg(long, default_value = "report")]
+    out: PathBuf,
+    /// Seconds a file may take to review before it's reported as hanging.
+    #[arg(long, default_value_t = 60.0)]
+    timeout: f64,
+    /// Review this file in-process and write its result for the parent to collect.
+    #[arg(long, hide = true)]
+    worker: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    if let Some(file) = &args.worker {
+        return match run_worker(&args, file) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{error}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    if !args.timeout.is_finite() || args.timeout <= 0.0 {
+        eprintln!("--timeout must be a positive number of seconds");
+        return ExitCode::FAILURE;
+    }
+
+    let files = match match_files(&args.old_dir, &args.new_dir) {
+        Ok(files) => files,
+        Err(error) => {
+            eprintln!("Failed to list .riv files: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for dir in ["results", "logs"] {
+        if let Err(error) = fs::create_dir_all(args.out.join(dir)) {
+            eprintln!("Failed to create {}: {error}", args.out.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let mut report = ReviewReport {
+        reviews: Vec::new(),
+        only_old: files.removed,
+        only_new: files.added,
+    };
+    for (i, file) in files.both.iter().enumerate() {
+        println!("[{}/{}] {file}", i + 1, files.both.len());
+        report.reviews.push(supervise(&args, file));
+    }
+    report.sort();
+
+    match report.write_html(&args.out) {
+        Ok(path) => {
+            let flagged = report.reviews.iter().filter(|r| r.is_flagged()).count();
+            println!("{flagged} flagged, report written to {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("Failed to write report: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_worker(args: &Args, file: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let gpu = pollster::block_on(request_device(
+        &wgpu::Instance::new(wgpu::InstanceDescriptor::default()),
+        None,
+    ))?;
+    let mut rasterizer = Rasterizer::new(gpu.device, gpu.queue);
+
+    let outcome = review_file(
+        &mut rasterizer,
+        &args.old_dir,
+        &args.new_dir,
+        file,
+        &args.out,
+    )?;
+    let review = FileReview {
+        file: file.to_string(),
+        outcome,
+    };
+    fs::write(result_path(&args.out, file), toml::to_string(&review)?)?;
+
+    Ok(())
+}
+
+/// Reviews `file` in a child process, so that a file which hangs or crashes the runtime only
+/// costs its own entry in the report. Panics can't be caught across the runtime's native code,
+/// and a hang never returns to catch anything.
+fn supervise(args: &Args, file: &str) -> FileReview {
+    let crashed = |reason: String| FileReview {
+        file: file.to_string(),
+        outcome: ReviewOutcome::Crashed { reason },
+    };
+
+    let log_path = args.out.join("logs").join(format!("{file}.log"));
+    let result_path = result_path(&args.out, file);
+    let _ = fs::remove_file(&result_path);
+
+    let child = File::create(&log_path).and_then(|log| {
+        Command::new(std::env::current_exe()?)
+            .arg(&args.old_dir)
+            .arg(&args.new_dir)
+            .arg("--out")
+            .arg(&args.out)
+            .arg("--worker")
+            .arg(file)
+            .stdout(Stdio::null())
+            .stderr(log)
+            .spawn()
+    });
+    let mut child = match child {
+        Ok(child) => child,
+        Err(error) => return crashed(format!("failed to start worker: {error}")),
+    };
+
+    let deadline = Instant::now() + Duration::from_secs_f64(args.timeout);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return FileReview {
+                    file: file.to_string(),
+                    outcome: ReviewOutcome::TimedOut {
+                        seconds: args.timeout,
+                    },
+                };
+            }
+            Err(error) => return crashed(format!("lost track of worker: {error}")),
+        }
+    };
+
+    if !status.success() {
+        return crashed(failure_reason(&log_path, status));
+    }
+
+    fs::read_to_string(&result_path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| toml::from_str(&text).map_err(|error| error.to_string()))
+        .unwrap_or_else(|error| crashed(format!("unreadable result: {error}")))
+}
+
+fn result_path(out: &Path, file: &str) -> PathBuf {
+    out.join("results").join(format!("{file}.toml"))
+}
+
+/// The last line the worker logged, e.g. its panic message, or else how it exited.
+fn failure_reason(log_path: &Path, status: ExitStatus) -> String {
+    let last_line = File::open(log_path).ok().and_then(|log| {
+        BufReader::new(log)
+            .lines()
+            .map_while(io::Result::ok)
+            .filter(|line| !line.trim().is_empty() && !line.starts_with("note: "))
+            .last()
+    });
+
+    last_line.unwrap_or_else(|| status.to_string())
+}
@@ -0,0 +1,367 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use clap::Parser;
+use rive_vello::{parse_color, Fit, HeadlessRenderer, SelectError, ViewerContent};
+use vello::peniko::Color;
+
+#[derive(Debug, Parser)]
+#[command(about = "Renders a .riv file to PNG frames, without a window")]
+struct Args {
+    file: PathBuf,
+    /// Artboard to render. Defaults to the file's first one.
+    #[arg(long)]
+    artboard: Option<String>,
+    /// State machine or animation to play. Defaults to the artboard's default one.
+    #[arg(long)]
+    scene: Option<String>,
+    #[arg(long, default_value_t = 30.0)]
+    fps: f64,
+    /// How long to render for, e.g. `2s` or `500ms`.
+    #[arg(long, default_value = "1s", value_parser = parse_duration)]
+    duration: f64,
+    /// Render a single frame this many seconds in. `--out` is then used as is.
+    #[arg(long)]
+    time: Option<f64>,
+    /// Output size in pixels, e.g. `800x600`.
+    #[arg(long, default_value = "512x512", value_parser = parse_size)]
+    size: (u32, u32),
+    /// One of fill, contain, cover, fit-width, fit-height, none or scale-down.
+    #[arg(long, default_value = "contain")]
+    fit: Fit,
+    /// Where frames are written. A printf-style `%d` or `%04d` is replaced by the frame number,
+    /// counted from 0.
+    #[arg(long, default_value = "frames/%04d.png")]
+    out: String,
+    /// Color for paints named `currentColor`, e.g. `#ffffff` to preview an icon on a dark
+    /// background.
+    #[arg(long, value_parser = parse_color)]
+    primary_color: Option<Color>,
+    /// Color behind the content, e.g. `#000000`. Transparent by default.
+    #[arg(long, default_value = "#00000000", value_parser = parse_color)]
+    background: Color,
+    /// Renders only these nodes or shapes, e.g. `head,torso`, each alone on a transparent
+    /// background and fitted to `--size`. Each frame is then saved once per component, with
+    /// the component's name after the frame's, as in `frames/0000-head.png`.
+    #[arg(long, value_delimiter = ',')]
+    extract: Vec<String>,
+    /// Pixels left free around extracted components.
+    #[arg(long, default_value_t = 0.0)]
+    padding: f32,
+    /// Applies the opacity and clips of extracted components' ancestors. Their transforms never
+    /// apply.
+    #[arg(long)]
+    ancestor_effects: bool,
+}
+
+fn main() -> ExitCode {
+    match run(&Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("rive-render: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    if !args.fps.is_finite() || args.fps <= 0.0 {
+        return Err("--fps must be a positive number".to_string());
+    }
+    if let Some(time) = args.time.filter(|time| !time.is_finite() || *time < 0.0) {
+        return Err(format!("--time must be at least 0, got {time}"));
+    }
+    if !args.padding.is_finite() || args.padding < 0.0 {
+        return Err(format!(
+            "--padding must be at least 0, got {}",
+            args.padding
+        ));
+    }
+
+    let content = load(args)?;
+
+    let mut headless = HeadlessRenderer::new().map_err(|error| error.to_string())?;
+    headless.set_background(args.background);
+    let mut renderer = Renderer {
+        content,
+        headless,
+        size: args.size,
+        extract: &args.extract,
+        padding: args.padding,
+        ancestor_effects: args.ancestor_effects,
+    };
+
+    if let Some(time) = args.time {
+        return renderer.render(0, time, Path::new(&args.out));
+    }
+
+    let count = (args.duration * args.fps).ceil() as u32;
+    for frame in 0..count {
+        let path = frame_path(&args.out, frame);
+        renderer.render(frame, frame as f64 / args.fps, Path::new(&path))?;
+    }
+
+    Ok(())
+}
+
+/// Opens the file with the requested artboard and scene, failing with the names to pick from.
+fn load(args: &Args) -> Result<ViewerContent, String> {
+    let bytes = fs::read(&args.file)
+        .map_err(|error| format!("failed to read {}: {error}", args.file.display()))?;
+    let mut content = ViewerContent::from_bytes(&bytes).ok_or_else(|| {
+        format!(
+            "{} isn't a .riv file the runtime can load",
+            args.file.display()
+        )
+    })?;
+
+    if args.artboard.is_some() || args.scene.is_some() {
+        content
+            .select(args.artboard.as_deref(), args.scene.as_deref())
+            .map_err(|error| {
+                let names = match &error {
+                    SelectError::Unavailable(_) => Vec::new(),
+                    SelectError::UnknownArtboard(_) => content.artboard_names(),
+                    SelectError::UnknownScene { artboard, .. } => content.scene_names(artboard),
+                };
+                with_choices(&error, &names)
+            })?;
+    }
+
+    if let Some(color) = args.primary_color {
+        content
+            .set_primary_color(color)
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(name) = args
+        .extract
+        .iter()
+        .find(|name| content.component_bounds(name).is_none())
+    {
+        return Err(format!("no node or shape called \"{name}\""));
+    }
+
+    content.set_fit(args.fit).map_err(|e| e.to_string())?;
+
+    Ok(content)
+}
+
+/// Describes `error` along with the names which could have been picked instead.
+fn with_choices(error: &SelectError, names: &[String]) -> String {
+    if names.is_empty() {
+        return error.to_string();
+    }
+    format!("{error}, pick one of: {}", names.join(", "))
+}
+
+struct Renderer<'r> {
+    content: ViewerContent,
+    headless: HeadlessRenderer,
+    size: (u32, u32),
+    /// Components rendered alone instead of the whole artboard, if any.
+    extract: &'r [String],
+    padding: f32,
+    ancestor_effects: bool,
+}
+
+impl Renderer<'_> {
+    /// Plays the content up to `time`, saves it to `path` and prints a JSON line describing
+    /// the frame.
+    fn render(&mut self, frame: u32, time: f64, path: &Path) -> Result<(), String> {
+        let start = Instant::now();
+        if !self.extract.is_empty() {
+            return self.render_components(frame, time, path, start);
+        }
+
+        let (width, height) = self.size;
+        let image = self
+            .headless
+            .render(&mut self.content, width, height, time)
+            .map_err(|error| error.to_string())?;
+
+        create_parent(path)?;
+        image
+            .save(path)
+            .map_err(|error| format!("failed to save {}: {error}", path.display()))?;
+
+        println!(
+            "{{\"frame\":{frame},\"time\":{time},\"file\":{},\"render_ms\":{:.3}}}",
+            json_string(&path.to_string_lossy()),
+            start.elapsed().as_secs_f64() * 1000.0
+        );
+
+        Ok(())
+    }
+
+    /// Plays the content up to `time` and saves each extracted component next to `path`,
+    /// printing a JSON line per component.
+    fn render_components(
+        &mut self,
+        frame: u32,
+        time: f64,
+        path: &Path,
+        start: Instant,
+    ) -> Result<(), String> {
+        let images = self
+            .headless
+            .render_components(
+                &mut self.content,
+                self.extract,
+                self.size,
+                time,
+                self.padding,
+                self.ancestor_effects,
+            )
+            .map_err(|error| error.to_string())?;
+
+        create_parent(path)?;
+        for (name, image) in self.extract.iter().zip(images) {
+            let path = component_path(path, name);
+            image
+                .save(&path)
+                .map_err(|error| format!("failed to save {}: {error}", path.display()))?;
+
+            println!(
+                "{{\"frame\":{frame},\"time\":{time},\"component\":{},\"file\":{},\
+                 \"render_ms\":{:.3}}}",
+                json_string(name),
+                json_string(&path.to_string_lossy()),
+                start.elapsed().as_secs_f64() * 1000.0
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn create_parent(path: &Path) -> Result<(), String> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)
+            .map_err(|error| format!("failed to create {}: {error}", dir.display()))?;
+    }
+    Ok(())
+}
+
+/// `path` with `-name` appended to its stem, e.g. `frames/0000-head.png`.
+fn component_path(path: &Path, name: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{stem}-{name}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{name}"),
+    };
+    path.with_file_name(file_name)
+}
+
+/// `pattern` with its first printf-style `%d`, optionally zero-padded as in `%04d`, replaced by
+/// `frame`. Patterns without one get the frame number before their extension.
+fn frame_path(pattern: &str, frame: u32) -> String {
+    if let Some(start) = pattern.find('%') {
+        let rest = &pattern[start + 1..];
+        if let Some(end) = rest.find('d') {
+            let digits = &rest[..end];
+            let width = match digits {
+                "" => Some(0),
+                digits => digits.parse::<usize>().ok(),
+            };
+            if let Some(width) = width {
+                return format!("{}{frame:0width$}{}", &pattern[..start], &rest[end + 1..]);
+            }
+        }
+    }
+
+    let path = Path::new(pattern);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}{frame:04}.{}", extension.to_string_lossy()),
+        None => format!("{stem}{frame:04}"),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+/// Parses `2s`, `500ms` or a bare number of seconds.
+fn parse_duration(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    let (value, scale) = match s.strip_suffix("ms") {
+        Some(ms) => (ms, 0.001),
+        None => (s.strip_suffix('s').unwrap_or(s), 1.0),
+    };
+
+    match value.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value * scale),
+        _ => Err(format!(
+            "expected a duration like `2s` or `500ms`, got `{s}`"
+        )),
+    }
+}
+
+/// Parses `WIDTHxHEIGHT`, e.g. `800x600`.
+fn parse_size(s: &str) -> Result<(u32, u32), String> {
+    let size = s
+        .split_once('x')
+        .and_then(|(width, height)| Some((width.trim().parse().ok()?, height.trim().parse().ok()?)))
+        .filter(|&(width, height)| width > 0 && height > 0);
+
+    size.ok_or_else(|| format!("expected a size like `800x600`, got `{s}`"))
+}
+
+/// `s` as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_paths_follow_printf_patterns() {
+        assert_eq!(frame_path("frames/%04d.png", 7), "frames/0007.png");
+        assert_eq!(frame_path("frame-%d.png", 12), "frame-12.png");
+        assert_eq!(frame_path("out/frame.png", 3), "out/frame0003.png");
+    }
+
+    #[test]
+    fn component_paths_follow_frame_paths() {
+        assert_eq!(
+            component_path(Path::new("frames/0007.png"), "head"),
+            Path::new("frames/0007-head.png")
+        );
+        assert_eq!(
+            component_path(Path::new("frame"), "torso"),
+            Path::new("frame-torso")
+        );
+    }
+
+    #[test]
+    fn durations_and_sizes_parse() {
+        assert_eq!(parse_duration("2s"), Ok(2.0));
+        assert_eq!(parse_duration("500ms"), Ok(0.5));
+        assert_eq!(parse_duration("1.5"), Ok(1.5));
+        assert!(parse_duration("-1s").is_err());
+
+        assert_eq!(parse_size("800x600"), Ok((800, 600)));
+        assert!(parse_size("800").is_err());
+        assert!(parse_size("0x600").is_err());
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("a\"b\\c"), r#""a\"b\\c""#);
+    }
+}
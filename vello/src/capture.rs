@@ -0,0 +1,412 @@
+use std::{fmt, path::Path, sync::Arc};
+
+use vello::{
+    kurbo::{Affine, Rect},
+    peniko::{Color, Fill},
+    Scene, SceneBuilder, SceneFragment,
+};
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Device, Extent3d,
+    ImageCopyBuffer, ImageDataLayout, Maintain, MapMode, Queue, COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+use crate::{
+    device::DeviceError,
+    gpu_memory::{GpuAllocation, GpuCategory},
+    TextureTarget,
+};
+
+/// What `Rasterizer::render` returns for scales above 1.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ScaledOutput {
+    /// Keeps every rendered pixel, i.e. an image `scale` times the asked size.
+    FullResolution,
+    /// Box-filters the pixels back down to the asked size, which smooths edges beyond what
+    /// rendering at that size does.
+    #[default]
+    Downsampled,
+}
+
+#[derive(Debug)]
+pub enum CaptureError {
+    ZeroScale,
+    /// The scaled size doesn't fit in a texture of the device.
+    ScaleTooLarge {
+        scale: u32,
+        width: u32,
+        height: u32,
+        max: u32,
+    },
+    Device(DeviceError),
+    Render(vello::Error),
+    Readback(BufferAsyncError),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroScale => write!(f, "capture scale must be at least 1"),
+            Self::ScaleTooLarge {
+                scale,
+                width,
+                height,
+                max,
+            } => write!(
+                f,
+                "a {scale}x capture of {width}x{height} needs a {}x{} texture, but the device \
+                 supports at most {max}x{max}",
+                *width as u64 * *scale as u64,
+                *height as u64 * *scale as u64,
+            ),
+            Self::Device(error) => write!(f, "{error}"),
+            Self::Render(error) => write!(f, "failed to render capture: {error}"),
+            Self::Readback(error) => write!(f, "failed to read capture back: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+/// RGBA8 pixels read back from the GPU, row by row without padding.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capture {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Capture {
+    /// Averages every `factor` by `factor` block into one pixel, weighting colors by their
+    /// alpha so that transparent pixels don't darken edges. Partial blocks at the right and
+    /// bottom are dropped.
+    pub fn downsample(&self, factor: u32) -> Capture {
+        let factor = factor.max(1);
+        let width = (self.width / factor).max(1);
+        let height = (self.height / factor).max(1);
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0u64; 4];
+                let mut count = 0;
+
+                for sy in y * factor..((y + 1) * factor).min(self.height) {
+                    for sx in x * factor..((x + 1) * factor).min(self.width) {
+                        let i = (sy * self.width + sx) as usize * 4;
+                        let pixel = &self.pixels[i..i + 4];
+                        let alpha = pixel[3] as u64;
+
+                        for (sum, &channel) in sum.iter_mut().zip(&pixel[..3]) {
+                            *sum += channel as u64 * alpha;
+                        }
+                        sum[3] += alpha;
+                        count += 1;
+                    }
+                }
+
+                for &channel in &sum[..3] {
+                    pixels.push(match sum[3] {
+                        0 => 0,
+                        alpha => ((channel + alpha / 2) / alpha) as u8,
+                    });
+                }
+                pixels.push(((sum[3] + count / 2) / count.max(1)) as u8);
+            }
+        }
+
+        Capture {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn into_image(self) -> image::RgbaImage {
+        image::RgbaImage::from_raw(self.width, self.height, self.pixels)
+            .expect("captures hold width * height pixels")
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        image::save_buffer(
+            path,
+            &self.pixels,
+            self.width,
+            self.height,
+            image::ColorType::Rgba8,
+        )
+    }
+}
+
+/// Renders scene fragments offscreen and reads them back, optionally supersampled, e.g. for
+/// screenshots. Independent of `ViewerContent`: anything drawn into a `VelloRenderer` or built
+/// by the host can be rasterized.
+pub struct Rasterizer {
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    /// Created on the first render and resized for the following ones.
+    target: Option<TextureTarget>,
+}
+
+impl Rasterizer {
+    pub fn new(device: Arc<Device>, queue: Arc<Queue>) -> Self {
+        Self {
+            device,
+            queue,
+            target: None,
+        }
+    }
+
+    /// Largest width or height a render can have once scaled.
+    pub fn max_dimension(&self) -> u32 {
+        self.device.limits().max_texture_dimension_2d
+    }
+
+    /// Renders `fragment`, laid out for a `width` by `height` target, at `scale` times that
+    /// size. Fragments are vector, so paths, strokes and gradients are scaled rather than
+    /// stretched. Fails instead of rendering smaller if the scaled size doesn't fit the device.
+    pub fn render(
+        &mut self,
+        fragment: &SceneFragment,
+        base_color: Color,
+        width: u32,
+        height: u32,
+        scale: u32,
+        output: ScaledOutput,
+    ) -> Result<Capture, CaptureError> {
+        let (scaled_width, scaled_height) =
+            scaled_size(width, height, scale, self.max_dimension())?;
+
+        if self.target.is_none() {
+            self.target = Some(
+                TextureTarget::new(
+                    self.device.clone(),
+                    self.queue.clone(),
+                    scaled_width,
+                    scaled_height,
+                )
+                .map_err(CaptureError::Device)?,
+            );
+        }
+        let target = self.target.as_mut().unwrap();
+        target.resize(scaled_width, scaled_height);
+
+        let mut scene = Scene::default();
+        let mut builder = SceneBuilder::for_scene(&mut scene);
+        builder.append(fragment, Some(Affine::scale(scale as f64)));
+        // Vello currently crashes when rendering an empty scene.
+        builder.fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            Color::TRANSPARENT,
+            None,
+            &Rect::new(0.0, 0.0, 0.0, 0.0),
+        );
+
+        target
+            .render(&scene, base_color)
+            .map_err(CaptureError::Render)?;
+        let capture = read_back(target)?;
+
+        Ok(match output {
+            ScaledOutput::Downsampled if scale > 1 => capture.downsample(scale),
+            _ => capture,
+        })
+    }
+}
+
+fn scaled_size(width: u32, height: u32, scale: u32, max: u32) -> Result<(u32, u32), CaptureError> {
+    if scale == 0 {
+        return Err(CaptureError::ZeroScale);
+    }
+
+    let too_large = CaptureError::ScaleTooLarge {
+        scale,
+        width,
+        height,
+        max,
+    };
+    let scaled_width = width.max(1).checked_mul(scale).filter(|&w| w <= max);
+    let scaled_height = height.max(1).checked_mul(scale).filter(|&h| h <= max);
+
+    scaled_width.zip(scaled_height).ok_or(too_large)
+}
+
+pub(crate) fn read_back(target: &TextureTarget) -> Result<Capture, CaptureError> {
+    let device = target.device();
+    let (width, height) = (target.width(), target.height());
+    let row_bytes = width * 4;
+    let padded_row_bytes =
+        row_bytes.div_ceil(COPY_BYTES_PER_ROW_ALIGNMENT) * COPY_BYTES_PER_ROW_ALIGNMENT;
+
+    let size = padded_row_bytes as u64 * height as u64;
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("rive capture"),
+        size,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let _memory = GpuAllocation::new(GpuCategory::Intermediates, size);
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("rive capture"),
+    });
+    encoder.copy_texture_to_buffer(
+        target.texture().as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    target.queue().submit(Some(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    slice.map_async(MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(Maintain::Wait);
+    receiver
+        .recv()
+        .expect("map callback dropped")
+        .map_err(CaptureError::Readback)?;
+
+    let mut pixels = Vec::with_capacity(row_bytes as usize * height as usize);
+    for row in slice.get_mapped_range().chunks(padded_row_bytes as usize) {
+        pixels.extend_from_slice(&row[..row_bytes as usize]);
+    }
+    buffer.unmap();
+
+    Ok(Capture {
+        width,
+        height,
+        pixels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use vello::{kurbo::Line, peniko::Stroke};
+
+    use super::*;
+
+    fn solid(width: u32, height: u32, pixel: impl Fn(u32, u32) -> [u8; 4]) -> Capture {
+        Capture {
+            width,
+            height,
+            pixels: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .flat_map(|(x, y)| pixel(x, y))
+                .collect(),
+        }
+    }
+
+    /// Mean squared difference of horizontally adjacent pixels' green channel.
+    fn edge_contrast(capture: &Capture) -> f64 {
+        let mut sum = 0.0;
+        let mut count = 0;
+
+        for row in capture.pixels.chunks(capture.width as usize * 4) {
+            for pair in row.chunks(4).collect::<Vec<_>>().windows(2) {
+                let delta = pair[0][1] as f64 - pair[1][1] as f64;
+                sum += delta * delta;
+                count += 1;
+            }
+        }
+
+        sum / count as f64
+    }
+
+    #[test]
+    fn downsampling_averages_blocks() {
+        let stripes = solid(4, 2, |x, _| match x % 2 {
+            0 => [255, 255, 255, 255],
+            _ => [0, 0, 0, 255],
+        });
+        let downsampled = stripes.downsample(2);
+
+        assert_eq!((downsampled.width, downsampled.height), (2, 1));
+        assert_eq!(downsampled.pixels, [128, 128, 128, 255, 128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn downsampling_ignores_transparent_colors() {
+        let edge = solid(2, 1, |x, _| match x {
+            0 => [255, 0, 0, 255],
+            _ => [0, 0, 0, 0],
+        });
+
+        assert_eq!(edge.downsample(2).pixels, [255, 0, 0, 128]);
+    }
+
+    #[test]
+    fn oversized_scales_list_the_maximum() {
+        assert_eq!(scaled_size(700, 500, 4, 8192).unwrap(), (2800, 2000));
+        assert!(matches!(scaled_size(700, 500, 1, 8192), Ok((700, 500))));
+        assert!(matches!(
+            scaled_size(700, 500, 0, 8192),
+            Err(CaptureError::ZeroScale)
+        ));
+
+        let error = scaled_size(3000, 500, 4, 8192).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "a 4x capture of 3000x500 needs a 12000x2000 texture, but the device supports at \
+             most 8192x8192"
+        );
+        assert!(scaled_size(u32::MAX, 1, 2, u32::MAX).is_err());
+    }
+
+    #[test]
+    fn supersampling_smooths_edges() {
+        let gpu = crate::device::test_device();
+
+        // A fan of hairlines converging in the middle, where they're closer than a pixel.
+        let mut fragment = SceneFragment::default();
+        {
+            let mut builder = SceneBuilder::for_fragment(&mut fragment);
+            for i in 0..64 {
+                let (sin, cos) = (i as f64 / 64.0 * std::f64::consts::PI).sin_cos();
+                let line = Line::new(
+                    (32.0 - cos * 30.0, 32.0 - sin * 30.0),
+                    (32.0 + cos * 30.0, 32.0 + sin * 30.0),
+                );
+                builder.stroke(
+                    &Stroke::new(0.5),
+                    Affine::IDENTITY,
+                    Color::BLACK,
+                    None,
+                    &line,
+                );
+            }
+        }
+
+        let mut rasterizer = Rasterizer::new(gpu.device, gpu.queue);
+        let mut render = |scale| {
+            rasterizer
+                .render(
+                    &fragment,
+                    Color::WHITE,
+                    64,
+                    64,
+                    scale,
+                    ScaledOutput::Downsampled,
+                )
+                .unwrap()
+        };
+        let single = render(1);
+        let supersampled = render(2);
+
+        assert_eq!((supersampled.width, supersampled.height), (64, 64));
+        assert_ne!(single, supersampled);
+        assert!(edge_contrast(&supersampled) < edge_contrast(&single));
+    }
+}
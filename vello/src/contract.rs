@@ -0,0 +1,26 @@
+use std::fmt;
+
+use rive_core::ContractReport;
+
+use crate::status::SceneStatus;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContractError {
+    Unavailable(SceneStatus),
+    /// The bytes aren't a `.riv` file the runtime can load.
+    Unloadable,
+    /// Only returned by strict loads, with what the file is missing.
+    Broken(ContractReport),
+}
+
+impl fmt::Display for ContractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(status) => write!(f, "scene is {status}"),
+            Self::Unloadable => write!(f, "not a .riv file the runtime can load"),
+            Self::Broken(report) => write!(f, "file breaks its contract:\n{report}"),
+        }
+    }
+}
+
+impl std::error::Error for ContractError {}
@@ -0,0 +1,600 @@
+use std::f64::consts::PI;
+
+use rive_core::writer::{RivWriter, Value};
+use vello::kurbo::{Affine, Arc, BezPath, Circle, PathEl, Point, Shape, Vec2};
+
+pub const HINT: &str = "drop a .riv file here";
+
+const SIZE: f32 = 500.0;
+/// Where the mark rests and its shadow lies.
+const FLOOR: f32 = 300.0;
+const RADIUS: f32 = 50.0;
+const BOUNCE_HEIGHT: f32 = 100.0;
+/// Frames of a rise, which the fall plays backwards.
+const RISE_FRAMES: u64 = 36;
+const FPS: u64 = 60;
+const HOVER_GROWTH: f32 = 0.15;
+/// Milliseconds the mark takes to change once the pointer enters or leaves it.
+const HOVER_MIX: u64 = 200;
+/// Size of the hint's font, in artboard units.
+const EM: f64 = 25.0;
+
+const IDLE_COLOR: u32 = 0xff8c9eff;
+const HOVER_COLOR: u32 = 0xffff9c5b;
+const HINT_COLOR: u32 = 0xb0ffffff;
+const SHADOW_COLOR: u32 = 0x40000000;
+
+// Values of the runtime's enums.
+const LOOP_PING_PONG: u64 = 2;
+const FILL_RULE_EVEN_ODD: u64 = 1;
+const STROKE_ROUND: u64 = 1;
+const INTERPOLATION_CUBIC: u64 = 2;
+const CONDITION_NOT_EQUAL: u64 = 1;
+const LISTENER_ENTER: u64 = 0;
+const LISTENER_EXIT: u64 = 1;
+
+/// Writes an artboard's components, counting them to know the ids other objects refer to them
+/// by. The artboard itself is 0.
+struct Components {
+    file: RivWriter,
+    count: u64,
+}
+
+impl Components {
+    fn new(artboard: &[(u64, Value<'_>)]) -> Self {
+        let mut file = RivWriter::new();
+        file.push(RivWriter::ARTBOARD, artboard);
+
+        Self { file, count: 1 }
+    }
+
+    /// Appends a component, returning its id.
+    fn add(&mut self, type_key: u64, properties: &[(u64, Value<'_>)]) -> u64 {
+        self.file.push(type_key, properties);
+        self.count += 1;
+        self.count - 1
+    }
+}
+
+/// Builds the file shown while no other one is loaded: a mark bouncing on a shadow, which grows
+/// and warms up under the pointer, above a hint to drop a file. Its state machine bounces the
+/// mark on one layer, and on another mixes to the hovered look while `hovered` is set by the
+/// enter and exit listeners of the mark.
+pub fn riv() -> Vec<u8> {
+    let mut components = Components::new(&[
+        (RivWriter::COMPONENT_NAME, Value::String("Demo")),
+        (RivWriter::ARTBOARD_WIDTH, Value::Double(SIZE)),
+        (RivWriter::ARTBOARD_HEIGHT, Value::Double(SIZE)),
+        (RivWriter::ARTBOARD_DEFAULT_STATE_MACHINE_ID, Value::Uint(0)),
+    ]);
+    let parent = |id: u64| (RivWriter::COMPONENT_PARENT_ID, Value::Uint(id));
+
+    // Fast off the floor and slow at the top, like a thrown ball. Played backwards, the fall
+    // speeds up again.
+    let ease = components.add(
+        RivWriter::CUBIC_EASE_INTERPOLATOR,
+        &[
+            (RivWriter::CUBIC_X1, Value::Double(0.33)),
+            (RivWriter::CUBIC_Y1, Value::Double(0.67)),
+            (RivWriter::CUBIC_X2, Value::Double(0.67)),
+            (RivWriter::CUBIC_Y2, Value::Double(1.0)),
+        ],
+    );
+
+    // Drawables are drawn over the ones after them. The mark is a disc with a diamond cut out
+    // of it.
+    let mark = components.add(
+        RivWriter::SHAPE,
+        &[
+            (RivWriter::COMPONENT_NAME, Value::String("mark")),
+            parent(0),
+            (RivWriter::NODE_X, Value::Double(SIZE / 2.0)),
+            (RivWriter::NODE_Y, Value::Double(FLOOR - RADIUS)),
+        ],
+    );
+    components.add(
+        RivWriter::ELLIPSE,
+        &[
+            parent(mark),
+            (
+                RivWriter::PARAMETRIC_PATH_WIDTH,
+                Value::Double(2.0 * RADIUS),
+            ),
+            (
+                RivWriter::PARAMETRIC_PATH_HEIGHT,
+                Value::Double(2.0 * RADIUS),
+            ),
+        ],
+    );
+    let hole = RADIUS * 0.45;
+    add_points_path(
+        &mut components,
+        mark,
+        &[(0.0, -hole), (hole, 0.0), (0.0, hole), (-hole, 0.0)],
+        true,
+    );
+    let fill = components.add(
+        RivWriter::FILL,
+        &[
+            parent(mark),
+            (RivWriter::FILL_RULE, Value::Uint(FILL_RULE_EVEN_ODD)),
+        ],
+    );
+    let mark_color = components.add(
+        RivWriter::SOLID_COLOR,
+        &[
+            parent(fill),
+            (RivWriter::SOLID_COLOR_VALUE, Value::Color(IDLE_COLOR)),
+        ],
+    );
+
+    let shadow = components.add(
+        RivWriter::SHAPE,
+        &[
+            (RivWriter::COMPONENT_NAME, Value::String("shadow")),
+            parent(0),
+            (RivWriter::NODE_X, Value::Double(SIZE / 2.0)),
+            (RivWriter::NODE_Y, Value::Double(FLOOR + 5.0)),
+        ],
+    );
+    let shadow_ellipse = components.add(
+        RivWriter::ELLIPSE,
+        &[
+            parent(shadow),
+            (
+                RivWriter::PARAMETRIC_PATH_WIDTH,
+                Value::Double(1.8 * RADIUS),
+            ),
+            (
+                RivWriter::PARAMETRIC_PATH_HEIGHT,
+                Value::Double(0.3 * RADIUS),
+            ),
+        ],
+    );
+    let fill = components.add(RivWriter::FILL, &[parent(shadow)]);
+    components.add(
+        RivWriter::SOLID_COLOR,
+        &[
+            parent(fill),
+            (RivWriter::SOLID_COLOR_VALUE, Value::Color(SHADOW_COLOR)),
+        ],
+    );
+
+    let (text, text_width) = text_path(HINT);
+    let hint = components.add(
+        RivWriter::SHAPE,
+        &[
+            (RivWriter::COMPONENT_NAME, Value::String("hint")),
+            parent(0),
+            (
+                RivWriter::NODE_X,
+                Value::Double((SIZE - (text_width * EM) as f32) / 2.0),
+            ),
+            (RivWriter::NODE_Y, Value::Double(FLOOR + 1.5 * RADIUS)),
+        ],
+    );
+    for (points, closed) in polylines(&text) {
+        let points: Vec<_> = points
+            .iter()
+            .map(|point| ((point.x * EM) as f32, (point.y * EM) as f32))
+            .collect();
+        add_points_path(&mut components, hint, &points, closed);
+    }
+    let stroke = components.add(
+        RivWriter::STROKE,
+        &[
+            parent(hint),
+            (
+                RivWriter::STROKE_THICKNESS,
+                Value::Double((0.08 * EM) as f32),
+            ),
+            (RivWriter::STROKE_CAP, Value::Uint(STROKE_ROUND)),
+            (RivWriter::STROKE_JOIN, Value::Uint(STROKE_ROUND)),
+        ],
+    );
+    components.add(
+        RivWriter::SOLID_COLOR,
+        &[
+            parent(stroke),
+            (RivWriter::SOLID_COLOR_VALUE, Value::Color(HINT_COLOR)),
+        ],
+    );
+
+    let mut file = components.file;
+
+    // Animation 0 rises, and ping-pongs to fall back.
+    file.push(
+        RivWriter::LINEAR_ANIMATION,
+        &[
+            (RivWriter::ANIMATION_NAME, Value::String("bounce")),
+            (RivWriter::ANIMATION_FPS, Value::Uint(FPS)),
+            (RivWriter::ANIMATION_DURATION, Value::Uint(RISE_FRAMES)),
+            (RivWriter::ANIMATION_LOOP, Value::Uint(LOOP_PING_PONG)),
+        ],
+    );
+    let floor = FLOOR - RADIUS;
+    key_eased(
+        &mut file,
+        mark,
+        RivWriter::NODE_Y,
+        ease,
+        floor,
+        floor - BOUNCE_HEIGHT,
+    );
+    // The shadow shrinks as the mark rises.
+    key_eased(
+        &mut file,
+        shadow_ellipse,
+        RivWriter::PARAMETRIC_PATH_WIDTH,
+        ease,
+        1.8 * RADIUS,
+        RADIUS,
+    );
+
+    // Animations 1 and 2 hold the mark's look away from and under the pointer.
+    for (name, color, scale) in [
+        ("rest", IDLE_COLOR, 1.0),
+        ("hover", HOVER_COLOR, 1.0 + HOVER_GROWTH),
+    ] {
+        file.push(
+            RivWriter::LINEAR_ANIMATION,
+            &[(RivWriter::ANIMATION_NAME, Value::String(name))],
+        );
+        file.push(
+            RivWriter::KEYED_OBJECT,
+            &[(RivWriter::KEYED_OBJECT_ID, Value::Uint(mark_color))],
+        );
+        file.push(
+            RivWriter::KEYED_PROPERTY,
+            &[(
+                RivWriter::KEYED_PROPERTY_KEY,
+                Value::Uint(RivWriter::SOLID_COLOR_VALUE),
+            )],
+        );
+        file.push(
+            RivWriter::KEY_FRAME_COLOR,
+            &[(RivWriter::KEY_FRAME_COLOR_VALUE, Value::Color(color))],
+        );
+        file.push(
+            RivWriter::KEYED_OBJECT,
+            &[(RivWriter::KEYED_OBJECT_ID, Value::Uint(mark))],
+        );
+        for key in [RivWriter::TRANSFORM_SCALE_X, RivWriter::TRANSFORM_SCALE_Y] {
+            file.push(
+                RivWriter::KEYED_PROPERTY,
+                &[(RivWriter::KEYED_PROPERTY_KEY, Value::Uint(key))],
+            );
+            file.push(
+                RivWriter::KEY_FRAME_DOUBLE,
+                &[(RivWriter::KEY_FRAME_DOUBLE_VALUE, Value::Double(scale))],
+            );
+        }
+    }
+
+    file.push(
+        RivWriter::STATE_MACHINE,
+        &[(RivWriter::ANIMATION_NAME, Value::String("demo"))],
+    );
+    // Input 0.
+    file.push(
+        RivWriter::STATE_MACHINE_BOOL,
+        &[(
+            RivWriter::STATE_MACHINE_COMPONENT_NAME,
+            Value::String("hovered"),
+        )],
+    );
+
+    // Layer states are numbered in order, starting with the any, entry and exit states.
+    push_layer(&mut file, "bounce");
+    file.push(
+        RivWriter::ANIMATION_STATE,
+        &[(RivWriter::ANIMATION_STATE_ANIMATION_ID, Value::Uint(0))],
+    );
+
+    push_layer(&mut file, "hover");
+    for (animation, to, op) in [(1, 4, None), (2, 3, Some(CONDITION_NOT_EQUAL))] {
+        file.push(
+            RivWriter::ANIMATION_STATE,
+            &[(
+                RivWriter::ANIMATION_STATE_ANIMATION_ID,
+                Value::Uint(animation),
+            )],
+        );
+        file.push(
+            RivWriter::STATE_TRANSITION,
+            &[
+                (RivWriter::STATE_TRANSITION_STATE_TO_ID, Value::Uint(to)),
+                (RivWriter::STATE_TRANSITION_DURATION, Value::Uint(HOVER_MIX)),
+            ],
+        );
+        // Equal by default.
+        let mut condition = vec![(RivWriter::TRANSITION_CONDITION_INPUT_ID, Value::Uint(0))];
+        condition.extend(op.map(|op| (RivWriter::TRANSITION_VALUE_CONDITION_OP, Value::Uint(op))));
+        file.push(RivWriter::TRANSITION_BOOL_CONDITION, &condition);
+    }
+
+    for (listener_type, hovered) in [(LISTENER_ENTER, 1), (LISTENER_EXIT, 0)] {
+        file.push(
+            RivWriter::STATE_MACHINE_LISTENER,
+            &[
+                (
+                    RivWriter::STATE_MACHINE_LISTENER_TARGET_ID,
+                    Value::Uint(mark),
+                ),
+                (
+                    RivWriter::STATE_MACHINE_LISTENER_TYPE,
+                    Value::Uint(listener_type),
+                ),
+            ],
+        );
+        file.push(
+            RivWriter::LISTENER_BOOL_CHANGE,
+            &[
+                (RivWriter::LISTENER_INPUT_CHANGE_INPUT_ID, Value::Uint(0)),
+                (RivWriter::LISTENER_BOOL_CHANGE_VALUE, Value::Uint(hovered)),
+            ],
+        );
+    }
+
+    file.finish()
+}
+
+fn add_points_path(components: &mut Components, shape: u64, points: &[(f32, f32)], closed: bool) {
+    let path = components.add(
+        RivWriter::POINTS_PATH,
+        &[
+            (RivWriter::COMPONENT_PARENT_ID, Value::Uint(shape)),
+            (RivWriter::POINTS_PATH_IS_CLOSED, Value::Uint(closed as u64)),
+        ],
+    );
+    for &(x, y) in points {
+        components.add(
+            RivWriter::STRAIGHT_VERTEX,
+            &[
+                (RivWriter::COMPONENT_PARENT_ID, Value::Uint(path)),
+                (RivWriter::VERTEX_X, Value::Double(x)),
+                (RivWriter::VERTEX_Y, Value::Double(y)),
+            ],
+        );
+    }
+}
+
+/// Keys `property` of `object` from `from` on the first frame to `to` on the last one of the
+/// rise, eased by the interpolator `ease`.
+fn key_eased(file: &mut RivWriter, object: u64, property: u64, ease: u64, from: f32, to: f32) {
+    file.push(
+        RivWriter::KEYED_OBJECT,
+        &[(RivWriter::KEYED_OBJECT_ID, Value::Uint(object))],
+    );
+    file.push(
+        RivWriter::KEYED_PROPERTY,
+        &[(RivWriter::KEYED_PROPERTY_KEY, Value::Uint(property))],
+    );
+    file.push(
+        RivWriter::KEY_FRAME_DOUBLE,
+        &[
+            (
+                RivWriter::KEY_FRAME_INTERPOLATION_TYPE,
+                Value::Uint(INTERPOLATION_CUBIC),
+            ),
+            (RivWriter::KEY_FRAME_INTERPOLATOR_ID, Value::Uint(ease)),
+            (RivWriter::KEY_FRAME_DOUBLE_VALUE, Value::Double(from)),
+        ],
+    );
+    file.push(
+        RivWriter::KEY_FRAME_DOUBLE,
+        &[
+            (RivWriter::KEY_FRAME_FRAME, Value::Uint(RISE_FRAMES)),
+            (RivWriter::KEY_FRAME_DOUBLE_VALUE, Value::Double(to)),
+        ],
+    );
+}
+
+/// Starts a state machine layer with the any, entry and exit states every layer needs, entering
+/// the first state after them.
+fn push_layer(file: &mut RivWriter, name: &str) {
+    file.push(
+        RivWriter::STATE_MACHINE_LAYER,
+        &[(RivWriter::STATE_MACHINE_COMPONENT_NAME, Value::String(name))],
+    );
+    file.push(RivWriter::ANY_STATE, &[]);
+    file.push(RivWriter::ENTRY_STATE, &[]);
+    file.push(
+        RivWriter::STATE_TRANSITION,
+        &[(RivWriter::STATE_TRANSITION_STATE_TO_ID, Value::Uint(3))],
+    );
+    file.push(RivWriter::EXIT_STATE, &[]);
+}
+
+/// Flattens `path` into polylines, each with whether it's closed.
+fn polylines(path: &BezPath) -> Vec<(Vec<Point>, bool)> {
+    let mut polylines: Vec<(Vec<Point>, bool)> = Vec::new();
+    path.flatten(0.01, |el| match el {
+        PathEl::MoveTo(point) => polylines.push((vec![point], false)),
+        PathEl::LineTo(point) => {
+            if let Some((points, _)) = polylines.last_mut() {
+                points.push(point);
+            }
+        }
+        PathEl::ClosePath => {
+            if let Some((points, closed)) = polylines.last_mut() {
+                *closed = true;
+                // The closing vertex would duplicate the first one.
+                if points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+            }
+        }
+        // Flattening only leaves lines.
+        _ => {}
+    });
+    polylines
+}
+
+/// Lays out `text` as strokes of a minimal single-line font covering `HINT`, with the baseline at
+/// 1 and the x-height at 0.5. Returns the path and its advance, both in ems. Characters without
+/// a glyph only advance.
+pub fn text_path(text: &str) -> (BezPath, f64) {
+    let mut path = BezPath::new();
+    let mut x = 0.0;
+
+    for c in text.chars() {
+        let (glyph, advance) = glyph(c);
+        let offset = Affine::translate((x, 0.0));
+        for el in glyph.elements() {
+            path.push(offset * *el);
+        }
+        x += advance;
+    }
+
+    (path, x)
+}
+
+fn glyph(c: char) -> (BezPath, f64) {
+    let mut path = BezPath::new();
+
+    let advance = match c {
+        'a' => {
+            line(&mut path, (0.55, 0.5), (0.55, 1.0));
+            bowl(&mut path, 0.0, 2.0 * PI);
+            0.75
+        }
+        'd' => {
+            line(&mut path, (0.55, 0.0), (0.55, 1.0));
+            bowl(&mut path, 0.0, 2.0 * PI);
+            0.75
+        }
+        'e' => {
+            line(&mut path, (0.05, 0.75), (0.55, 0.75));
+            bowl(&mut path, 0.0, -1.75 * PI);
+            0.75
+        }
+        'f' => {
+            path.move_to((0.15, 1.0));
+            path.line_to((0.15, 0.25));
+            path.quad_to((0.15, 0.0), (0.4, 0.0));
+            path.move_to((0.0, 0.5));
+            path.line_to((0.35, 0.5));
+            0.5
+        }
+        'h' => {
+            line(&mut path, (0.05, 0.0), (0.05, 1.0));
+            path.move_to((0.05, 0.75));
+            path.quad_to((0.05, 0.5), (0.3, 0.5));
+            path.quad_to((0.55, 0.5), (0.55, 0.75));
+            path.line_to((0.55, 1.0));
+            0.75
+        }
+        'i' => {
+            line(&mut path, (0.05, 0.5), (0.05, 1.0));
+            append(&mut path, &Circle::new((0.05, 0.3), 0.04));
+            0.25
+        }
+        'l' => {
+            line(&mut path, (0.05, 0.0), (0.05, 1.0));
+            0.25
+        }
+        'o' => {
+            bowl(&mut path, 0.0, 2.0 * PI);
+            0.75
+        }
+        'p' => {
+            line(&mut path, (0.05, 0.5), (0.05, 1.3));
+            bowl(&mut path, 0.0, 2.0 * PI);
+            0.75
+        }
+        'r' => {
+            line(&mut path, (0.05, 0.5), (0.05, 1.0));
+            path.move_to((0.05, 0.75));
+            path.quad_to((0.05, 0.5), (0.4, 0.5));
+            0.5
+        }
+        'v' => {
+            path.move_to((0.0, 0.5));
+            path.line_to((0.25, 1.0));
+            path.line_to((0.5, 0.5));
+            0.65
+        }
+        '.' => {
+            append(&mut path, &Circle::new((0.05, 0.96), 0.04));
+            0.25
+        }
+        _ => 0.4,
+    };
+
+    (path, advance)
+}
+
+fn line(path: &mut BezPath, from: (f64, f64), to: (f64, f64)) {
+    path.move_to(from);
+    path.line_to(to);
+}
+
+/// The bowl shared by a, d, e, o and p.
+fn bowl(path: &mut BezPath, start_angle: f64, sweep_angle: f64) {
+    let arc = Arc {
+        center: Point::new(0.3, 0.75),
+        radii: Vec2::new(0.25, 0.25),
+        start_angle,
+        sweep_angle,
+        x_rotation: 0.0,
+    };
+    append(path, &arc);
+}
+
+fn append(path: &mut BezPath, shape: &impl Shape) {
+    for el in shape.path_elements(0.01) {
+        path.push(el);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rive_vello::ViewerContent;
+
+    use super::*;
+
+    #[test]
+    fn hint_has_every_glyph() {
+        for c in HINT.chars().filter(|&c| c != ' ') {
+            assert!(!glyph(c).0.elements().is_empty(), "no glyph for {c:?}");
+        }
+
+        let (path, width) = text_path(HINT);
+        let bounds = path.bounding_box();
+        assert!(bounds.x0 >= -0.1 && bounds.x1 <= width + 0.1);
+        assert!(bounds.y0 >= -0.1 && bounds.y1 <= 1.4);
+    }
+
+    #[test]
+    fn hovering_the_mark_warms_it_up() {
+        let mut content = ViewerContent::from_bytes(&riv()).unwrap();
+        content.handle_resize(SIZE as u32, SIZE as u32);
+        assert_eq!(content.scene().as_deref(), Some("demo"));
+        assert_eq!(content.boolean("hovered"), Some(false));
+
+        // Resting on the floor before the first advance.
+        let center = Vec2::new((SIZE / 2.0) as f64, (FLOOR - RADIUS) as f64);
+        content.handle_pointer_move(center).unwrap();
+        assert_eq!(content.boolean("hovered"), Some(true));
+        content.advance(1.0 / 60.0).unwrap();
+
+        content.handle_pointer_move(Vec2::new(10.0, 10.0)).unwrap();
+        assert_eq!(content.boolean("hovered"), Some(false));
+    }
+
+    #[test]
+    fn bounces_above_the_floor() {
+        let mut content = ViewerContent::from_bytes(&riv()).unwrap();
+        let rest = content.component_bounds("mark").unwrap();
+        let mut highest = rest.y0;
+
+        for _ in 0..2 * RISE_FRAMES {
+            content.advance(1.0 / FPS as f64).unwrap();
+            let bounds = content.component_bounds("mark").unwrap();
+            assert!(bounds.y1 <= rest.y1 + 1e-3, "{bounds:?}");
+            highest = highest.min(bounds.y0);
+        }
+        assert!((rest.y0 - highest - BOUNCE_HEIGHT as f64).abs() <= 2.0);
+    }
+}
@@ -0,0 +1,357 @@
+use std::{fmt, sync::Arc};
+
+use vello::{Renderer, RendererOptions};
+use wgpu::{
+    Adapter, Device, DeviceDescriptor, DeviceType, Features, Instance, Limits, PowerPreference,
+    Queue, RequestAdapterOptions, Surface, TextureFormat,
+};
+
+/// Features a host-provided device needs. Vello only uses core WebGPU functionality.
+pub fn required_features() -> Features {
+    Features::empty()
+}
+
+/// Limits a host-provided device needs. Vello's compute pipelines are sized for the WebGPU
+/// defaults, so a device requested with `Limits::downlevel_defaults()` is not enough.
+pub fn required_limits() -> Limits {
+    Limits::default()
+}
+
+/// Limits of the `Reduced` tier. Smaller storage buffers cap the size of the scenes which can
+/// be rendered and smaller textures the size of the targets, but Vello's compute pipelines run
+/// as is.
+pub fn reduced_limits() -> Limits {
+    Limits {
+        max_texture_dimension_1d: 2048,
+        max_texture_dimension_2d: 2048,
+        max_storage_textures_per_shader_stage: 4,
+        max_storage_buffer_binding_size: 32 << 20,
+        max_buffer_size: 32 << 20,
+        max_compute_workgroup_storage_size: 16352,
+        ..required_limits()
+    }
+}
+
+/// Steps of the degradation ladder `request_device` walks down, from best to worst.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RenderTier {
+    /// `required_limits`, with GPU timestamps when the adapter has them.
+    Full,
+    /// `reduced_limits` and no GPU timestamps.
+    Reduced,
+    /// wgpu's software fallback adapter, or any CPU adapter such as lavapipe, with the best
+    /// limits it has. Only ever reported by `request_device`.
+    Software,
+}
+
+impl RenderTier {
+    pub fn limits(self) -> Limits {
+        match self {
+            Self::Full => required_limits(),
+            Self::Reduced | Self::Software => reduced_limits(),
+        }
+    }
+}
+
+/// Picks the best tier whose limits `actual` meets. Fails with the limits missing for the
+/// `Reduced` tier otherwise.
+pub fn probe_limits(actual: &Limits) -> Result<RenderTier, DeviceError> {
+    if missing_limits(&required_limits(), actual).is_empty() {
+        return Ok(RenderTier::Full);
+    }
+
+    let missing = missing_limits(&reduced_limits(), actual);
+    if missing.is_empty() {
+        Ok(RenderTier::Reduced)
+    } else {
+        Err(DeviceError::MissingLimits(missing))
+    }
+}
+
+/// A device requested through `request_device`.
+#[derive(Debug)]
+pub struct TieredDevice {
+    pub adapter: Adapter,
+    pub device: Arc<Device>,
+    pub queue: Arc<Queue>,
+    pub tier: RenderTier,
+}
+
+/// Requests a device for the best tier an adapter supports: the full configuration first, then
+/// reduced limits without timestamps, and finally wgpu's software fallback adapter. Every step
+/// which fails falls through to the next one instead of panicking.
+pub async fn request_device(
+    instance: &Instance,
+    compatible_surface: Option<&Surface>,
+) -> Result<TieredDevice, DeviceError> {
+    let mut error = DeviceError::NoAdapter;
+
+    for force_fallback_adapter in [false, true] {
+        let Some(adapter) = instance
+            .request_adapter(&RequestAdapterOptions {
+                power_preference: PowerPreference::HighPerformance,
+                compatible_surface,
+                force_fallback_adapter,
+            })
+            .await
+        else {
+            continue;
+        };
+
+        let is_software =
+            force_fallback_adapter || adapter.get_info().device_type == DeviceType::Cpu;
+        let best = match probe_limits(&adapter.limits()) {
+            Ok(tier) => tier,
+            Err(probe_error) => {
+                error = probe_error;
+                continue;
+            }
+        };
+
+        for tier in [RenderTier::Full, RenderTier::Reduced] {
+            if tier < best {
+                continue;
+            }
+
+            let features = match tier {
+                RenderTier::Full => adapter.features() & Features::TIMESTAMP_QUERY,
+                _ => Features::empty(),
+            };
+            let result = adapter
+                .request_device(
+                    &DeviceDescriptor {
+                        label: Some("rive"),
+                        features: required_features() | features,
+                        limits: tier.limits(),
+                    },
+                    None,
+                )
+                .await;
+
+            match result {
+                Ok((device, queue)) => {
+                    return Ok(TieredDevice {
+                        adapter,
+                        device: Arc::new(device),
+                        queue: Arc::new(queue),
+                        tier: if is_software {
+                            RenderTier::Software
+                        } else {
+                            tier
+                        },
+                    });
+                }
+                Err(request_error) => error = DeviceError::Request(request_error),
+            }
+        }
+    }
+
+    Err(error)
+}
+
+/// A limit of a host-provided device which is lower than Vello needs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MissingLimit {
+    pub name: &'static str,
+    pub required: u32,
+    pub actual: u32,
+}
+
+#[derive(Debug)]
+pub enum DeviceError {
+    /// The device lacks some of the `required_features`.
+    MissingFeatures(Features),
+    /// The device doesn't meet the `required_limits`.
+    MissingLimits(Vec<MissingLimit>),
+    /// No adapter was found, not even a software one.
+    NoAdapter,
+    Request(wgpu::RequestDeviceError),
+    Renderer(vello::Error),
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFeatures(features) => {
+                write!(f, "device is missing required features: {features:?}")
+            }
+            Self::MissingLimits(limits) => {
+                write!(f, "device limits are too low:")?;
+
+                for limit in limits {
+                    write!(
+                        f,
+                        " {} (requires {}, has {})",
+                        limit.name, limit.required, limit.actual
+                    )?;
+                }
+
+                Ok(())
+            }
+            Self::NoAdapter => write!(f, "no suitable adapter found"),
+            Self::Request(error) => write!(f, "failed to request device: {error}"),
+            Self::Renderer(error) => write!(f, "failed to create renderer: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for DeviceError {}
+
+/// Checks that `device` was requested with `required_features` and at least `reduced_limits`,
+/// and returns the tier its limits allow. Software adapters can't be told apart from the device
+/// alone, so this never returns `RenderTier::Software`.
+pub fn validate_device(device: &Device) -> Result<RenderTier, DeviceError> {
+    let missing_features = required_features() - device.features();
+    if !missing_features.is_empty() {
+        return Err(DeviceError::MissingFeatures(missing_features));
+    }
+
+    probe_limits(&device.limits())
+}
+
+fn missing_limits(required: &Limits, actual: &Limits) -> Vec<MissingLimit> {
+    let checks = [
+        (
+            "max_storage_buffers_per_shader_stage",
+            required.max_storage_buffers_per_shader_stage,
+            actual.max_storage_buffers_per_shader_stage,
+        ),
+        (
+            "max_storage_textures_per_shader_stage",
+            required.max_storage_textures_per_shader_stage,
+            actual.max_storage_textures_per_shader_stage,
+        ),
+        (
+            "max_storage_buffer_binding_size",
+            required.max_storage_buffer_binding_size,
+            actual.max_storage_buffer_binding_size,
+        ),
+        (
+            "max_compute_workgroup_storage_size",
+            required.max_compute_workgroup_storage_size,
+            actual.max_compute_workgroup_storage_size,
+        ),
+        (
+            "max_compute_invocations_per_workgroup",
+            required.max_compute_invocations_per_workgroup,
+            actual.max_compute_invocations_per_workgroup,
+        ),
+        (
+            "max_compute_workgroup_size_x",
+            required.max_compute_workgroup_size_x,
+            actual.max_compute_workgroup_size_x,
+        ),
+        (
+            "max_compute_workgroups_per_dimension",
+            required.max_compute_workgroups_per_dimension,
+            actual.max_compute_workgroups_per_dimension,
+        ),
+        (
+            "max_texture_dimension_2d",
+            required.max_texture_dimension_2d,
+            actual.max_texture_dimension_2d,
+        ),
+    ];
+
+    checks
+        .into_iter()
+        .filter(|&(_, required, actual)| actual < required)
+        .map(|(name, required, actual)| MissingLimit {
+            name,
+            required,
+            actual,
+        })
+        .collect()
+}
+
+/// Creates a Vello `Renderer` on a host-provided device. `surface_format` is only needed
+/// for rendering directly to a surface. GPU timing is only enabled on devices requested with
+/// `Features::TIMESTAMP_QUERY`.
+pub fn create_renderer(
+    device: &Device,
+    queue: &Queue,
+    surface_format: Option<TextureFormat>,
+) -> Result<Renderer, DeviceError> {
+    validate_device(device)?;
+
+    let timestamp_period = if device.features().contains(Features::TIMESTAMP_QUERY) {
+        queue.get_timestamp_period()
+    } else {
+        0.0
+    };
+
+    Renderer::new(
+        device,
+        &RendererOptions {
+            surface_format,
+            timestamp_period,
+        },
+    )
+    .map_err(DeviceError::Renderer)
+}
+
+/// Requests a device for tests which render. wgpu's software fallback adapter is enough, so
+/// a machine without any adapter fails these tests instead of skipping them.
+#[cfg(test)]
+pub(crate) fn test_device() -> TieredDevice {
+    let instance = Instance::new(wgpu::InstanceDescriptor::default());
+    pollster::block_on(request_device(&instance, None))
+        .unwrap_or_else(|error| panic!("tests which render need an adapter: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_missing_limit() {
+        let error = DeviceError::MissingLimits(vec![
+            MissingLimit {
+                name: "max_storage_buffers_per_shader_stage",
+                required: 8,
+                actual: 4,
+            },
+            MissingLimit {
+                name: "max_compute_workgroup_storage_size",
+                required: 16352,
+                actual: 0,
+            },
+        ]);
+
+        assert_eq!(
+            error.to_string(),
+            "device limits are too low: max_storage_buffers_per_shader_stage (requires 8, has 4) \
+             max_compute_workgroup_storage_size (requires 16352, has 0)"
+        );
+    }
+
+    #[test]
+    fn default_limits_are_full() {
+        assert_eq!(probe_limits(&Limits::default()).unwrap(), RenderTier::Full);
+    }
+
+    #[test]
+    fn small_textures_and_buffers_fall_back_to_reduced() {
+        let limits = Limits {
+            max_texture_dimension_2d: 4096,
+            max_storage_buffer_binding_size: 64 << 20,
+            ..Limits::default()
+        };
+
+        assert_eq!(probe_limits(&limits).unwrap(), RenderTier::Reduced);
+    }
+
+    #[test]
+    fn downlevel_limits_report_what_reduced_is_missing() {
+        let Err(DeviceError::MissingLimits(missing)) =
+            probe_limits(&Limits::downlevel_webgl2_defaults())
+        else {
+            panic!("WebGL2 limits can't run Vello");
+        };
+
+        assert!(missing
+            .iter()
+            .any(|limit| limit.name == "max_storage_buffers_per_shader_stage"));
+        assert!(missing.iter().all(|limit| limit.actual < limit.required));
+    }
+}
@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use vello::{
+    kurbo::{Affine, Rect},
+    peniko::{Color, Fill},
+    RenderParams, Renderer, Scene, SceneBuilder,
+};
+use wgpu::{Device, Maintain, Queue, SurfaceTexture, TextureFormat};
+
+use crate::{
+    device::{self, DeviceError},
+    gpu_memory::{GpuAllocation, GpuCategory, SceneMemory},
+    DownscaleFilter, RenderStats, SceneUnavailable, TextureTarget, VelloRenderer, ViewerContent,
+};
+
+/// Plays a `ViewerContent` on a device owned by the host, either to one of the host's surfaces
+/// or to a `TextureTarget`.
+///
+/// The viewer keeps the device alive, so the host may drop its own handles first. Dropping
+/// the viewer waits for the GPU to finish its submissions before the renderer's resources are
+/// released. The content never touches the device and can be dropped at any point.
+pub struct Viewer {
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    renderer: Renderer,
+    content: Option<ViewerContent>,
+    base_color: Color,
+    downscale_filter: DownscaleFilter,
+    stats: RenderStats,
+    /// What the last scene built encoded, kept until the next one replaces it.
+    scene_memory: SceneMemory,
+    /// The texture Vello renders to before blitting to surfaces, once it rendered to one.
+    surface_target: Option<GpuAllocation>,
+}
+
+impl Viewer {
+    /// Fails if the device doesn't meet `required_features` or `required_limits`.
+    /// `surface_format` is only needed for `render_to_surface`.
+    pub fn new(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        surface_format: Option<TextureFormat>,
+    ) -> Result<Self, DeviceError> {
+        let renderer = device::create_renderer(&device, &queue, surface_format)?;
+
+        Ok(Self {
+            device,
+            queue,
+            renderer,
+            content: None,
+            base_color: Color::TRANSPARENT,
+            downscale_filter: DownscaleFilter::default(),
+            stats: RenderStats::default(),
+            scene_memory: SceneMemory::default(),
+            surface_target: None,
+        })
+    }
+
+    pub fn content(&self) -> Option<&ViewerContent> {
+        self.content.as_ref()
+    }
+
+    pub fn content_mut(&mut self) -> Option<&mut ViewerContent> {
+        self.content.as_mut()
+    }
+
+    pub fn set_content(&mut self, content: Option<ViewerContent>) {
+        self.content = content;
+    }
+
+    pub fn set_base_color(&mut self, base_color: Color) {
+        self.base_color = base_color;
+    }
+
+    pub fn set_downscale_filter(&mut self, filter: DownscaleFilter) {
+        self.downscale_filter = filter;
+    }
+
+    pub fn resize(&self, width: u32, height: u32) {
+        if let Some(content) = &self.content {
+            content.handle_resize(width, height);
+        }
+    }
+
+    /// Stats of the last scene built.
+    pub fn stats(&self) -> &RenderStats {
+        &self.stats
+    }
+
+    /// Advances the content by `elapsed` seconds and records it into a new scene.
+    pub fn build_scene(&mut self, elapsed: f64) -> Scene {
+        self.record_scene(|content, renderer| content.handle_draw(renderer, elapsed))
+    }
+
+    /// Advances the content by `elapsed` seconds without recording anything. Follow with one
+    /// `draw_scene` per target to present the same pose on several of them. Unavailable content
+    /// is left as is; check `ViewerContent::status`.
+    pub fn advance(&mut self, elapsed: f64) {
+        if let Some(content) = &mut self.content {
+            let _ = content.advance(elapsed);
+        }
+    }
+
+    /// Records the pose of the last `advance` into a new scene, fitted to `width` by `height`
+    /// independently of `resize`.
+    pub fn draw_scene(&mut self, width: u32, height: u32) -> Scene {
+        self.record_scene(|content, renderer| content.draw(renderer, width, height))
+    }
+
+    fn record_scene(
+        &mut self,
+        draw: impl FnOnce(&mut ViewerContent, &mut VelloRenderer) -> Result<(), SceneUnavailable>,
+    ) -> Scene {
+        let mut scene = Scene::default();
+        let mut builder = SceneBuilder::for_scene(&mut scene);
+
+        // Unavailable content draws nothing, like no content at all.
+        let drawn = self.content.as_mut().and_then(|content| {
+            let mut renderer = VelloRenderer::default();
+            renderer.set_downscale_filter(self.downscale_filter);
+            draw(content, &mut renderer).ok()?;
+            renderer.end_frame();
+
+            Some(renderer)
+        });
+
+        if let Some(mut renderer) = drawn {
+            builder.append(&renderer.scene, None);
+            self.stats = std::mem::take(&mut renderer.stats);
+            self.scene_memory = std::mem::take(&mut renderer.scene_memory);
+        } else {
+            self.stats = RenderStats::default();
+            self.scene_memory = SceneMemory::default();
+
+            // Vello currently crashes when rendering an empty scene.
+            builder.fill(
+                Fill::NonZero,
+                Affine::IDENTITY,
+                Color::TRANSPARENT,
+                None,
+                &Rect::new(0.0, 0.0, 0.0, 0.0),
+            );
+        }
+
+        scene
+    }
+
+    pub fn render_to_surface(
+        &mut self,
+        surface: &SurfaceTexture,
+        elapsed: f64,
+    ) -> Result<(), vello::Error> {
+        let scene = self.build_scene(elapsed);
+        let (width, height) = (surface.texture.width(), surface.texture.height());
+        match &mut self.surface_target {
+            Some(target) => target.resize(width as u64 * height as u64 * 4),
+            target => {
+                *target = Some(GpuAllocation::texture(
+                    GpuCategory::Intermediates,
+                    width,
+                    height,
+                ))
+            }
+        }
+
+        vello::block_on_wgpu(
+            &self.device,
+            self.renderer.render_to_surface_async(
+                &self.device,
+                &self.queue,
+                &scene,
+                surface,
+                &RenderParams {
+                    base_color: self.base_color,
+                    width,
+                    height,
+                },
+            ),
+        )
+    }
+
+    /// Renders to `target`, which must have been created on the same device.
+    pub fn render_to_texture(
+        &mut self,
+        target: &mut TextureTarget,
+        elapsed: f64,
+    ) -> Result<(), vello::Error> {
+        debug_assert!(Arc::ptr_eq(target.device(), &self.device));
+
+        let scene = self.build_scene(elapsed);
+        target.render(&scene, self.base_color)
+    }
+
+    /// Advances once, then renders the same pose to every target, each fitted to its own size.
+    /// Targets must have been created on the same device. Stops at the first target that fails.
+    pub fn render_to_textures(
+        &mut self,
+        targets: &mut [&mut TextureTarget],
+        elapsed: f64,
+    ) -> Result<(), vello::Error> {
+        self.advance(elapsed);
+
+        for target in targets {
+            debug_assert!(Arc::ptr_eq(target.device(), &self.device));
+
+            let scene = self.draw_scene(target.width(), target.height());
+            target.render(&scene, self.base_color)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Viewer {
+    fn drop(&mut self) {
+        self.device.poll(Maintain::Wait);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{capture, review};
+
+    #[test]
+    fn mirrored_targets_draw_the_same_pose() {
+        let gpu = crate::device::test_device();
+        let target = |width, height| {
+            TextureTarget::new(gpu.device.clone(), gpu.queue.clone(), width, height).unwrap()
+        };
+        let viewer = |width, height| {
+            let mut viewer = Viewer::new(gpu.device.clone(), gpu.queue.clone(), None).unwrap();
+            viewer.set_content(ViewerContent::new("../test/assets/fix_rectangle.riv"));
+            viewer.resize(width, height);
+            viewer
+        };
+
+        let (mut primary, mut mirror) = (target(64, 48), target(128, 96));
+        viewer(64, 48)
+            .render_to_textures(&mut [&mut primary, &mut mirror], 0.25)
+            .unwrap();
+        // The same content played by a viewer with a single target.
+        let mut single = target(64, 48);
+        viewer(64, 48).render_to_texture(&mut single, 0.25).unwrap();
+
+        let primary = capture::read_back(&primary).unwrap();
+        let mirror = capture::read_back(&mirror).unwrap();
+        let single = capture::read_back(&single).unwrap();
+        assert_eq!(review::perceptual_diff(&single, &primary).changed, 0.0);
+        // The mirror is fitted to twice the size, so only antialiased edges may differ.
+        let diff = review::perceptual_diff(&primary, &mirror.downsample(2));
+        assert!(diff.score < 0.01, "mirror differs by {}", diff.score);
+    }
+}
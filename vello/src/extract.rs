@@ -0,0 +1,24 @@
+use std::fmt;
+
+use crate::{capture::CaptureError, status::SceneStatus};
+
+/// Why `ViewerContent::rasterize_component` couldn't render a component.
+#[derive(Debug)]
+pub enum ExtractError {
+    Unavailable(SceneStatus),
+    /// The artboard has no node or shape with this name.
+    UnknownComponent(String),
+    Capture(CaptureError),
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(status) => write!(f, "scene is {status}"),
+            Self::UnknownComponent(name) => write!(f, "no node or shape called \"{name}\""),
+            Self::Capture(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {}
@@ -0,0 +1,202 @@
+use vello::kurbo::Vec2;
+
+/// How far, in view pixels, a pointer has to move from where it went down before a press turns
+/// into a scrub and stops being a click.
+pub const DRAG_THRESHOLD: f32 = 4.0;
+
+/// Number inputs the viewer scrubs without being told to.
+pub const AUTO_SCRUB_INPUTS: [&str; 2] = ["scrub", "progress"];
+
+/// Range auto-bound inputs are assumed to cover across the artboard's width.
+pub const AUTO_SCRUB_RANGE: (f32, f32) = (0.0, 100.0);
+
+/// Drives a number input from horizontal drags, e.g. one blending a timeline from 0 to 100.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrubBinding {
+    pub input: String,
+    /// Input units per artboard unit dragged. Deltas are measured in artboard space, so zooming
+    /// the view doesn't change how far a drag scrubs. Negative values scrub right to left.
+    pub sensitivity: f32,
+    /// Inclusive bounds the value is kept within.
+    pub clamp: Option<(f32, f32)>,
+    /// When set, only presses starting on the shape of that name scrub.
+    pub hit_area: Option<String>,
+}
+
+impl ScrubBinding {
+    pub fn new(input: &str, sensitivity: f32, clamp: Option<(f32, f32)>) -> Self {
+        Self {
+            input: input.to_string(),
+            sensitivity,
+            clamp,
+            hit_area: None,
+        }
+    }
+
+    fn apply(&self, value: f32) -> f32 {
+        match self.clamp {
+            Some((min, max)) => value.clamp(min.min(max), max.max(min)),
+            None => value,
+        }
+    }
+}
+
+/// One press of the primary pointer, from down to up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ScrubDrag {
+    /// Where the press started, in view pixels.
+    start_view: (f32, f32),
+    /// Where the press started horizontally, in artboard units.
+    start_x: f32,
+    /// Value of the input when the press started.
+    start_value: f32,
+    scrubbing: bool,
+}
+
+impl ScrubDrag {
+    pub fn new(view: (f32, f32), artboard_x: f32, value: f32) -> Self {
+        Self {
+            start_view: view,
+            start_x: artboard_x,
+            start_value: value,
+            scrubbing: false,
+        }
+    }
+
+    /// Whether the drag went past `DRAG_THRESHOLD`, in which case its release isn't a click.
+    pub fn is_scrubbing(&self) -> bool {
+        self.scrubbing
+    }
+
+    /// The input's value for the pointer at `view` pixels and `artboard_x`, or `None` while the
+    /// press is still within the threshold. Values are relative to where the press started
+    /// rather than accumulated, so they don't drift, and the input keeps the last value once
+    /// released.
+    pub fn move_to(
+        &mut self,
+        binding: &ScrubBinding,
+        view: (f32, f32),
+        artboard_x: f32,
+    ) -> Option<f32> {
+        if !self.scrubbing {
+            let (dx, dy) = (view.0 - self.start_view.0, view.1 - self.start_view.1);
+            self.scrubbing = dx.hypot(dy) > DRAG_THRESHOLD;
+        }
+
+        self.scrubbing.then(|| {
+            binding.apply(self.start_value + (artboard_x - self.start_x) * binding.sensitivity)
+        })
+    }
+}
+
+/// Keeps a captured press moving while the cursor is outside the window, where the windowing
+/// system stops reporting its position. Raw device deltas are added to the last position it did
+/// report, in the same view pixels, and may go past the window's edges; listeners decide
+/// whether to clamp. Devices which report deltas with acceleration or in other units than
+/// pixels drift from the cursor until it comes back in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointerCapture {
+    position: Vec2,
+    outside: bool,
+}
+
+impl PointerCapture {
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            outside: false,
+        }
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    /// Follows a position reported by the windowing system, which is exact.
+    pub fn cursor_moved(&mut self, position: Vec2) {
+        self.position = position;
+        self.outside = false;
+    }
+
+    /// Starts synthesizing positions from device deltas.
+    pub fn cursor_left(&mut self) {
+        self.outside = true;
+    }
+
+    /// Returns the synthesized position, or `None` while the cursor is inside the window and
+    /// its moves are reported directly.
+    pub fn device_moved(&mut self, delta: Vec2) -> Option<Vec2> {
+        if !self.outside {
+            return None;
+        }
+
+        self.position += delta;
+        Some(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presses_within_threshold_stay_clicks() {
+        let binding = ScrubBinding::new("scrub", 1.0, None);
+        let mut drag = ScrubDrag::new((10.0, 10.0), 10.0, 50.0);
+
+        assert_eq!(drag.move_to(&binding, (12.0, 12.0), 12.0), None);
+        assert!(!drag.is_scrubbing());
+
+        assert_eq!(drag.move_to(&binding, (15.0, 10.0), 15.0), Some(55.0));
+        assert!(drag.is_scrubbing());
+
+        // Coming back doesn't turn the scrub into a click again.
+        assert_eq!(drag.move_to(&binding, (10.0, 10.0), 10.0), Some(50.0));
+        assert!(drag.is_scrubbing());
+    }
+
+    #[test]
+    fn sensitivity_applies_to_artboard_units() {
+        let binding = ScrubBinding::new("scrub", 0.5, None);
+
+        // The same 20 view pixels span 40 artboard units zoomed out and 10 zoomed in.
+        let mut zoomed_out = ScrubDrag::new((0.0, 0.0), 0.0, 0.0);
+        let mut zoomed_in = ScrubDrag::new((0.0, 0.0), 0.0, 0.0);
+
+        assert_eq!(zoomed_out.move_to(&binding, (20.0, 0.0), 40.0), Some(20.0));
+        assert_eq!(zoomed_in.move_to(&binding, (20.0, 0.0), 10.0), Some(5.0));
+    }
+
+    #[test]
+    fn clamps_to_bounds() {
+        let binding = ScrubBinding::new("progress", -2.0, Some((0.0, 100.0)));
+        let mut drag = ScrubDrag::new((0.0, 0.0), 0.0, 10.0);
+
+        assert_eq!(drag.move_to(&binding, (100.0, 0.0), 100.0), Some(0.0));
+        assert_eq!(drag.move_to(&binding, (-100.0, 0.0), -100.0), Some(100.0));
+        assert_eq!(drag.move_to(&binding, (-10.0, 0.0), -10.0), Some(30.0));
+    }
+
+    #[test]
+    fn deltas_only_apply_outside_the_window() {
+        let mut capture = PointerCapture::new(Vec2::new(10.0, 10.0));
+
+        assert_eq!(capture.device_moved(Vec2::new(5.0, 0.0)), None);
+        capture.cursor_moved(Vec2::new(2.0, 10.0));
+        capture.cursor_left();
+
+        assert_eq!(
+            capture.device_moved(Vec2::new(-5.0, 1.0)),
+            Some(Vec2::new(-3.0, 11.0))
+        );
+        assert_eq!(
+            capture.device_moved(Vec2::new(-5.0, 1.0)),
+            Some(Vec2::new(-8.0, 12.0))
+        );
+
+        // Coming back in snaps to the real position, dropping any drift.
+        capture.cursor_moved(Vec2::new(0.0, 12.0));
+        assert_eq!(capture.device_moved(Vec2::new(-5.0, 0.0)), None);
+        assert_eq!(capture.position(), Vec2::new(0.0, 12.0));
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    rc::{Rc, Weak},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use vello::{
+    kurbo::PathEl,
+    peniko::{Brush, Gradient},
+};
+
+/// Bytes Vello encodes a draw into besides its path: its tag, transform and brush data.
+const DRAW_BYTES: u64 = 4 + 24 + 16;
+/// Bytes of a gradient ramp. Vello samples each distinct gradient into a row of 512 RGBA8
+/// texels of its ramp texture.
+const RAMP_BYTES: u64 = 512 * 4;
+
+/// What a tracked GPU allocation holds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GpuCategory {
+    /// Vello's encoding of the scenes being built, which it uploads into its scene buffers.
+    Scene,
+    /// Vello's gradient ramp texture.
+    Gradients,
+    /// Pixels of the images drawn, which Vello uploads into its image atlas.
+    Images,
+    /// `TextureTarget`s.
+    Targets,
+    /// Textures and buffers in between Vello and the presented image, like render scale
+    /// textures, surface blit targets and capture readbacks.
+    Intermediates,
+}
+
+/// GPU memory of the calling thread, in bytes, by category. wgpu doesn't report allocations,
+/// so these are the allocations tracked with `GpuAllocation` when they're created: exact for
+/// targets and intermediates, estimated from what was encoded for Vello's own resources.
+/// Vello's fixed-size pipeline buffers aren't included.
+///
+/// Accounting follows threads rather than viewers or devices, since contents render on the
+/// thread that owns them: viewers drawn on one thread share a report and an image budget,
+/// while viewers on separate threads are counted and budgeted apart, even on one device.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GpuMemoryReport {
+    pub scene: u64,
+    pub gradients: u64,
+    pub images: u64,
+    pub targets: u64,
+    pub intermediates: u64,
+}
+
+impl GpuMemoryReport {
+    pub fn total(&self) -> u64 {
+        self.scene + self.gradients + self.images + self.targets + self.intermediates
+    }
+}
+
+/// The total, then each category, in MB.
+impl fmt::Display for GpuMemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mb = |bytes: u64| bytes as f64 / (1 << 20) as f64;
+        write!(
+            f,
+            "{:.1} MB (scene {:.1}, gradients {:.1}, images {:.1}, targets {:.1}, \
+             intermediates {:.1})",
+            mb(self.total()),
+            mb(self.scene),
+            mb(self.gradients),
+            mb(self.images),
+            mb(self.targets),
+            mb(self.intermediates)
+        )
+    }
+}
+
+type Counters = Arc<[AtomicU64; 5]>;
+
+thread_local! {
+    static COUNTERS: Counters = Counters::default();
+    static IMAGE_TEXTURES: RefCell<ImageTextures> = RefCell::default();
+}
+
+/// Keeps `bytes` of GPU memory counted in `gpu_memory_report` from its creation to its drop.
+/// Like `LiveCounts`, bytes belong to the thread that created the allocation, even if it's
+/// dropped on another one. Hosts can track their own textures and buffers with it.
+#[derive(Debug)]
+pub struct GpuAllocation {
+    counters: Counters,
+    category: GpuCategory,
+    bytes: u64,
+}
+
+impl GpuAllocation {
+    pub fn new(category: GpuCategory, bytes: u64) -> Self {
+        let counters = COUNTERS.with(Arc::clone);
+        counters[category as usize].fetch_add(bytes, Ordering::Relaxed);
+
+        Self {
+            counters,
+            category,
+            bytes,
+        }
+    }
+
+    /// An allocation of `width` by `height` RGBA8 texels.
+    pub fn texture(category: GpuCategory, width: u32, height: u32) -> Self {
+        Self::new(category, width as u64 * height as u64 * 4)
+    }
+
+    pub fn category(&self) -> GpuCategory {
+        self.category
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Counts `bytes` instead, e.g. after the allocation was recreated at another size.
+    pub fn resize(&mut self, bytes: u64) {
+        let counter = &self.counters[self.category as usize];
+        counter.fetch_sub(self.bytes, Ordering::Relaxed);
+        counter.fetch_add(bytes, Ordering::Relaxed);
+        self.bytes = bytes;
+    }
+}
+
+impl Drop for GpuAllocation {
+    fn drop(&mut self) {
+        self.counters[self.category as usize].fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// GPU memory allocated on the calling thread and not released yet.
+pub fn gpu_memory_report() -> GpuMemoryReport {
+    COUNTERS.with(|counters| {
+        let bytes = |category: GpuCategory| counters[category as usize].load(Ordering::Relaxed);
+
+        GpuMemoryReport {
+            scene: bytes(GpuCategory::Scene),
+            gradients: bytes(GpuCategory::Gradients),
+            images: bytes(GpuCategory::Images),
+            targets: bytes(GpuCategory::Targets),
+            intermediates: bytes(GpuCategory::Intermediates),
+        }
+    })
+}
+
+/// Bytes of image textures the calling thread keeps uploaded, or `None`, the default, for no
+/// limit. Past the budget, the least recently drawn images are evicted: their pixels are
+/// dropped, then decoded and uploaded again the next time they're drawn. The image being
+/// drawn is never evicted, so a budget smaller than one frame's images makes them thrash.
+pub fn set_gpu_image_budget(budget: Option<u64>) {
+    let evicted = IMAGE_TEXTURES.with(|textures| {
+        let mut textures = textures.borrow_mut();
+        textures.budget = budget;
+        textures.evict_over_budget(None)
+    });
+
+    for image in evicted {
+        image.evict();
+    }
+}
+
+pub fn gpu_image_budget() -> Option<u64> {
+    IMAGE_TEXTURES.with(|textures| textures.borrow().budget)
+}
+
+/// An image whose texture can be evicted.
+pub(crate) trait Evict {
+    /// Drops the texture's pixels along with its `GpuAllocation`.
+    fn evict(&self);
+}
+
+#[derive(Debug)]
+struct Resident {
+    image: Weak<dyn Evict>,
+    bytes: u64,
+    /// Content which drew the image last.
+    owner: usize,
+}
+
+/// Image textures of the thread in least to most recently drawn order.
+#[derive(Debug, Default)]
+struct ImageTextures {
+    budget: Option<u64>,
+    resident: Vec<Resident>,
+}
+
+impl ImageTextures {
+    /// Evicts the least recently drawn textures but `keep` until the rest fit the budget.
+    fn evict_over_budget(&mut self, keep: Option<*const ()>) -> Vec<Rc<dyn Evict>> {
+        self.resident
+            .retain(|resident| resident.image.strong_count() > 0);
+
+        let Some(budget) = self.budget else {
+            return Vec::new();
+        };
+        let mut bytes: u64 = self.resident.iter().map(|resident| resident.bytes).sum();
+        let mut evicted = Vec::new();
+
+        while bytes > budget {
+            let Some(index) = self
+                .resident
+                .iter()
+                .position(|resident| Some(resident.image.as_ptr() as *const ()) != keep)
+            else {
+                break;
+            };
+
+            let resident = self.resident.remove(index);
+            bytes -= resident.bytes;
+            evicted.extend(resident.image.upgrade());
+        }
+
+        evicted
+    }
+}
+
+/// Marks `image` as drawn by `owner` with a texture of `bytes`, evicting other images if
+/// that takes the thread past its budget.
+pub(crate) fn touch_image(image: Weak<dyn Evict>, bytes: u64, owner: usize) {
+    let evicted = IMAGE_TEXTURES.with(|textures| {
+        let mut textures = textures.borrow_mut();
+        let ptr = image.as_ptr() as *const ();
+        textures
+            .resident
+            .retain(|resident| resident.image.as_ptr() as *const () != ptr);
+        textures.resident.push(Resident {
+            image,
+            bytes,
+            owner,
+        });
+
+        textures.evict_over_budget(Some(ptr))
+    });
+
+    for image in evicted {
+        image.evict();
+    }
+}
+
+/// Evicts the textures of the images `owner` drew last, e.g. when it hibernates.
+pub(crate) fn evict_images(owner: usize) {
+    let evicted: Vec<_> = IMAGE_TEXTURES.with(|textures| {
+        let mut textures = textures.borrow_mut();
+        let (evicted, kept) = std::mem::take(&mut textures.resident)
+            .into_iter()
+            .partition(|resident| resident.owner == owner);
+        textures.resident = kept;
+
+        evicted
+    });
+
+    for resident in evicted {
+        if let Some(image) = resident.image.upgrade() {
+            image.evict();
+        }
+    }
+}
+
+/// Estimates of what a `VelloRenderer` encoded for Vello's scene buffers and ramp texture.
+#[derive(Debug)]
+pub(crate) struct SceneMemory {
+    scene: GpuAllocation,
+    gradients: GpuAllocation,
+    /// Stops of the distinct gradients drawn, as offset and RGBA bits.
+    ramps: Vec<Vec<(u32, u32)>>,
+}
+
+impl SceneMemory {
+    pub fn record_draw(&mut self, path: impl IntoIterator<Item = PathEl>, brush: Option<&Brush>) {
+        let path_bytes: u64 = path.into_iter().map(|el| element_bytes(&el)).sum();
+        self.scene
+            .resize(self.scene.bytes() + DRAW_BYTES + path_bytes);
+
+        if let Some(Brush::Gradient(gradient)) = brush {
+            let ramp = ramp(gradient);
+            if !self.ramps.contains(&ramp) {
+                self.ramps.push(ramp);
+                self.gradients.resize(self.ramps.len() as u64 * RAMP_BYTES);
+            }
+        }
+    }
+
+    /// Takes over what `other` encoded, once its fragment is appended to this one's.
+    pub fn absorb(&mut self, other: SceneMemory) {
+        self.scene.resize(self.scene.bytes() + other.scene.bytes());
+        for ramp in other.ramps {
+            if !self.ramps.contains(&ramp) {
+                self.ramps.push(ramp);
+            }
+        }
+        self.gradients.resize(self.ramps.len() as u64 * RAMP_BYTES);
+    }
+}
+
+impl Default for SceneMemory {
+    fn default() -> Self {
+        Self {
+            scene: GpuAllocation::new(GpuCategory::Scene, 0),
+            gradients: GpuAllocation::new(GpuCategory::Gradients, 0),
+            ramps: Vec::new(),
+        }
+    }
+}
+
+/// A tag byte, then each point as a pair of `f32`s.
+fn element_bytes(el: &PathEl) -> u64 {
+    let points = match el {
+        PathEl::MoveTo(_) | PathEl::LineTo(_) => 1,
+        PathEl::QuadTo(..) => 2,
+        PathEl::CurveTo(..) => 3,
+        PathEl::ClosePath => 0,
+    };
+    1 + points * 8
+}
+
+fn ramp(gradient: &Gradient) -> Vec<(u32, u32)> {
+    gradient
+        .stops
+        .iter()
+        .map(|stop| {
+            let color = stop.color;
+            let rgba = u32::from_be_bytes([color.r, color.g, color.b, color.a]);
+            (stop.offset.to_bits(), rgba)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use vello::peniko::Color;
+
+    use super::*;
+
+    struct Image {
+        texture: RefCell<Option<GpuAllocation>>,
+        evictions: Cell<usize>,
+    }
+
+    impl Image {
+        fn drawn(bytes: u64, owner: usize) -> Rc<Self> {
+            let image = Rc::new(Self {
+                texture: RefCell::new(None),
+                evictions: Cell::new(0),
+            });
+            image.draw(bytes, owner);
+            image
+        }
+
+        fn draw(self: &Rc<Self>, bytes: u64, owner: usize) {
+            *self.texture.borrow_mut() = Some(GpuAllocation::new(GpuCategory::Images, bytes));
+            let image: Rc<dyn Evict> = self.clone();
+            touch_image(Rc::downgrade(&image), bytes, owner);
+        }
+    }
+
+    impl Evict for Image {
+        fn evict(&self) {
+            self.texture.borrow_mut().take();
+            self.evictions.set(self.evictions.get() + 1);
+        }
+    }
+
+    #[test]
+    fn allocations_are_counted_until_dropped() {
+        let mut target = GpuAllocation::texture(GpuCategory::Targets, 10, 10);
+        let readback = GpuAllocation::new(GpuCategory::Intermediates, 64);
+        assert_eq!(gpu_memory_report().targets, 400);
+        assert_eq!(gpu_memory_report().total(), 464);
+
+        target.resize(100);
+        assert_eq!(gpu_memory_report().targets, 100);
+
+        drop((target, readback));
+        assert_eq!(gpu_memory_report(), GpuMemoryReport::default());
+    }
+
+    #[test]
+    fn accounting_belongs_to_the_allocating_thread() {
+        set_gpu_image_budget(Some(100));
+        let target = GpuAllocation::texture(GpuCategory::Targets, 10, 10);
+
+        std::thread::spawn(move || {
+            assert_eq!(gpu_memory_report(), GpuMemoryReport::default());
+            assert_eq!(gpu_image_budget(), None);
+            drop(target);
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(gpu_memory_report(), GpuMemoryReport::default());
+        assert_eq!(gpu_image_budget(), Some(100));
+    }
+
+    #[test]
+    fn least_recently_drawn_images_are_evicted_past_the_budget() {
+        set_gpu_image_budget(Some(300));
+        let a = Image::drawn(100, 0);
+        let b = Image::drawn(100, 0);
+        let c = Image::drawn(100, 0);
+        a.draw(100, 0);
+        assert_eq!(gpu_memory_report().images, 300);
+
+        let d = Image::drawn(100, 0);
+        assert_eq!(b.evictions.get(), 1);
+        assert_eq!(a.evictions.get() + c.evictions.get() + d.evictions.get(), 0);
+        assert_eq!(gpu_memory_report().images, 300);
+
+        set_gpu_image_budget(Some(100));
+        assert_eq!((c.evictions.get(), a.evictions.get()), (1, 1));
+        assert_eq!(d.evictions.get(), 0);
+        assert_eq!(gpu_memory_report().images, 100);
+    }
+
+    #[test]
+    fn images_are_evicted_by_owner() {
+        let first = Image::drawn(100, 1);
+        let second = Image::drawn(100, 2);
+
+        evict_images(1);
+        assert_eq!(first.evictions.get(), 1);
+        assert_eq!(second.evictions.get(), 0);
+        assert_eq!(gpu_memory_report().images, 100);
+    }
+
+    #[test]
+    fn ramps_are_counted_once_per_gradient() {
+        let gradient = |color| {
+            Brush::Gradient(
+                Gradient::new_linear((0.0, 0.0), (1.0, 0.0))
+                    .with_stops([Color::BLACK, color].as_slice()),
+            )
+        };
+        let line = [
+            PathEl::MoveTo((0.0, 0.0).into()),
+            PathEl::LineTo((1.0, 1.0).into()),
+        ];
+
+        let mut memory = SceneMemory::default();
+        memory.record_draw(line, Some(&gradient(Color::WHITE)));
+        memory.record_draw(line, Some(&gradient(Color::WHITE)));
+        assert_eq!(gpu_memory_report().gradients, RAMP_BYTES);
+        assert_eq!(gpu_memory_report().scene, 2 * (DRAW_BYTES + 18));
+
+        let mut other = SceneMemory::default();
+        other.record_draw(line, Some(&gradient(Color::RED)));
+        memory.absorb(other);
+        assert_eq!(gpu_memory_report().gradients, 2 * RAMP_BYTES);
+
+        drop(memory);
+        assert_eq!(gpu_memory_report(), GpuMemoryReport::default());
+    }
+}
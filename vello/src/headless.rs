@@ -0,0 +1,335 @@
+use std::{fmt, sync::Arc};
+
+use vello::peniko::Color;
+use wgpu::{Device, Queue};
+
+use crate::{
+    background::Substeps,
+    capture::{CaptureError, Rasterizer, ScaledOutput},
+    device::{self, DeviceError, RenderTier},
+    extract::ExtractError,
+    status::SceneStatus,
+    VelloRenderer, ViewerContent,
+};
+
+/// Why a headless render failed.
+#[derive(Debug)]
+pub enum RenderError {
+    /// Width or height is 0.
+    ZeroSize {
+        width: u32,
+        height: u32,
+    },
+    /// `time` is NaN or infinite.
+    InvalidTime(f64),
+    /// The content already played past `time`, which only a fresh content can go back to.
+    Rewound {
+        time: f64,
+        played: f64,
+    },
+    Unavailable(SceneStatus),
+    Device(DeviceError),
+    Capture(CaptureError),
+    Extract(ExtractError),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroSize { width, height } => {
+                write!(
+                    f,
+                    "can't render to {width}x{height}, both sides must be at least 1"
+                )
+            }
+            Self::InvalidTime(time) => write!(f, "can't render {time}s in, times must be finite"),
+            Self::Rewound { time, played } => {
+                write!(
+                    f,
+                    "can't render {time}s in, the content already played {played}s"
+                )
+            }
+            Self::Unavailable(status) => write!(f, "scene is {status}"),
+            Self::Device(error) => write!(f, "{error}"),
+            Self::Capture(error) => write!(f, "{error}"),
+            Self::Extract(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// Renders contents to RGBA images without a window, e.g. to compare frames against golden
+/// images in CI. The device and the offscreen target are kept across renders, so rendering
+/// many frames only pays for the frames.
+pub struct HeadlessRenderer {
+    rasterizer: Rasterizer,
+    tier: RenderTier,
+    background: Color,
+}
+
+impl HeadlessRenderer {
+    /// Requests a device of its own like the viewer does, down to wgpu's software fallback
+    /// adapter on machines without a GPU.
+    pub fn new() -> Result<Self, DeviceError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let gpu = pollster::block_on(device::request_device(&instance, None))?;
+
+        Ok(Self::with_device(gpu.device, gpu.queue, gpu.tier))
+    }
+
+    /// Renders on a device the host already has, requested as `Viewer::new` expects.
+    pub fn with_device(device: Arc<Device>, queue: Arc<Queue>, tier: RenderTier) -> Self {
+        Self {
+            rasterizer: Rasterizer::new(device, queue),
+            tier,
+            background: Color::TRANSPARENT,
+        }
+    }
+
+    /// The tier of the device, `RenderTier::Software` on the fallback adapter.
+    pub fn tier(&self) -> RenderTier {
+        self.tier
+    }
+
+    /// Color behind the content. Transparent by default.
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color;
+    }
+
+    /// Plays `content` up to `time` seconds after it started, see `ViewerContent::played`, and
+    /// renders it fitted to `width` by `height` pixels, the way the viewer draws a frame of
+    /// that size. Times have to increase from one call to the next for the same content.
+    pub fn render(
+        &mut self,
+        content: &mut ViewerContent,
+        width: u32,
+        height: u32,
+        time: f64,
+    ) -> Result<image::RgbaImage, RenderError> {
+        let last = play(content, width, height, time)?;
+        let mut renderer = VelloRenderer::default();
+        content
+            .handle_draw(&mut renderer, last)
+            .map_err(|unavailable| RenderError::Unavailable(unavailable.0))?;
+        renderer.end_frame();
+
+        let capture = self
+            .rasterizer
+            .render(
+                &renderer.scene,
+                self.background,
+                width,
+                height,
+                1,
+                ScaledOutput::Downsampled,
+            )
+            .map_err(RenderError::Capture)?;
+
+        Ok(capture.into_image())
+    }
+
+    /// Plays `content` up to `time` like `render`, then renders each of `names` alone, see
+    /// `ViewerContent::rasterize_components`. Components are always on a transparent
+    /// background.
+    pub fn render_components<S: AsRef<str>>(
+        &mut self,
+        content: &mut ViewerContent,
+        names: &[S],
+        (width, height): (u32, u32),
+        time: f64,
+        padding: f32,
+        ancestor_effects: bool,
+    ) -> Result<Vec<image::RgbaImage>, RenderError> {
+        let last = play(content, width, height, time)?;
+        content
+            .advance(last)
+            .map_err(|unavailable| RenderError::Unavailable(unavailable.0))?;
+
+        content
+            .rasterize_components(
+                &mut self.rasterizer,
+                names,
+                (width, height),
+                padding,
+                ancestor_effects,
+            )
+            .map_err(RenderError::Extract)
+    }
+}
+
+/// Advances `content` in steps no larger than the viewer's up to `time`, and returns the last
+/// step for the caller to take, e.g. while drawing. The first frame has a last step of 0,
+/// which still poses the content.
+fn play(
+    content: &mut ViewerContent,
+    width: u32,
+    height: u32,
+    time: f64,
+) -> Result<f64, RenderError> {
+    check_size(width, height)?;
+    if !time.is_finite() {
+        return Err(RenderError::InvalidTime(time));
+    }
+    let played = content.played();
+    if time < played {
+        return Err(RenderError::Rewound { time, played });
+    }
+
+    content.handle_resize(width, height);
+    let mut steps: Vec<f64> = Substeps::new(time - played).collect();
+    let last = steps.pop().unwrap_or(0.0);
+    for step in steps {
+        content
+            .advance(step)
+            .map_err(|unavailable| RenderError::Unavailable(unavailable.0))?;
+    }
+    Ok(last)
+}
+
+fn check_size(width: u32, height: u32) -> Result<(), RenderError> {
+    if width == 0 || height == 0 {
+        return Err(RenderError::ZeroSize { width, height });
+    }
+    Ok(())
+}
+
+/// Renders a single frame of `content` with a `HeadlessRenderer` of its own. Prefer keeping a
+/// `HeadlessRenderer` around to render several frames, since this requests a device each
+/// time.
+pub fn render_to_image(
+    content: &mut ViewerContent,
+    width: u32,
+    height: u32,
+    time: f64,
+) -> Result<image::RgbaImage, RenderError> {
+    check_size(width, height)?;
+    let mut renderer = HeadlessRenderer::new().map_err(RenderError::Device)?;
+    renderer.render(content, width, height, time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextDirection;
+
+    fn renderer() -> HeadlessRenderer {
+        let gpu = crate::device::test_device();
+        HeadlessRenderer::with_device(gpu.device, gpu.queue, gpu.tier)
+    }
+
+    #[test]
+    fn zero_sizes_fail_before_requesting_a_device() {
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+
+        for (width, height) in [(0, 10), (10, 0), (0, 0)] {
+            assert!(matches!(
+                render_to_image(&mut content, width, height, 0.0),
+                Err(RenderError::ZeroSize { .. })
+            ));
+        }
+        assert_eq!(content.played(), 0.0);
+    }
+
+    #[test]
+    fn renders_frames_in_order_on_one_device() {
+        let mut renderer = renderer();
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+
+        let first = renderer.render(&mut content, 64, 48, 0.0).unwrap();
+        assert_eq!(first.dimensions(), (64, 48));
+        // The artboard's background, a dark grey, is fitted to the middle of the image.
+        assert_eq!(first.get_pixel(32, 24).0[3], 255);
+
+        let later = renderer.render(&mut content, 64, 48, 0.5).unwrap();
+        assert!((content.played() - 0.5).abs() < 1e-9);
+        assert_eq!(later.dimensions(), (64, 48));
+        assert_eq!(later.get_pixel(32, 24).0[3], 255);
+        // Half a second into the 20 frame ping-pong, the stroke and fill have moved on.
+        assert_ne!(later, first);
+
+        assert!(matches!(
+            renderer.render(&mut content, 64, 48, 0.25),
+            Err(RenderError::Rewound { .. })
+        ));
+    }
+
+    #[test]
+    fn non_finite_times_fail_before_playing() {
+        let mut renderer = renderer();
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+
+        for time in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                renderer.render(&mut content, 64, 48, time),
+                Err(RenderError::InvalidTime(_))
+            ));
+        }
+        assert_eq!(content.played(), 0.0);
+    }
+
+    /// Spans of the columns with text in them, as their first column and width. The text is
+    /// cyan over a dark grey background.
+    fn ink_spans(image: &image::RgbaImage) -> Vec<(u32, u32)> {
+        let mut spans: Vec<(u32, u32)> = Vec::new();
+        for x in 0..image.width() {
+            let inked = (0..image.height()).any(|y| {
+                let [r, _, b, _] = image.get_pixel(x, y).0;
+                b > 128 && r < 64
+            });
+            match spans.last_mut() {
+                Some((start, width)) if inked && *start + *width == x => *width += 1,
+                _ if inked => spans.push((x, 1)),
+                _ => {}
+            }
+        }
+        spans
+    }
+
+    #[test]
+    fn right_to_left_text_leads_with_its_punctuation() {
+        let mut renderer = renderer();
+        let mut render = |direction| {
+            let mut content = ViewerContent::new("../test/assets/hello_world.riv").unwrap();
+            assert_eq!(content.set_text_direction(direction), Ok(1));
+            renderer.render(&mut content, 250, 250, 0.0).unwrap()
+        };
+
+        let ltr = ink_spans(&render(TextDirection::Ltr));
+        let rtl = ink_spans(&render(TextDirection::Rtl));
+        // The trailing "!" takes the paragraph's direction and moves in front of the words,
+        // which keep their order.
+        let (bang, words) = ltr.split_last().unwrap();
+        let expected = std::iter::once(bang).chain(words);
+        assert_eq!(rtl.len(), ltr.len(), "{ltr:?} against {rtl:?}");
+        for (&(_, actual), &(_, expected)) in rtl.iter().zip(expected) {
+            // Glyphs may land on other subpixel offsets.
+            assert!(actual.abs_diff(expected) <= 1, "{ltr:?} against {rtl:?}");
+        }
+        assert!(rtl[0].0 < bang.0);
+    }
+
+    #[test]
+    fn mixed_direction_text_takes_its_first_strong_direction() {
+        // "مرحبا بكم في Rive!" in IBM Plex Sans Arabic, loaded out of band. The Latin brand
+        // name keeps its own direction inside the Arabic sentence.
+        let mut renderer = renderer();
+        let mut render = |direction| {
+            let bytes = std::fs::read("../test/assets/mixed_direction.riv").unwrap();
+            let mut content = ViewerContent::from_bytes_with_assets(&bytes, |_| {
+                std::fs::read("../test/assets/IBMPlexSansArabic-Regular.ttf")
+                    .map_err(|error| error.to_string())
+            })
+            .unwrap();
+            assert!(content.missing_assets().is_empty());
+            assert_eq!(content.set_text_direction(direction), Ok(1));
+            renderer.render(&mut content, 250, 250, 0.0).unwrap()
+        };
+
+        let auto = render(TextDirection::Auto);
+        assert!(!ink_spans(&auto).is_empty());
+        // The first strong character is Arabic, so the paragraph runs right to left.
+        assert_eq!(auto, render(TextDirection::Rtl));
+        assert_ne!(auto, render(TextDirection::Ltr));
+    }
+}
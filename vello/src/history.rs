@@ -0,0 +1,282 @@
+use std::mem;
+
+use vello::peniko::Color;
+
+use crate::{
+    from_bgra8,
+    snapshot::{InputSnapshot, LayerSnapshot},
+    viewer::FiredEvent,
+};
+
+/// Rings estimated to grow past this many bytes make `ViewerContent` emit
+/// `ViewerEvent::LargeHistory`.
+pub const HISTORY_WARNING_BYTES: usize = 64 << 20;
+
+/// A property which the artboard's animations key on a watched component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchedProperty {
+    /// Name of the component, or of its closest named ancestor for unnamed ones like vertices.
+    pub component: String,
+    /// The runtime's key for the property, e.g. 13 for a node's x.
+    pub property_key: u32,
+    value_type: i32,
+}
+
+impl WatchedProperty {
+    /// `value_type` is the C++ `WatchedPropertyInfo::type`.
+    pub(crate) fn new(component: String, property_key: u32, value_type: i32) -> Self {
+        Self {
+            component,
+            property_key,
+            value_type,
+        }
+    }
+
+    fn value(&self, bits: u32) -> PropertyValue {
+        match self.value_type {
+            0 => PropertyValue::Number(f32::from_bits(bits)),
+            1 => PropertyValue::Bool(bits != 0),
+            2 => PropertyValue::Color(from_bgra8(bits)),
+            _ => PropertyValue::Uint(bits),
+        }
+    }
+}
+
+/// The value a watched property had when its frame was recorded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PropertyValue {
+    Number(f32),
+    Bool(bool),
+    Color(Color),
+    /// Ids, e.g. of the component a solo shows.
+    Uint(u32),
+}
+
+impl PropertyValue {
+    pub(crate) fn bits(self) -> u32 {
+        match self {
+            Self::Number(value) => value.to_bits(),
+            Self::Bool(value) => u32::from(value),
+            Self::Color(color) => u32::from_be_bytes([color.a, color.r, color.g, color.b]),
+            Self::Uint(value) => value,
+        }
+    }
+}
+
+/// What one advance of a `ViewerContent` with history enabled left behind.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameRecord {
+    /// Advances recorded since history was enabled, counting from 0.
+    pub frame: u64,
+    /// Seconds the content was advanced by to reach this frame.
+    pub elapsed: f64,
+    /// State of each layer of the active state machine. Empty for linear animations.
+    pub layers: Vec<LayerSnapshot>,
+    /// Number and boolean inputs of the active state machine.
+    pub inputs: Vec<InputSnapshot>,
+    /// Events the advance built, as `ViewerContent::events_this_frame` listed them.
+    pub events: Vec<FiredEvent>,
+    /// Values of `ViewerContent::watched_properties`, in the same order.
+    pub values: Vec<PropertyValue>,
+}
+
+/// Ring of the last frames' records. Once full, the oldest record is reused for the next
+/// frame, so memory stays bounded by the ring's size and the watch list.
+#[derive(Debug, Default)]
+pub(crate) struct History {
+    capacity: usize,
+    records: Vec<FrameRecord>,
+    next_frame: u64,
+    watched: Vec<WatchedProperty>,
+    /// Index of the record on display instead of the live pose.
+    shown: Option<usize>,
+    /// Values as read from or written to the content.
+    bits: Vec<u32>,
+}
+
+impl History {
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Keeps the last `capacity` records, dropping older ones. 0 drops them all.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        let excess = self.records.len().saturating_sub(capacity);
+        self.records.drain(..excess);
+        self.records.shrink_to(capacity);
+        self.shown = None;
+    }
+
+    pub fn records(&self) -> &[FrameRecord] {
+        &self.records
+    }
+
+    pub fn watched(&self) -> &[WatchedProperty] {
+        &self.watched
+    }
+
+    /// Replaces the watch list. Records of the previous one are dropped, since their values
+    /// no longer line up with it.
+    pub fn set_watched(&mut self, watched: Vec<WatchedProperty>) {
+        self.watched = watched;
+        self.records.clear();
+        self.shown = None;
+    }
+
+    pub fn shown(&self) -> Option<usize> {
+        self.shown
+    }
+
+    pub fn show_live(&mut self) {
+        self.shown = None;
+    }
+
+    /// Upper estimate of the bytes the ring holds once full, without the layers, inputs and
+    /// events, which are usually few.
+    pub fn estimated_bytes(&self) -> usize {
+        let per_frame =
+            mem::size_of::<FrameRecord>() + self.watched.len() * mem::size_of::<PropertyValue>();
+        self.capacity * per_frame
+    }
+
+    /// Records a frame, with the watched values `read` writes. Does nothing while history is
+    /// disabled.
+    pub fn push(
+        &mut self,
+        elapsed: f64,
+        layers: Vec<LayerSnapshot>,
+        inputs: Vec<InputSnapshot>,
+        events: &[FiredEvent],
+        read: impl FnOnce(&mut [u32]),
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.records.len() < self.capacity {
+            self.records.push(FrameRecord::default());
+        } else {
+            self.records.rotate_left(1);
+        }
+        let record = self.records.last_mut().unwrap();
+
+        record.frame = self.next_frame;
+        record.elapsed = elapsed;
+        record.layers = layers;
+        record.inputs = inputs;
+        record.events.clear();
+        record.events.extend_from_slice(events);
+
+        self.bits.clear();
+        self.bits.resize(self.watched.len(), 0);
+        read(&mut self.bits);
+        record.values.clear();
+        record.values.extend(
+            self.watched
+                .iter()
+                .zip(&self.bits)
+                .map(|(property, &bits)| property.value(bits)),
+        );
+
+        self.next_frame += 1;
+        // Advancing puts the live pose back on display.
+        self.shown = None;
+    }
+
+    /// Passes the values of the record at `index` to `write` and remembers it's on display.
+    /// Returns `false` if there's no such record.
+    pub fn show(&mut self, index: usize, write: impl FnOnce(&[u32])) -> bool {
+        let Some(record) = self.records.get(index) else {
+            return false;
+        };
+
+        self.bits.clear();
+        self.bits
+            .extend(record.values.iter().map(|value| value.bits()));
+        write(&self.bits);
+        self.shown = Some(index);
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watched(value_type: i32) -> WatchedProperty {
+        WatchedProperty {
+            component: "face".to_string(),
+            property_key: 13,
+            value_type,
+        }
+    }
+
+    fn push(history: &mut History, bits: u32) {
+        history.push(1.0 / 60.0, Vec::new(), Vec::new(), &[], |values| {
+            values.fill(bits)
+        });
+    }
+
+    #[test]
+    fn the_ring_keeps_the_last_frames() {
+        let mut history = History::default();
+        history.set_watched(vec![watched(3)]);
+        push(&mut history, 0);
+        assert!(history.records().is_empty());
+
+        history.set_capacity(4);
+        for bits in 0..10 {
+            push(&mut history, bits);
+        }
+
+        let frames: Vec<_> = history
+            .records()
+            .iter()
+            .map(|record| record.frame)
+            .collect();
+        assert_eq!(frames, [6, 7, 8, 9]);
+        assert_eq!(history.records()[3].values, [PropertyValue::Uint(9)]);
+
+        history.set_capacity(2);
+        let frames: Vec<_> = history
+            .records()
+            .iter()
+            .map(|record| record.frame)
+            .collect();
+        assert_eq!(frames, [8, 9]);
+    }
+
+    #[test]
+    fn shown_values_are_the_recorded_bits() {
+        let mut history = History::default();
+        history.set_capacity(2);
+        history.set_watched(vec![watched(0), watched(1), watched(2)]);
+
+        let color = Color::rgba8(0x12, 0x34, 0x56, 0x78);
+        let recorded = [1.5f32.to_bits(), 1, 0x78123456];
+        history.push(0.0, Vec::new(), Vec::new(), &[], |values| {
+            values.copy_from_slice(&recorded)
+        });
+        assert_eq!(
+            history.records()[0].values,
+            [
+                PropertyValue::Number(1.5),
+                PropertyValue::Bool(true),
+                PropertyValue::Color(color),
+            ]
+        );
+
+        let mut shown = Vec::new();
+        assert!(history.show(0, |values| shown.extend_from_slice(values)));
+        assert_eq!(shown, recorded);
+        assert_eq!(history.shown(), Some(0));
+        assert!(!history.show(1, |_| unreachable!()));
+
+        // Values of another watch list wouldn't line up.
+        history.set_watched(vec![watched(0)]);
+        assert!(history.records().is_empty());
+        assert_eq!(history.shown(), None);
+    }
+}
@@ -0,0 +1,30 @@
+/// Snaps `value` to the nearest of `values`, preferring the lower one on ties. Values are passed
+/// through unchanged when there are none to snap to.
+pub(crate) fn quantize(values: &[f32], value: f32) -> f32 {
+    values
+        .iter()
+        .copied()
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snaps_to_the_nearest_value() {
+        let values = [-1.0, 0.0, 2.5];
+
+        assert_eq!(quantize(&values, -7.0), -1.0);
+        assert_eq!(quantize(&values, 0.4), 0.0);
+        assert_eq!(quantize(&values, 1.25), 0.0);
+        assert_eq!(quantize(&values, 1.3), 2.5);
+        assert_eq!(quantize(&values, 40.0), 2.5);
+    }
+
+    #[test]
+    fn continuous_inputs_pass_through() {
+        assert_eq!(quantize(&[], 1.000_000_1), 1.000_000_1);
+    }
+}
@@ -1,25 +1,105 @@
 #![allow(clippy::missing_safety_doc)]
 
 use core::slice;
-use std::{io::Cursor, ptr::NonNull};
+use std::{
+    borrow::Cow,
+    ffi::{c_char, CStr},
+    ptr::NonNull,
+};
 
-use image::io::Reader;
+use gpu_memory::SceneMemory;
+use live::{Kind, Live};
+use paint::{CustomPaints, MissingAssetStyle};
+use raster::VelloImage;
 use smallvec::SmallVec;
-use util::{ScaleFromOrigin, UnwrapAndDeref};
+use util::UnwrapAndDeref;
 use vello::{
-    kurbo::{Affine, BezPath, Line, PathSeg, Point, Rect, Shape, Vec2},
+    kurbo::{Affine, BezPath, Line, PathEl, PathSeg, Point, Rect, Shape, Size, Vec2},
     peniko::{
-        BlendMode, Brush, BrushRef, Cap, Color, ColorStop, ColorStopsSource, Fill, Format,
-        Gradient, Image, Join, Mix, Stroke,
+        BlendMode, Brush, Cap, Color, ColorStop, ColorStopsSource, Fill, Gradient, Join, Mix,
+        Stroke,
     },
     SceneBuilder, SceneFragment,
 };
 
+mod assets;
+mod background;
+mod blit;
+mod capture;
+mod contract;
+mod device;
+mod embed;
+mod extract;
+mod gesture;
+mod gpu_memory;
+mod headless;
+mod history;
+mod input;
+mod live;
+mod loader;
+mod paint;
+mod playback;
+mod quality;
+mod raster;
+mod review;
 mod rive;
+mod select;
+mod snapshot;
+mod status;
+mod target;
+mod text;
+mod transform;
 mod util;
 mod viewer;
 
-pub use viewer::ViewerContent;
+pub use assets::{AssetFallback, AssetKind, MissingAsset, RebindError};
+pub use background::{BackgroundAdvance, Substeps, Throttle, MAX_STEP};
+pub use blit::Blitter;
+pub use capture::{Capture, CaptureError, Rasterizer, ScaledOutput};
+pub use contract::ContractError;
+pub use device::{
+    create_renderer, probe_limits, reduced_limits, request_device, required_features,
+    required_limits, validate_device, DeviceError, MissingLimit, RenderTier, TieredDevice,
+};
+pub use embed::Viewer;
+pub use extract::ExtractError;
+pub use gesture::{
+    PointerCapture, ScrubBinding, AUTO_SCRUB_INPUTS, AUTO_SCRUB_RANGE, DRAG_THRESHOLD,
+};
+pub use gpu_memory::{
+    gpu_image_budget, gpu_memory_report, set_gpu_image_budget, GpuAllocation, GpuCategory,
+    GpuMemoryReport,
+};
+pub use headless::{render_to_image, HeadlessRenderer, RenderError};
+pub use history::{FrameRecord, PropertyValue, WatchedProperty, HISTORY_WARNING_BYTES};
+pub use live::LiveCounts;
+pub use loader::{read_file_cancellable, LoadHandle, Loader};
+pub use paint::{Checkerboard, CustomPaintProvider, CustomPaints, MissingAssetStyle};
+pub use quality::{QualityGovernor, QualityLevel};
+pub use raster::{DownscaleFilter, ImageStats};
+pub use review::{
+    image_path, match_files, perceptual_diff, review_file, ArtboardReview, FileReview, FrameReview,
+    MatchedNames, PixelDiff, ReviewError, ReviewOutcome, ReviewReport, REVIEW_TIMES,
+    THUMBNAIL_SIZE,
+};
+pub use rive_core::{
+    ContractReport, InputContract, InputDescriptor, InputType, InteractionContract, RangeMismatch,
+    TypeMismatch,
+};
+pub use select::SelectError;
+pub use snapshot::{
+    InputSnapshot, InputValue, LayerSnapshot, RestoreError, RestoreMode, RestoreReport,
+    StateSnapshot, SNAPSHOT_SCHEMA,
+};
+pub use status::{SceneStatus, SceneUnavailable};
+pub use target::{TextureTarget, TARGET_FORMAT};
+pub use text::{TextApplyReport, TextDirection};
+pub use transform::{Camera, Orientation, TransformStack};
+pub use viewer::{
+    AdvancePhase, AppliedAnimation, ArtboardMut, ArtboardView, DeferredMutations, EventProperty,
+    EventRef, FiredEvent, Fit, NodeTransform, PointerAction, PointerButton, RedrawPolicy,
+    ViewerContent, ViewerEvent,
+};
 
 fn from_bgra8(color: u32) -> Color {
     Color::rgba8(
@@ -30,6 +110,30 @@ fn from_bgra8(color: u32) -> Color {
     )
 }
 
+/// Parses a CSS-style hex color: `#rgb`, `#rrggbb` or `#rrggbbaa`, the `#` being optional.
+pub fn parse_color(s: &str) -> Result<Color, String> {
+    let hex = s.trim().trim_start_matches('#');
+    let digits = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<_>>>();
+    let channel = |pair: &[u8]| pair[0] * 16 + pair[1];
+
+    match digits.as_deref() {
+        Some(&[r, g, b]) => Ok(Color::rgb8(r * 17, g * 17, b * 17)),
+        Some(digits) if digits.len() == 6 || digits.len() == 8 => {
+            let channels: Vec<_> = digits.chunks(2).map(channel).collect();
+            Ok(Color::rgba8(
+                channels[0],
+                channels[1],
+                channels[2],
+                channels.get(3).copied().unwrap_or(255),
+            ))
+        }
+        _ => Err(format!("expected a color like `#ff8800`, got `{s}`")),
+    }
+}
+
 #[derive(Debug)]
 enum RenderStyle {
     Fill,
@@ -100,29 +204,42 @@ pub unsafe extern "C" fn vello_gradient_release(gradient: Option<NonNull<Gradien
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn vello_image_new(data: *const u8, len: usize) -> Option<NonNull<Image>> {
-    let image = Reader::new(Cursor::new(slice::from_raw_parts(data, len)))
-        .with_guessed_format()
-        .ok()?
-        .decode()
-        .ok()?
-        .into_rgba8();
-    let width = image.width();
-    let height = image.height();
+pub unsafe extern "C" fn vello_image_new(
+    data: *const u8,
+    len: usize,
+) -> Option<NonNull<VelloImage>> {
+    let image = VelloImage::new(slice::from_raw_parts(data, len))?;
+    NonNull::new(Box::into_raw(Box::new(image)))
+}
 
-    NonNull::new(Box::into_raw(Box::new(Image::new(
-        image.into_raw().into(),
-        Format::Rgba8,
-        width,
-        height,
-    ))))
+#[no_mangle]
+pub unsafe extern "C" fn vello_image_new_missing(
+    width: u32,
+    height: u32,
+) -> Option<NonNull<VelloImage>> {
+    NonNull::new(Box::into_raw(Box::new(VelloImage::missing(width, height))))
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn vello_image_release(image: Option<NonNull<Image>>) {
+pub unsafe extern "C" fn vello_image_release(image: Option<NonNull<VelloImage>>) {
     image.map(|ptr| Box::from_raw(ptr.as_ptr())).unwrap();
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn vello_images_link_variants(
+    images: *const Option<NonNull<VelloImage>>,
+    names: *const *const c_char,
+    len: usize,
+) {
+    let images = slice::from_raw_parts(images, len);
+    let names = slice::from_raw_parts(names, len);
+
+    raster::link_variants(images.iter().zip(names).filter_map(|(&image, &name)| {
+        let name = CStr::from_ptr(name).to_str().ok()?;
+        Some((&mut *image?.as_ptr(), name))
+    }));
+}
+
 #[derive(Debug)]
 pub struct VelloPaint {
     style: RenderStyle,
@@ -252,6 +369,105 @@ impl Default for VelloPath {
     }
 }
 
+impl VelloPath {
+    fn move_to(&mut self, p: Point) {
+        // Only the last of consecutive moves starts a contour.
+        if let Some(PathEl::MoveTo(_)) = self.path.elements().last() {
+            self.path.truncate(self.path.elements().len() - 1);
+        }
+
+        self.path.move_to(p);
+    }
+
+    fn line_to(&mut self, p: Point) {
+        self.start_contour();
+        self.path.line_to(p);
+    }
+
+    fn cubic_to(&mut self, p1: Point, p2: Point, p3: Point) {
+        self.start_contour();
+
+        // Segments collapsed to a point have no tangent and break trimming downstream.
+        if self.current_point() == Some(p1) && p1 == p2 && p2 == p3 {
+            return;
+        }
+
+        self.path.curve_to(p1, p2, p3);
+    }
+
+    fn close(&mut self) {
+        if self.current_point().is_some() {
+            self.path.close_path();
+        }
+    }
+
+    fn extend(&mut self, path: &BezPath) {
+        for el in path.elements() {
+            match *el {
+                PathEl::MoveTo(p) => self.move_to(p),
+                PathEl::LineTo(p) => self.line_to(p),
+                PathEl::QuadTo(p1, p2) => {
+                    self.start_contour();
+                    self.path.quad_to(p1, p2);
+                }
+                PathEl::CurveTo(p1, p2, p3) => self.cubic_to(p1, p2, p3),
+                PathEl::ClosePath => self.close(),
+            }
+        }
+    }
+
+    /// End of the last segment, or `None` if there's no open contour.
+    fn current_point(&self) -> Option<Point> {
+        self.path.elements().last()?.end_point()
+    }
+
+    fn contour_start(&self) -> Option<Point> {
+        self.path.elements().iter().rev().find_map(|el| match el {
+            PathEl::MoveTo(p) => Some(*p),
+            _ => None,
+        })
+    }
+
+    /// Drawing after a close (or on an empty path) continues from the closed contour's start,
+    /// in a new contour, instead of connecting to it.
+    fn start_contour(&mut self) {
+        if self.current_point().is_none() {
+            let start = self.contour_start().unwrap_or_default();
+            self.path.move_to(start);
+        }
+    }
+
+    /// The path with every open contour closed, as fills need. Strokes use the path as is.
+    fn fill_path(&self) -> Cow<'_, BezPath> {
+        let elements = self.path.elements();
+        let is_open =
+            |i: usize| i > 0 && !matches!(elements[i - 1], PathEl::ClosePath | PathEl::MoveTo(_));
+
+        let needs_closing = elements
+            .iter()
+            .enumerate()
+            .any(|(i, el)| matches!(el, PathEl::MoveTo(_)) && is_open(i))
+            || is_open(elements.len());
+
+        if !needs_closing {
+            return Cow::Borrowed(&self.path);
+        }
+
+        let mut path = BezPath::new();
+        for (i, el) in elements.iter().enumerate() {
+            if matches!(el, PathEl::MoveTo(_)) && is_open(i) {
+                path.close_path();
+            }
+            path.push(*el);
+        }
+        if is_open(elements.len()) {
+            path.close_path();
+        }
+
+        Cow::Owned(path)
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn vello_path_new() -> Option<NonNull<VelloPath>> {
     NonNull::new(Box::into_raw(Box::default()))
@@ -287,22 +503,18 @@ pub unsafe extern "C" fn vello_path_extend(
     let mut from = from.unwrap_and_deref().path.clone();
     from.apply_affine(Affine::new(transform.unwrap_and_deref().map(Into::into)));
 
-    path.unwrap_and_deref()
-        .path
-        .extend(from.elements().iter().cloned());
+    path.unwrap_and_deref().extend(&from);
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn vello_path_move_to(mut path: Option<NonNull<VelloPath>>, x: f32, y: f32) {
     path.unwrap_and_deref()
-        .path
         .move_to(Point::new(x as f64, y as f64));
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn vello_path_line_to(mut path: Option<NonNull<VelloPath>>, x: f32, y: f32) {
     path.unwrap_and_deref()
-        .path
         .line_to(Point::new(x as f64, y as f64));
 }
 
@@ -316,7 +528,7 @@ pub unsafe extern "C" fn vello_path_cubic_to(
     x: f32,
     y: f32,
 ) {
-    path.unwrap_and_deref().path.curve_to(
+    path.unwrap_and_deref().cubic_to(
         Point::new(ox as f64, oy as f64),
         Point::new(ix as f64, iy as f64),
         Point::new(x as f64, y as f64),
@@ -325,17 +537,221 @@ pub unsafe extern "C" fn vello_path_cubic_to(
 
 #[no_mangle]
 pub unsafe extern "C" fn vello_path_close(mut path: Option<NonNull<VelloPath>>) {
-    path.unwrap_and_deref().path.close_path();
+    path.unwrap_and_deref().close();
+}
+
+/// Identifies a host fragment inserted with [`VelloRenderer::insert_marker`] until the next
+/// [`VelloRenderer::end_frame`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SceneFragmentHandle {
+    index: usize,
+    /// `VelloRenderer::frames` when the marker was inserted.
+    frame: u64,
+}
+
+#[derive(Debug)]
+struct OrderedLayer {
+    z: i32,
+    fragment: Box<SceneFragment>,
+}
+
+/// What was drawn during a frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    /// Every image draw, in drawing order, with the density variant it picked.
+    pub images: Vec<ImageStats>,
+    /// Key of every custom paint slot drawn, in drawing order. Slots aren't in `images`.
+    pub custom_paints: Vec<String>,
+    /// Asset name of every image drawn as a missing asset placeholder, in drawing order.
+    /// Placeholders aren't in `images`.
+    pub missing_images: Vec<String>,
+    /// Asset name of every image skipped because its pixels can't be decoded, in drawing
+    /// order.
+    pub undecodable_images: Vec<String>,
+    /// Asset name of every image mesh skipped because its vertex indices are out of bounds,
+    /// in drawing order.
+    pub broken_meshes: Vec<String>,
+    /// GPU memory of the thread as of `VelloRenderer::end_frame`, this frame's scene included.
+    pub gpu_memory: GpuMemoryReport,
 }
 
 pub struct VelloRenderer {
     pub scene: Box<SceneFragment>,
     builder: SceneBuilder<'static>,
+    placement: Affine,
     transforms: Vec<Affine>,
     clips: Vec<bool>,
+    layers: Vec<OrderedLayer>,
+    /// Frames ended so far, which tells handles of earlier frames apart.
+    frames: u64,
+    stats: RenderStats,
+    downscale_filter: DownscaleFilter,
+    custom_paints: CustomPaints,
+    missing_asset_style: MissingAssetStyle,
+    /// Passed to custom paint providers.
+    time: f64,
+    scene_memory: SceneMemory,
+    /// Content being drawn, which the images drawn are evicted with.
+    owner: usize,
+    _live: Live,
 }
 
 impl VelloRenderer {
+    /// Sets the transform at the root of the transform stack. Everything drawn afterwards is
+    /// composed with it, so a `ViewerContent` placed in world space ends up at its placement
+    /// without the host having to bake it into the `append` transform.
+    pub(crate) fn set_placement(&mut self, placement: Affine) {
+        self.placement = placement;
+        *self.transforms.first_mut().unwrap() = placement;
+    }
+
+    pub(crate) fn set_owner(&mut self, owner: usize) {
+        self.owner = owner;
+    }
+
+    /// Draws `content` into its own layer which is composited at `end_frame` in increasing `z`
+    /// order. Layers with equal `z` keep their submission order. The content's placement is
+    /// applied inside the layer, so `z` only decides stacking, never position. Unavailable
+    /// contents add no layer.
+    pub fn draw_with_order(
+        &mut self,
+        content: &mut ViewerContent,
+        elapsed: f64,
+        z: i32,
+    ) -> Result<(), SceneUnavailable> {
+        let mut renderer = VelloRenderer::default();
+        renderer.downscale_filter = self.downscale_filter;
+        renderer.custom_paints = self.custom_paints.clone();
+        renderer.missing_asset_style = self.missing_asset_style;
+        renderer.time = self.time;
+        content.handle_draw(&mut renderer, elapsed)?;
+
+        self.scene_memory.absorb(renderer.scene_memory);
+        self.layers.push(OrderedLayer {
+            z,
+            fragment: renderer.scene,
+        });
+        self.stats.images.extend(renderer.stats.images);
+        self.stats
+            .custom_paints
+            .extend(renderer.stats.custom_paints);
+        self.stats
+            .missing_images
+            .extend(renderer.stats.missing_images);
+        self.stats
+            .undecodable_images
+            .extend(renderer.stats.undecodable_images);
+        self.stats
+            .broken_meshes
+            .extend(renderer.stats.broken_meshes);
+
+        Ok(())
+    }
+
+    /// Reserves an empty layer at `z` for the host's own vello content. The fragment is
+    /// appended untransformed, i.e. in the same world space as the contents' placements.
+    pub fn insert_marker(&mut self, z: i32) -> SceneFragmentHandle {
+        self.layers.push(OrderedLayer {
+            z,
+            fragment: Box::default(),
+        });
+
+        SceneFragmentHandle {
+            index: self.layers.len() - 1,
+            frame: self.frames,
+        }
+    }
+
+    /// The fragment reserved by `insert_marker`, or `None` once the frame it was inserted in
+    /// ended.
+    pub fn fragment_mut(&mut self, handle: SceneFragmentHandle) -> Option<&mut SceneFragment> {
+        if handle.frame != self.frames {
+            return None;
+        }
+        self.layers
+            .get_mut(handle.index)
+            .map(|layer| &mut *layer.fragment)
+    }
+
+    /// Appends all ordered layers on top of what was drawn directly into the renderer.
+    pub fn end_frame(&mut self) {
+        let mut layers = std::mem::take(&mut self.layers);
+        layers.sort_by_key(|layer| layer.z);
+
+        for layer in &layers {
+            self.builder.append(&layer.fragment, None);
+        }
+        self.frames += 1;
+
+        self.stats.gpu_memory = gpu_memory_report();
+    }
+
+    pub fn stats(&self) -> &RenderStats {
+        &self.stats
+    }
+
+    pub fn downscale_filter(&self) -> DownscaleFilter {
+        self.downscale_filter
+    }
+
+    /// Sets how images drawn at less than half their size are sampled. `ImageStats::level`
+    /// reports the mip level each image was drawn with.
+    pub fn set_downscale_filter(&mut self, filter: DownscaleFilter) {
+        self.downscale_filter = filter;
+    }
+
+    /// Fills the image assets named `key`, extension aside, with `provider`'s brush. See
+    /// `CustomPaintProvider`.
+    pub fn register_custom_paint(&mut self, key: &str, provider: Box<dyn CustomPaintProvider>) {
+        self.custom_paints.register(key, provider);
+    }
+
+    /// The registered providers, to pass on to the renderers of later frames with
+    /// `set_custom_paints`.
+    pub fn custom_paints(&self) -> &CustomPaints {
+        &self.custom_paints
+    }
+
+    pub fn set_custom_paints(&mut self, custom_paints: CustomPaints) {
+        self.custom_paints = custom_paints;
+    }
+
+    pub fn missing_asset_style(&self) -> MissingAssetStyle {
+        self.missing_asset_style
+    }
+
+    /// Sets how images whose asset failed to load are drawn. `RenderStats::missing_images`
+    /// reports the placeholders drawn.
+    pub fn set_missing_asset_style(&mut self, style: MissingAssetStyle) {
+        self.missing_asset_style = style;
+    }
+
+    /// Sets the time, in seconds, custom paint providers are asked for brushes at.
+    pub fn set_time(&mut self, time: f64) {
+        self.time = time;
+    }
+
+    /// The brush drawn instead of `image`'s pixels and the size it fills: the missing asset
+    /// placeholder, or the brush of the custom paint slot `image` stands for if it has a
+    /// provider.
+    fn custom_brush(&mut self, image: &VelloImage) -> Option<(Brush, Size)> {
+        if let Some(size) = image.missing_size() {
+            self.stats.missing_images.push(image.name().to_string());
+            return Some((self.missing_asset_style.brush(size), size));
+        }
+
+        if self.custom_paints.is_empty() {
+            return None;
+        }
+
+        let placeholder = image.own_image()?;
+        let size = Size::new(placeholder.width as f64, placeholder.height as f64);
+        let (key, brush) = self.custom_paints.brush(image.name(), size, self.time)?;
+        self.stats.custom_paints.push(key);
+
+        Some((brush, size))
+    }
+
     fn last_transform(&mut self) -> &mut Affine {
         self.transforms.last_mut().unwrap()
     }
@@ -360,8 +776,19 @@ impl Default for VelloRenderer {
         Self {
             scene,
             builder,
+            placement: Affine::IDENTITY,
             transforms: vec![Affine::IDENTITY],
             clips: vec![false],
+            layers: Vec::new(),
+            frames: 0,
+            stats: RenderStats::default(),
+            downscale_filter: DownscaleFilter::default(),
+            custom_paints: CustomPaints::default(),
+            missing_asset_style: MissingAssetStyle::default(),
+            time: 0.0,
+            scene_memory: SceneMemory::default(),
+            owner: 0,
+            _live: Live::new(Kind::Scene),
         }
     }
 }
@@ -386,7 +813,7 @@ pub unsafe extern "C" fn vello_renderer_restore(mut renderer: Option<NonNull<Vel
     }
 
     if renderer.transforms.is_empty() {
-        renderer.transforms.push(Affine::IDENTITY);
+        renderer.transforms.push(renderer.placement);
         renderer.clips.push(false);
     }
 }
@@ -416,12 +843,18 @@ pub unsafe extern "C" fn vello_renderer_draw_path(
 
     let skip_blending = paint.blend_mode == Mix::Normal.into();
 
+    renderer
+        .scene_memory
+        .record_draw(path.path.elements().iter().copied(), Some(&paint.brush));
+
     if !skip_blending {
         builder.push_layer(paint.blend_mode, 1.0, transform, &path.path.bounding_box());
     }
 
     match &paint.style {
-        RenderStyle::Fill => builder.fill(path.fill, transform, &paint.brush, None, &path.path),
+        RenderStyle::Fill => {
+            builder.fill(path.fill, transform, &paint.brush, None, &*path.fill_path())
+        }
         RenderStyle::Stroke(stroke) => {
             builder.stroke(stroke, transform, &paint.brush, None, &path.path)
         }
@@ -445,9 +878,13 @@ pub unsafe extern "C" fn vello_renderer_clip_path(
         renderer.builder.pop_layer();
     }
 
+    let clip = clip.unwrap_and_deref().fill_path();
+    renderer
+        .scene_memory
+        .record_draw(clip.elements().iter().copied(), None);
     renderer
         .builder
-        .push_layer(Mix::Clip, 1.0, transform, &clip.unwrap_and_deref().path);
+        .push_layer(Mix::Clip, 1.0, transform, &*clip);
 
     *renderer.last_clip() = true;
 }
@@ -455,35 +892,77 @@ pub unsafe extern "C" fn vello_renderer_clip_path(
 #[no_mangle]
 pub unsafe extern "C" fn vello_renderer_draw_image(
     mut renderer: Option<NonNull<VelloRenderer>>,
-    mut image: Option<NonNull<Image>>,
+    mut image: Option<NonNull<VelloImage>>,
     blend_mode: rive::BlendMode,
     opacity: f32,
 ) {
     let renderer = renderer.unwrap_and_deref();
-    let image = image.unwrap_and_deref();
     let mix: Mix = blend_mode.into();
 
-    let transform = renderer.last_transform().pre_translate(Vec2::new(
-        image.width as f64 * -0.5,
-        image.height as f64 * -0.5,
-    ));
+    let transform = *renderer.last_transform();
+
+    if let Some((brush, size)) = renderer.custom_brush(image.unwrap_and_deref()) {
+        let transform = transform.pre_translate(size.to_vec2() * -0.5);
+        let rect = size.to_rect();
+        renderer
+            .scene_memory
+            .record_draw(rect.path_elements(0.1), Some(&brush));
+
+        let builder = &mut renderer.builder;
+        let skip_blending = mix == Mix::Normal && opacity == 1.0;
+
+        if !skip_blending {
+            builder.push_layer(mix, opacity, transform, &rect);
+        }
+        builder.fill(Fill::NonZero, transform, &brush, None, &rect);
+        if !skip_blending {
+            builder.pop_layer();
+        }
+
+        return;
+    }
+
+    let image = image.unwrap_and_deref();
+    let Some((image, scale, stats)) =
+        image.select(transform, renderer.downscale_filter, renderer.owner)
+    else {
+        renderer
+            .stats
+            .undecodable_images
+            .push(image.name().to_string());
+        return;
+    };
+    renderer.stats.images.push(stats);
+
+    let transform = transform
+        .pre_scale_non_uniform(scale.x, scale.y)
+        .pre_translate(Vec2::new(
+            image.width as f64 * -0.5,
+            image.height as f64 * -0.5,
+        ));
     let rect = Rect::new(0.0, 0.0, image.width as f64, image.height as f64);
+    renderer
+        .scene_memory
+        .record_draw(rect.path_elements(0.1), None);
 
     let builder = &mut renderer.builder;
 
     let skip_blending = mix == Mix::Normal && opacity == 1.0;
 
-    if skip_blending {
+    if !skip_blending {
         builder.push_layer(mix, opacity, transform, &rect);
     }
 
-    builder.draw_image(image, transform);
+    builder.draw_image(&image, transform);
 
-    if skip_blending {
+    if !skip_blending {
         builder.pop_layer();
     }
 }
 
+/// On-screen pixels by which image mesh triangles overlap their neighbors.
+const MESH_SEAM_OVERLAP: f64 = 0.5;
+
 fn triangle_path(points: [Point; 3]) -> BezPath {
     BezPath::from_path_segments(
         [
@@ -498,7 +977,7 @@ fn triangle_path(points: [Point; 3]) -> BezPath {
 #[no_mangle]
 pub unsafe extern "C" fn vello_renderer_draw_image_mesh(
     mut renderer: Option<NonNull<VelloRenderer>>,
-    mut image: Option<NonNull<Image>>,
+    mut image: Option<NonNull<VelloImage>>,
     vertices_data: *const rive::Vec2D,
     vertices_len: usize,
     uvs_data: *const rive::Vec2D,
@@ -509,7 +988,29 @@ pub unsafe extern "C" fn vello_renderer_draw_image_mesh(
     opacity: f32,
 ) {
     let renderer = renderer.unwrap_and_deref();
+
+    // The transform maps the mesh's vertices rather than the image's pixels, so it doesn't
+    // tell how small the image is drawn.
+    let transform = *renderer.last_transform();
     let image = image.unwrap_and_deref();
+    let (brush, width, height) = match renderer.custom_brush(image) {
+        Some((brush, size)) => (brush, size.width as u32, size.height as u32),
+        None => {
+            let Some((pixels, _, stats)) =
+                image.select(transform, DownscaleFilter::Naive, renderer.owner)
+            else {
+                renderer
+                    .stats
+                    .undecodable_images
+                    .push(image.name().to_string());
+                return;
+            };
+            renderer.stats.images.push(stats);
+
+            let (width, height) = (pixels.width, pixels.height);
+            (Brush::Image(pixels), width, height)
+        }
+    };
 
     let vertices = slice::from_raw_parts(vertices_data, vertices_len);
     let uvs = slice::from_raw_parts(uvs_data, uvs_len);
@@ -517,48 +1018,551 @@ pub unsafe extern "C" fn vello_renderer_draw_image_mesh(
 
     let mix: Mix = blend_mode.into();
 
+    // Each triangle is filled with the image mapped onto it, grown by `MESH_SEAM_OVERLAP`
+    // so that its neighbors' antialiased edges don't let the background through. The brush
+    // keeps mapping the untouched triangle, so the overlap samples the same pixels.
+    let mut triangles = Vec::with_capacity(indices.len() / 3);
     for triangle_indices in indices.chunks_exact(3) {
-        let points = [
-            vertices[triangle_indices[0] as usize],
-            vertices[triangle_indices[1] as usize],
-            vertices[triangle_indices[2] as usize],
+        let corner = |i: usize| {
+            let index = triangle_indices[i] as usize;
+            Some((*vertices.get(index)?, *uvs.get(index)?))
+        };
+        let (Some(a), Some(b), Some(c)) = (corner(0), corner(1), corner(2)) else {
+            renderer.stats.broken_meshes.push(image.name().to_string());
+            return;
+        };
+        let points = [a.0, b.0, c.0];
+        let triangle_uvs = [a.1, b.1, c.1];
+
+        let Some(dilated) = util::dilate_triangle(
+            points.map(|v| Point::new(v.x as f64, v.y as f64)),
+            transform,
+            MESH_SEAM_OVERLAP,
+        ) else {
+            continue;
+        };
+        let path = triangle_path(dilated);
+        let brush_transform = util::map_uvs_to_triangle(&points, &triangle_uvs, width, height);
+        renderer
+            .scene_memory
+            .record_draw(path.elements().iter().copied(), Some(&brush));
+
+        triangles.push((path, brush_transform));
+    }
+
+    let Some(bounds) = triangles
+        .iter()
+        .map(|(path, _)| path.bounding_box())
+        .reduce(|bounds, triangle| bounds.union(triangle))
+    else {
+        return;
+    };
+
+    let builder = &mut renderer.builder;
+
+    // Opacity applies to the mesh as a whole, so overlapping triangles don't blend twice.
+    let skip_blending = mix == Mix::Normal && opacity == 1.0;
+
+    if !skip_blending {
+        builder.push_layer(mix, opacity, transform, &bounds);
+    }
+
+    for (path, brush_transform) in &triangles {
+        builder.fill(
+            Fill::NonZero,
+            transform,
+            &brush,
+            Some(*brush_transform),
+            path,
+        );
+    }
+
+    if !skip_blending {
+        builder.pop_layer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use vello::kurbo::{ParamCurve, ParamCurveArclen};
+
+    #[test]
+    fn colors_parse_from_hex() {
+        assert_eq!(parse_color("#f80"), Ok(Color::rgb8(255, 136, 0)));
+        assert_eq!(parse_color("ff8800"), Ok(Color::rgb8(255, 136, 0)));
+        assert_eq!(parse_color("#ff880080"), Ok(Color::rgba8(255, 136, 0, 128)));
+        assert!(parse_color("#ff88").is_err());
+        assert!(parse_color("orange").is_err());
+    }
+
+    fn build(f: impl FnOnce(&mut VelloPath)) -> VelloPath {
+        let mut path = VelloPath::default();
+        f(&mut path);
+        path
+    }
+
+    fn count(path: &BezPath, pred: impl Fn(&PathEl) -> bool) -> usize {
+        path.elements().iter().filter(|el| pred(el)).count()
+    }
+
+    fn closed_contour(path: &mut VelloPath, corners: &[(f64, f64)]) {
+        path.move_to(corners[0].into());
+        for &corner in &corners[1..] {
+            path.line_to(corner.into());
+        }
+        path.close();
+    }
+
+    #[test]
+    fn donut_keeps_its_hole() {
+        let path = build(|path| {
+            // The hole winds the other way, as authored donuts do.
+            closed_contour(
+                path,
+                &[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)],
+            );
+            closed_contour(
+                path,
+                &[(25.0, 25.0), (25.0, 75.0), (75.0, 75.0), (75.0, 25.0)],
+            );
+        });
+
+        assert_eq!(count(&path.path, |el| matches!(el, PathEl::MoveTo(_))), 2);
+        assert_eq!(path.fill_path().as_ref(), &path.path);
+        assert!(path.fill_path().contains(Point::new(10.0, 10.0)));
+        assert!(!path.fill_path().contains(Point::new(50.0, 50.0)));
+    }
+
+    #[test]
+    fn open_contours_are_only_closed_for_fills() {
+        let path = build(|path| {
+            path.move_to(Point::new(0.0, 0.0));
+            for i in 1..6 {
+                path.line_to(Point::new(i as f64 * 10.0, (i % 2) as f64 * 10.0));
+            }
+            path.move_to(Point::new(0.0, 50.0));
+            path.line_to(Point::new(50.0, 50.0));
+            path.line_to(Point::new(50.0, 60.0));
+        });
+
+        assert_eq!(count(&path.path, |el| matches!(el, PathEl::ClosePath)), 0);
+
+        let fill = path.fill_path();
+        assert_eq!(count(&fill, |el| matches!(el, PathEl::ClosePath)), 2);
+        // No segment bridges the end of the zig-zag and the start of the second contour.
+        assert!(
+            !fill
+                .segments()
+                .any(|seg| seg.start() == Point::new(50.0, 10.0)
+                    && seg.end() == Point::new(0.0, 50.0))
+        );
+    }
+
+    #[test]
+    fn move_starts_a_new_contour() {
+        let path = build(|path| {
+            path.move_to(Point::new(0.0, 0.0));
+            path.line_to(Point::new(10.0, 0.0));
+            path.move_to(Point::new(99.0, 99.0));
+            path.move_to(Point::new(20.0, 20.0));
+            path.line_to(Point::new(30.0, 20.0));
+        });
+
+        assert_eq!(
+            path.path.elements(),
+            &[
+                PathEl::MoveTo(Point::new(0.0, 0.0)),
+                PathEl::LineTo(Point::new(10.0, 0.0)),
+                PathEl::MoveTo(Point::new(20.0, 20.0)),
+                PathEl::LineTo(Point::new(30.0, 20.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn drawing_after_close_restarts_at_contour_start() {
+        let path = build(|path| {
+            path.move_to(Point::new(5.0, 5.0));
+            path.line_to(Point::new(10.0, 5.0));
+            path.line_to(Point::new(10.0, 10.0));
+            path.close();
+            path.line_to(Point::new(0.0, 10.0));
+        });
+
+        assert_eq!(
+            &path.path.elements()[4..],
+            &[
+                PathEl::MoveTo(Point::new(5.0, 5.0)),
+                PathEl::LineTo(Point::new(0.0, 10.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_paints_replace_their_placeholder() {
+        let mut content = ViewerContent::new("../test/assets/walle.riv").unwrap();
+        content.handle_resize(100, 100);
+
+        let mut renderer = VelloRenderer::default();
+        content.handle_draw(&mut renderer, 0.0).unwrap();
+        let asset = renderer.stats().images[0].asset.clone();
+        let key = asset.rsplit_once('.').unwrap().0;
+
+        let mut renderer = VelloRenderer::default();
+        renderer.register_custom_paint(key, Box::<Checkerboard>::default());
+        content.handle_draw(&mut renderer, 0.0).unwrap();
+
+        let stats = renderer.stats();
+        assert!(stats.custom_paints.iter().any(|drawn| drawn == key));
+        assert!(stats.images.iter().all(|image| image.asset != asset));
+    }
+
+    /// Draws with `draw` and renders the result at 32x32.
+    fn rasterize(draw: impl FnOnce(&mut VelloRenderer)) -> image::RgbaImage {
+        let gpu = device::test_device();
+        let mut renderer = VelloRenderer::default();
+        draw(&mut renderer);
+
+        let capture = Rasterizer::new(gpu.device, gpu.queue)
+            .render(
+                &renderer.scene,
+                Color::TRANSPARENT,
+                32,
+                32,
+                1,
+                ScaledOutput::Downsampled,
+            )
+            .unwrap();
+        capture.into_image()
+    }
+
+    fn white_image(size: u32) -> VelloImage {
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(size, size, image::Rgba([255; 4]))
+            .write_to(
+                &mut std::io::Cursor::new(&mut png),
+                image::ImageOutputFormat::Png,
+            )
+            .unwrap();
+        VelloImage::new(&png).unwrap()
+    }
+
+    fn is_half_transparent(pixel: &image::Rgba<u8>) -> bool {
+        pixel[3].abs_diff(128) <= 2
+    }
+
+    #[test]
+    fn images_are_drawn_with_their_opacity() {
+        let mut image = white_image(16);
+        let drawn = rasterize(|renderer| unsafe {
+            renderer.transforms = vec![Affine::translate((16.0, 16.0))];
+            vello_renderer_draw_image(
+                NonNull::new(renderer),
+                NonNull::new(&mut image),
+                rive::BlendMode::SrcOver,
+                0.5,
+            );
+        });
+
+        assert!(is_half_transparent(drawn.get_pixel(16, 16)));
+        assert_eq!(drawn.get_pixel(2, 2)[3], 0);
+    }
+
+    #[test]
+    fn translucent_meshes_have_no_seams() {
+        let mut image = white_image(16);
+        let vertex = |x, y| rive::Vec2D { x, y };
+        let vertices = [
+            vertex(0.0, 0.0),
+            vertex(32.0, 0.0),
+            vertex(32.0, 32.0),
+            vertex(0.0, 32.0),
         ];
         let uvs = [
-            uvs[triangle_indices[0] as usize],
-            uvs[triangle_indices[1] as usize],
-            uvs[triangle_indices[2] as usize],
+            vertex(0.0, 0.0),
+            vertex(1.0, 0.0),
+            vertex(1.0, 1.0),
+            vertex(0.0, 1.0),
         ];
+        // Two triangles sharing the diagonal.
+        let indices = [0, 1, 2, 0, 2, 3];
+
+        let drawn = rasterize(|renderer| unsafe {
+            vello_renderer_draw_image_mesh(
+                NonNull::new(renderer),
+                NonNull::new(&mut image),
+                vertices.as_ptr(),
+                vertices.len(),
+                uvs.as_ptr(),
+                uvs.len(),
+                indices.as_ptr(),
+                indices.len(),
+                rive::BlendMode::SrcOver,
+                0.5,
+            );
+        });
+
+        // Overlapping triangles would be darker along the diagonal, and gaps lighter.
+        for (x, y, pixel) in drawn.enumerate_pixels() {
+            assert!(is_half_transparent(pixel), "{x}, {y}: {pixel:?}");
+        }
+    }
+
+    #[test]
+    fn skipped_draws_are_reported() {
+        let mut png = Vec::new();
+        image::RgbaImage::new(64, 64)
+            .write_to(
+                &mut std::io::Cursor::new(&mut png),
+                image::ImageOutputFormat::Png,
+            )
+            .unwrap();
+        png.truncate(png.len() / 2);
+        let mut corrupt = VelloImage::new(&png).unwrap();
+        let mut image = white_image(16);
+        let vertex = |x, y| rive::Vec2D { x, y };
+        let vertices = [vertex(0.0, 0.0), vertex(32.0, 0.0), vertex(32.0, 32.0)];
+        // The last triangle refers to a fourth vertex.
+        let indices = [0, 1, 2, 0, 2, 3];
+
+        let mut renderer = VelloRenderer::default();
+        for _ in 0..2 {
+            unsafe {
+                vello_renderer_draw_image(
+                    NonNull::new(&mut renderer),
+                    NonNull::new(&mut corrupt),
+                    rive::BlendMode::SrcOver,
+                    1.0,
+                );
+                vello_renderer_draw_image_mesh(
+                    NonNull::new(&mut renderer),
+                    NonNull::new(&mut image),
+                    vertices.as_ptr(),
+                    vertices.len(),
+                    vertices.as_ptr(),
+                    vertices.len(),
+                    indices.as_ptr(),
+                    indices.len(),
+                    rive::BlendMode::SrcOver,
+                    1.0,
+                );
+            }
+        }
+
+        // Reported on each draw, for the host to log or show as it sees fit.
+        let stats = renderer.stats();
+        assert_eq!(stats.undecodable_images.len(), 2);
+        assert_eq!(stats.broken_meshes.len(), 2);
+        assert_eq!(stats.images.len(), 2);
+    }
 
-        let center = Point::new(
-            ((points[0].x + points[1].x + points[2].x) / 3.0) as f64,
-            ((points[0].y + points[1].y + points[2].y) / 3.0) as f64,
+    fn fill_marker(renderer: &mut VelloRenderer, z: i32, color: Color, rect: Rect) {
+        let handle = renderer.insert_marker(z);
+        let fragment = renderer.fragment_mut(handle).unwrap();
+        SceneBuilder::for_fragment(fragment).fill(
+            Fill::NonZero,
+            Affine::IDENTITY,
+            color,
+            None,
+            &rect,
         );
+    }
 
-        let path = triangle_path(points.map(|v| Point::new(v.x as f64, v.y as f64)));
+    #[test]
+    fn layers_composite_by_z() {
+        // Submitted out of order, and overlapping each other.
+        let layers = [
+            (1, Color::rgb8(0, 255, 0), Rect::new(8.0, 8.0, 24.0, 24.0)),
+            (0, Color::rgb8(255, 0, 0), Rect::new(0.0, 0.0, 16.0, 32.0)),
+            (2, Color::rgb8(0, 0, 255), Rect::new(12.0, 0.0, 32.0, 12.0)),
+        ];
+        let drawn = rasterize(|renderer| {
+            for (z, color, rect) in layers {
+                fill_marker(renderer, z, color, rect);
+            }
+            renderer.end_frame();
+        });
+
+        // Every pixel shows the highest layer covering its center, or nothing.
+        let mut by_z = layers;
+        by_z.sort_by_key(|(z, ..)| -z);
+        let golden = image::RgbaImage::from_fn(32, 32, |x, y| {
+            let center = Point::new(x as f64 + 0.5, y as f64 + 0.5);
+            by_z.iter()
+                .find(|(_, _, rect)| rect.contains(center))
+                .map_or(image::Rgba([0; 4]), |(_, color, _)| {
+                    image::Rgba([color.r, color.g, color.b, color.a])
+                })
+        });
+        for (x, y, pixel) in drawn.enumerate_pixels() {
+            let expected = golden.get_pixel(x, y);
+            let close = |(a, b): (&u8, u8)| a.abs_diff(b) <= 1;
+            assert!(
+                pixel.0.iter().zip(expected.0).all(close),
+                "{x}, {y}: {pixel:?} instead of {expected:?}"
+            );
+        }
+    }
 
-        let transform = renderer
-            .last_transform()
-            .pre_scale_from_origin(1.03, center);
-        let brush_transform = util::map_uvs_to_triangle(&points, &uvs, image.width, image.height);
+    #[test]
+    fn contents_sit_between_markers() {
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+        content.handle_resize(32, 32);
+        let red = Color::rgb8(255, 0, 0);
+        let blue = Color::rgb8(0, 0, 255);
+
+        let drawn = rasterize(|renderer| {
+            renderer.draw_with_order(&mut content, 0.0, 1).unwrap();
+            fill_marker(renderer, 2, blue, Rect::new(0.0, 0.0, 4.0, 32.0));
+            fill_marker(renderer, 0, red, Rect::new(0.0, 0.0, 32.0, 32.0));
+            renderer.end_frame();
+        });
+
+        assert_eq!(drawn.get_pixel(1, 16).0, [0, 0, 255, 255]);
+        // The artboard's opaque background covers the red marker below it.
+        let center = drawn.get_pixel(16, 16).0;
+        assert_eq!(center[3], 255);
+        assert_ne!(center, [255, 0, 0, 255]);
+    }
 
-        let builder = &mut renderer.builder;
+    #[test]
+    fn handles_expire_with_their_frame() {
+        let mut renderer = VelloRenderer::default();
+        let handle = renderer.insert_marker(0);
+        assert!(renderer.fragment_mut(handle).is_some());
 
-        let skip_blending = mix == Mix::Normal;
+        renderer.end_frame();
+        assert!(renderer.fragment_mut(handle).is_none());
 
-        if !skip_blending {
-            builder.push_layer(mix, opacity, transform, &path.bounding_box());
+        // The next frame's marker reuses the index, but not the handle.
+        let next = renderer.insert_marker(0);
+        assert_ne!(next, handle);
+        assert!(renderer.fragment_mut(handle).is_none());
+        assert!(renderer.fragment_mut(next).is_some());
+    }
+
+    #[test]
+    fn drops_collapsed_cubics() {
+        let p = Point::new(10.0, 10.0);
+        let path = build(|path| {
+            path.move_to(Point::ZERO);
+            path.line_to(p);
+            path.cubic_to(p, p, p);
+            path.cubic_to(p, Point::new(20.0, 10.0), Point::new(20.0, 20.0));
+        });
+
+        assert_eq!(count(&path.path, |el| matches!(el, PathEl::CurveTo(..))), 1);
+        assert!(path
+            .path
+            .segments()
+            .all(|seg| seg.arclen(1e-3).is_finite() && seg.arclen(1e-3) > 0.0));
+    }
+
+    /// Draws `path` in red with `style` and renders it with `rasterize`.
+    fn rasterize_path(path: &mut VelloPath, style: RenderStyle) -> image::RgbaImage {
+        let mut paint = VelloPaint {
+            style,
+            brush: Brush::Solid(Color::RED),
+            ..Default::default()
+        };
+        rasterize(|renderer| unsafe {
+            vello_renderer_draw_path(
+                NonNull::new(renderer),
+                NonNull::new(path),
+                NonNull::new(&mut paint),
+            );
+        })
+    }
+
+    /// Checks that pixels are red where `covered` holds for their center, and empty elsewhere.
+    /// Edges have to lie on pixel boundaries, so no pixel is partly covered.
+    fn assert_covers(drawn: &image::RgbaImage, covered: impl Fn(Point) -> bool) {
+        for (x, y, pixel) in drawn.enumerate_pixels() {
+            let center = Point::new(x as f64 + 0.5, y as f64 + 0.5);
+            let expected = if covered(center) {
+                [255, 0, 0, 255]
+            } else {
+                [0; 4]
+            };
+            let close = |(a, b): (&u8, u8)| a.abs_diff(b) <= 1;
+            assert!(
+                pixel.0.iter().zip(expected).all(close),
+                "{x}, {y}: {pixel:?} instead of {expected:?}"
+            );
         }
+    }
 
-        builder.fill(
-            Fill::NonZero,
-            transform,
-            BrushRef::Image(image),
-            Some(brush_transform),
-            &path,
-        );
+    #[test]
+    fn donut_renders_with_its_hole() {
+        let mut path = build(|path| {
+            closed_contour(path, &[(4.0, 4.0), (28.0, 4.0), (28.0, 28.0), (4.0, 28.0)]);
+            closed_contour(
+                path,
+                &[(12.0, 12.0), (12.0, 20.0), (20.0, 20.0), (20.0, 12.0)],
+            );
+        });
+
+        let drawn = rasterize_path(&mut path, RenderStyle::Fill);
+        assert_covers(&drawn, |center| {
+            Rect::new(4.0, 4.0, 28.0, 28.0).contains(center)
+                && !Rect::new(12.0, 12.0, 20.0, 20.0).contains(center)
+        });
+    }
 
-        if !skip_blending {
-            builder.pop_layer();
+    #[test]
+    fn open_zig_zag_renders_without_closing() {
+        let mut path = build(|path| {
+            path.move_to(Point::new(4.0, 8.0));
+            for (x, y) in [(10.0, 24.0), (16.0, 8.0), (22.0, 24.0), (28.0, 8.0)] {
+                path.line_to(Point::new(x, y));
+            }
+        });
+
+        let drawn = rasterize_path(&mut path, RenderStyle::Stroke(Stroke::new(2.0)));
+        let alpha = |x, y| drawn.get_pixel(x, y)[3];
+        // Halfway down the first and last legs.
+        assert!(alpha(7, 16) > 128, "{}", alpha(7, 16));
+        assert!(alpha(25, 16) > 128, "{}", alpha(25, 16));
+        // A segment closing the stroke would run along y = 8, between the legs' tops.
+        for x in [9, 10, 21, 22] {
+            assert_eq!(alpha(x, 8), 0, "{x}, 8");
+        }
+    }
+
+    #[test]
+    fn duplicated_points_render_their_shape() {
+        let corner = Point::new(24.0, 8.0);
+        let mut path = build(|path| {
+            path.move_to(Point::new(8.0, 8.0));
+            path.line_to(corner);
+            path.line_to(corner);
+            path.cubic_to(corner, corner, corner);
+            path.line_to(Point::new(24.0, 24.0));
+            path.line_to(Point::new(8.0, 24.0));
+            path.line_to(Point::new(8.0, 24.0));
+            path.close();
+        });
+
+        let filled = rasterize_path(&mut path, RenderStyle::Fill);
+        assert_covers(&filled, |center| {
+            Rect::new(8.0, 8.0, 24.0, 24.0).contains(center)
+        });
+
+        // Stroked, the outline stays a 2 pixel band around the square. Joins blown up by a
+        // missing tangent would reach into the middle or out past the band.
+        let stroked = rasterize_path(&mut path, RenderStyle::Stroke(Stroke::new(2.0)));
+        for (x, y, pixel) in stroked.enumerate_pixels() {
+            let center = Point::new(x as f64 + 0.5, y as f64 + 0.5);
+            if !Rect::new(6.0, 6.0, 26.0, 26.0).contains(center)
+                || Rect::new(10.0, 10.0, 22.0, 22.0).contains(center)
+            {
+                assert_eq!(pixel[3], 0, "{x}, {y}");
+            }
+        }
+        for (x, y) in [(16, 7), (23, 16), (16, 24), (7, 16), (23, 7)] {
+            assert_eq!(stroked.get_pixel(x, y).0, [255, 0, 0, 255], "{x}, {y}");
         }
     }
 }
@@ -0,0 +1,91 @@
+#[cfg(debug_assertions)]
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Objects of the crate alive on the calling thread, counted in debug builds only. Release
+/// builds always report zero.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LiveCounts {
+    /// `ViewerContent`s, each owning a `.riv` file and its artboard and scene instances.
+    pub instances: usize,
+    /// Images decoded from, or waiting to be decoded from, the files' image assets.
+    pub images: usize,
+    /// `VelloRenderer`s, each holding an encoded scene fragment.
+    pub scenes: usize,
+    pub texture_targets: usize,
+}
+
+impl LiveCounts {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Kind {
+    Instance,
+    Image,
+    Scene,
+    TextureTarget,
+}
+
+#[cfg(debug_assertions)]
+type Counters = Arc<[AtomicUsize; 4]>;
+
+#[cfg(debug_assertions)]
+thread_local! {
+    static COUNTERS: Counters = Counters::default();
+}
+
+/// Keeps an object counted from its creation to its drop. Counts belong to the thread that
+/// created the object, even if it's dropped on another one, so that tests running in parallel
+/// don't see each other's objects.
+#[derive(Debug)]
+pub(crate) struct Live {
+    #[cfg(debug_assertions)]
+    counters: Counters,
+    #[cfg(debug_assertions)]
+    kind: Kind,
+}
+
+impl Live {
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    pub fn new(kind: Kind) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            let counters = COUNTERS.with(Arc::clone);
+            counters[kind as usize].fetch_add(1, Ordering::Relaxed);
+
+            Self { counters, kind }
+        }
+        #[cfg(not(debug_assertions))]
+        Self {}
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Drop for Live {
+    fn drop(&mut self) {
+        self.counters[self.kind as usize].fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn counts() -> LiveCounts {
+    #[cfg(debug_assertions)]
+    {
+        COUNTERS.with(|counters| {
+            let count = |kind: Kind| counters[kind as usize].load(Ordering::Relaxed);
+
+            LiveCounts {
+                instances: count(Kind::Instance),
+                images: count(Kind::Image),
+                scenes: count(Kind::Scene),
+                texture_targets: count(Kind::TextureTarget),
+            }
+        })
+    }
+    #[cfg(not(debug_assertions))]
+    LiveCounts::default()
+}
@@ -0,0 +1,243 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+};
+
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Identifies a load started by a `Loader`. Later loads have larger generations.
+#[derive(Clone, Debug)]
+pub struct LoadHandle {
+    generation: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl LoadHandle {
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Asks the load to stop. Whatever it still produces is dropped on its own thread.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Runs loads on background threads and only ever hands out the result of the latest one.
+/// Starting a load cancels the one in flight, so results can't arrive out of order.
+#[derive(Debug)]
+pub struct Loader<T> {
+    next_generation: u64,
+    latest: Option<LoadHandle>,
+    sender: Sender<(u64, io::Result<T>)>,
+    receiver: Receiver<(u64, io::Result<T>)>,
+}
+
+impl<T: Send + 'static> Loader<T> {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            next_generation: 0,
+            latest: None,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Runs `job` on a new thread. Long jobs should check `LoadHandle::is_cancelled` and bail
+    /// out early so that superseded loads free their memory promptly.
+    pub fn load<F>(&mut self, job: F) -> LoadHandle
+    where
+        F: FnOnce(&LoadHandle) -> io::Result<T> + Send + 'static,
+    {
+        self.cancel();
+
+        let handle = LoadHandle {
+            generation: self.next_generation,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        self.next_generation += 1;
+        self.latest = Some(handle.clone());
+
+        let sender = self.sender.clone();
+        let job_handle = handle.clone();
+        thread::spawn(move || {
+            let result = job(&job_handle);
+
+            if !job_handle.is_cancelled() {
+                // The loader is gone if this fails, so there's nobody left to tell.
+                let _ = sender.send((job_handle.generation, result));
+            }
+        });
+
+        handle
+    }
+
+    /// The load in flight, if any.
+    pub fn pending(&self) -> Option<&LoadHandle> {
+        self.latest.as_ref()
+    }
+
+    /// Cancels the load in flight, if any.
+    pub fn cancel(&mut self) {
+        if let Some(handle) = self.latest.take() {
+            handle.cancel();
+        }
+    }
+
+    /// Returns the result of the latest load once it completed. Results of superseded loads
+    /// which finished before noticing their cancellation are dropped here.
+    pub fn poll(&mut self) -> Option<io::Result<T>> {
+        while let Ok((generation, result)) = self.receiver.try_recv() {
+            let is_latest = self
+                .latest
+                .as_ref()
+                .is_some_and(|handle| handle.generation == generation);
+
+            if is_latest {
+                self.latest = None;
+                return Some(result);
+            }
+        }
+
+        None
+    }
+}
+
+impl<T: Send + 'static> Default for Loader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads a whole file, giving up between chunks once `handle` is cancelled.
+pub fn read_file_cancellable(path: &Path, handle: &LoadHandle) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut bytes = Vec::new();
+    let mut chunk = vec![0; READ_CHUNK_SIZE];
+
+    loop {
+        if handle.is_cancelled() {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        match file.read(&mut chunk)? {
+            0 => return Ok(bytes),
+            len => bytes.extend_from_slice(&chunk[..len]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{Duration, Instant};
+
+    /// Loads `value` after `delay`, like a slow disk or network would.
+    fn slow(
+        value: u32,
+        delay: Duration,
+    ) -> impl FnOnce(&LoadHandle) -> io::Result<u32> + Send + 'static {
+        move |_| {
+            thread::sleep(delay);
+            Ok(value)
+        }
+    }
+
+    fn wait(loader: &mut Loader<u32>) -> u32 {
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        while Instant::now() < deadline {
+            if let Some(result) = loader.poll() {
+                return result.unwrap();
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        panic!("load never completed");
+    }
+
+    #[test]
+    fn only_the_last_load_is_displayed() {
+        let delays = [[60, 30, 0], [0, 30, 60], [30, 0, 60], [60, 0, 30]];
+
+        for delays in delays {
+            let mut loader = Loader::new();
+
+            for (value, delay) in delays.into_iter().enumerate() {
+                loader.load(slow(value as u32, Duration::from_millis(delay)));
+            }
+
+            assert_eq!(wait(&mut loader), 2);
+
+            // Nothing stale shows up once the earlier loads finish too.
+            thread::sleep(Duration::from_millis(80));
+            assert!(loader.poll().is_none());
+        }
+    }
+
+    #[test]
+    fn superseded_loads_are_cancelled() {
+        let mut loader = Loader::new();
+
+        let first = loader.load(slow(0, Duration::from_millis(20)));
+        let second = loader.load(slow(1, Duration::from_millis(20)));
+
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+        assert!(second.generation() > first.generation());
+
+        assert_eq!(wait(&mut loader), 1);
+        assert!(loader.pending().is_none());
+    }
+
+    #[test]
+    fn stale_completions_are_ignored() {
+        let mut loader = Loader::new();
+
+        loader.load(slow(0, Duration::ZERO));
+        loader.load(slow(1, Duration::from_millis(20)));
+
+        // A superseded load which finished just before it got cancelled.
+        loader.sender.send((0, Ok(0))).unwrap();
+
+        assert_eq!(wait(&mut loader), 1);
+    }
+
+    #[test]
+    fn cancelling_drops_the_result() {
+        let mut loader = Loader::new();
+
+        loader.load(slow(0, Duration::from_millis(10)));
+        loader.cancel();
+
+        thread::sleep(Duration::from_millis(40));
+        assert!(loader.poll().is_none());
+    }
+
+    #[test]
+    fn cancelled_reads_stop_early() {
+        let handle = LoadHandle {
+            generation: 0,
+            cancelled: Arc::new(AtomicBool::new(true)),
+        };
+
+        let path = std::env::current_exe().unwrap();
+        let error = read_file_cancellable(&path, &handle).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+    }
+}
@@ -1,74 +1,749 @@
-use std::time::Instant;
+mod demo;
+mod session;
+mod surface;
 
-use rive_vello::{VelloRenderer, ViewerContent};
+use std::{
+    io,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use clap::{Parser, ValueEnum};
+use rive_vello::{
+    create_renderer, gpu_memory_report, read_file_cancellable, request_device, BackgroundAdvance,
+    Blitter, Checkerboard, CustomPaints, DownscaleFilter, GpuAllocation, GpuCategory,
+    GpuMemoryReport, InputType, Loader, PointerAction, PointerButton, PointerCapture,
+    QualityGovernor, QualityLevel, Rasterizer, RedrawPolicy, ScaledOutput, SceneStatus, Throttle,
+    TieredDevice, VelloRenderer, ViewerContent, ViewerEvent,
+};
+use session::Session;
+use surface::WindowSurface;
 use vello::{
     kurbo::{Affine, Rect, Vec2},
     peniko::{Color, Fill},
-    util::{RenderContext, RenderSurface},
-    Renderer, RendererOptions, Scene, SceneBuilder,
+    Renderer, Scene, SceneBuilder, SceneFragment,
 };
 use winit::{
     dpi::LogicalSize,
-    event::{ElementState, Event, MouseButton, WindowEvent},
+    event::{
+        DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, MouseButton,
+        VirtualKeyCode, WindowEvent,
+    },
     event_loop::{ControlFlow, EventLoop},
-    window::{Window, WindowBuilder},
+    window::{CursorGrabMode, Window, WindowBuilder},
 };
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Quality {
+    /// Always render at full quality.
+    Fixed,
+    /// Lower the render scale, then the frame rate, while frames keep dropping.
+    Auto,
+}
+
+#[derive(Debug, Parser)]
+#[command(about = "Rive on Vello demo")]
+struct Args {
+    /// File to open. Without one, the previous session is restored.
+    file: Option<PathBuf>,
+    /// Restore the previous session's settings even when opening a file.
+    #[arg(long)]
+    restore: bool,
+    #[arg(long, value_enum, default_value_t = Quality::Fixed)]
+    quality: Quality,
+    /// Open a second window presenting the same content at its own size.
+    #[arg(long)]
+    mirror: bool,
+    /// Sample images at full resolution however small they're drawn, e.g. to compare against
+    /// the default mipmapping.
+    #[arg(long)]
+    naive_downscale: bool,
+    /// Fill the grid with separately loaded copies starting at random points of their
+    /// animation instead of repeating the first cell.
+    #[arg(long)]
+    desync: bool,
+    /// Render screenshots, taken with S, at this multiple of the window's size.
+    #[arg(long, default_value_t = 1)]
+    screenshot_scale: u32,
+    /// Save scaled screenshots at the size they're rendered at instead of downsampling them to
+    /// the window's size.
+    #[arg(long)]
+    screenshot_full_resolution: bool,
+    /// What the content does while the window is minimized or occluded: `pause`, or keep
+    /// advancing at a rate such as `5hz`.
+    #[arg(long, default_value = "pause")]
+    background_advance: BackgroundAdvance,
+    /// Fill the image assets named KEY, extension aside, with a scrolling checkerboard, as an
+    /// example of custom paints.
+    #[arg(long, value_name = "KEY")]
+    checkerboard: Option<String>,
+    /// Keep the artboard's position on whole pixels as the window is resized.
+    #[arg(long)]
+    pixel_snap: bool,
+    /// Color for paints named `currentColor`, e.g. `#ff8000`, instead of the one that contrasts
+    /// with the backdrop.
+    #[arg(long, value_parser = rive_vello::parse_color)]
+    primary_color: Option<Color>,
+    /// Record this many frames to step through with Ctrl+Left and Ctrl+Right while paused.
+    #[arg(long, default_value_t = 0, value_name = "FRAMES")]
+    history: usize,
+    /// Component whose animated properties `--history` records, with its descendants. Can be
+    /// repeated. Every component is recorded without one.
+    #[arg(long, value_name = "NAME")]
+    watch: Vec<String>,
+}
+
 struct RenderState {
-    surface: RenderSurface,
+    surface: WindowSurface,
+    window: Window,
+    blitter: Blitter,
+    intermediate: Option<IntermediateTarget>,
+}
+
+/// Loop-in and loop-out points of the active linear animation, in seconds. Playback only loops
+/// between them once both are set.
+#[derive(Default)]
+struct LoopRegion {
+    start: Option<f32>,
+    end: Option<f32>,
+}
+
+impl LoopRegion {
+    /// Loops `viewer_content` over the region, saying so when the region had to be clamped.
+    fn apply(&self, viewer_content: &mut ViewerContent) {
+        let (Some(start), Some(end)) = (self.start, self.end) else {
+            return;
+        };
+        let applied = match viewer_content.set_play_range(Some((start, end))) {
+            Ok(Some(applied)) => applied,
+            Ok(None) => return,
+            Err(error) => {
+                eprintln!("Couldn't loop the region: {error}");
+                return;
+            }
+        };
+
+        if applied != (start.min(end), start.max(end)) {
+            eprintln!(
+                "Loop region clamped to {:.3}s..{:.3}s, it can't be narrower than a frame",
+                applied.0, applied.1
+            );
+        }
+    }
+}
+
+/// Color drawn behind the content, cycled with B to preview icons on light and dark
+/// backgrounds.
+#[derive(Clone, Copy, Debug, Default)]
+enum Backdrop {
+    #[default]
+    Neutral,
+    Light,
+    Dark,
+}
+
+impl Backdrop {
+    fn next(self) -> Self {
+        match self {
+            Self::Neutral => Self::Light,
+            Self::Light => Self::Dark,
+            Self::Dark => Self::Neutral,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Neutral => Color::DIM_GRAY,
+            Self::Light => Color::WHITE,
+            Self::Dark => Color::BLACK,
+        }
+    }
+
+    /// Color that `currentColor` paints take on this backdrop. The neutral one keeps the
+    /// authored colors.
+    fn primary_color(self) -> Option<Color> {
+        match self {
+            Self::Neutral => None,
+            Self::Light => Some(Color::BLACK),
+            Self::Dark => Some(Color::WHITE),
+        }
+    }
+}
+
+/// Recolors `viewer_content`'s `currentColor` paints with `primary_color`, or the color that
+/// contrasts with `backdrop`.
+fn apply_primary_color(
+    viewer_content: &mut ViewerContent,
+    primary_color: Option<Color>,
+    backdrop: Backdrop,
+) {
+    let _ = match primary_color.or(backdrop.primary_color()) {
+        Some(color) => viewer_content.set_primary_color(color).map(drop),
+        None => viewer_content.clear_primary_color(),
+    };
+}
+
+/// Records `frames` frames of the `watch` components, or of every component without any.
+fn apply_history(viewer_content: &mut ViewerContent, frames: usize, watch: &[String]) {
+    if frames == 0 {
+        return;
+    }
+
+    viewer_content.enable_history(frames);
+    if watch.is_empty() {
+        let _ = viewer_content.watch_all_components();
+    }
+    for name in watch {
+        let _ = viewer_content.watch_component(name);
+    }
+}
+
+/// Index of the item after `current` in a list of `len` items, or before it, wrapping around.
+fn cycle(current: usize, len: usize, forward: bool) -> usize {
+    if forward {
+        (current + 1) % len
+    } else {
+        (current + len - 1) % len
+    }
+}
+
+fn cycle_artboard(viewer_content: &mut ViewerContent, forward: bool) {
+    let artboards = viewer_content.artboard_names();
+    if artboards.is_empty() {
+        return;
+    }
+    let current = viewer_content.artboard();
+    let index = artboards
+        .iter()
+        .position(|name| Some(name) == current.as_ref());
+    let index = cycle(index.unwrap_or(0), artboards.len(), forward);
+
+    let name = &artboards[index];
+    match viewer_content.select(Some(name), None) {
+        Ok(()) => println!("Artboard {name} ({}/{})", index + 1, artboards.len()),
+        Err(error) => eprintln!("Couldn't select artboard {name}: {error}"),
+    }
+}
+
+fn cycle_state_machine(viewer_content: &mut ViewerContent, forward: bool) {
+    let state_machines = viewer_content
+        .artboard()
+        .map(|artboard| viewer_content.state_machine_names(&artboard))
+        .unwrap_or_default();
+    if state_machines.is_empty() {
+        return;
+    }
+    let current = viewer_content
+        .snapshot()
+        .map(|snapshot| snapshot.state_machine);
+    let index = state_machines
+        .iter()
+        .position(|name| Some(name) == current.as_ref());
+    let index = cycle(index.unwrap_or(0), state_machines.len(), forward);
+
+    let name = &state_machines[index];
+    let count = state_machines.len();
+    match viewer_content.select(None, Some(name)) {
+        Ok(()) => println!("State machine {name} ({}/{count})", index + 1),
+        Err(error) => eprintln!("Couldn't select state machine {name}: {error}"),
+    }
+}
+
+/// Fires the `index`th input if it's a trigger, toggles it if it's a boolean, and steps it by 1
+/// if it's a number, down when `down` is set.
+fn poke_input(viewer_content: &ViewerContent, index: usize, down: bool) {
+    let inputs = viewer_content.inputs();
+    let Some(input) = inputs.get(index) else {
+        return;
+    };
+    let name = &input.name;
+
+    let (poked, change) = match input.input_type {
+        InputType::Trigger => (viewer_content.fire_trigger(name), "fired".to_string()),
+        InputType::Bool => {
+            let value = !viewer_content.boolean(name).unwrap_or_default();
+            (viewer_content.set_bool(name, value), format!("= {value}"))
+        }
+        InputType::Number => {
+            let step = if down { -1.0 } else { 1.0 };
+            let value = viewer_content.number(name).unwrap_or_default() + step;
+            (viewer_content.set_number(name, value), format!("= {value}"))
+        }
+    };
+    match poked {
+        Ok(_) => println!("{name} {change}"),
+        Err(error) => eprintln!("Couldn't set {name}: {error}"),
+    }
+}
+
+/// Shows the recorded frame before or after the one on display. The live pose counts as the
+/// last recorded frame.
+fn step_history(viewer_content: &mut ViewerContent, forward: bool) {
+    let count = viewer_content.history().len();
+    let Some(last) = count.checked_sub(1) else {
+        return;
+    };
+    let shown = viewer_content.shown_history_frame().unwrap_or(last);
+    let index = if forward {
+        (shown + 1).min(last)
+    } else {
+        shown.saturating_sub(1)
+    };
+
+    if index == last {
+        let _ = viewer_content.show_live_frame();
+        println!("Live frame");
+        return;
+    }
+    if viewer_content.show_history_frame(index) != Ok(true) {
+        return;
+    }
+
+    let record = &viewer_content.history()[index];
+    let states: Vec<_> = record.layers.iter().map(|layer| layer.state).collect();
+    let events: Vec<_> = record.events.iter().map(|event| &event.name).collect();
+    println!(
+        "Frame {} ({}/{count}), layer states {states:?}, events {events:?}",
+        record.frame,
+        index + 1
+    );
+}
+
+/// Second window showing the primary window's content. It's drawn from the pose the primary
+/// window advanced to and presents best-effort: frames are skipped rather than waited for.
+struct MirrorState {
+    surface: WindowSurface,
     window: Window,
+    renderer: Renderer,
+}
+
+/// Texture the scene is rendered to when the quality governor lowers the render scale.
+struct IntermediateTarget {
+    _texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    width: u32,
+    height: u32,
+    _memory: GpuAllocation,
+}
+
+impl IntermediateTarget {
+    fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("intermediate"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            _texture: texture,
+            view,
+            width,
+            height,
+            _memory: GpuAllocation::texture(GpuCategory::Intermediates, width, height),
+        }
+    }
 }
 
 const INITIAL_WINDOW_SIZE: LogicalSize<u32> = LogicalSize::new(700, 700);
 const FRAME_STATS_CAPACITY: usize = 30;
 const SCROLL_FACTOR_THRESHOLD: f64 = 100.0;
+const TARGET_FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+const LOOP_STRIP_HEIGHT: f64 = 6.0;
+const LOOP_STRIP_COLOR: Color = Color::rgba8(0, 0, 0, 160);
+const LOOP_REGION_COLOR: Color = Color::rgba8(255, 255, 255, 64);
 
 fn main() {
-    let mut viewer_content: Option<ViewerContent> = None;
+    let args = Args::parse();
+
+    let session_path = Session::path();
+    let mut session = if args.restore || args.file.is_none() {
+        session_path
+            .as_deref()
+            .and_then(Session::load)
+            .unwrap_or_default()
+    } else {
+        Session::default()
+    };
+    if let Some(file) = args.file {
+        // What was playing only applies to the file it was played from.
+        if session.file.as_ref() != Some(&file) {
+            session = Session {
+                file: Some(file),
+                grid_scroll: session.grid_scroll,
+                paused: session.paused,
+                ..Session::default()
+            };
+        }
+    }
+
+    let mut backdrop = Backdrop::default();
+    let mut viewer_content = session.file.as_ref().and_then(ViewerContent::new);
+    if let Some(viewer_content) = &mut viewer_content {
+        viewer_content.auto_bind_scrub();
+        viewer_content.set_pixel_snap(args.pixel_snap);
+        apply_primary_color(viewer_content, args.primary_color, backdrop);
+        apply_history(viewer_content, args.history, &args.watch);
+        session.restore(viewer_content);
+    }
+
+    let save_session = move |session: &Session| {
+        let Some(path) = &session_path else { return };
+
+        if let Err(error) = session.save(path) {
+            eprintln!("Failed to save session to {}: {error}", path.display());
+        }
+    };
+
+    // Dropped files are read in the background. Dropping another one while a file is still
+    // loading cancels the older load so it can't replace the newer content.
+    let mut loader = Loader::<(PathBuf, Vec<u8>)>::new();
 
     let event_loop = EventLoop::new();
     let mut cached_window: Option<Window> = None;
     let mut renderer: Option<Renderer> = None;
-    let mut render_cx = RenderContext::new().unwrap();
+    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+    // Requested with the first window, walking down the tiers the adapter supports.
+    let mut gpu: Option<TieredDevice> = None;
     let mut render_state: Option<RenderState> = None;
+    let mut mirror_state: Option<MirrorState> = None;
 
     let mut mouse_pos = Vec2::default();
-    let mut scroll_delta = 0.0;
+    // Shown instead of an empty window while no file is loaded.
+    let mut demo_content = ViewerContent::from_bytes(&demo::riv());
+    // Set while a press on the content drags, until it's released.
+    let mut capture: Option<PointerCapture> = None;
+    let mut modifiers = ModifiersState::default();
+    // With `--desync`, the grid cells after the first one, loaded again from the session's file.
+    let mut desynced: Vec<ViewerContent> = Vec::new();
+    let mut rasterizer: Option<Rasterizer> = None;
+    let mut screenshot_requested = false;
+    let mut loop_region = LoopRegion::default();
+    // Set while the content's redraw policy lets the viewer skip frames.
+    let mut resting = false;
+    let mut scroll_delta = session.grid_scroll;
     let mut frame_start_time = Instant::now();
     let mut stats = Vec::with_capacity(FRAME_STATS_CAPACITY);
+    // As of the last frame submitted, while its renderer was still alive.
+    let mut gpu_memory = GpuMemoryReport::default();
+    let downscale_filter = if args.naive_downscale {
+        DownscaleFilter::Naive
+    } else {
+        DownscaleFilter::Mipmap
+    };
+
+    // Stops rendering while the window is minimized or occluded.
+    let mut throttle = Throttle::new(args.background_advance);
+    let mut occluded = false;
+    let mut minimized = false;
+
+    // Shared by every frame's renderers so that providers keep their brush caches.
+    let custom_paints = CustomPaints::default();
+    if let Some(key) = &args.checkerboard {
+        custom_paints.register(key, Box::<Checkerboard>::default());
+    }
+    let start_time = Instant::now();
+
+    let mut governor = match args.quality {
+        Quality::Fixed => QualityGovernor::fixed(QualityLevel::Full),
+        Quality::Auto => QualityGovernor::auto(TARGET_FRAME_TIME),
+    };
 
     event_loop.run(move |event, _event_loop, control_flow| match event {
+        Event::WindowEvent {
+            window_id,
+            ref event,
+        } if mirror_state
+            .as_ref()
+            .is_some_and(|mirror| mirror.window.id() == window_id) =>
+        {
+            match event {
+                WindowEvent::CloseRequested => mirror_state = None,
+                WindowEvent::Resized(size) => {
+                    let mirror = mirror_state.as_mut().unwrap();
+                    let gpu = gpu.as_ref().unwrap();
+                    mirror.surface.resize(&gpu.device, size.width, size.height);
+                }
+                _ => {}
+            }
+        }
         Event::WindowEvent { ref event, .. } => {
-            let Some(render_state) = &mut render_state else { return };
+            let Some(render_state) = &mut render_state else {
+                return;
+            };
 
             match event {
-                WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
-                WindowEvent::Resized(size) => {
+                WindowEvent::CloseRequested => {
+                    session.grid_scroll = scroll_delta;
                     if let Some(viewer_content) = &viewer_content {
+                        session.capture(viewer_content);
+                    }
+                    save_session(&session);
+
+                    *control_flow = ControlFlow::Exit;
+                }
+                WindowEvent::Resized(size) => {
+                    // Minimized windows shrink to nothing on some platforms.
+                    minimized = size.width == 0 || size.height == 0;
+                    if minimized {
+                        hide(&mut throttle, &render_state.window);
+                        return;
+                    }
+
+                    for viewer_content in
+                        viewer_content.iter().chain(&desynced).chain(&demo_content)
+                    {
                         viewer_content.handle_resize(size.width, size.height);
                     }
 
-                    render_cx.resize_surface(&mut render_state.surface, size.width, size.height);
+                    let gpu = gpu.as_ref().unwrap();
+                    render_state
+                        .surface
+                        .resize(&gpu.device, size.width, size.height);
+                    if !occluded {
+                        show(
+                            &mut throttle,
+                            &render_state.window,
+                            &mut frame_start_time,
+                            &mut viewer_content,
+                            &mut desynced,
+                        );
+                    }
+                    render_state.window.request_redraw();
+                }
+                WindowEvent::Occluded(is_occluded) => {
+                    occluded = *is_occluded;
+                    if occluded {
+                        hide(&mut throttle, &render_state.window);
+                    } else if !minimized {
+                        show(
+                            &mut throttle,
+                            &render_state.window,
+                            &mut frame_start_time,
+                            &mut viewer_content,
+                            &mut desynced,
+                        );
+                        render_state.window.request_redraw();
+                    }
+                }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    if let Some(viewer_content) = &mut viewer_content {
+                        let button = match button {
+                            MouseButton::Left => PointerButton::Left,
+                            MouseButton::Right => PointerButton::Right,
+                            MouseButton::Middle => PointerButton::Middle,
+                            MouseButton::Other(id) => PointerButton::Other(*id),
+                        };
+                        let action = match state {
+                            ElementState::Pressed => PointerAction::Down(button),
+                            ElementState::Released => PointerAction::Up(button),
+                        };
+
+                        let _ = viewer_content.handle_pointer_surface(action, mouse_pos);
+
+                        let captured = viewer_content.wants_pointer_capture();
+                        if captured && capture.is_none() {
+                            // Confining keeps the cursor inside where it's supported. Elsewhere,
+                            // moves outside the window are synthesized from device deltas.
+                            let _ = render_state
+                                .window
+                                .set_cursor_grab(CursorGrabMode::Confined);
+                            capture = Some(PointerCapture::new(mouse_pos));
+                        } else if !captured && capture.take().is_some() {
+                            let _ = render_state.window.set_cursor_grab(CursorGrabMode::None);
+                        }
+                    }
+                }
+                WindowEvent::Focused(false) => {
+                    if capture.take().is_some() {
+                        let _ = render_state.window.set_cursor_grab(CursorGrabMode::None);
+                        if let Some(viewer_content) = &mut viewer_content {
+                            let _ = viewer_content.cancel_pointer();
+                        }
+                    }
+                }
+                WindowEvent::CursorLeft { .. } => {
+                    if let Some(demo_content) = &mut demo_content {
+                        // Off the window, so off the mark.
+                        let _ = demo_content
+                            .handle_pointer_surface(PointerAction::Move, Vec2::new(-1.0, -1.0));
+                    }
+                    if let Some(capture) = &mut capture {
+                        capture.cursor_left();
+                    }
+                }
+                WindowEvent::ModifiersChanged(state) => modifiers = *state,
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(VirtualKeyCode::Space),
+                            ..
+                        },
+                    ..
+                } => {
+                    if let Some(viewer_content) = &mut viewer_content {
+                        if modifiers.shift() {
+                            viewer_content.pause_at_loop_end();
+                        } else if viewer_content.is_paused() {
+                            viewer_content.resume();
+                        } else {
+                            viewer_content.pause();
+                        }
+                    }
+                }
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(VirtualKeyCode::S),
+                            ..
+                        },
+                    ..
+                } => {
+                    screenshot_requested = true;
                     render_state.window.request_redraw();
                 }
-                WindowEvent::MouseInput {
-                    state,
-                    button: MouseButton::Left,
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(VirtualKeyCode::B),
+                            ..
+                        },
+                    ..
+                } => {
+                    backdrop = backdrop.next();
+                    for viewer_content in viewer_content.iter_mut().chain(desynced.iter_mut()) {
+                        apply_primary_color(viewer_content, args.primary_color, backdrop);
+                    }
+                    render_state.window.request_redraw();
+                }
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode:
+                                Some(key @ (VirtualKeyCode::Left | VirtualKeyCode::Right)),
+                            ..
+                        },
+                    ..
+                } if modifiers.ctrl() => {
+                    let Some(viewer_content) = &mut viewer_content else {
+                        return;
+                    };
+                    if viewer_content.is_paused() {
+                        step_history(viewer_content, *key == VirtualKeyCode::Right);
+                        render_state.window.request_redraw();
+                    }
+                }
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode:
+                                Some(
+                                    key @ (VirtualKeyCode::Left
+                                    | VirtualKeyCode::Right
+                                    | VirtualKeyCode::Up
+                                    | VirtualKeyCode::Down),
+                                ),
+                            ..
+                        },
+                    ..
+                } => {
+                    if let Some(viewer_content) = &mut viewer_content {
+                        match key {
+                            VirtualKeyCode::Left => cycle_artboard(viewer_content, false),
+                            VirtualKeyCode::Right => cycle_artboard(viewer_content, true),
+                            VirtualKeyCode::Up => cycle_state_machine(viewer_content, false),
+                            _ => cycle_state_machine(viewer_content, true),
+                        }
+                        render_state.window.request_redraw();
+                    }
+                }
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode:
+                                Some(
+                                    key @ (VirtualKeyCode::Key1
+                                    | VirtualKeyCode::Key2
+                                    | VirtualKeyCode::Key3
+                                    | VirtualKeyCode::Key4
+                                    | VirtualKeyCode::Key5
+                                    | VirtualKeyCode::Key6
+                                    | VirtualKeyCode::Key7
+                                    | VirtualKeyCode::Key8
+                                    | VirtualKeyCode::Key9),
+                                ),
+                            ..
+                        },
                     ..
                 } => {
                     if let Some(viewer_content) = &viewer_content {
-                        let handler = match state {
-                            ElementState::Pressed => ViewerContent::handle_pointer_down,
-                            ElementState::Released => ViewerContent::handle_pointer_up,
-                        };
+                        let index = *key as usize - VirtualKeyCode::Key1 as usize;
+                        poke_input(viewer_content, index, modifiers.shift());
+                        render_state.window.request_redraw();
+                    }
+                }
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode:
+                                Some(
+                                    key @ (VirtualKeyCode::Comma
+                                    | VirtualKeyCode::Period
+                                    | VirtualKeyCode::Back),
+                                ),
+                            ..
+                        },
+                    ..
+                } => {
+                    let Some(viewer_content) = &mut viewer_content else {
+                        return;
+                    };
 
-                        handler(viewer_content, mouse_pos);
+                    match key {
+                        VirtualKeyCode::Back => {
+                            loop_region = LoopRegion::default();
+                            let _ = viewer_content.set_play_range(None);
+                        }
+                        _ => {
+                            let Some(time) = viewer_content.time() else {
+                                return;
+                            };
+                            if *key == VirtualKeyCode::Comma {
+                                loop_region.start = Some(time);
+                            } else {
+                                loop_region.end = Some(time);
+                            }
+                            loop_region.apply(viewer_content);
+                        }
                     }
                 }
                 WindowEvent::CursorMoved { position, .. } => {
                     mouse_pos = Vec2::new(position.x, position.y);
-                    if let Some(viewer_content) = &viewer_content {
-                        viewer_content.handle_pointer_move(mouse_pos);
+                    if let Some(demo_content) = &mut demo_content {
+                        let _ = demo_content.handle_pointer_surface(PointerAction::Move, mouse_pos);
+                    }
+                    if let Some(capture) = &mut capture {
+                        capture.cursor_moved(mouse_pos);
+                    }
+                    if let Some(viewer_content) = &mut viewer_content {
+                        let _ =
+                            viewer_content.handle_pointer_surface(PointerAction::Move, mouse_pos);
                     }
                 }
                 WindowEvent::MouseWheel { delta, .. } => match delta {
@@ -82,23 +757,125 @@ fn main() {
                     }
                 },
                 WindowEvent::DroppedFile(path) => {
-                    viewer_content = ViewerContent::new(path);
+                    let path = path.clone();
 
-                    if let Some(viewer_content) = &viewer_content {
-                        let size = render_state.window.inner_size();
-                        viewer_content.handle_resize(size.width, size.height);
-                    }
+                    loader.load(move |handle| {
+                        let bytes = read_file_cancellable(&path, handle).map_err(|error| {
+                            io::Error::new(error.kind(), format!("{}: {error}", path.display()))
+                        })?;
+
+                        Ok((path, bytes))
+                    });
                 }
                 _ => {}
             }
         }
+        Event::DeviceEvent {
+            event: DeviceEvent::MouseMotion { delta },
+            ..
+        } => {
+            let Some(position) = capture
+                .as_mut()
+                .and_then(|capture| capture.device_moved(Vec2::new(delta.0, delta.1)))
+            else {
+                return;
+            };
+
+            mouse_pos = position;
+            if let Some(viewer_content) = &mut viewer_content {
+                let _ = viewer_content.handle_pointer_surface(PointerAction::Move, mouse_pos);
+            }
+        }
         Event::MainEventsCleared => {
             if let Some(render_state) = &mut render_state {
-                render_state.window.request_redraw();
+                match loader.poll() {
+                    Some(Ok((path, bytes))) => {
+                        viewer_content = ViewerContent::from_bytes(&bytes);
+                        desynced.clear();
+                        loop_region = LoopRegion::default();
+
+                        if let Some(viewer_content) = &mut viewer_content {
+                            viewer_content.auto_bind_scrub();
+                            viewer_content.set_pixel_snap(args.pixel_snap);
+                            apply_primary_color(viewer_content, args.primary_color, backdrop);
+                            apply_history(viewer_content, args.history, &args.watch);
+
+                            let size = render_state.window.inner_size();
+                            viewer_content.handle_resize(size.width, size.height);
+
+                            session = Session {
+                                file: Some(path),
+                                grid_scroll: session.grid_scroll,
+                                ..Session::default()
+                            };
+                            save_session(&session);
+                        }
+                    }
+                    Some(Err(error)) => eprintln!("Failed to load {error}"),
+                    None => {}
+                }
+
+                if throttle.is_hidden() {
+                    *control_flow = match throttle.tick_interval() {
+                        Some(interval) if frame_start_time.elapsed() < interval => {
+                            ControlFlow::WaitUntil(frame_start_time + interval)
+                        }
+                        Some(interval) => {
+                            let elapsed = frame_start_time.elapsed().as_secs_f64();
+                            frame_start_time = Instant::now();
+                            for step in throttle.tick(elapsed) {
+                                for content in viewer_content.iter_mut().chain(&mut desynced) {
+                                    let _ = content.advance(step);
+                                }
+                            }
+
+                            ControlFlow::WaitUntil(frame_start_time + interval)
+                        }
+                        None => ControlFlow::Wait,
+                    };
+                    return;
+                }
+
+                // Content at rest is only redrawn once an event changed it. Copies and mirrors
+                // are left out, as they'd need redrawing on their own.
+                let policy = viewer_content.as_ref().map(ViewerContent::redraw_policy);
+                let at_rest = !args.desync
+                    && mirror_state.is_none()
+                    && policy.is_some_and(|policy| policy != RedrawPolicy::Continuous);
+                if at_rest {
+                    resting = true;
+                    *control_flow = ControlFlow::Wait;
+                    return;
+                }
+                if std::mem::take(&mut resting) {
+                    // Time spent at rest isn't played back when the content wakes.
+                    frame_start_time = Instant::now();
+                }
+
+                match governor.level().frame_cap() {
+                    Some(cap) if frame_start_time.elapsed() < cap => {
+                        *control_flow = ControlFlow::WaitUntil(frame_start_time + cap);
+                    }
+                    _ => {
+                        *control_flow = ControlFlow::Poll;
+                        render_state.window.request_redraw();
+                    }
+                }
             }
         }
-        Event::RedrawRequested(_) => {
+        Event::RedrawRequested(window_id)
+            if render_state
+                .as_ref()
+                .is_some_and(|state| state.window.id() == window_id) =>
+        {
+            if throttle.is_hidden() {
+                return;
+            }
+
             let mut vello_renderer = VelloRenderer::default();
+            vello_renderer.set_downscale_filter(downscale_filter);
+            vello_renderer.set_custom_paints(custom_paints.clone());
+            vello_renderer.set_time(start_time.elapsed().as_secs_f64());
             let factor = (scroll_delta / SCROLL_FACTOR_THRESHOLD).max(1.0) as u32;
 
             let elapsed = &frame_start_time.elapsed();
@@ -111,25 +888,47 @@ fn main() {
                     let copies = (factor > 1)
                         .then(|| format!(" ({} copies)", factor.pow(2)))
                         .unwrap_or_default();
+                    let quality = governor
+                        .is_auto()
+                        .then(|| format!(" | quality: {:?}", governor.level()))
+                        .unwrap_or_default();
+                    let tier = gpu
+                        .as_ref()
+                        .map(|gpu| format!(" | tier: {:?}", gpu.tier))
+                        .unwrap_or_default();
+                    let status = viewer_content
+                        .as_ref()
+                        .map(ViewerContent::status)
+                        .filter(|&status| status != SceneStatus::Ready)
+                        .map(|status| format!(" | scene: {status}"))
+                        .unwrap_or_default();
                     state.window.set_title(&format!(
-                        "Rive on Vello demo | {:.2}ms{}",
+                        "Rive on Vello demo | {:.2}ms{}{}{}{} | gpu: {}",
                         average * 1000.0,
-                        copies
+                        copies,
+                        quality,
+                        tier,
+                        status,
+                        gpu_memory
                     ));
                 }
             }
 
             frame_start_time = Instant::now();
 
-            let Some(render_state) = &mut render_state else { return };
+            let Some(render_state) = &mut render_state else {
+                return;
+            };
             let width = render_state.surface.config.width;
             let height = render_state.surface.config.height;
-            let device_handle = &render_cx.devices[render_state.surface.dev_id];
+            let Some(device_handle) = &gpu else { return };
 
+            let render_scale = governor.level().render_scale();
+            let root = Affine::scale(render_scale);
             let render_params = vello::RenderParams {
-                base_color: Color::DIM_GRAY,
-                width,
-                height,
+                base_color: backdrop.color(),
+                width: ((width as f64 * render_scale) as u32).max(1),
+                height: ((height as f64 * render_scale) as u32).max(1),
             };
 
             let surface_texture = render_state
@@ -137,51 +936,192 @@ fn main() {
                 .surface
                 .get_current_texture()
                 .expect("failed to get surface texture");
+            // Acquiring the texture may wait for vsync, which the governor mustn't count.
+            let work_start = Instant::now();
 
-            let mut scene = Scene::default();
-            let mut builder = SceneBuilder::for_scene(&mut scene);
+            // Laid out at the surface's size. The render scale and screenshots scale it as a whole.
+            let mut frame = SceneFragment::default();
+            let mut builder = SceneBuilder::for_fragment(&mut frame);
 
             if let Some(viewer_content) = &mut viewer_content {
-                viewer_content.handle_draw(&mut vello_renderer, elapsed.as_secs_f64());
+                // The first cell of the grid is the placed instance; the other ones are copies
+                // of it and don't receive pointer events.
+                viewer_content.set_placement(Affine::scale(1.0 / factor as f64));
+                if viewer_content
+                    .handle_draw(&mut vello_renderer, elapsed.as_secs_f64())
+                    .is_err()
+                {
+                    placeholder(&mut builder);
+                }
+
+                for event in viewer_content.take_events() {
+                    match event {
+                        ViewerEvent::LoopCompleted { iteration } => {
+                            println!("Paused at the end of loop {iteration}");
+                        }
+                        ViewerEvent::StatusChanged { status } => {
+                            eprintln!("Scene is {status}; it's no longer advanced or drawn");
+                        }
+                        // Polled once events are cleared, to skip redraws while at rest.
+                        ViewerEvent::RedrawPolicyChanged { .. } => {}
+                        ViewerEvent::LargeHistory { bytes } => {
+                            eprintln!(
+                                "History may take {} MB, pass --watch to record fewer components",
+                                bytes >> 20
+                            );
+                        }
+                    }
+                }
+
+                if args.desync {
+                    let copies = factor.pow(2) as usize - 1;
+                    if desynced.len() != copies {
+                        desynced = (0..copies)
+                            .filter_map(|_| {
+                                let mut copy = ViewerContent::new(session.file.as_ref()?)?;
+                                copy.randomize_phase(&mut rand::thread_rng()).ok()?;
+                                copy.set_pixel_snap(args.pixel_snap);
+                                apply_primary_color(&mut copy, args.primary_color, backdrop);
+                                copy.handle_resize(width, height);
+                                Some(copy)
+                            })
+                            .collect();
+                    }
 
-                for i in 0..factor.pow(2) {
-                    builder.append(
+                    builder.append(&vello_renderer.scene, None);
+                    for (i, copy) in desynced.iter_mut().enumerate() {
+                        let mut copy_renderer = VelloRenderer::default();
+                        copy_renderer.set_downscale_filter(downscale_filter);
+                        copy_renderer.set_custom_paints(custom_paints.clone());
+                        copy_renderer.set_time(start_time.elapsed().as_secs_f64());
+                        copy.set_placement(Affine::scale(1.0 / factor as f64));
+                        let _ = copy.handle_draw(&mut copy_renderer, elapsed.as_secs_f64());
+
+                        builder.append(
+                            &copy_renderer.scene,
+                            Some(grid_cell(i as u32 + 1, factor, width, height)),
+                        );
+                    }
+                } else {
+                    append_grid(
+                        &mut builder,
                         &vello_renderer.scene,
-                        Some(
-                            Affine::default()
-                                .then_scale(1.0 / factor as f64)
-                                .then_translate(Vec2::new(
-                                    (i % factor) as f64 * width as f64 / factor as f64,
-                                    (i / factor) as f64 * height as f64 / factor as f64,
-                                )),
-                        ),
+                        Affine::IDENTITY,
+                        factor,
+                        width,
+                        height,
                     );
                 }
-            } else {
-                // Vello currently crashes when rendering an empty scene.
-                builder.fill(
-                    Fill::NonZero,
-                    Affine::IDENTITY,
-                    Color::TRANSPARENT,
-                    None,
-                    &Rect::new(0.0, 0.0, 0.0, 0.0),
+            } else if let Some(demo_content) = &mut demo_content {
+                let _ = demo_content.handle_draw(&mut vello_renderer, elapsed.as_secs_f64());
+                builder.append(&vello_renderer.scene, None);
+            }
+
+            if std::mem::take(&mut screenshot_requested) {
+                let rasterizer = rasterizer.get_or_insert_with(|| {
+                    Rasterizer::new(device_handle.device.clone(), device_handle.queue.clone())
+                });
+                let output = if args.screenshot_full_resolution {
+                    ScaledOutput::FullResolution
+                } else {
+                    ScaledOutput::Downsampled
+                };
+                save_screenshot(
+                    rasterizer,
+                    &frame,
+                    width,
+                    height,
+                    args.screenshot_scale,
+                    output,
+                    backdrop.color(),
                 );
             }
 
-            vello::block_on_wgpu(
-                &device_handle.device,
-                renderer.as_mut().unwrap().render_to_surface_async(
+            let mut scene = Scene::default();
+            {
+                let mut builder = SceneBuilder::for_scene(&mut scene);
+                builder.append(&frame, Some(root));
+                // Drawn over the frame rather than into it, so it stays out of screenshots.
+                if let Some(viewer_content) = &viewer_content {
+                    draw_loop_region(
+                        &mut builder,
+                        viewer_content,
+                        &loop_region,
+                        root,
+                        width,
+                        height,
+                    );
+                }
+            }
+
+            if render_scale < 1.0 {
+                let intermediate = match render_state.intermediate.take() {
+                    Some(intermediate)
+                        if intermediate.width == render_params.width
+                            && intermediate.height == render_params.height =>
+                    {
+                        intermediate
+                    }
+                    _ => IntermediateTarget::new(
+                        &device_handle.device,
+                        render_params.width,
+                        render_params.height,
+                    ),
+                };
+
+                vello::block_on_wgpu(
+                    &device_handle.device,
+                    renderer.as_mut().unwrap().render_to_texture_async(
+                        &device_handle.device,
+                        &device_handle.queue,
+                        &scene,
+                        &intermediate.view,
+                        &render_params,
+                    ),
+                )
+                .expect("failed to render to texture");
+
+                render_state.blitter.blit(
                     &device_handle.device,
                     &device_handle.queue,
-                    &scene,
-                    &surface_texture,
-                    &render_params,
-                ),
-            )
-            .expect("failed to render to surface");
+                    &intermediate.view,
+                    &surface_texture
+                        .texture
+                        .create_view(&wgpu::TextureViewDescriptor::default()),
+                );
 
+                render_state.intermediate = Some(intermediate);
+            } else {
+                render_state.intermediate = None;
+
+                vello::block_on_wgpu(
+                    &device_handle.device,
+                    renderer.as_mut().unwrap().render_to_surface_async(
+                        &device_handle.device,
+                        &device_handle.queue,
+                        &scene,
+                        &surface_texture,
+                        &render_params,
+                    ),
+                )
+                .expect("failed to render to surface");
+            }
+
+            governor.record(work_start.elapsed().as_secs_f64());
             surface_texture.present();
             device_handle.device.poll(wgpu::Maintain::Poll);
+            gpu_memory = gpu_memory_report();
+
+            if let (Some(mirror), Some(viewer_content)) = (&mut mirror_state, &viewer_content) {
+                present_mirror(
+                    device_handle,
+                    mirror,
+                    viewer_content,
+                    factor,
+                    downscale_filter,
+                    backdrop.color(),
+                );
+            }
         }
         Event::Suspended => {
             if let Some(render_state) = render_state.take() {
@@ -203,27 +1143,340 @@ fn main() {
                     .unwrap()
             });
             let size = window.inner_size();
-            let surface_future = render_cx.create_surface(&window, size.width, size.height);
+            let surface =
+                unsafe { instance.create_surface(&window) }.expect("Error creating surface");
+
+            if gpu.is_none() {
+                match pollster::block_on(request_device(&instance, Some(&surface))) {
+                    Ok(device) => {
+                        let info = device.adapter.get_info();
+                        eprintln!(
+                            "Rendering on {} ({:?}) with tier {:?}",
+                            info.name, info.backend, device.tier
+                        );
+                        gpu = Some(device);
+                    }
+                    Err(error) => {
+                        eprintln!("No device to render on: {error}");
+                        *control_flow = ControlFlow::Exit;
+                        return;
+                    }
+                }
+            }
+            let device_handle = gpu.as_ref().unwrap();
+
+            let Some(surface) = WindowSurface::new(
+                surface,
+                &device_handle.adapter,
+                &device_handle.device,
+                size.width,
+                size.height,
+                wgpu::PresentMode::AutoVsync,
+            ) else {
+                eprintln!("The window's surface has no format Vello can render to");
+                *control_flow = ControlFlow::Exit;
+                return;
+            };
+
+            match create_renderer(
+                &device_handle.device,
+                &device_handle.queue,
+                Some(surface.format()),
+            ) {
+                Ok(created) => renderer = Some(created),
+                Err(error) => {
+                    eprintln!("Failed to create renderer: {error}");
+                    *control_flow = ControlFlow::Exit;
+                    return;
+                }
+            }
 
-            let surface = pollster::block_on(surface_future).expect("Error creating surface");
             render_state = {
-                let render_state = RenderState { window, surface };
-                renderer = Some(
-                    Renderer::new(
-                        &render_cx.devices[render_state.surface.dev_id].device,
-                        &RendererOptions {
-                            surface_format: Some(render_state.surface.format),
-                            timestamp_period: render_cx.devices[render_state.surface.dev_id]
-                                .queue
-                                .get_timestamp_period(),
-                        },
-                    )
-                    .expect("Could create renderer"),
-                );
-                Some(render_state)
+                let blitter = Blitter::new(&device_handle.device, surface.format());
+
+                for viewer_content in viewer_content.iter().chain(&demo_content) {
+                    viewer_content.handle_resize(size.width, size.height);
+                }
+
+                Some(RenderState {
+                    window,
+                    surface,
+                    blitter,
+                    intermediate: None,
+                })
             };
+            if args.mirror && mirror_state.is_none() {
+                let window = WindowBuilder::new()
+                    .with_inner_size(INITIAL_WINDOW_SIZE)
+                    .with_resizable(true)
+                    .with_title("Rive on Vello demo | mirror")
+                    .build(_event_loop)
+                    .unwrap();
+                mirror_state = create_mirror(&instance, device_handle, window);
+            }
+
             *control_flow = ControlFlow::Poll;
         }
+        Event::LoopDestroyed => {
+            // Contents only hold CPU memory and go first. GPU objects are released once the
+            // device is idle, surfaces before their windows and everything before the device.
+            desynced.clear();
+            viewer_content = None;
+            if let Some(gpu) = &gpu {
+                gpu.device.poll(wgpu::Maintain::Wait);
+            }
+            rasterizer = None;
+            mirror_state = None;
+            render_state = None;
+            cached_window = None;
+            renderer = None;
+            gpu = None;
+
+            let live = ViewerContent::debug_live_counts();
+            if !live.is_empty() {
+                eprintln!("Leaked on shutdown: {live:?}");
+            }
+        }
         _ => {}
     });
 }
+
+/// Appends `factor`² copies of `fragment` in a grid covering a `width` by `height` surface.
+fn append_grid(
+    builder: &mut SceneBuilder,
+    fragment: &SceneFragment,
+    root: Affine,
+    factor: u32,
+    width: u32,
+    height: u32,
+) {
+    for i in 0..factor.pow(2) {
+        builder.append(fragment, Some(root * grid_cell(i, factor, width, height)));
+    }
+}
+
+/// Offset of the `i`th cell, in reading order, of a `factor` by `factor` grid covering a `width`
+/// by `height` surface.
+fn grid_cell(i: u32, factor: u32, width: u32, height: u32) -> Affine {
+    Affine::translate(Vec2::new(
+        (i % factor) as f64 * width as f64 / factor as f64,
+        (i / factor) as f64 * height as f64 / factor as f64,
+    ))
+}
+
+/// Keeps `builder` from staying empty when the content can't be drawn. Vello currently crashes
+/// when rendering an empty scene.
+fn placeholder(builder: &mut SceneBuilder) {
+    builder.fill(
+        Fill::NonZero,
+        Affine::IDENTITY,
+        Color::TRANSPARENT,
+        None,
+        &Rect::new(0.0, 0.0, 0.0, 0.0),
+    );
+}
+
+/// A strip along the bottom of the window showing the loop points on the active animation's
+/// timeline, along with the playhead. Only drawn while a loop point is set.
+fn draw_loop_region(
+    builder: &mut SceneBuilder,
+    viewer_content: &ViewerContent,
+    region: &LoopRegion,
+    transform: Affine,
+    width: u32,
+    height: u32,
+) {
+    if region.start.is_none() && region.end.is_none() {
+        return;
+    }
+    let (Some(info), Some(time)) = (viewer_content.animation_info(), viewer_content.time()) else {
+        return;
+    };
+    let length = info.time_at_frame(info.duration_frames);
+    if length <= 0.0 {
+        return;
+    }
+
+    let (width, height) = (width as f64, height as f64);
+    let top = height - LOOP_STRIP_HEIGHT;
+    let x = |seconds: f32| (seconds / length).clamp(0.0, 1.0) as f64 * width;
+    let line = |seconds: f32| Rect::new(x(seconds) - 1.0, top, x(seconds) + 1.0, height);
+    let mut fill = |color: Color, rect: Rect| {
+        builder.fill(Fill::NonZero, transform, color, None, &rect);
+    };
+
+    fill(LOOP_STRIP_COLOR, Rect::new(0.0, top, width, height));
+    if let (Some(start), Some(end)) = (region.start, region.end) {
+        let (start, end) = (x(start.min(end)), x(start.max(end)));
+        fill(LOOP_REGION_COLOR, Rect::new(start, top, end, height));
+    }
+    for marker in [region.start, region.end].into_iter().flatten() {
+        fill(Color::YELLOW, line(marker));
+    }
+    fill(Color::WHITE, line(time));
+}
+
+/// Stops rendering and says so in the title bar, which stays visible in task switchers.
+fn hide(throttle: &mut Throttle, window: &Window) {
+    if !throttle.is_hidden() {
+        throttle.hide();
+        window.set_title(&format!(
+            "Rive on Vello demo | hidden, {}",
+            throttle.background()
+        ));
+    }
+}
+
+/// Resumes rendering, first catching up on the time the content missed while hidden.
+fn show(
+    throttle: &mut Throttle,
+    window: &Window,
+    frame_start_time: &mut Instant,
+    viewer_content: &mut Option<ViewerContent>,
+    desynced: &mut [ViewerContent],
+) {
+    if !throttle.is_hidden() {
+        return;
+    }
+
+    window.set_title("Rive on Vello demo");
+    for step in throttle.show(frame_start_time.elapsed().as_secs_f64()) {
+        for viewer_content in viewer_content.iter_mut().chain(desynced.iter_mut()) {
+            let _ = viewer_content.advance(step);
+        }
+    }
+    *frame_start_time = Instant::now();
+}
+
+/// Renders `frame` at `scale` times the window's size and saves it as a PNG in the working
+/// directory. Failures are reported without interrupting the viewer.
+fn save_screenshot(
+    rasterizer: &mut Rasterizer,
+    frame: &SceneFragment,
+    width: u32,
+    height: u32,
+    scale: u32,
+    output: ScaledOutput,
+    background: Color,
+) {
+    let capture = match rasterizer.render(frame, background, width, height, scale, output) {
+        Ok(capture) => capture,
+        Err(error) => {
+            eprintln!("Failed to take screenshot: {error}");
+            return;
+        }
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = PathBuf::from(format!("rive-screenshot-{timestamp}.png"));
+
+    match capture.save(&path) {
+        Ok(()) => println!(
+            "Saved {}x{} screenshot to {}",
+            capture.width,
+            capture.height,
+            path.display()
+        ),
+        Err(error) => eprintln!("Failed to save {}: {error}", path.display()),
+    }
+}
+
+fn create_mirror(
+    instance: &wgpu::Instance,
+    device_handle: &TieredDevice,
+    window: Window,
+) -> Option<MirrorState> {
+    let size = window.inner_size();
+    let surface = unsafe { instance.create_surface(&window) }.ok()?;
+
+    // Never wait for the mirror's vsync; the primary window paces the frames.
+    let surface = WindowSurface::new(
+        surface,
+        &device_handle.adapter,
+        &device_handle.device,
+        size.width,
+        size.height,
+        wgpu::PresentMode::AutoNoVsync,
+    )?;
+
+    let renderer = match create_renderer(
+        &device_handle.device,
+        &device_handle.queue,
+        Some(surface.format()),
+    ) {
+        Ok(renderer) => renderer,
+        Err(error) => {
+            eprintln!("Failed to create mirror renderer: {error}");
+            return None;
+        }
+    };
+
+    Some(MirrorState {
+        surface,
+        window,
+        renderer,
+    })
+}
+
+/// Draws the pose the primary window advanced to, fitted to the mirror's own size. Skips the
+/// frame if the mirror's surface isn't ready or the scene is unavailable.
+fn present_mirror(
+    device_handle: &TieredDevice,
+    mirror: &mut MirrorState,
+    viewer_content: &ViewerContent,
+    factor: u32,
+    downscale_filter: DownscaleFilter,
+    background: Color,
+) {
+    let width = mirror.surface.config.width;
+    let height = mirror.surface.config.height;
+
+    let mut vello_renderer = VelloRenderer::default();
+    vello_renderer.set_downscale_filter(downscale_filter);
+    if viewer_content
+        .draw(&mut vello_renderer, width, height)
+        .is_err()
+    {
+        return;
+    }
+
+    let Ok(surface_texture) = mirror.surface.surface.get_current_texture() else {
+        return;
+    };
+
+    let mut scene = Scene::default();
+    let mut builder = SceneBuilder::for_scene(&mut scene);
+    append_grid(
+        &mut builder,
+        &vello_renderer.scene,
+        Affine::IDENTITY,
+        factor,
+        width,
+        height,
+    );
+
+    let result = vello::block_on_wgpu(
+        &device_handle.device,
+        mirror.renderer.render_to_surface_async(
+            &device_handle.device,
+            &device_handle.queue,
+            &scene,
+            &surface_texture,
+            &vello::RenderParams {
+                base_color: background,
+                width,
+                height,
+            },
+        ),
+    );
+
+    if let Err(error) = result {
+        eprintln!("Failed to render mirror: {error}");
+        return;
+    }
+
+    surface_texture.present();
+}
@@ -0,0 +1,240 @@
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use vello::{
+    kurbo::Size,
+    peniko::{Brush, Color, Format, Image},
+};
+
+/// Supplies the brush of a custom paint slot, e.g. from a video frame or a chart rendered by the
+/// host.
+///
+/// Slots are authored as image assets named after the key the provider is registered with,
+/// extension aside: an image of `video.png` is filled by the provider registered as `video`.
+/// The placeholder image only gives the slot its size; it's drawn with the provider's brush
+/// instead, under the image's transform, blend mode, opacity and clips.
+pub trait CustomPaintProvider {
+    /// The brush filling a slot of `size` pixels at `time`, in the slot's own pixels with the
+    /// origin at its top-left corner.
+    fn brush(&mut self, size: Size, time: f64) -> Brush;
+
+    /// Changes whenever `brush` would return a different brush at `time`. The renderer reuses
+    /// the last brush of a slot until the generation or the slot's size changes, so providers
+    /// returning a new image each frame must bump it each frame too.
+    fn generation(&self, time: f64) -> u64;
+}
+
+struct Cached {
+    generation: u64,
+    size: Size,
+    brush: Brush,
+}
+
+struct Slot {
+    provider: Box<dyn CustomPaintProvider>,
+    cached: Option<Cached>,
+}
+
+/// Custom paint providers by key. Clones share the same providers, so that the renderers of
+/// successive frames, and the layers of `VelloRenderer::draw_with_order`, use the same brush
+/// caches.
+#[derive(Clone, Default)]
+pub struct CustomPaints {
+    slots: Rc<RefCell<HashMap<String, Slot>>>,
+}
+
+impl CustomPaints {
+    /// Fills the slots named `key`, replacing any previous provider.
+    pub fn register(&self, key: &str, provider: Box<dyn CustomPaintProvider>) {
+        self.slots.borrow_mut().insert(
+            key.to_string(),
+            Slot {
+                provider,
+                cached: None,
+            },
+        );
+    }
+
+    /// Returns `false` if nothing was registered as `key`. Its slots show their placeholder
+    /// image again.
+    pub fn unregister(&self, key: &str) -> bool {
+        self.slots.borrow_mut().remove(key).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.borrow().is_empty()
+    }
+
+    /// The key and brush of the slot of image asset `asset`, if it has a provider.
+    pub(crate) fn brush(&self, asset: &str, size: Size, time: f64) -> Option<(String, Brush)> {
+        let key = slot_key(asset);
+        let mut slots = self.slots.borrow_mut();
+        let slot = slots.get_mut(key)?;
+
+        let generation = slot.provider.generation(time);
+        let brush = match &slot.cached {
+            Some(cached) if cached.generation == generation && cached.size == size => {
+                cached.brush.clone()
+            }
+            _ => {
+                let brush = slot.provider.brush(size, time);
+                slot.cached = Some(Cached {
+                    generation,
+                    size,
+                    brush: brush.clone(),
+                });
+                brush
+            }
+        };
+
+        Some((key.to_string(), brush))
+    }
+}
+
+impl fmt::Debug for CustomPaints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.slots.borrow().keys()).finish()
+    }
+}
+
+/// The slot an image asset fills: its name up to the extension.
+fn slot_key(asset: &str) -> &str {
+    asset.rsplit_once('.').map_or(asset, |(stem, _)| stem)
+}
+
+/// Example provider scrolling a two-color checkerboard diagonally, one step per generation.
+#[derive(Clone, Debug)]
+pub struct Checkerboard {
+    /// Cells across the slot's shorter side.
+    pub cells: u32,
+    pub colors: [Color; 2],
+    /// Steps per second. Each step moves the pattern by one pixel.
+    pub rate: f64,
+}
+
+impl Default for Checkerboard {
+    fn default() -> Self {
+        Self {
+            cells: 8,
+            colors: [Color::rgb8(0x20, 0x20, 0x20), Color::rgb8(0xe0, 0xe0, 0xe0)],
+            rate: 30.0,
+        }
+    }
+}
+
+impl CustomPaintProvider for Checkerboard {
+    fn brush(&mut self, size: Size, time: f64) -> Brush {
+        let width = (size.width.ceil() as u32).max(1);
+        let height = (size.height.ceil() as u32).max(1);
+        let cell = (width.min(height) / self.cells.max(1)).max(1);
+        let offset = self.generation(time) as u32 % (2 * cell);
+
+        let data: Vec<u8> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| {
+                let parity = ((x + offset) / cell + (y + offset) / cell) % 2;
+                let color = self.colors[parity as usize];
+                [color.r, color.g, color.b, color.a]
+            })
+            .collect();
+
+        Brush::Image(Image::new(data.into(), Format::Rgba8, width, height))
+    }
+
+    fn generation(&self, time: f64) -> u64 {
+        (time * self.rate).max(0.0) as u64
+    }
+}
+
+/// How images whose asset failed to load are drawn, over their authored size. See
+/// `ViewerContent::missing_assets`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MissingAssetStyle {
+    /// A still magenta and black checkerboard.
+    #[default]
+    Checker,
+    Solid(Color),
+}
+
+impl MissingAssetStyle {
+    pub(crate) fn brush(&self, size: Size) -> Brush {
+        match *self {
+            Self::Checker => Checkerboard {
+                colors: [Color::rgb8(0xff, 0x00, 0xff), Color::BLACK],
+                rate: 0.0,
+                ..Checkerboard::default()
+            }
+            .brush(size, 0.0),
+            Self::Solid(color) => Brush::Solid(color),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the brushes it was asked for.
+    struct Counting(Rc<RefCell<u32>>);
+
+    impl CustomPaintProvider for Counting {
+        fn brush(&mut self, _size: Size, _time: f64) -> Brush {
+            *self.0.borrow_mut() += 1;
+            Brush::Solid(Color::RED)
+        }
+
+        fn generation(&self, time: f64) -> u64 {
+            time as u64
+        }
+    }
+
+    #[test]
+    fn matches_assets_by_stem() {
+        assert_eq!(slot_key("video.png"), "video");
+        assert_eq!(slot_key("chart.v2.webp"), "chart.v2");
+        assert_eq!(slot_key("video"), "video");
+    }
+
+    #[test]
+    fn brushes_are_cached_per_generation() {
+        let calls = Rc::new(RefCell::new(0));
+        let paints = CustomPaints::default();
+        paints.register("video", Box::new(Counting(calls.clone())));
+
+        let size = Size::new(16.0, 9.0);
+        assert!(paints.brush("other.png", size, 0.0).is_none());
+
+        for time in [0.0, 0.25, 0.5] {
+            let (key, _) = paints.brush("video.png", size, time).unwrap();
+            assert_eq!(key, "video");
+        }
+        assert_eq!(*calls.borrow(), 1);
+
+        paints.brush("video.png", size, 1.0);
+        paints.brush("video.png", Size::new(32.0, 18.0), 1.0);
+        assert_eq!(*calls.borrow(), 3);
+
+        assert!(paints.unregister("video"));
+        assert!(paints.brush("video.png", size, 1.0).is_none());
+    }
+
+    #[test]
+    fn checkerboard_scrolls() {
+        let mut checkerboard = Checkerboard {
+            cells: 2,
+            rate: 1.0,
+            ..Checkerboard::default()
+        };
+        let size = Size::new(4.0, 4.0);
+
+        let Brush::Image(first) = checkerboard.brush(size, 0.0) else {
+            panic!()
+        };
+        let Brush::Image(moved) = checkerboard.brush(size, 1.0) else {
+            panic!()
+        };
+
+        assert_eq!((first.width, first.height), (4, 4));
+        assert_ne!(first.data.data(), moved.data.data());
+        assert_eq!(checkerboard.generation(1.5), 1);
+    }
+}
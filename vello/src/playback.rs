@@ -0,0 +1,218 @@
+const LOOP_ONE_SHOT: i32 = 0;
+const LOOP_PING_PONG: i32 = 2;
+
+/// Mirrors the C++ `PlaybackState`: where the active linear animation is in its timeline.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct PlaybackState {
+    pub time: f32,
+    pub start: f32,
+    pub end: f32,
+    /// Animation seconds per second, signed by the current direction.
+    pub speed: f32,
+    pub loop_value: i32,
+}
+
+impl PlaybackState {
+    fn duration(&self) -> f32 {
+        self.end - self.start
+    }
+
+    /// Animation seconds already covered in the current direction since the boundary the
+    /// animation left.
+    fn progress(&self) -> f32 {
+        let progress = if self.speed >= 0.0 {
+            self.time - self.start
+        } else {
+            self.end - self.time
+        };
+        progress.clamp(0.0, self.duration().max(0.0))
+    }
+
+    /// The boundary the animation is heading to and how many animation seconds are left until
+    /// it. An animation resting on a boundary only stops at the next one.
+    fn next_boundary(&self) -> (f32, f32) {
+        let (boundary, other, distance) = if self.speed >= 0.0 {
+            (self.end, self.start, self.end - self.time)
+        } else {
+            (self.start, self.end, self.time - self.start)
+        };
+
+        match self.loop_value {
+            _ if distance > 0.0 => (boundary, distance),
+            LOOP_ONE_SHOT => (boundary, 0.0),
+            LOOP_PING_PONG => (other, self.duration()),
+            _ => (boundary, self.duration()),
+        }
+    }
+}
+
+/// What the content should do with the current frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Step {
+    /// Advance by the given number of seconds.
+    Advance(f64),
+    /// Seek to `time` without advancing and stay paused there.
+    Boundary { time: f32, iteration: u32 },
+}
+
+/// Host-side clock sitting between the frame time and the content.
+#[derive(Debug)]
+pub(crate) struct Playback {
+    paused: bool,
+    pause_at_loop_end: bool,
+    speed: f64,
+    travelled: f64,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            pause_at_loop_end: false,
+            speed: 1.0,
+            travelled: 0.0,
+        }
+    }
+}
+
+impl Playback {
+    /// Starts counting loops again from where `state` is, after a scene was selected or the
+    /// animation's time or range was moved.
+    pub fn restart(&mut self, state: Option<PlaybackState>) {
+        self.travelled = state.map_or(0.0, |state| state.progress() as f64);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.pause_at_loop_end = false;
+    }
+
+    /// Resumes playback and cancels a pending `pause_at_loop_end`.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.pause_at_loop_end = false;
+    }
+
+    pub fn pause_at_loop_end(&mut self) {
+        if !self.paused {
+            self.pause_at_loop_end = true;
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn step(&mut self, elapsed: f64, state: Option<PlaybackState>) -> Step {
+        if self.paused {
+            return Step::Advance(0.0);
+        }
+
+        let elapsed = elapsed * self.speed;
+
+        let Some(state) = state else {
+            return Step::Advance(elapsed);
+        };
+        let travel = elapsed * state.speed.abs() as f64;
+        let (boundary, distance) = state.next_boundary();
+
+        if !self.pause_at_loop_end || travel < distance as f64 {
+            self.travelled += travel;
+            return Step::Advance(elapsed);
+        }
+
+        self.travelled += distance as f64;
+        self.paused = true;
+        self.pause_at_loop_end = false;
+
+        // A range without length completes a pass at every boundary.
+        let iteration = if state.loop_value == LOOP_ONE_SHOT || state.duration() <= 0.0 {
+            1
+        } else {
+            (self.travelled / state.duration() as f64).round() as u32
+        };
+
+        Step::Boundary {
+            time: boundary,
+            iteration,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOOP: i32 = 1;
+    const FRAME: f64 = 0.013;
+
+    fn at(time: f32, speed: f32) -> PlaybackState {
+        PlaybackState {
+            time,
+            start: 0.5,
+            end: 1.5,
+            speed,
+            loop_value: LOOP,
+        }
+    }
+
+    #[test]
+    fn restarts_from_the_current_pass() {
+        let mut playback = Playback::default();
+
+        // A quarter of a pass left in either direction makes the boundary the first one.
+        for state in [at(1.25, 1.0), at(0.75, -1.0)] {
+            playback.restart(Some(state));
+            playback.resume();
+            playback.pause_at_loop_end();
+            assert!(matches!(
+                playback.step(1.0, Some(state)),
+                Step::Boundary { iteration: 1, .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn zero_length_loops_count_one_pass() {
+        let mut playback = Playback::default();
+        let state = PlaybackState {
+            start: 1.5,
+            ..at(1.5, 1.0)
+        };
+
+        playback.restart(Some(state));
+        playback.pause_at_loop_end();
+        assert_eq!(
+            playback.step(FRAME, Some(state)),
+            Step::Boundary {
+                time: 1.5,
+                iteration: 1
+            }
+        );
+    }
+
+    #[test]
+    fn resume_cancels_pending_pause() {
+        let mut playback = Playback::default();
+        let state = at(1.4, 1.0);
+
+        playback.pause_at_loop_end();
+        playback.resume();
+
+        for _ in 0..1_000 {
+            assert!(matches!(
+                playback.step(FRAME, Some(state)),
+                Step::Advance(elapsed) if elapsed == FRAME
+            ));
+        }
+    }
+}
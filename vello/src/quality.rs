@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+const WINDOW_LEN: usize = 30;
+const STEP_DOWN_WINDOWS: u32 = 3;
+const STEP_UP_WINDOWS: u32 = 10;
+const HEADROOM_FACTOR: f64 = 0.7;
+
+/// Steps of the quality ladder, from best to cheapest. `VelloRenderer` has no approximation
+/// passes that could be turned off, so the ladder starts by lowering the internal render
+/// scale and ends by capping the frame rate.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+pub enum QualityLevel {
+    #[default]
+    Full,
+    Scaled75,
+    Scaled50,
+    Capped,
+}
+
+impl QualityLevel {
+    const LADDER: [Self; 4] = [Self::Full, Self::Scaled75, Self::Scaled50, Self::Capped];
+
+    /// Scale at which the scene should be rendered before being stretched over the surface.
+    pub fn render_scale(self) -> f64 {
+        match self {
+            Self::Full => 1.0,
+            Self::Scaled75 => 0.75,
+            Self::Scaled50 | Self::Capped => 0.5,
+        }
+    }
+
+    /// Minimum time between two frames, if any.
+    pub fn frame_cap(self) -> Option<Duration> {
+        match self {
+            Self::Capped => Some(Duration::from_secs_f64(1.0 / 30.0)),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::LADDER
+            .iter()
+            .position(|&level| level == self)
+            .unwrap()
+    }
+
+    fn lower(self) -> Self {
+        Self::LADDER[(self.index() + 1).min(Self::LADDER.len() - 1)]
+    }
+
+    fn higher(self) -> Self {
+        Self::LADDER[self.index().saturating_sub(1)]
+    }
+}
+
+/// Adapts the `QualityLevel` to the measured frame times. Frame times are grouped in windows
+/// of `WINDOW_LEN` frames; the quality drops one step after `STEP_DOWN_WINDOWS` consecutive
+/// windows whose 95th percentile misses the target, and climbs back one step after
+/// `STEP_UP_WINDOWS` consecutive windows with comfortable headroom.
+#[derive(Debug)]
+pub struct QualityGovernor {
+    target: f64,
+    level: QualityLevel,
+    auto: bool,
+    window: Vec<f64>,
+    slow_windows: u32,
+    fast_windows: u32,
+}
+
+impl QualityGovernor {
+    /// A governor which adapts the quality in order to hit `target` frame times.
+    pub fn auto(target: Duration) -> Self {
+        Self {
+            target: target.as_secs_f64(),
+            level: QualityLevel::Full,
+            auto: true,
+            window: Vec::with_capacity(WINDOW_LEN),
+            slow_windows: 0,
+            fast_windows: 0,
+        }
+    }
+
+    /// A governor which never leaves `level`.
+    pub fn fixed(level: QualityLevel) -> Self {
+        Self {
+            level,
+            auto: false,
+            ..Self::auto(Duration::ZERO)
+        }
+    }
+
+    pub fn level(&self) -> QualityLevel {
+        self.level
+    }
+
+    pub fn is_auto(&self) -> bool {
+        self.auto
+    }
+
+    /// Overrides the current level. An automatic governor keeps adapting from there.
+    pub fn set_level(&mut self, level: QualityLevel) {
+        self.level = level;
+        self.window.clear();
+        self.slow_windows = 0;
+        self.fast_windows = 0;
+    }
+
+    /// Records the time spent encoding and rendering a frame, in seconds. Time spent waiting
+    /// for vsync or for the frame cap mustn't be included, or a capped governor would never
+    /// see the headroom to step back up. Returns the new level if it changed.
+    pub fn record(&mut self, frame_time: f64) -> Option<QualityLevel> {
+        if !self.auto {
+            return None;
+        }
+
+        self.window.push(frame_time);
+
+        if self.window.len() < WINDOW_LEN {
+            return None;
+        }
+
+        let p95 = percentile(&mut self.window, 0.95);
+        self.window.clear();
+
+        if p95 > self.target {
+            self.slow_windows += 1;
+            self.fast_windows = 0;
+        } else if p95 < self.target * HEADROOM_FACTOR {
+            self.fast_windows += 1;
+            self.slow_windows = 0;
+        } else {
+            self.slow_windows = 0;
+            self.fast_windows = 0;
+        }
+
+        let level = if self.slow_windows == STEP_DOWN_WINDOWS {
+            self.level.lower()
+        } else if self.fast_windows == STEP_UP_WINDOWS {
+            self.level.higher()
+        } else {
+            return None;
+        };
+
+        self.slow_windows = 0;
+        self.fast_windows = 0;
+
+        (level != self.level).then(|| {
+            self.level = level;
+            level
+        })
+    }
+}
+
+fn percentile(samples: &mut [f64], percentile: f64) -> f64 {
+    samples.sort_by(f64::total_cmp);
+
+    let index = (samples.len() as f64 * percentile).ceil() as usize;
+    samples[index.saturating_sub(1).min(samples.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: Duration = Duration::from_millis(16);
+
+    fn record_windows(governor: &mut QualityGovernor, windows: u32, frame_time: f64) {
+        for _ in 0..windows as usize * WINDOW_LEN {
+            governor.record(frame_time);
+        }
+    }
+
+    #[test]
+    fn steps_down_after_sustained_drops() {
+        let mut governor = QualityGovernor::auto(TARGET);
+
+        record_windows(&mut governor, STEP_DOWN_WINDOWS - 1, 0.030);
+        assert_eq!(governor.level(), QualityLevel::Full);
+
+        record_windows(&mut governor, 1, 0.030);
+        assert_eq!(governor.level(), QualityLevel::Scaled75);
+
+        record_windows(&mut governor, STEP_DOWN_WINDOWS * 10, 0.030);
+        assert_eq!(governor.level(), QualityLevel::Capped);
+    }
+
+    #[test]
+    fn ignores_isolated_spikes() {
+        let mut governor = QualityGovernor::auto(TARGET);
+
+        for i in 0..WINDOW_LEN * STEP_DOWN_WINDOWS as usize * 4 {
+            governor.record(if i % WINDOW_LEN == 0 { 0.1 } else { 0.010 });
+        }
+
+        assert_eq!(governor.level(), QualityLevel::Full);
+    }
+
+    #[test]
+    fn steps_up_with_headroom() {
+        let mut governor = QualityGovernor::auto(TARGET);
+        governor.set_level(QualityLevel::Scaled50);
+
+        record_windows(&mut governor, STEP_UP_WINDOWS, 0.005);
+        assert_eq!(governor.level(), QualityLevel::Scaled75);
+
+        // Frame times close to the target are neither slow nor fast.
+        record_windows(&mut governor, STEP_UP_WINDOWS * 2, 0.014);
+        assert_eq!(governor.level(), QualityLevel::Scaled75);
+    }
+
+    #[test]
+    fn capped_frames_recover() {
+        let mut governor = QualityGovernor::auto(TARGET);
+        governor.set_level(QualityLevel::Capped);
+        let interval = QualityLevel::Capped.frame_cap().unwrap().as_secs_f64();
+
+        // Frames 1/30 s apart whose work only takes 5 ms have plenty of headroom.
+        let work = 0.005;
+        assert!(interval > TARGET.as_secs_f64());
+        record_windows(&mut governor, STEP_UP_WINDOWS, work);
+        assert_eq!(governor.level(), QualityLevel::Scaled50);
+
+        record_windows(&mut governor, STEP_UP_WINDOWS * 2, work);
+        assert_eq!(governor.level(), QualityLevel::Full);
+    }
+
+    #[test]
+    fn fixed_never_changes() {
+        let mut governor = QualityGovernor::fixed(QualityLevel::Full);
+
+        record_windows(&mut governor, STEP_DOWN_WINDOWS * 4, 1.0);
+        assert_eq!(governor.level(), QualityLevel::Full);
+    }
+}
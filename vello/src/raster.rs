@@ -0,0 +1,741 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    io::Cursor,
+    rc::{Rc, Weak},
+};
+
+use image::{io::Reader, ImageResult};
+use vello::{
+    kurbo::{Affine, Size, Vec2},
+    peniko::{Format, Image},
+};
+
+use crate::{
+    gpu_memory::{self, Evict, GpuAllocation, GpuCategory},
+    live::{Kind, Live},
+};
+
+/// Relative change of the on-screen scale after which the variant is picked again. Smaller
+/// changes keep the current variant so that zooming around a threshold doesn't thrash.
+const RESELECT_THRESHOLD: f64 = 0.25;
+/// A variant this much smaller than the on-screen density is still preferred over the next
+/// larger one.
+const DENSITY_TOLERANCE: f64 = 0.05;
+/// PNG `pHYs` pixels per meter of a 1x image, i.e. 72 DPI.
+const PPM_1X: f64 = 72.0 / 0.0254;
+/// How far past a mip level's scale range the on-screen scale has to go before another level
+/// is picked, relative to the boundary.
+const MIP_HYSTERESIS: f64 = 0.1;
+
+/// How images are sampled when they're drawn smaller than their pixels.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DownscaleFilter {
+    /// Samples the full resolution pixels, which shimmers once images are drawn at less than
+    /// half their size.
+    Naive,
+    /// Samples a box-filtered mip level within a factor of 2 of the on-screen size. Levels are
+    /// generated on the CPU the first time they're needed and kept with the image.
+    #[default]
+    Mipmap,
+}
+
+/// The variant an image was drawn with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageStats {
+    /// Name of the asset the artboard refers to.
+    pub asset: String,
+    /// Name of the asset actually drawn.
+    pub variant: String,
+    pub density: f64,
+    /// On-screen pixels per 1x pixel.
+    pub scale: f64,
+    pub width: u32,
+    pub height: u32,
+    /// Mip level drawn, 0 being the variant's own pixels.
+    pub level: u32,
+    /// Bytes of the mip levels generated for the variant so far.
+    pub mip_bytes: usize,
+}
+
+#[derive(Debug)]
+struct Variant {
+    name: String,
+    density: f64,
+    encoded: Rc<[u8]>,
+    /// `None` until the variant is drawn, and again once its texture is evicted.
+    decoded: RefCell<Option<Option<Image>>>,
+    /// Levels 1 and up, each half the size of the previous one.
+    mips: RefCell<Vec<Image>>,
+    /// The level last drawn, which Vello uploads.
+    texture: RefCell<Option<GpuAllocation>>,
+}
+
+impl Variant {
+    fn new(name: String, density: f64, encoded: Rc<[u8]>) -> Self {
+        Self {
+            name,
+            density,
+            encoded,
+            decoded: RefCell::new(None),
+            mips: RefCell::new(Vec::new()),
+            texture: RefCell::new(None),
+        }
+    }
+
+    /// The decoded pixels, or `None` if they can't be decoded. Either is kept, so a corrupt
+    /// image drawn every frame is only decoded once.
+    fn image(&self) -> Option<Image> {
+        self.decoded
+            .borrow_mut()
+            .get_or_insert_with(|| decode(&self.encoded).ok())
+            .clone()
+    }
+
+    /// Counts `level` as the variant's texture, drawn by `owner`.
+    fn upload(self: &Rc<Self>, level: &Image, owner: usize) {
+        let bytes = level.data.data().len() as u64;
+        match &mut *self.texture.borrow_mut() {
+            Some(texture) => texture.resize(bytes),
+            texture => *texture = Some(GpuAllocation::new(GpuCategory::Images, bytes)),
+        }
+
+        let variant: Weak<dyn Evict> = Rc::downgrade(self);
+        gpu_memory::touch_image(variant, bytes, owner);
+    }
+
+    /// The pixels of mip `level`, generating the missing levels up to it. Levels stop at 1x1,
+    /// so the returned level may be smaller than asked for.
+    fn mip(&self, image: &Image, level: u32) -> (Image, u32) {
+        if level == 0 {
+            return (image.clone(), 0);
+        }
+
+        let mut mips = self.mips.borrow_mut();
+        while mips.len() < level as usize {
+            let last = mips.last().unwrap_or(image);
+            if last.width == 1 && last.height == 1 {
+                break;
+            }
+            let next = downscale(last);
+            mips.push(next);
+        }
+
+        match mips.get(level as usize - 1).or(mips.last()) {
+            Some(mip) => (mip.clone(), (mips.len() as u32).min(level)),
+            None => (image.clone(), 0),
+        }
+    }
+
+    fn mip_bytes(&self) -> usize {
+        self.mips
+            .borrow()
+            .iter()
+            .map(|mip| mip.data.data().len())
+            .sum()
+    }
+}
+
+/// Clones don't share the texture, which is counted again once they're drawn.
+impl Clone for Variant {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            density: self.density,
+            encoded: self.encoded.clone(),
+            decoded: self.decoded.clone(),
+            mips: self.mips.clone(),
+            texture: RefCell::new(None),
+        }
+    }
+}
+
+impl Evict for Variant {
+    fn evict(&self) {
+        self.texture.borrow_mut().take();
+        self.mips.borrow_mut().clear();
+        self.decoded.borrow_mut().take();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Selection {
+    index: usize,
+    scale: f64,
+    /// Mip level last drawn from the selected variant.
+    level: Option<u32>,
+}
+
+/// An image asset along with the other density variants of the same image. Variants are only
+/// decoded once they're drawn.
+#[derive(Debug)]
+pub struct VelloImage {
+    own: Rc<Variant>,
+    /// Every variant, `own` included, by increasing density.
+    variants: Vec<Rc<Variant>>,
+    selection: Cell<Option<Selection>>,
+    /// Authored size of the asset this image stands in for when it failed to load.
+    missing: Option<Size>,
+    _live: Live,
+}
+
+impl VelloImage {
+    /// Returns `None` if the header can't be read. The pixels are decoded lazily.
+    pub(crate) fn new(encoded: &[u8]) -> Option<Self> {
+        Reader::new(Cursor::new(encoded))
+            .with_guessed_format()
+            .ok()?
+            .into_dimensions()
+            .ok()?;
+
+        let own = Rc::new(Variant::new(
+            String::new(),
+            png_density(encoded).unwrap_or(1.0),
+            encoded.into(),
+        ));
+
+        Some(Self {
+            variants: vec![own.clone()],
+            own,
+            selection: Cell::new(None),
+            missing: None,
+            _live: Live::new(Kind::Image),
+        })
+    }
+
+    /// A pixel-less image standing in for an asset of `width` x `height` which failed to load.
+    /// Renderers draw it with their `MissingAssetStyle`.
+    pub(crate) fn missing(width: u32, height: u32) -> Self {
+        let own = Variant::new(String::new(), 1.0, Rc::new([]));
+        *own.decoded.borrow_mut() = Some(None);
+        let own = Rc::new(own);
+
+        Self {
+            variants: vec![own.clone()],
+            own,
+            selection: Cell::new(None),
+            missing: Some(Size::new(width as f64, height as f64)),
+            _live: Live::new(Kind::Image),
+        }
+    }
+
+    /// The size of the asset the image stands in for, if it's a missing asset placeholder.
+    pub(crate) fn missing_size(&self) -> Option<Size> {
+        self.missing
+    }
+
+    /// The image's own pixels, as drawn when it has no other variants.
+    pub(crate) fn own_image(&self) -> Option<Image> {
+        self.own.image()
+    }
+
+    /// Name of the image's asset, once `link_variants` ran.
+    pub(crate) fn name(&self) -> &str {
+        &self.own.name
+    }
+
+    fn select_index(&self, scale: f64) -> usize {
+        let selection = match self.selection.get() {
+            Some(selection) if is_within_threshold(scale, selection.scale) => selection,
+            _ => Selection {
+                index: best_variant(self.variants.iter().map(|variant| variant.density), scale),
+                scale,
+                level: None,
+            },
+        };
+        self.selection.set(Some(selection));
+
+        selection.index
+    }
+
+    /// Picks the mip level for the selected variant drawn at `scale` on-screen pixels per pixel
+    /// and remembers it.
+    fn select_level(&self, scale: f64) -> u32 {
+        let Some(mut selection) = self.selection.get() else {
+            return 0;
+        };
+        let level = mip_level(scale, selection.level);
+
+        selection.level = Some(level);
+        self.selection.set(Some(selection));
+
+        level
+    }
+
+    /// Picks the variant and mip level to draw under `transform`, which maps the image's own
+    /// pixels to the screen. Returns their pixels along with the scale mapping them to the
+    /// area the own pixels would cover. The level counts as the variant's texture, evicted
+    /// along with `owner`'s.
+    pub(crate) fn select(
+        &self,
+        transform: Affine,
+        filter: DownscaleFilter,
+        owner: usize,
+    ) -> Option<(Image, Vec2, ImageStats)> {
+        let scale = transform.determinant().abs().sqrt() * self.own.density;
+        let variant = &self.variants[self.select_index(scale)];
+
+        let (variant, image) = match variant.image() {
+            Some(image) => (variant, image),
+            None => (&self.own, self.own.image()?),
+        };
+
+        let (mip, level) = match filter {
+            DownscaleFilter::Naive => (image.clone(), 0),
+            DownscaleFilter::Mipmap => {
+                variant.mip(&image, self.select_level(scale / variant.density))
+            }
+        };
+        variant.upload(&mip, owner);
+
+        let stats = ImageStats {
+            asset: self.own.name.clone(),
+            variant: variant.name.clone(),
+            density: variant.density,
+            scale,
+            width: mip.width,
+            height: mip.height,
+            level,
+            mip_bytes: variant.mip_bytes(),
+        };
+
+        // Levels round odd sizes down, so each axis is scaled back on its own.
+        let to_own = self.own.density / variant.density;
+        let scale = Vec2::new(
+            to_own * image.width as f64 / mip.width as f64,
+            to_own * image.height as f64 / mip.height as f64,
+        );
+
+        Some((mip, scale, stats))
+    }
+}
+
+/// The mip level to sample for an image drawn at `scale` on-screen pixels per pixel: the
+/// smallest level which is still at least as large as the image on screen. `current` is kept
+/// until `scale` leaves its range by more than `MIP_HYSTERESIS`.
+fn mip_level(scale: f64, current: Option<u32>) -> u32 {
+    if scale.is_nan() || scale <= 0.0 {
+        return current.unwrap_or(0);
+    }
+
+    if let Some(level) = current {
+        let upper = 0.5f64.powi(level as i32) * (1.0 + MIP_HYSTERESIS);
+        let lower = 0.5f64.powi(level as i32 + 1) * (1.0 - MIP_HYSTERESIS);
+
+        if scale >= lower && (level == 0 || scale <= upper) {
+            return level;
+        }
+    }
+
+    (-scale.log2()).floor().max(0.0) as u32
+}
+
+/// Halves `image` with a 2x2 box filter. Colors are averaged weighted by alpha so that
+/// transparent pixels don't darken their neighbors. Odd sizes repeat their last row or column.
+fn downscale(image: &Image) -> Image {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let (half_width, half_height) = ((width / 2).max(1), (height / 2).max(1));
+    let pixels = image.data.data();
+    let mut data = Vec::with_capacity(half_width * half_height * 4);
+
+    for y in 0..half_height {
+        for x in 0..half_width {
+            let mut sum = [0u32; 4];
+
+            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                let sx = (x * 2 + dx).min(width - 1);
+                let sy = (y * 2 + dy).min(height - 1);
+                let pixel = &pixels[(sy * width + sx) * 4..][..4];
+                let alpha = pixel[3] as u32;
+
+                for c in 0..3 {
+                    sum[c] += pixel[c] as u32 * alpha;
+                }
+                sum[3] += alpha;
+            }
+
+            let alpha = sum[3];
+            if alpha == 0 {
+                data.extend([0; 4]);
+            } else {
+                data.extend(sum[..3].iter().map(|c| ((c + alpha / 2) / alpha) as u8));
+                data.push(((alpha + 2) / 4) as u8);
+            }
+        }
+    }
+
+    Image::new(
+        data.into(),
+        Format::Rgba8,
+        half_width as u32,
+        half_height as u32,
+    )
+}
+
+fn is_within_threshold(scale: f64, selected_at: f64) -> bool {
+    let ratio = scale / selected_at;
+    ratio <= 1.0 + RESELECT_THRESHOLD && ratio >= 1.0 / (1.0 + RESELECT_THRESHOLD)
+}
+
+/// The smallest density which doesn't need upscaling, or the largest one if they all do.
+fn best_variant(densities: impl ExactSizeIterator<Item = f64>, scale: f64) -> usize {
+    let last = densities.len() - 1;
+    densities
+        .enumerate()
+        .find(|&(_, density)| density >= scale * (1.0 - DENSITY_TOLERANCE))
+        .map_or(last, |(i, _)| i)
+}
+
+/// Splits an `@2x`-style suffix off an asset name, e.g. `hero@2x.png` into `hero.png` and 2.
+fn split_density(name: &str) -> (String, Option<f64>) {
+    let Some(at) = name.rfind('@') else {
+        return (name.to_owned(), None);
+    };
+    let suffix = &name[at + 1..];
+    let Some(x) = suffix.find(['x', 'X']) else {
+        return (name.to_owned(), None);
+    };
+    let rest = &suffix[x + 1..];
+
+    match suffix[..x].parse::<f64>() {
+        Ok(density) if density > 0.0 && (rest.is_empty() || rest.starts_with('.')) => {
+            (format!("{}{rest}", &name[..at]), Some(density))
+        }
+        _ => (name.to_owned(), None),
+    }
+}
+
+/// Density from the `pHYs` chunk of a PNG, relative to 72 DPI.
+fn png_density(encoded: &[u8]) -> Option<f64> {
+    let mut chunks = encoded.strip_prefix(b"\x89PNG\r\n\x1a\n")?;
+
+    while chunks.len() >= 12 {
+        let len = u32::from_be_bytes(chunks[..4].try_into().unwrap()) as usize;
+        let data = chunks.get(8..8 + len)?;
+
+        match &chunks[4..8] {
+            b"pHYs" if len == 9 && data[8] == 1 => {
+                let ppm = u32::from_be_bytes(data[..4].try_into().unwrap());
+                return (ppm > 0).then(|| (ppm as f64 / PPM_1X * 100.0).round() / 100.0);
+            }
+            // pHYs must come before the image data.
+            b"IDAT" | b"IEND" => return None,
+            _ => (),
+        }
+
+        chunks = chunks.get(12 + len..)?;
+    }
+
+    None
+}
+
+/// Decodes PNGs, JPEGs and the other formats the `image` crate reads into RGBA8, whatever
+/// their own pixel format.
+fn decode(encoded: &[u8]) -> ImageResult<Image> {
+    let image = Reader::new(Cursor::new(encoded))
+        .with_guessed_format()?
+        .decode()?
+        .into_rgba8();
+    let width = image.width();
+    let height = image.height();
+
+    Ok(Image::new(
+        image.into_raw().into(),
+        Format::Rgba8,
+        width,
+        height,
+    ))
+}
+
+/// Groups images whose asset names only differ by their density suffix so that each of them
+/// can be drawn with the variant closest to its on-screen density. Images without a suffix use
+/// their PNG resolution, or 1x.
+pub(crate) fn link_variants<'i>(images: impl IntoIterator<Item = (&'i mut VelloImage, &'i str)>) {
+    let mut groups: HashMap<String, Vec<&mut VelloImage>> = HashMap::new();
+
+    for (image, name) in images {
+        let (key, density) = split_density(name);
+
+        let own = Rc::make_mut(&mut image.own);
+        own.name = name.to_owned();
+        if let Some(density) = density {
+            own.density = density;
+        }
+
+        groups.entry(key).or_default().push(image);
+    }
+
+    for images in groups.into_values() {
+        let mut variants: Vec<_> = images.iter().map(|image| image.own.clone()).collect();
+        variants.sort_by(|a, b| a.density.total_cmp(&b.density));
+
+        for image in images {
+            image.variants = variants.clone();
+            image.selection.set(None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        for (kind, data) in chunks {
+            png.extend((data.len() as u32).to_be_bytes());
+            png.extend(*kind);
+            png.extend(*data);
+            // The CRC isn't checked.
+            png.extend([0; 4]);
+        }
+
+        png
+    }
+
+    fn image(density: f64) -> VelloImage {
+        let own = Rc::new(Variant::new(String::new(), density, Rc::new([])));
+
+        VelloImage {
+            variants: vec![own.clone()],
+            own,
+            selection: Cell::new(None),
+            missing: None,
+            _live: Live::new(Kind::Image),
+        }
+    }
+
+    /// An opaque image of alternating black and white columns, the worst case for minification.
+    fn stripes(width: u32, height: u32) -> Image {
+        let data = (0..width * height)
+            .flat_map(|i| {
+                let value = if i % width % 2 == 0 { 0 } else { 255 };
+                [value, value, value, 255]
+            })
+            .collect::<Vec<u8>>();
+
+        Image::new(data.into(), Format::Rgba8, width, height)
+    }
+
+    fn decoded(pixels: Image) -> VelloImage {
+        let decoded = image(1.0);
+        *decoded.own.decoded.borrow_mut() = Some(Some(pixels));
+        decoded
+    }
+
+    fn densities(image: &VelloImage) -> Vec<f64> {
+        image
+            .variants
+            .iter()
+            .map(|variant| variant.density)
+            .collect()
+    }
+
+    #[test]
+    fn splits_density_suffixes() {
+        assert_eq!(split_density("hero@2x.png"), ("hero.png".into(), Some(2.0)));
+        assert_eq!(split_density("hero@1.5X"), ("hero".into(), Some(1.5)));
+        assert_eq!(split_density("hero.png"), ("hero.png".into(), None));
+        assert_eq!(split_density("me@home.png"), ("me@home.png".into(), None));
+        assert_eq!(split_density("hero@2xl.png"), ("hero@2xl.png".into(), None));
+    }
+
+    #[test]
+    fn reads_png_resolution() {
+        let phys = |ppm: u32, unit: u8| {
+            let mut data = [ppm.to_be_bytes(), ppm.to_be_bytes()].concat();
+            data.push(unit);
+            data
+        };
+
+        let header: (&[u8; 4], &[u8]) = (b"IHDR", &[0; 13]);
+        let at_144_dpi = phys(5669, 1);
+        let at_144_dpi = (b"pHYs", at_144_dpi.as_slice());
+        assert_eq!(png_density(&png(&[header, at_144_dpi])), Some(2.0));
+
+        let unknown_unit = phys(5669, 0);
+        assert_eq!(png_density(&png(&[header, (b"pHYs", &unknown_unit)])), None);
+
+        let after_data = png(&[header, (b"IDAT", &[]), at_144_dpi]);
+        assert_eq!(png_density(&after_data), None);
+        assert_eq!(png_density(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn groups_variants_by_name() {
+        let mut hero = image(1.0);
+        let mut hero_2x = image(1.0);
+        let mut hero_3x = image(1.0);
+        let mut other = image(1.0);
+
+        link_variants([
+            (&mut hero_3x, "hero@3x.png"),
+            (&mut hero, "hero.png"),
+            (&mut other, "other.png"),
+            (&mut hero_2x, "hero@2x.png"),
+        ]);
+
+        assert_eq!(densities(&hero), [1.0, 2.0, 3.0]);
+        assert_eq!(densities(&hero_3x), [1.0, 2.0, 3.0]);
+        assert_eq!(hero_2x.own.density, 2.0);
+        assert_eq!(densities(&other), [1.0]);
+    }
+
+    #[test]
+    fn picks_the_smallest_variant_without_upscaling() {
+        let mut hero = image(1.0);
+        let mut hero_2x = image(1.0);
+        link_variants([(&mut hero, "hero.png"), (&mut hero_2x, "hero@2x.png")]);
+
+        assert_eq!(hero.select_index(0.5), 0);
+        hero.selection.set(None);
+        assert_eq!(hero.select_index(1.0), 0);
+        hero.selection.set(None);
+        assert_eq!(hero.select_index(1.7), 1);
+        hero.selection.set(None);
+        // Nothing is large enough, so the largest variant is upscaled.
+        assert_eq!(hero.select_index(4.0), 1);
+    }
+
+    #[test]
+    fn keeps_the_variant_until_zoom_changes_enough() {
+        let mut hero = image(1.0);
+        let mut hero_2x = image(1.0);
+        link_variants([(&mut hero, "hero.png"), (&mut hero_2x, "hero@2x.png")]);
+
+        assert_eq!(hero.select_index(1.0), 0);
+        // Within the threshold of the last selection, even though 1.2 would pick 2x.
+        assert_eq!(hero.select_index(1.2), 0);
+        assert_eq!(hero.select_index(1.3), 1);
+        // Zooming back slightly doesn't switch back.
+        assert_eq!(hero.select_index(1.1), 1);
+        assert_eq!(hero.select_index(1.05), 1);
+        assert_eq!(hero.select_index(0.9), 0);
+    }
+
+    #[test]
+    fn mip_levels_follow_scale_with_hysteresis() {
+        assert_eq!(mip_level(1.0, None), 0);
+        assert_eq!(mip_level(0.6, None), 0);
+        assert_eq!(mip_level(0.5, None), 1);
+        assert_eq!(mip_level(0.3, None), 1);
+        assert_eq!(mip_level(0.25, None), 2);
+        assert_eq!(mip_level(0.1, None), 3);
+
+        // Just past a boundary keeps the current level.
+        assert_eq!(mip_level(0.27, Some(2)), 2);
+        assert_eq!(mip_level(0.28, Some(2)), 1);
+        assert_eq!(mip_level(0.52, Some(1)), 1);
+        assert_eq!(mip_level(0.56, Some(1)), 0);
+        assert_eq!(mip_level(0.48, Some(0)), 0);
+        assert_eq!(mip_level(0.44, Some(0)), 1);
+        assert_eq!(mip_level(8.0, Some(0)), 0);
+    }
+
+    #[test]
+    fn minified_stripes_average_instead_of_aliasing() {
+        let image = stripes(64, 4);
+
+        // Drawn at 0.25x, naive sampling hits every 4th column, which are all black.
+        let naive = image.data.data();
+        assert!((0..16).all(|x| naive[x * 4 * 4] == 0));
+
+        let (mip, level) = decoded(image.clone()).own.mip(&image, 2);
+        assert_eq!((mip.width, mip.height, level), (16, 1, 2));
+        assert!(mip
+            .data
+            .data()
+            .chunks_exact(4)
+            .all(|pixel| (127..=128).contains(&pixel[0]) && pixel[3] == 255));
+    }
+
+    #[test]
+    fn transparent_pixels_dont_darken_levels() {
+        let data = vec![255, 0, 0, 255, 0, 0, 0, 0, 255, 0, 0, 255, 0, 0, 0, 0];
+        let image = Image::new(data.into(), Format::Rgba8, 2, 2);
+
+        assert_eq!(downscale(&image).data.data(), [255, 0, 0, 128]);
+    }
+
+    #[test]
+    fn levels_stop_at_one_pixel() {
+        let image = stripes(5, 3);
+        let decoded = decoded(image.clone());
+
+        let (mip, level) = decoded.own.mip(&image, 10);
+        assert_eq!((mip.width, mip.height, level), (1, 1, 2));
+        // 2x1 and 1x1.
+        assert_eq!(decoded.own.mip_bytes(), 12);
+    }
+
+    #[test]
+    fn reports_the_level_drawn() {
+        let image = decoded(stripes(8, 8));
+        let quarter = Affine::scale(0.25);
+
+        let (mip, scale, stats) = image.select(quarter, DownscaleFilter::Mipmap, 0).unwrap();
+        assert_eq!((mip.width, stats.level), (2, 2));
+        assert_eq!(scale, Vec2::new(4.0, 4.0));
+
+        let (full, scale, stats) = image.select(quarter, DownscaleFilter::Naive, 0).unwrap();
+        assert_eq!((full.width, stats.level), (8, 0));
+        assert_eq!(scale, Vec2::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn evicted_variants_are_decoded_again() {
+        let mut png = Vec::new();
+        image::RgbaImage::new(8, 8)
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        let first = VelloImage::new(&png).unwrap();
+        let second = VelloImage::new(&png).unwrap();
+        gpu_memory::set_gpu_image_budget(Some(256));
+
+        first.select(Affine::IDENTITY, DownscaleFilter::Mipmap, 0);
+        second.select(Affine::IDENTITY, DownscaleFilter::Mipmap, 0);
+        assert!(first.own.decoded.borrow().is_none());
+        assert_eq!(gpu_memory::gpu_memory_report().images, 256);
+
+        let (pixels, _, _) = first
+            .select(Affine::IDENTITY, DownscaleFilter::Mipmap, 0)
+            .unwrap();
+        assert_eq!(pixels.width, 8);
+        assert!(second.own.decoded.borrow().is_none());
+        assert_eq!(gpu_memory::gpu_memory_report().images, 256);
+    }
+
+    #[test]
+    fn images_are_decoded_once() {
+        let mut png = Vec::new();
+        image::RgbaImage::new(8, 8)
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        let image = VelloImage::new(&png).unwrap();
+
+        let (first, _, _) = image
+            .select(Affine::IDENTITY, DownscaleFilter::Naive, 0)
+            .unwrap();
+        let (second, _, _) = image
+            .select(Affine::IDENTITY, DownscaleFilter::Naive, 0)
+            .unwrap();
+        assert_eq!(first.data.id(), second.data.id());
+    }
+
+    #[test]
+    fn corrupt_pixels_skip_the_draw() {
+        let mut png = Vec::new();
+        image::RgbaImage::new(64, 64)
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        // The header still reads, so the asset loads, but the pixel data is cut short.
+        png.truncate(png.len() / 2);
+        let image = VelloImage::new(&png).unwrap();
+
+        assert!(image
+            .select(Affine::IDENTITY, DownscaleFilter::Mipmap, 0)
+            .is_none());
+        assert_eq!(*image.own.decoded.borrow(), Some(None));
+    }
+}
@@ -0,0 +1,726 @@
+use std::{
+    error, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use vello::peniko::Color;
+
+use crate::{
+    Capture, CaptureError, Rasterizer, ScaledOutput, SceneUnavailable, SelectError, Substeps,
+    VelloRenderer, ViewerContent,
+};
+
+/// Seconds from the start at which every matched artboard is compared.
+pub const REVIEW_TIMES: [f64; 4] = [0.0, 0.5, 1.0, 2.0];
+/// Width and height of the compared thumbnails, in pixels.
+pub const THUMBNAIL_SIZE: u32 = 256;
+
+/// Largest weighted squared YIQ distance between two opaque 8-bit colors.
+const MAX_YIQ_DELTA: f64 = 35215.0;
+/// Differences below this, out of 1, are too faint to notice.
+const NOTICEABLE: f64 = 0.05;
+
+/// The perceptual difference between two captures of the same size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PixelDiff {
+    /// The new capture faded to gray, with differing pixels in red, brighter the larger the
+    /// difference.
+    pub image: Capture,
+    /// Mean difference of every pixel, from 0 for identical captures to about 1 for black
+    /// against white everywhere.
+    pub score: f64,
+    /// Fraction of the pixels which differ noticeably.
+    pub changed: f64,
+}
+
+/// Compares two captures pixel by pixel in YIQ, which weighs brightness over hue the way eyes
+/// do. Both are composited over white first. Captures of different sizes differ completely.
+pub fn perceptual_diff(old: &Capture, new: &Capture) -> PixelDiff {
+    let (width, height) = (new.width, new.height);
+    if (old.width, old.height) != (width, height) {
+        return PixelDiff {
+            image: new.clone(),
+            score: 1.0,
+            changed: 1.0,
+        };
+    }
+
+    let mut pixels = Vec::with_capacity(new.pixels.len());
+    let mut sum = 0.0;
+    let mut changed = 0usize;
+
+    for (old, new) in old.pixels.chunks_exact(4).zip(new.pixels.chunks_exact(4)) {
+        let (old, new) = (over_white(old), over_white(new));
+        let delta = yiq_delta(old, new);
+        sum += delta;
+
+        if delta >= NOTICEABLE {
+            changed += 1;
+            let red = (128.0 + 127.0 * delta).round() as u8;
+            pixels.extend([red, 0, 0, 255]);
+        } else {
+            let faded = (255.0 - (255.0 - luma(new)) * 0.1).round() as u8;
+            pixels.extend([faded, faded, faded, 255]);
+        }
+    }
+
+    let count = (width as usize * height as usize).max(1) as f64;
+    PixelDiff {
+        image: Capture {
+            width,
+            height,
+            pixels,
+        },
+        score: sum / count,
+        changed: changed as f64 / count,
+    }
+}
+
+fn over_white(pixel: &[u8]) -> [f64; 3] {
+    let alpha = pixel[3] as f64 / 255.0;
+    [0, 1, 2].map(|i| 255.0 + (pixel[i] as f64 - 255.0) * alpha)
+}
+
+fn luma([r, g, b]: [f64; 3]) -> f64 {
+    r * 0.29889531 + g * 0.58662247 + b * 0.11448223
+}
+
+/// Distance between two colors, out of 1.
+fn yiq_delta(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let i = |[r, g, b]: [f64; 3]| r * 0.59597799 - g * 0.2741761 - b * 0.32180189;
+    let q = |[r, g, b]: [f64; 3]| r * 0.21147017 - g * 0.52261711 + b * 0.31114694;
+
+    let y = luma(a) - luma(b);
+    let i = i(a) - i(b);
+    let q = q(a) - q(b);
+
+    ((0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q) / MAX_YIQ_DELTA)
+        .sqrt()
+        .min(1.0)
+}
+
+/// Names of two lists paired up, keeping the order of `new`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchedNames {
+    pub both: Vec<String>,
+    /// Only in the old list.
+    pub removed: Vec<String>,
+    /// Only in the new list.
+    pub added: Vec<String>,
+}
+
+impl MatchedNames {
+    pub fn new(old: &[String], new: &[String]) -> Self {
+        let mut matched = Self::default();
+
+        for name in new {
+            if old.contains(name) {
+                matched.both.push(name.clone());
+            } else {
+                matched.added.push(name.clone());
+            }
+        }
+        matched.removed = old
+            .iter()
+            .filter(|name| !new.contains(name))
+            .cloned()
+            .collect();
+
+        matched
+    }
+}
+
+/// Matches the `.riv` files directly in `old_dir` and `new_dir` by name. Subdirectories are
+/// ignored.
+pub fn match_files(old_dir: &Path, new_dir: &Path) -> io::Result<MatchedNames> {
+    let riv_files = |dir: &Path| -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "riv") {
+                names.extend(
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .map(String::from),
+                );
+            }
+        }
+        names.sort();
+
+        Ok(names)
+    };
+
+    Ok(MatchedNames::new(
+        &riv_files(old_dir)?,
+        &riv_files(new_dir)?,
+    ))
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FrameReview {
+    pub time: f64,
+    pub score: f64,
+    pub changed: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArtboardReview {
+    pub name: String,
+    pub frames: Vec<FrameReview>,
+}
+
+impl ArtboardReview {
+    /// Score of the most different frame.
+    pub fn score(&self) -> f64 {
+        self.frames
+            .iter()
+            .map(|frame| frame.score)
+            .fold(0.0, f64::max)
+    }
+}
+
+/// What reviewing one file found.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReviewOutcome {
+    /// Both versions loaded. Artboards present in only one of them aren't rendered.
+    Compared {
+        artboards: Vec<ArtboardReview>,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// The old version loaded, the new one doesn't.
+    NewlyFailing,
+    /// Only the new version loads.
+    Fixed,
+    /// Neither version loads.
+    StillFailing,
+    /// Reviewing the file didn't finish in time.
+    TimedOut { seconds: f64 },
+    /// Reviewing the file panicked, crashed or failed to render.
+    Crashed { reason: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileReview {
+    pub file: String,
+    pub outcome: ReviewOutcome,
+}
+
+impl FileReview {
+    /// How much the file changed, from 0 to 1. Files which newly fail in any way rank above
+    /// every visual change.
+    pub fn magnitude(&self) -> f64 {
+        match &self.outcome {
+            ReviewOutcome::Compared { artboards, .. } => artboards
+                .iter()
+                .map(ArtboardReview::score)
+                .fold(0.0, f64::max),
+            ReviewOutcome::NewlyFailing
+            | ReviewOutcome::TimedOut { .. }
+            | ReviewOutcome::Crashed { .. } => f64::INFINITY,
+            ReviewOutcome::Fixed => 1.0,
+            ReviewOutcome::StillFailing => 0.0,
+        }
+    }
+
+    /// Whether the file needs a look beyond its score: it no longer loads or renders, or its
+    /// artboards changed.
+    pub fn is_flagged(&self) -> bool {
+        match &self.outcome {
+            ReviewOutcome::Compared { added, removed, .. } => {
+                !added.is_empty() || !removed.is_empty()
+            }
+            ReviewOutcome::NewlyFailing
+            | ReviewOutcome::TimedOut { .. }
+            | ReviewOutcome::Crashed { .. } => true,
+            ReviewOutcome::Fixed | ReviewOutcome::StillFailing => false,
+        }
+    }
+
+    /// One line describing what happened beyond the score.
+    fn notes(&self) -> String {
+        match &self.outcome {
+            ReviewOutcome::Compared { added, removed, .. } => {
+                let mut notes = Vec::new();
+                if !added.is_empty() {
+                    notes.push(format!("gained {}", added.join(", ")));
+                }
+                if !removed.is_empty() {
+                    notes.push(format!("lost {}", removed.join(", ")));
+                }
+                notes.join("; ")
+            }
+            ReviewOutcome::NewlyFailing => "no longer loads".to_string(),
+            ReviewOutcome::Fixed => "loads again".to_string(),
+            ReviewOutcome::StillFailing => "still doesn't load".to_string(),
+            ReviewOutcome::TimedOut { seconds } => format!("timed out after {seconds}s"),
+            ReviewOutcome::Crashed { reason } => format!("crashed: {reason}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ReviewError {
+    Io(io::Error),
+    Unavailable(SceneUnavailable),
+    Select(SelectError),
+    Capture(CaptureError),
+    Save(image::ImageError),
+}
+
+impl fmt::Display for ReviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Unavailable(error) => write!(f, "{error}"),
+            Self::Select(error) => write!(f, "{error}"),
+            Self::Capture(error) => write!(f, "{error}"),
+            Self::Save(error) => write!(f, "failed to save image: {error}"),
+        }
+    }
+}
+
+impl error::Error for ReviewError {}
+
+impl From<io::Error> for ReviewError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Where the `kind` image (`old`, `new` or `diff`) of a frame is saved, relative to the report.
+pub fn image_path(file: &str, artboard: &str, time: f64, kind: &str) -> PathBuf {
+    let time = (time * 1000.0).round() as u32;
+    Path::new("images")
+        .join(slug(file))
+        .join(format!("{}-{time:05}ms-{kind}.png", slug(artboard)))
+}
+
+/// `name` with anything but ASCII letters, digits, `-`, `_` and `.` replaced, so that it's safe
+/// in paths and URLs.
+fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Renders `file` from `old_dir` and `new_dir` at `REVIEW_TIMES`, artboard by artboard, and
+/// saves the old, new and diff images under `out`.
+///
+/// Files can hang or crash the runtime, which no caller can recover from. Run this in a
+/// separate process, as `rive-diff` does, to review several files.
+pub fn review_file(
+    rasterizer: &mut Rasterizer,
+    old_dir: &Path,
+    new_dir: &Path,
+    file: &str,
+    out: &Path,
+) -> Result<ReviewOutcome, ReviewError> {
+    let old = ViewerContent::from_bytes(&fs::read(old_dir.join(file))?);
+    let new = ViewerContent::from_bytes(&fs::read(new_dir.join(file))?);
+
+    let (mut old, mut new) = match (old, new) {
+        (Some(old), Some(new)) => (old, new),
+        (Some(_), None) => return Ok(ReviewOutcome::NewlyFailing),
+        (None, Some(_)) => return Ok(ReviewOutcome::Fixed),
+        (None, None) => return Ok(ReviewOutcome::StillFailing),
+    };
+
+    let matched = MatchedNames::new(&old.artboard_names(), &new.artboard_names());
+    let mut artboards = Vec::new();
+
+    for artboard in &matched.both {
+        let old_frames = render_artboard(rasterizer, &mut old, artboard)?;
+        let new_frames = render_artboard(rasterizer, &mut new, artboard)?;
+        let mut frames = Vec::new();
+
+        for ((time, old), new) in REVIEW_TIMES.into_iter().zip(&old_frames).zip(&new_frames) {
+            let diff = perceptual_diff(old, new);
+
+            for (kind, capture) in [("old", old), ("new", new), ("diff", &diff.image)] {
+                let path = out.join(image_path(file, artboard, time, kind));
+                fs::create_dir_all(path.parent().unwrap())?;
+                capture.save(path).map_err(ReviewError::Save)?;
+            }
+
+            frames.push(FrameReview {
+                time,
+                score: diff.score,
+                changed: diff.changed,
+            });
+        }
+
+        artboards.push(ArtboardReview {
+            name: artboard.clone(),
+            frames,
+        });
+    }
+
+    Ok(ReviewOutcome::Compared {
+        artboards,
+        added: matched.added,
+        removed: matched.removed,
+    })
+}
+
+/// Captures of `artboard` at every `REVIEW_TIMES`, played from the start.
+fn render_artboard(
+    rasterizer: &mut Rasterizer,
+    content: &mut ViewerContent,
+    artboard: &str,
+) -> Result<Vec<Capture>, ReviewError> {
+    content
+        .select(Some(artboard), None)
+        .map_err(ReviewError::Select)?;
+    content.handle_resize(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+
+    let mut played = 0.0;
+    let mut captures = Vec::new();
+
+    for time in REVIEW_TIMES {
+        for step in Substeps::new(time - played) {
+            content.advance(step).map_err(ReviewError::Unavailable)?;
+        }
+        played = time;
+
+        let mut renderer = VelloRenderer::default();
+        content
+            .draw(&mut renderer, THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+            .map_err(ReviewError::Unavailable)?;
+        renderer.end_frame();
+
+        let capture = rasterizer
+            .render(
+                &renderer.scene,
+                Color::WHITE,
+                THUMBNAIL_SIZE,
+                THUMBNAIL_SIZE,
+                1,
+                ScaledOutput::Downsampled,
+            )
+            .map_err(ReviewError::Capture)?;
+        captures.push(capture);
+    }
+
+    Ok(captures)
+}
+
+/// Every reviewed file, and those found in only one of the directories.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReviewReport {
+    pub reviews: Vec<FileReview>,
+    pub only_old: Vec<String>,
+    pub only_new: Vec<String>,
+}
+
+impl ReviewReport {
+    /// Orders the reviews by decreasing magnitude, then by name.
+    pub fn sort(&mut self) {
+        self.reviews.sort_by(|a, b| {
+            b.magnitude()
+                .total_cmp(&a.magnitude())
+                .then_with(|| a.file.cmp(&b.file))
+        });
+    }
+
+    /// Writes `index.html` into `out`, next to the images saved by `review_file`, and returns its
+    /// path.
+    pub fn write_html(&self, out: &Path) -> io::Result<PathBuf> {
+        let path = out.join("index.html");
+        fs::write(&path, self.to_html())?;
+
+        Ok(path)
+    }
+
+    fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>rive-diff report</title>\n<style>\n\
+             body { font-family: sans-serif; }\n\
+             table { border-collapse: collapse; }\n\
+             td, th { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }\n\
+             tr.flagged { background: #fdd; }\n\
+             img { width: 128px; height: 128px; }\n\
+             </style>\n</head>\n<body>\n<h1>rive-diff report</h1>\n",
+        );
+
+        html.push_str("<table>\n<tr><th>File</th><th>Difference</th><th>Notes</th></tr>\n");
+        for (i, review) in self.reviews.iter().enumerate() {
+            let class = if review.is_flagged() {
+                " class=\"flagged\""
+            } else {
+                ""
+            };
+            let magnitude = match review.magnitude() {
+                magnitude if magnitude.is_finite() => format!("{:.2}%", magnitude * 100.0),
+                _ => "failed".to_string(),
+            };
+            html.push_str(&format!(
+                "<tr{class}><td><a href=\"#file-{i}\">{}</a></td><td>{magnitude}</td>\
+                 <td>{}</td></tr>\n",
+                escape(&review.file),
+                escape(&review.notes()),
+            ));
+        }
+        html.push_str("</table>\n");
+
+        for (heading, files) in [
+            ("Only in old", &self.only_old),
+            ("Only in new", &self.only_new),
+        ] {
+            if !files.is_empty() {
+                html.push_str(&format!(
+                    "<p>{heading}: {}</p>\n",
+                    escape(&files.join(", "))
+                ));
+            }
+        }
+
+        for (i, review) in self.reviews.iter().enumerate() {
+            html.push_str(&format!(
+                "<h2 id=\"file-{i}\">{}</h2>\n",
+                escape(&review.file)
+            ));
+
+            let ReviewOutcome::Compared { artboards, .. } = &review.outcome else {
+                html.push_str(&format!("<p>{}</p>\n", escape(&review.notes())));
+                continue;
+            };
+
+            for artboard in artboards {
+                html.push_str(&format!(
+                    "<h3>{}</h3>\n<table>\n<tr><th>Time</th><th>Old</th><th>New</th>\
+                     <th>Diff</th><th>Difference</th><th>Changed pixels</th></tr>\n",
+                    escape(&artboard.name)
+                ));
+                for frame in &artboard.frames {
+                    let image = |kind| {
+                        let path = image_path(&review.file, &artboard.name, frame.time, kind);
+                        format!("<td><img src=\"{}\"></td>", path.display())
+                    };
+                    html.push_str(&format!(
+                        "<tr><td>{}s</td>{}{}{}<td>{:.2}%</td><td>{:.2}%</td></tr>\n",
+                        frame.time,
+                        image("old"),
+                        image("new"),
+                        image("diff"),
+                        frame.score * 100.0,
+                        frame.changed * 100.0,
+                    ));
+                }
+                html.push_str("</table>\n");
+            }
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Capture {
+        Capture {
+            width,
+            height,
+            pixels: rgba.repeat((width * height) as usize),
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn compared(file: &str, score: f64, added: &[&str]) -> FileReview {
+        FileReview {
+            file: file.to_string(),
+            outcome: ReviewOutcome::Compared {
+                artboards: vec![ArtboardReview {
+                    name: "Main".to_string(),
+                    frames: vec![FrameReview {
+                        time: 0.0,
+                        score,
+                        changed: score,
+                    }],
+                }],
+                added: names(added),
+                removed: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn identical_captures_dont_differ() {
+        let capture = solid(4, 4, [20, 120, 220, 255]);
+        let diff = perceptual_diff(&capture, &capture);
+
+        assert_eq!(diff.score, 0.0);
+        assert_eq!(diff.changed, 0.0);
+        assert!(diff.image.pixels.chunks_exact(4).all(|p| p[0] == p[1]));
+
+        // Transparent pixels are compared over white.
+        let diff = perceptual_diff(&solid(2, 2, [0, 0, 0, 0]), &solid(2, 2, [255; 4]));
+        assert_eq!(diff.score, 0.0);
+    }
+
+    #[test]
+    fn differences_are_weighted_and_highlighted() {
+        let old = solid(2, 2, [255; 4]);
+        let mut new = old.clone();
+        new.pixels[..4].copy_from_slice(&[0, 0, 0, 255]);
+
+        let diff = perceptual_diff(&old, &new);
+        assert!((0.2..=0.25).contains(&diff.score));
+        assert_eq!(diff.changed, 0.25);
+        assert!(diff.image.pixels[0] > 240);
+        assert_eq!(&diff.image.pixels[1..4], &[0, 0, 255]);
+
+        // Brightness counts for more than hue.
+        let gray = perceptual_diff(
+            &solid(1, 1, [128, 128, 128, 255]),
+            &solid(1, 1, [96, 96, 96, 255]),
+        );
+        let hue = perceptual_diff(
+            &solid(1, 1, [128, 128, 128, 255]),
+            &solid(1, 1, [128, 118, 160, 255]),
+        );
+        assert!(hue.score < gray.score);
+
+        let resized = perceptual_diff(&old, &solid(3, 2, [255; 4]));
+        assert_eq!(resized.score, 1.0);
+    }
+
+    #[test]
+    fn matches_names_in_new_order() {
+        let matched = MatchedNames::new(
+            &names(&["Intro", "Main", "Old"]),
+            &names(&["Main", "New", "Intro"]),
+        );
+
+        assert_eq!(matched.both, ["Main", "Intro"]);
+        assert_eq!(matched.added, ["New"]);
+        assert_eq!(matched.removed, ["Old"]);
+    }
+
+    #[test]
+    fn failures_rank_first_and_are_flagged() {
+        let mut report = ReviewReport {
+            reviews: vec![
+                compared("small.riv", 0.01, &[]),
+                FileReview {
+                    file: "broken.riv".to_string(),
+                    outcome: ReviewOutcome::NewlyFailing,
+                },
+                compared("large.riv", 0.4, &["Extra"]),
+                FileReview {
+                    file: "hangs.riv".to_string(),
+                    outcome: ReviewOutcome::TimedOut { seconds: 30.0 },
+                },
+            ],
+            ..ReviewReport::default()
+        };
+        report.sort();
+
+        let order: Vec<_> = report
+            .reviews
+            .iter()
+            .map(|review| &review.file[..])
+            .collect();
+        assert_eq!(order, ["broken.riv", "hangs.riv", "large.riv", "small.riv"]);
+
+        let flagged: Vec<_> = report.reviews.iter().map(FileReview::is_flagged).collect();
+        assert_eq!(flagged, [true, true, true, false]);
+    }
+
+    #[test]
+    fn report_links_every_frame() {
+        let report = ReviewReport {
+            reviews: vec![compared("a<b>.riv", 0.125, &["New"])],
+            only_new: names(&["fresh.riv"]),
+            ..ReviewReport::default()
+        };
+        let html = report.to_html();
+
+        assert!(html.contains("a&lt;b&gt;.riv"));
+        assert!(html.contains("12.50%"));
+        assert!(html.contains("gained New"));
+        assert!(html.contains("Only in new: fresh.riv"));
+        for kind in ["old", "new", "diff"] {
+            let path = image_path("a<b>.riv", "Main", 0.0, kind);
+            assert!(
+                html.contains(&path.display().to_string()),
+                "no {kind} image"
+            );
+        }
+        assert_eq!(
+            image_path("a<b>.riv", "Main Menu", 0.5, "diff"),
+            Path::new("images/a_b_.riv/Main_Menu-00500ms-diff.png")
+        );
+    }
+
+    #[test]
+    fn outcomes_round_trip_through_toml() {
+        for review in [
+            compared("a.riv", 0.5, &["New"]),
+            FileReview {
+                file: "b.riv".to_string(),
+                outcome: ReviewOutcome::Crashed {
+                    reason: "signal 11".to_string(),
+                },
+            },
+            FileReview {
+                file: "c.riv".to_string(),
+                outcome: ReviewOutcome::StillFailing,
+            },
+        ] {
+            let text = toml::to_string(&review).unwrap();
+            assert_eq!(toml::from_str::<FileReview>(&text).unwrap(), review);
+        }
+    }
+
+    #[test]
+    fn unchanged_files_score_zero() {
+        let gpu = crate::device::test_device();
+
+        let out = std::env::temp_dir().join(format!("rive-vello-{}-review", std::process::id()));
+        let assets = Path::new("../test/assets");
+        let mut rasterizer = Rasterizer::new(gpu.device, gpu.queue);
+
+        let outcome = review_file(&mut rasterizer, assets, assets, "two_artboards.riv", &out);
+        let Ok(ReviewOutcome::Compared {
+            artboards,
+            added,
+            removed,
+        }) = &outcome
+        else {
+            panic!("{outcome:?}")
+        };
+
+        assert_eq!(artboards.len(), 2);
+        assert!(added.is_empty() && removed.is_empty());
+        assert!(artboards.iter().all(|artboard| artboard.score() == 0.0));
+        assert!(out
+            .join(image_path(
+                "two_artboards.riv",
+                &artboards[1].name,
+                2.0,
+                "diff"
+            ))
+            .is_file());
+
+        fs::remove_dir_all(out).unwrap();
+    }
+}
@@ -0,0 +1,33 @@
+use std::fmt;
+
+use crate::status::SceneStatus;
+
+/// Why `ViewerContent::select` couldn't switch scenes. The content keeps playing what it was.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectError {
+    Unavailable(SceneStatus),
+    /// The file has no artboard with this name.
+    UnknownArtboard(String),
+    /// The artboard has no state machine or animation with this name.
+    UnknownScene {
+        artboard: String,
+        name: String,
+    },
+}
+
+impl fmt::Display for SelectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(status) => write!(f, "scene is {status}"),
+            Self::UnknownArtboard(name) => write!(f, "no artboard called \"{name}\""),
+            Self::UnknownScene { artboard, name } => {
+                write!(
+                    f,
+                    "\"{artboard}\" has no state machine or animation called \"{name}\""
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SelectError {}
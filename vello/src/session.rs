@@ -0,0 +1,166 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use directories::ProjectDirs;
+use rive_vello::ViewerContent;
+use serde::{Deserialize, Serialize};
+
+/// Viewer state restored on startup. The viewer shows a single file at a time and has no
+/// camera beyond the grid of copies, so that's all there is to persist.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Session {
+    pub file: Option<PathBuf>,
+    /// Accumulated scroll which sets the size of the grid of copies.
+    pub grid_scroll: f64,
+    pub paused: bool,
+    pub artboard: Option<String>,
+    /// State machine or animation of the artboard.
+    pub scene: Option<String>,
+    /// Frame of the scene, for scenes which are linear animations.
+    pub frame: Option<u32>,
+}
+
+impl Session {
+    pub fn path() -> Option<PathBuf> {
+        ProjectDirs::from("app", "Rive", "rive-vello")
+            .map(|dirs| dirs.config_dir().join("session.toml"))
+    }
+
+    /// Loads the session at `path`. Files that no longer exist are dropped with a note.
+    pub fn load(path: &Path) -> Option<Self> {
+        let text = fs::read_to_string(path).ok()?;
+        let mut session: Self = match toml::from_str(&text) {
+            Ok(session) => session,
+            Err(error) => {
+                eprintln!("Ignoring session at {}: {error}", path.display());
+                return None;
+            }
+        };
+
+        if let Some(file) = &session.file {
+            if !file.exists() {
+                eprintln!("Skipping {} which no longer exists", file.display());
+                session.file = None;
+            }
+        }
+
+        Some(session)
+    }
+
+    /// Records what `viewer_content` is playing and where.
+    pub fn capture(&mut self, viewer_content: &ViewerContent) {
+        self.paused = viewer_content.is_paused();
+        self.artboard = viewer_content.artboard();
+        self.scene = viewer_content.scene();
+        self.frame = viewer_content.current_frame();
+    }
+
+    /// Plays what was captured on `viewer_content`, which is expected to hold the session's
+    /// file. Names the file no longer has are skipped with a note.
+    pub fn restore(&self, viewer_content: &mut ViewerContent) {
+        if let Some(artboard) = &self.artboard {
+            if viewer_content.artboard().as_ref() != Some(artboard)
+                && viewer_content.select(Some(artboard), None).is_err()
+            {
+                eprintln!("Skipping artboard {artboard:?} which no longer exists");
+                return;
+            }
+        }
+        if let Some(scene) = &self.scene {
+            if viewer_content.scene().as_ref() != Some(scene)
+                && viewer_content.select(None, Some(scene)).is_err()
+            {
+                eprintln!("Skipping scene {scene:?} which no longer exists");
+                return;
+            }
+        }
+        if let Some(frame) = self.frame {
+            let _ = viewer_content.seek_frame(frame);
+        }
+        if self.paused {
+            viewer_content.pause();
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let text =
+            toml::to_string(self).map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        fs::write(path, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("rive-vello-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn round_trips_and_skips_missing_files() {
+        let path = temp_path("session.toml");
+
+        let session = Session {
+            file: Some(path.clone()),
+            grid_scroll: 250.0,
+            paused: true,
+            artboard: Some("Smoke".to_string()),
+            scene: Some("Timeline 1".to_string()),
+            frame: Some(45),
+        };
+        session.save(&path).unwrap();
+        assert_eq!(Session::load(&path), Some(session.clone()));
+
+        let missing = Session {
+            file: Some(temp_path("missing.riv")),
+            ..session
+        };
+        missing.save(&path).unwrap();
+
+        let restored = Session::load(&path).unwrap();
+        assert_eq!(restored.file, None);
+        assert_eq!(restored.grid_scroll, 250.0);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn restores_artboard_scene_and_frame() {
+        let path = temp_path("playback.toml");
+        let file = PathBuf::from("../test/assets/death_knight.riv");
+
+        let mut content = ViewerContent::new(&file).unwrap();
+        content.select(Some("Smoke"), Some("Timeline 1")).unwrap();
+        // Timeline 1 plays frames 30 to 60.
+        assert!(content.seek_frame(45).unwrap());
+        content.pause();
+
+        let mut session = Session {
+            file: Some(file.clone()),
+            ..Session::default()
+        };
+        session.capture(&content);
+        session.save(&path).unwrap();
+        let restored = Session::load(&path).unwrap();
+        assert_eq!(restored, session);
+
+        let mut content = ViewerContent::new(&file).unwrap();
+        restored.restore(&mut content);
+        assert_eq!(content.artboard().as_deref(), Some("Smoke"));
+        assert_eq!(content.scene().as_deref(), Some("Timeline 1"));
+        assert_eq!(content.current_frame(), Some(45));
+        assert!(content.is_paused());
+
+        fs::remove_file(path).unwrap();
+    }
+}
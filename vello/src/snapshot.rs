@@ -0,0 +1,441 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::status::SceneStatus;
+
+/// Version of the snapshot layout this crate writes and the newest one it restores.
+///
+/// 1. Layers without their animation's time; they restore from the start of the state.
+/// 2. Layers with `time`.
+/// 3. `pulses`, the inputs set for one advance only.
+///
+/// Fields are only ever added, so snapshots of any version deserialize: unknown fields are
+/// skipped and missing ones default. The version tells what the fields mean.
+pub const SNAPSHOT_SCHEMA: u32 = 3;
+
+/// The state of a `ViewerContent`'s state machine, e.g. for save games. Serialize it with any
+/// serde format; restore it with `ViewerContent::restore`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct StateSnapshot {
+    pub schema: u32,
+    /// Hash of the `.riv` file the snapshot was taken from.
+    pub file_hash: String,
+    pub state_machine: String,
+    pub layers: Vec<LayerSnapshot>,
+    /// Numbers and booleans. Triggers only last until the next advance and aren't kept.
+    pub inputs: Vec<InputSnapshot>,
+    /// Inputs set with `ViewerContent::set_bool_pulse` or `set_number_for_one_frame` which
+    /// haven't been advanced yet, with the value each goes back to after the next advance.
+    /// Their pulsed value is in `inputs`.
+    pub pulses: Vec<InputSnapshot>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct LayerSnapshot {
+    /// Index of the state among the layer's states.
+    pub state: usize,
+    /// Number of states the layer had, to tell whether `state` still points to the same one.
+    pub state_count: usize,
+    /// Time of the state's animation, or 0 for states without one.
+    pub time: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct InputSnapshot {
+    pub name: String,
+    pub value: InputValue,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputValue {
+    Number(f32),
+    Bool(bool),
+}
+
+impl InputValue {
+    pub(crate) fn is_same_kind(self, other: Self) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RestoreMode {
+    /// Restores nothing unless the snapshot matches the content exactly.
+    #[default]
+    Strict,
+    /// Restores every layer and input which still matches, even from another version of the
+    /// file, and reports the rest.
+    Partial,
+}
+
+/// What a restore applied and what it left alone. Layers are identified by index and inputs
+/// by name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RestoreReport {
+    /// Whether the snapshot was taken from a different file, or another version of it.
+    pub file_changed: bool,
+    pub restored_layers: Vec<usize>,
+    /// Layers which no longer exist, whose states changed, or of another state machine.
+    pub skipped_layers: Vec<usize>,
+    pub restored_inputs: Vec<String>,
+    /// Inputs which no longer exist or changed type.
+    pub skipped_inputs: Vec<String>,
+    /// Inputs of the state machine the snapshot has no value for. They keep their value.
+    pub new_inputs: Vec<String>,
+}
+
+impl RestoreReport {
+    fn is_complete(&self) -> bool {
+        self.skipped_layers.is_empty()
+            && self.skipped_inputs.is_empty()
+            && self.new_inputs.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RestoreError {
+    /// The content's scene can't be changed in its current status.
+    Unavailable(SceneStatus),
+    /// The content plays a linear animation and has no state machine to restore.
+    NoStateMachine,
+    /// Written by a newer version of the crate, whose fields may mean something else.
+    SchemaTooNew { schema: u32, supported: u32 },
+    /// Only returned by strict restores; partial ones report it instead.
+    FileMismatch { expected: String, found: String },
+    /// The state machine's layers or inputs differ from the snapshot's. Only returned by strict
+    /// restores, with what a partial restore would do.
+    ShapeChanged(RestoreReport),
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable(status) => write!(f, "scene is {status}"),
+            Self::NoStateMachine => write!(f, "content has no state machine"),
+            Self::SchemaTooNew { schema, supported } => write!(
+                f,
+                "snapshot schema {schema} is newer than the supported {supported}"
+            ),
+            Self::FileMismatch { expected, found } => {
+                write!(f, "snapshot was taken from file {expected}, not {found}")
+            }
+            Self::ShapeChanged(report) => write!(
+                f,
+                "state machine changed: {} layers and {} inputs don't match, {} inputs are new",
+                report.skipped_layers.len(),
+                report.skipped_inputs.len(),
+                report.new_inputs.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RestoreError {}
+
+/// What to apply to restore `snapshot` onto content currently in the state of `current`.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct RestorePlan {
+    pub layers: Vec<(usize, LayerSnapshot)>,
+    pub inputs: Vec<(String, InputValue)>,
+    /// Pulses of the restored inputs, with the value to go back to.
+    pub pulses: Vec<InputSnapshot>,
+    pub report: RestoreReport,
+}
+
+pub(crate) fn plan_restore(
+    snapshot: &StateSnapshot,
+    current: &StateSnapshot,
+    mode: RestoreMode,
+) -> Result<RestorePlan, RestoreError> {
+    if snapshot.schema > SNAPSHOT_SCHEMA {
+        return Err(RestoreError::SchemaTooNew {
+            schema: snapshot.schema,
+            supported: SNAPSHOT_SCHEMA,
+        });
+    }
+
+    let file_changed = snapshot.file_hash != current.file_hash;
+    if file_changed && mode == RestoreMode::Strict {
+        return Err(RestoreError::FileMismatch {
+            expected: snapshot.file_hash.clone(),
+            found: current.file_hash.clone(),
+        });
+    }
+
+    let mut plan = RestorePlan::default();
+    plan.report.file_changed = file_changed;
+
+    let same_machine = snapshot.state_machine == current.state_machine;
+    for (index, layer) in snapshot.layers.iter().enumerate() {
+        let matches = same_machine
+            && current
+                .layers
+                .get(index)
+                .is_some_and(|current| current.state_count == layer.state_count);
+
+        if matches {
+            let mut layer = layer.clone();
+            if snapshot.schema < 2 {
+                layer.time = 0.0;
+            }
+            plan.layers.push((index, layer));
+            plan.report.restored_layers.push(index);
+        } else {
+            plan.report.skipped_layers.push(index);
+        }
+    }
+
+    for input in &snapshot.inputs {
+        let matches = current
+            .inputs
+            .iter()
+            .any(|current| current.name == input.name && current.value.is_same_kind(input.value));
+
+        if matches {
+            plan.inputs.push((input.name.clone(), input.value));
+            plan.report.restored_inputs.push(input.name.clone());
+        } else {
+            plan.report.skipped_inputs.push(input.name.clone());
+        }
+    }
+    plan.pulses = snapshot
+        .pulses
+        .iter()
+        .filter(|pulse| {
+            plan.inputs
+                .iter()
+                .any(|(name, value)| *name == pulse.name && value.is_same_kind(pulse.value))
+        })
+        .cloned()
+        .collect();
+
+    plan.report.new_inputs = current
+        .inputs
+        .iter()
+        .filter(|current| {
+            !snapshot
+                .inputs
+                .iter()
+                .any(|input| input.name == current.name)
+        })
+        .map(|current| current.name.clone())
+        .collect();
+
+    if mode == RestoreMode::Strict && !plan.report.is_complete() {
+        return Err(RestoreError::ShapeChanged(plan.report));
+    }
+
+    Ok(plan)
+}
+
+/// 64-bit FNV-1a of `bytes`, in hex. Unlike `std`'s hashers it's stable across Rust versions,
+/// so persisted snapshots keep matching their file.
+pub(crate) fn file_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Written before layers had times.
+    const SCHEMA_1: &str = r#"
+schema = 1
+file_hash = "af63bd4c8601b7df"
+state_machine = "Button"
+
+[[layers]]
+state = 3
+state_count = 6
+
+[[inputs]]
+name = "Hover"
+value = { bool = true }
+
+[[inputs]]
+name = "Press"
+value = { bool = false }
+"#;
+
+    /// Written before pulses were kept.
+    const SCHEMA_2: &str = r#"
+schema = 2
+file_hash = "af63bd4c8601b7df"
+state_machine = "Button"
+
+[[layers]]
+state = 3
+state_count = 6
+time = 0.25
+
+[[inputs]]
+name = "Hover"
+value = { bool = true }
+
+[[inputs]]
+name = "Press"
+value = { bool = false }
+"#;
+
+    fn current() -> StateSnapshot {
+        StateSnapshot {
+            schema: SNAPSHOT_SCHEMA,
+            file_hash: "af63bd4c8601b7df".to_string(),
+            state_machine: "Button".to_string(),
+            layers: vec![LayerSnapshot {
+                state: 1,
+                state_count: 6,
+                time: 0.0,
+            }],
+            inputs: vec![
+                InputSnapshot {
+                    name: "Hover".to_string(),
+                    value: InputValue::Bool(false),
+                },
+                InputSnapshot {
+                    name: "Press".to_string(),
+                    value: InputValue::Bool(false),
+                },
+            ],
+            pulses: vec![],
+        }
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let snapshot: StateSnapshot = toml::from_str(SCHEMA_2).unwrap();
+        let text = toml::to_string(&snapshot).unwrap();
+
+        assert_eq!(toml::from_str::<StateSnapshot>(&text).unwrap(), snapshot);
+    }
+
+    #[test]
+    fn restores_older_schemas() {
+        let old: StateSnapshot = toml::from_str(SCHEMA_1).unwrap();
+        let plan = plan_restore(&old, &current(), RestoreMode::Strict).unwrap();
+
+        assert_eq!(plan.layers[0].1.state, 3);
+        assert_eq!(plan.layers[0].1.time, 0.0);
+        assert_eq!(
+            plan.inputs,
+            [
+                ("Hover".to_string(), InputValue::Bool(true)),
+                ("Press".to_string(), InputValue::Bool(false)),
+            ]
+        );
+
+        let new: StateSnapshot = toml::from_str(SCHEMA_2).unwrap();
+        let plan = plan_restore(&new, &current(), RestoreMode::Strict).unwrap();
+        assert_eq!(plan.layers[0].1.time, 0.25);
+    }
+
+    #[test]
+    fn skips_unknown_fields_but_rejects_newer_schemas() {
+        let newer = SCHEMA_2.replace("schema = 2", "schema = 4\nlayer_weights = [0.5]");
+        let snapshot: StateSnapshot = toml::from_str(&newer).unwrap();
+
+        assert_eq!(
+            plan_restore(&snapshot, &current(), RestoreMode::Partial),
+            Err(RestoreError::SchemaTooNew {
+                schema: 4,
+                supported: SNAPSHOT_SCHEMA,
+            })
+        );
+    }
+
+    #[test]
+    fn pulses_restore_with_their_input() {
+        let mut snapshot: StateSnapshot = toml::from_str(SCHEMA_2).unwrap();
+        assert!(snapshot.pulses.is_empty());
+
+        snapshot.schema = SNAPSHOT_SCHEMA;
+        snapshot.pulses = vec![
+            InputSnapshot {
+                name: "Press".to_string(),
+                value: InputValue::Bool(false),
+            },
+            InputSnapshot {
+                name: "Hover".to_string(),
+                value: InputValue::Bool(false),
+            },
+        ];
+        let text = toml::to_string(&snapshot).unwrap();
+        assert_eq!(toml::from_str::<StateSnapshot>(&text).unwrap(), snapshot);
+
+        let mut current = current();
+        current.inputs[0].value = InputValue::Number(0.0);
+        let plan = plan_restore(&snapshot, &current, RestoreMode::Partial).unwrap();
+        assert_eq!(plan.pulses, snapshot.pulses[..1]);
+    }
+
+    #[test]
+    fn strict_restores_need_the_same_file() {
+        let snapshot: StateSnapshot = toml::from_str(SCHEMA_2).unwrap();
+        let mut current = current();
+        current.file_hash = file_hash(b"RIVE other");
+
+        let Err(RestoreError::FileMismatch { expected, found }) =
+            plan_restore(&snapshot, &current, RestoreMode::Strict)
+        else {
+            panic!("file mismatch expected");
+        };
+        assert_eq!(expected, "af63bd4c8601b7df");
+        assert_eq!(found, current.file_hash);
+
+        let plan = plan_restore(&snapshot, &current, RestoreMode::Partial).unwrap();
+        assert!(plan.report.file_changed);
+        assert_eq!(plan.report.restored_layers, [0]);
+    }
+
+    #[test]
+    fn partial_restores_apply_what_still_matches() {
+        let snapshot: StateSnapshot = toml::from_str(SCHEMA_2).unwrap();
+        let mut current = current();
+        current.layers[0].state_count = 7;
+        current.inputs[0].value = InputValue::Number(0.0);
+        current.inputs.push(InputSnapshot {
+            name: "Speed".to_string(),
+            value: InputValue::Number(1.0),
+        });
+
+        let Err(RestoreError::ShapeChanged(report)) =
+            plan_restore(&snapshot, &current, RestoreMode::Strict)
+        else {
+            panic!("shape change expected");
+        };
+
+        let plan = plan_restore(&snapshot, &current, RestoreMode::Partial).unwrap();
+        assert_eq!(plan.report, report);
+        assert!(plan.layers.is_empty());
+        assert_eq!(
+            plan.inputs,
+            [("Press".to_string(), InputValue::Bool(false))]
+        );
+        assert_eq!(
+            report,
+            RestoreReport {
+                file_changed: false,
+                restored_layers: vec![],
+                skipped_layers: vec![0],
+                restored_inputs: vec!["Press".to_string()],
+                skipped_inputs: vec!["Hover".to_string()],
+                new_inputs: vec!["Speed".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        assert_eq!(file_hash(b""), "cbf29ce484222325");
+        assert_eq!(file_hash(b"RIVE"), file_hash(b"RIVE"));
+        assert_ne!(file_hash(b"RIVE"), file_hash(b"RIVF"));
+    }
+}
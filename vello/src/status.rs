@@ -0,0 +1,84 @@
+use std::{cell::Cell, fmt, rc::Rc};
+
+/// Whether a `ViewerContent` can be advanced, drawn and have its inputs set.
+///
+/// Contents start `Ready`. `Hibernated` goes back to `Ready`; `Poisoned` and `Detached` are
+/// final. Calls on an unavailable content return `SceneUnavailable` and leave it untouched.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SceneStatus {
+    #[default]
+    Ready,
+    /// Suspended by `ViewerContent::hibernate` until `wake`.
+    Hibernated,
+    /// A panic unwound through an advance, draw or input change, which may have left the scene
+    /// half updated.
+    Poisoned,
+    /// The scene was released by `ViewerContent::detach`, or never loaded.
+    Detached,
+}
+
+impl fmt::Display for SceneStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ready => write!(f, "ready"),
+            Self::Hibernated => write!(f, "hibernated"),
+            Self::Poisoned => write!(f, "poisoned"),
+            Self::Detached => write!(f, "detached"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SceneUnavailable(pub SceneStatus);
+
+impl fmt::Display for SceneUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "scene is {}", self.0)
+    }
+}
+
+impl std::error::Error for SceneUnavailable {}
+
+/// A content's status, shared with the guards of the calls in progress.
+#[derive(Debug, Default)]
+pub(crate) struct Status(Rc<Cell<SceneStatus>>);
+
+impl Status {
+    pub fn new(status: SceneStatus) -> Self {
+        Self(Rc::new(Cell::new(status)))
+    }
+
+    pub fn get(&self) -> SceneStatus {
+        self.0.get()
+    }
+
+    /// Moves to `to` if currently `from`.
+    pub fn transition(&self, from: SceneStatus, to: SceneStatus) {
+        if self.0.get() == from {
+            self.0.set(to);
+        }
+    }
+
+    pub fn set(&self, status: SceneStatus) {
+        self.0.set(status);
+    }
+
+    /// Starts a call which needs the scene, poisoning it if the call panics.
+    pub fn enter(&self) -> Result<PoisonGuard, SceneUnavailable> {
+        match self.0.get() {
+            SceneStatus::Ready => Ok(PoisonGuard(self.0.clone())),
+            status => Err(SceneUnavailable(status)),
+        }
+    }
+}
+
+#[must_use]
+pub(crate) struct PoisonGuard(Rc<Cell<SceneStatus>>);
+
+impl Drop for PoisonGuard {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.set(SceneStatus::Poisoned);
+        }
+    }
+}
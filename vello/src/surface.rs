@@ -0,0 +1,61 @@
+use wgpu::{
+    Adapter, CompositeAlphaMode, Device, PresentMode, Surface, SurfaceConfiguration, TextureFormat,
+    TextureUsages,
+};
+
+/// A window's surface configured for Vello, which can only render to non-sRGB RGBA or BGRA.
+pub struct WindowSurface {
+    pub surface: Surface,
+    pub config: SurfaceConfiguration,
+}
+
+impl WindowSurface {
+    pub fn new(
+        surface: Surface,
+        adapter: &Adapter,
+        device: &Device,
+        width: u32,
+        height: u32,
+        present_mode: PresentMode,
+    ) -> Option<Self> {
+        let format = surface
+            .get_capabilities(adapter)
+            .formats
+            .into_iter()
+            .find(|format| {
+                matches!(
+                    format,
+                    TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm
+                )
+            })?;
+
+        let mut window_surface = Self {
+            surface,
+            config: SurfaceConfiguration {
+                usage: TextureUsages::RENDER_ATTACHMENT,
+                format,
+                width,
+                height,
+                present_mode,
+                alpha_mode: CompositeAlphaMode::Auto,
+                view_formats: vec![],
+            },
+        };
+        window_surface.resize(device, width, height);
+
+        Some(window_surface)
+    }
+
+    pub fn format(&self) -> TextureFormat {
+        self.config.format
+    }
+
+    /// Reconfigures the surface, clamped to the device's texture limit.
+    pub fn resize(&mut self, device: &Device, width: u32, height: u32) {
+        let max = device.limits().max_texture_dimension_2d;
+
+        self.config.width = width.clamp(1, max);
+        self.config.height = height.clamp(1, max);
+        self.surface.configure(device, &self.config);
+    }
+}
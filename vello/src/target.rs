@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use vello::{peniko::Color, RenderParams, Renderer, Scene};
+use wgpu::{
+    Device, Extent3d, Maintain, Queue, Texture, TextureDescriptor, TextureDimension, TextureFormat,
+    TextureUsages, TextureView, TextureViewDescriptor,
+};
+
+use crate::{
+    device::{self, DeviceError},
+    gpu_memory::{GpuAllocation, GpuCategory},
+    live::{Kind, Live},
+};
+
+/// The only format Vello can render to.
+pub const TARGET_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+/// An offscreen texture Vello renders to, on a device which may be owned by the host. The
+/// texture can be bound as a sampled texture in the host's own passes on the same device.
+///
+/// The target keeps the device alive, so it can be dropped before or after the host's own
+/// handles. Dropping it waits for the GPU to be done with the texture before destroying it.
+pub struct TextureTarget {
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    renderer: Renderer,
+    texture: Texture,
+    view: TextureView,
+    width: u32,
+    height: u32,
+    memory: GpuAllocation,
+    _live: Live,
+}
+
+impl TextureTarget {
+    pub fn new(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, DeviceError> {
+        let (width, height) = clamp_size(&device, width, height);
+        let renderer = device::create_renderer(&device, &queue, None)?;
+        let (texture, view) = create_texture(&device, width, height);
+
+        Ok(Self {
+            device,
+            queue,
+            renderer,
+            texture,
+            view,
+            width,
+            height,
+            memory: GpuAllocation::texture(GpuCategory::Targets, width, height),
+            _live: Live::new(Kind::TextureTarget),
+        })
+    }
+
+    pub fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+
+    pub fn queue(&self) -> &Arc<Queue> {
+        &self.queue
+    }
+
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn view(&self) -> &TextureView {
+        &self.view
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Recreates the texture if the size changed. Views previously returned by `view` keep
+    /// pointing to the old texture. Sizes are clamped to the device's texture limit, so a
+    /// target on a `RenderTier::Reduced` device may end up smaller than asked for.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        let (width, height) = clamp_size(&self.device, width, height);
+        if (width, height) == (self.width, self.height) {
+            return;
+        }
+
+        (self.texture, self.view) = create_texture(&self.device, width, height);
+        self.width = width;
+        self.height = height;
+        self.memory.resize(width as u64 * height as u64 * 4);
+    }
+
+    /// Renders `scene` over `base_color` and waits for the GPU to finish, so the texture can
+    /// be sampled right away.
+    pub fn render(&mut self, scene: &Scene, base_color: Color) -> Result<(), vello::Error> {
+        vello::block_on_wgpu(
+            &self.device,
+            self.renderer.render_to_texture_async(
+                &self.device,
+                &self.queue,
+                scene,
+                &self.view,
+                &RenderParams {
+                    base_color,
+                    width: self.width,
+                    height: self.height,
+                },
+            ),
+        )
+    }
+}
+
+impl Drop for TextureTarget {
+    fn drop(&mut self) {
+        self.device.poll(Maintain::Wait);
+        self.texture.destroy();
+    }
+}
+
+fn clamp_size(device: &Device, width: u32, height: u32) -> (u32, u32) {
+    let max = device.limits().max_texture_dimension_2d;
+    (width.clamp(1, max), height.clamp(1, max))
+}
+
+fn create_texture(device: &Device, width: u32, height: u32) -> (Texture, TextureView) {
+    let texture = device.create_texture(&TextureDescriptor {
+        label: Some("rive target"),
+        size: Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: TARGET_FORMAT,
+        usage: TextureUsages::STORAGE_BINDING
+            | TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&TextureViewDescriptor::default());
+
+    (texture, view)
+}
@@ -0,0 +1,150 @@
+use std::collections::{HashMap, HashSet};
+
+/// Outcome of `ViewerContent::apply_text_table`. Runs are identified by their path.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TextApplyReport {
+    /// Runs which were set, in artboard order.
+    pub applied: Vec<String>,
+    /// Keys which matched no run, sorted.
+    pub unused_keys: Vec<String>,
+    /// Named runs which no key matched, in artboard order.
+    pub untouched_runs: Vec<String>,
+}
+
+/// Base direction of a text object's paragraphs, see `ViewerContent::set_text_direction`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TextDirection {
+    /// Taken from the first strong character of each paragraph.
+    #[default]
+    Auto,
+    Ltr,
+    Rtl,
+}
+
+impl TextDirection {
+    /// Value of `rive::TextBaseDirection`.
+    pub(crate) fn raw(self) -> u8 {
+        match self {
+            Self::Auto => 0,
+            Self::Ltr => 1,
+            Self::Rtl => 2,
+        }
+    }
+}
+
+/// The run name without the nested artboards leading to it.
+fn bare_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Pairs every run in `paths` with its value from `table`. A key matching a run's full path
+/// wins over one matching its bare name, which is only considered with `bare_names`.
+pub(crate) fn match_runs<'t>(
+    paths: &[String],
+    table: &'t HashMap<String, String>,
+    bare_names: bool,
+) -> (Vec<(usize, &'t str)>, TextApplyReport) {
+    let mut matches = Vec::new();
+    let mut used = HashSet::new();
+    let mut report = TextApplyReport::default();
+
+    for (index, path) in paths.iter().enumerate() {
+        let entry = table.get_key_value(path.as_str()).or_else(|| {
+            bare_names
+                .then(|| table.get_key_value(bare_name(path)))
+                .flatten()
+        });
+
+        match entry {
+            Some((key, value)) => {
+                used.insert(key.as_str());
+                matches.push((index, value.as_str()));
+                report.applied.push(path.clone());
+            }
+            None => report.untouched_runs.push(path.clone()),
+        }
+    }
+
+    report.unused_keys = table
+        .keys()
+        .filter(|key| !used.contains(key.as_str()))
+        .cloned()
+        .collect();
+    report.unused_keys.sort();
+
+    (matches, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    fn table(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_rtl_strings_intact() {
+        let runs = paths(&["title", "price"]);
+        let arabic = "\u{200f}مرحبا بالعالم";
+        let mixed = "המחיר: 42 ₪";
+        let table = table(&[("title", arabic), ("price", mixed)]);
+
+        let (matches, report) = match_runs(&runs, &table, false);
+
+        assert_eq!(matches, [(0, arabic), (1, mixed)]);
+        assert!(report.unused_keys.is_empty());
+        assert!(report.untouched_runs.is_empty());
+    }
+
+    #[test]
+    fn addresses_nested_runs() {
+        let runs = paths(&["title", "card/title", "card/badge/label"]);
+
+        let by_path = table(&[("card/title", "Karte"), ("card/badge/label", "Neu")]);
+        let (matches, _) = match_runs(&runs, &by_path, false);
+        assert_eq!(matches, [(1, "Karte"), (2, "Neu")]);
+
+        // Bare names reach every nested run, but a full path still wins.
+        let by_name = table(&[
+            ("title", "Titel"),
+            ("card/title", "Karte"),
+            ("label", "Neu"),
+        ]);
+        let (matches, _) = match_runs(&runs, &by_name, true);
+        assert_eq!(matches, [(0, "Titel"), (1, "Karte"), (2, "Neu")]);
+
+        let (matches, report) = match_runs(&runs, &by_name, false);
+        assert_eq!(matches, [(0, "Titel"), (1, "Karte")]);
+        assert_eq!(report.unused_keys, ["label"]);
+    }
+
+    #[test]
+    fn reports_unused_keys_and_untouched_runs() {
+        let runs = paths(&["title", "subtitle", "card/title", "card/footer"]);
+        let table = table(&[
+            ("title", "Title"),
+            ("card/title", "Card"),
+            ("legal", "Legal"),
+            ("card/header", "Header"),
+        ]);
+
+        let (_, report) = match_runs(&runs, &table, false);
+
+        assert_eq!(
+            report,
+            TextApplyReport {
+                applied: paths(&["title", "card/title"]),
+                unused_keys: paths(&["card/header", "legal"]),
+                untouched_runs: paths(&["subtitle", "card/footer"]),
+            }
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use vello::kurbo::{Affine, Point, Rect, Size, Vec2};
+
+use crate::Fit;
+
+/// Which way the view is turned on the surface, e.g. to follow a device's orientation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Orientation {
+    #[default]
+    Up,
+    /// A quarter turn clockwise: the view's top edge runs down the surface's right edge.
+    Right,
+    Down,
+    /// A quarter turn counterclockwise.
+    Left,
+}
+
+impl Orientation {
+    /// Whether the view's width runs along the surface's height.
+    pub fn is_sideways(self) -> bool {
+        matches!(self, Self::Right | Self::Left)
+    }
+}
+
+/// Pans and zooms the world within the view: the view's origin shows the world at `pan`, and
+/// a world unit spans `zoom` view units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    pub pan: Vec2,
+    pub zoom: f64,
+}
+
+impl Camera {
+    pub fn transform(&self) -> Affine {
+        Affine::scale(self.zoom) * Affine::translate(-self.pan)
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            pan: Vec2::ZERO,
+            zoom: 1.0,
+        }
+    }
+}
+
+/// Every transform between a surface pixel and a point of the artboard, composed in one
+/// place so that drawing, pointers, scrubbing and node queries agree. From the surface in:
+///
+/// 1. `dpi_scale` maps logical units to the surface's physical pixels.
+/// 2. `orientation` turns the view within the logical surface.
+/// 3. `camera` pans and zooms the world within the view.
+/// 4. `placement` places the content in the world.
+/// 5. `fit` fits the artboard to the view, centered, in the content's local units.
+///
+/// The view is the logical surface, turned, and rounded to whole units since that's what the
+/// runtime fits artboards to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransformStack {
+    /// Size of the surface in physical pixels.
+    pub surface_size: (u32, u32),
+    pub dpi_scale: f64,
+    pub orientation: Orientation,
+    pub camera: Camera,
+    pub placement: Affine,
+    pub fit: Fit,
+    /// Rounds the artboard's position in the view to whole units, halves up.
+    pub pixel_snap: bool,
+    pub artboard_bounds: Rect,
+}
+
+impl Default for TransformStack {
+    fn default() -> Self {
+        Self {
+            surface_size: (0, 0),
+            dpi_scale: 1.0,
+            orientation: Orientation::default(),
+            camera: Camera::default(),
+            placement: Affine::IDENTITY,
+            fit: Fit::default(),
+            pixel_snap: false,
+            artboard_bounds: Rect::ZERO,
+        }
+    }
+}
+
+impl TransformStack {
+    /// Size of the view in logical units, which the artboard is fitted to.
+    pub fn view_size(&self) -> (u32, u32) {
+        let (width, height) = self.surface_size;
+        let logical = |pixels: u32| (pixels as f64 / self.dpi_scale).round() as u32;
+        let (width, height) = (logical(width), logical(height));
+
+        if self.orientation.is_sideways() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Maps the view into the logical surface.
+    pub fn orientation_transform(&self) -> Affine {
+        let (width, height) = self.view_size();
+        let (width, height) = (width as f64, height as f64);
+
+        match self.orientation {
+            Orientation::Up => Affine::IDENTITY,
+            Orientation::Right => Affine::new([0.0, 1.0, -1.0, 0.0, height, 0.0]),
+            Orientation::Down => Affine::new([-1.0, 0.0, 0.0, -1.0, width, height]),
+            Orientation::Left => Affine::new([0.0, -1.0, 1.0, 0.0, 0.0, width]),
+        }
+    }
+
+    /// Maps the artboard into the content's local units. Mirrors `rive::computeAlignment`
+    /// with a centered alignment, down to its `f32` rounding.
+    pub fn fit_transform(&self) -> Affine {
+        let (width, height) = self.view_size();
+        let frame = Size::new(width as f64, height as f64);
+        fit_transform(self.fit, frame, self.artboard_bounds, self.pixel_snap)
+    }
+
+    pub fn surface_from_world(&self) -> Affine {
+        Affine::scale(self.dpi_scale) * self.orientation_transform() * self.camera.transform()
+    }
+
+    /// The root transform the content is drawn with, the fit aside.
+    pub fn surface_from_local(&self) -> Affine {
+        self.surface_from_world() * self.placement
+    }
+
+    pub fn surface_from_artboard(&self) -> Affine {
+        self.surface_from_local() * self.fit_transform()
+    }
+
+    /// `None` if a layer collapses the surface, e.g. a zoom of 0.
+    pub fn surface_to_world(&self, pos: Vec2) -> Option<Vec2> {
+        map_inverse(self.surface_from_world(), pos)
+    }
+
+    pub fn surface_to_local(&self, pos: Vec2) -> Option<Vec2> {
+        map_inverse(self.surface_from_local(), pos)
+    }
+
+    pub fn surface_to_artboard(&self, pos: Vec2) -> Option<Vec2> {
+        map_inverse(self.surface_from_artboard(), pos)
+    }
+
+    pub fn world_to_local(&self, pos: Vec2) -> Option<Vec2> {
+        map_inverse(self.placement, pos)
+    }
+
+    pub fn local_to_artboard(&self, pos: Vec2) -> Option<Vec2> {
+        map_inverse(self.fit_transform(), pos)
+    }
+
+    pub fn artboard_to_surface(&self, pos: Vec2) -> Vec2 {
+        (self.surface_from_artboard() * pos.to_point()).to_vec2()
+    }
+}
+
+fn map_inverse(transform: Affine, pos: Vec2) -> Option<Vec2> {
+    let det = transform.determinant();
+    if det == 0.0 || !det.is_finite() {
+        return None;
+    }
+
+    Some((transform.inverse() * Point::new(pos.x, pos.y)).to_vec2())
+}
+
+/// The ratio scaling content to frame along the side which fits it, i.e. the smaller ratio, or
+/// the larger one for cover.
+fn fit_ratio(frame: (f32, f32), content: (f32, f32), larger: bool) -> f32 {
+    let width_cross = frame.0 as f64 * content.1 as f64;
+    let height_cross = frame.1 as f64 * content.0 as f64;
+    if (width_cross <= height_cross) != larger {
+        (frame.0 as f64 / content.0 as f64) as f32
+    } else {
+        (frame.1 as f64 / content.1 as f64) as f32
+    }
+}
+
+fn fit_transform(fit: Fit, frame: Size, content: Rect, snap: bool) -> Affine {
+    let frame = (frame.width as f32, frame.height as f32);
+    let (left, top) = (content.x0 as f32, content.y0 as f32);
+    let size = (content.width() as f32, content.height() as f32);
+
+    let (scale_x, scale_y) = match fit {
+        Fit::Fill => (frame.0 / size.0, frame.1 / size.1),
+        Fit::Contain => {
+            let ratio = fit_ratio(frame, size, false);
+            (ratio, ratio)
+        }
+        Fit::Cover => {
+            let ratio = fit_ratio(frame, size, true);
+            (ratio, ratio)
+        }
+        Fit::FitWidth => (frame.0 / size.0, frame.0 / size.0),
+        Fit::FitHeight => (frame.1 / size.1, frame.1 / size.1),
+        Fit::None => (1.0, 1.0),
+        Fit::ScaleDown => {
+            let ratio = fit_ratio(frame, size, false).min(1.0);
+            (ratio, ratio)
+        }
+    };
+
+    let mut x = (frame.0 - size.0 * scale_x) * 0.5;
+    let mut y = (frame.1 - size.1 * scale_y) * 0.5;
+    if snap {
+        x = (x + 0.5).floor();
+        y = (y + 0.5).floor();
+    }
+
+    Affine::new([
+        scale_x as f64,
+        0.0,
+        0.0,
+        scale_y as f64,
+        (x - left * scale_x) as f64,
+        (y - top * scale_y) as f64,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const FITS: [Fit; 7] = [
+        Fit::Fill,
+        Fit::Contain,
+        Fit::Cover,
+        Fit::FitWidth,
+        Fit::FitHeight,
+        Fit::None,
+        Fit::ScaleDown,
+    ];
+    const ORIENTATIONS: [Orientation; 4] = [
+        Orientation::Up,
+        Orientation::Right,
+        Orientation::Down,
+        Orientation::Left,
+    ];
+
+    prop_compose! {
+        fn stacks()(
+            // At least a logical unit at any scale, so the artboard doesn't collapse to a point.
+            surface_size in (4u32..2000, 4u32..2000),
+            dpi_scale in prop::sample::select(vec![1.0, 1.25, 1.5, 2.0, 3.0]),
+            orientation in prop::sample::select(ORIENTATIONS.to_vec()),
+            pan in (-500.0..500.0, -500.0..500.0),
+            zoom in 0.1..10.0,
+            placement in (-1000.0..1000.0, -1000.0..1000.0, 0.1..4.0, -3.2..3.2),
+            fit in prop::sample::select(FITS.to_vec()),
+            pixel_snap in any::<bool>(),
+            artboard in (-100.0..100.0, -100.0..100.0, 1.0..2000.0, 1.0..2000.0),
+        ) -> TransformStack {
+            let (tx, ty, scale, angle) = placement;
+            let (x, y, width, height) = artboard;
+            TransformStack {
+                surface_size,
+                dpi_scale,
+                orientation,
+                camera: Camera {
+                    pan: Vec2::new(pan.0, pan.1),
+                    zoom,
+                },
+                placement: Affine::translate((tx, ty))
+                    * Affine::rotate(angle)
+                    * Affine::scale(scale),
+                fit,
+                pixel_snap,
+                artboard_bounds: Rect::new(x, y, x + width, y + height),
+            }
+        }
+    }
+
+    /// Within a thousandth of a pixel per 1000 units the point is from the origin, so that
+    /// rounding grows with the coordinates rather than failing far-away points.
+    fn assert_close(a: Vec2, b: Vec2) {
+        let tolerance = 1e-3 * (1.0 + a.hypot().max(b.hypot()) / 1000.0);
+        assert!((a - b).hypot() < tolerance, "{a:?} != {b:?}");
+    }
+
+    proptest! {
+        #[test]
+        fn surface_points_round_trip_through_the_artboard(
+            stack in stacks(),
+            pos in (0.0..2000.0, 0.0..2000.0),
+        ) {
+            let pos = Vec2::new(pos.0, pos.1);
+            let artboard = stack.surface_to_artboard(pos).unwrap();
+            assert_close(stack.artboard_to_surface(artboard), pos);
+        }
+
+        #[test]
+        fn layers_compose_to_the_whole_stack(
+            stack in stacks(),
+            pos in (0.0..2000.0, 0.0..2000.0),
+        ) {
+            let pos = Vec2::new(pos.0, pos.1);
+            let world = stack.surface_to_world(pos).unwrap();
+            let local = stack.world_to_local(world).unwrap();
+            let artboard = stack.local_to_artboard(local).unwrap();
+            assert_close(artboard, stack.surface_to_artboard(pos).unwrap());
+        }
+
+        #[test]
+        fn the_view_covers_the_surface(stack in stacks()) {
+            let (width, height) = stack.view_size();
+            let surface_from_view = Affine::scale(stack.dpi_scale) * stack.orientation_transform();
+            let bounds = surface_from_view.transform_rect_bbox(Rect::new(
+                0.0,
+                0.0,
+                width as f64,
+                height as f64,
+            ));
+
+            // Views are rounded to whole logical units.
+            let slack = stack.dpi_scale * 0.5 + 1e-6;
+            let (surface_width, surface_height) = stack.surface_size;
+            prop_assert!(bounds.x0.abs() < slack && bounds.y0.abs() < slack);
+            prop_assert!((bounds.x1 - surface_width as f64).abs() < slack);
+            prop_assert!((bounds.y1 - surface_height as f64).abs() < slack);
+        }
+    }
+
+    #[test]
+    fn quarter_turns_move_the_view_origin_clockwise() {
+        let stack = |orientation| TransformStack {
+            surface_size: (200, 100),
+            orientation,
+            ..Default::default()
+        };
+        let origin = |orientation| stack(orientation).orientation_transform() * Point::ORIGIN;
+
+        assert_eq!(stack(Orientation::Right).view_size(), (100, 200));
+        assert_eq!(origin(Orientation::Up), Point::new(0.0, 0.0));
+        assert_eq!(origin(Orientation::Right), Point::new(200.0, 0.0));
+        assert_eq!(origin(Orientation::Down), Point::new(200.0, 100.0));
+        assert_eq!(origin(Orientation::Left), Point::new(0.0, 100.0));
+    }
+
+    #[test]
+    fn collapsed_stacks_map_nothing_back() {
+        let stack = TransformStack {
+            surface_size: (100, 100),
+            camera: Camera {
+                zoom: 0.0,
+                ..Default::default()
+            },
+            artboard_bounds: Rect::new(0.0, 0.0, 10.0, 10.0),
+            ..Default::default()
+        };
+        assert_eq!(stack.surface_to_artboard(Vec2::new(50.0, 50.0)), None);
+    }
+}
@@ -113,17 +113,41 @@ pub fn map_uvs_to_triangle(
     )
 }
 
-pub trait ScaleFromOrigin {
-    fn pre_scale_from_origin(self, scale: f64, origin: Point) -> Self;
-}
-
-impl ScaleFromOrigin for Affine {
-    fn pre_scale_from_origin(self, scale: f64, origin: Point) -> Self {
-        let origin = origin.to_vec2();
-        self.pre_translate(origin)
-            .pre_scale(scale)
-            .pre_translate(-origin)
+/// Grows the triangle `points` so that its edges move out by `amount` once `transform` maps it
+/// to the screen. Neighboring mesh triangles then overlap along their shared edge instead of
+/// leaving an antialiased seam. The triangle is scaled about its on-screen incenter, which moves
+/// every edge by the same distance, but no vertex moves by more than `4 * amount` so slivers
+/// don't grow spikes. Returns `None` for triangles with no area on screen.
+pub fn dilate_triangle(points: [Point; 3], transform: Affine, amount: f64) -> Option<[Point; 3]> {
+    let screen = points.map(|point| transform * point);
+    let [a, b, c] = screen;
+    let area = (b - a).cross(c - a).abs();
+    // Opposite edges weigh the vertices' barycentric coordinates of the incenter.
+    let weights = [(c - b).hypot(), (a - c).hypot(), (b - a).hypot()];
+    let perimeter: f64 = weights.iter().sum();
+    if !(area > 1e-9 && perimeter.is_finite()) {
+        return None;
     }
+
+    // Affine maps keep barycentric coordinates, so the incenter on screen has the same ones
+    // among the untransformed points.
+    let barycentric = |points: &[Point; 3]| {
+        Point::ZERO
+            + (points[0].to_vec2() * weights[0]
+                + points[1].to_vec2() * weights[1]
+                + points[2].to_vec2() * weights[2])
+                / perimeter
+    };
+    let incenter = barycentric(&screen);
+    let inradius = area / perimeter;
+    let farthest = screen
+        .iter()
+        .map(|point| (*point - incenter).hypot())
+        .fold(0.0, f64::max);
+    let scale = 1.0 + (amount / inradius).min(4.0 * amount / farthest);
+
+    let origin = barycentric(&points);
+    Some(points.map(|point| origin + (point - origin) * scale))
 }
 
 #[cfg(test)]
@@ -154,4 +178,50 @@ mod tests {
             assert_eq!(res.y as f32, to.y);
         }
     }
+
+    fn edge_distance(point: Point, from: Point, to: Point) -> f64 {
+        (to - from).cross(point - from) / (to - from).hypot()
+    }
+
+    #[test]
+    fn dilated_edges_move_out_on_screen() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+        ];
+        let transform = Affine::new([2.0, 0.0, 1.0, 3.0, 5.0, 7.0]);
+
+        let dilated = dilate_triangle(points, transform, 0.5).unwrap();
+
+        let screen = points.map(|point| transform * point);
+        let grown = dilated.map(|point| transform * point);
+        for i in 0..3 {
+            let (from, to) = (screen[i], screen[(i + 1) % 3]);
+            let (grown_from, grown_to) = (grown[i], grown[(i + 1) % 3]);
+            // Grown edges are parallel to the original ones, half a pixel further out.
+            let distance = edge_distance(grown_from, from, to);
+            assert!((distance.abs() - 0.5).abs() < 1e-9, "{distance}");
+            assert!((edge_distance(grown_to, from, to) - distance).abs() < 1e-9);
+            assert!(edge_distance(screen[(i + 2) % 3], from, to).signum() != distance.signum());
+        }
+    }
+
+    #[test]
+    fn slivers_dont_spike() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            Point::new(50.0, 0.01),
+        ];
+
+        let dilated = dilate_triangle(points, Affine::IDENTITY, 0.5).unwrap();
+        for (point, dilated) in points.iter().zip(dilated) {
+            assert!((dilated - *point).hypot() <= 2.0 + 1e-9);
+        }
+
+        let collapsed = [Point::ZERO, Point::new(1.0, 1.0), Point::new(2.0, 2.0)];
+        assert_eq!(dilate_triangle(collapsed, Affine::IDENTITY, 0.5), None);
+        assert_eq!(dilate_triangle(points, Affine::scale(0.0), 0.5), None);
+    }
 }
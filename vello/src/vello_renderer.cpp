@@ -1,5 +1,8 @@
 #include "vello_renderer.hpp"
 
+#include "rive/assets/image_asset.hpp"
+#include "rive/file.hpp"
+
 VelloPath::~VelloPath() { vello_path_release(m_path); }
 
 void VelloPath::rewind() { vello_path_rewind(m_path); }
@@ -174,8 +177,38 @@ std::unique_ptr<RenderPaint> VelloFactory::makeRenderPaint()
 
 std::unique_ptr<RenderImage> VelloFactory::decodeImage(Span<const uint8_t> encoded)
 {
-    return std::make_unique<VelloImage>(vello_image_new(encoded.data(), encoded.size()));
+    auto image = vello_image_new(encoded.data(), encoded.size());
+    if (image == nullptr)
+    {
+        return nullptr;
+    }
+    return std::make_unique<VelloImage>(image);
 }
 
 static VelloFactory factory;
 rive::Factory* ViewerContent::RiveFactory() { return &factory; }
+
+void ViewerContent::LinkImageVariants(const rive::File* file)
+{
+    std::vector<RawVelloImage> images;
+    std::vector<const char*> names;
+    for (auto asset : file->assets())
+    {
+        if (!asset->is<ImageAsset>())
+        {
+            continue;
+        }
+        auto image = static_cast<const VelloImage*>(asset->as<ImageAsset>()->renderImage());
+        if (image != nullptr && image->image() != nullptr)
+        {
+            images.push_back(image->image());
+            names.push_back(asset->name().c_str());
+        }
+    }
+    vello_images_link_variants(images.data(), names.data(), images.size());
+}
+
+std::unique_ptr<RenderImage> ViewerContent::MissingImage(uint32_t width, uint32_t height)
+{
+    return std::make_unique<VelloImage>(vello_image_new_missing(width, height));
+}
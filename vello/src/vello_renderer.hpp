@@ -59,7 +59,11 @@ extern "C"
     void vello_gradient_release(const RawVelloGradient gradient);
 
     const RawVelloImage vello_image_new(const uint8_t* data, size_t len);
+    const RawVelloImage vello_image_new_missing(uint32_t width, uint32_t height);
     void vello_image_release(const RawVelloImage image);
+    void vello_images_link_variants(const RawVelloImage* images,
+                                    const char* const* names,
+                                    size_t len);
 
     const RawVelloPaint vello_paint_new();
     void vello_paint_release(const RawVelloPaint paint);
@@ -1,16 +1,115 @@
 use std::{
-    ffi::CString, fs::File, io::Read, os::unix::prelude::OsStrExt, path::Path, ptr::NonNull,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::{c_char, c_void, CStr, CString},
+    fmt, fs,
+    marker::PhantomData,
+    path::Path,
+    ptr::NonNull,
+    str::FromStr,
 };
 
-use vello::kurbo::Vec2;
+use rand::Rng;
+use rive_core::{
+    AnimationInfo, ContractReport, FileInterface, InputDescriptor, InputIndices, InputType,
+    InteractionContract, LoopMode, RawInputInfo,
+};
+use vello::{
+    kurbo::{Affine, Point, Rect, Vec2},
+    peniko::Color,
+};
 
-use crate::VelloRenderer;
+use crate::{
+    assets::{MissingAsset, RebindError, NOT_IN_FILE},
+    capture::{Rasterizer, ScaledOutput},
+    contract::ContractError,
+    extract::ExtractError,
+    gesture::{ScrubBinding, ScrubDrag, AUTO_SCRUB_INPUTS, AUTO_SCRUB_RANGE},
+    gpu_memory,
+    history::{FrameRecord, History, WatchedProperty, HISTORY_WARNING_BYTES},
+    input,
+    live::{self, Kind, Live, LiveCounts},
+    playback::{Playback, PlaybackState, Step},
+    select::SelectError,
+    snapshot::{
+        self, InputSnapshot, InputValue, LayerSnapshot, RestoreError, RestoreMode, RestoreReport,
+        StateSnapshot, SNAPSHOT_SCHEMA,
+    },
+    status::{SceneStatus, SceneUnavailable, Status},
+    text::{self, TextApplyReport, TextDirection},
+    transform::{Camera, Orientation, TransformStack},
+    VelloRenderer,
+};
 
 #[derive(Debug)]
 enum RawViewerContent {}
 
+/// Mirrors the C++ `AppliedAnimationInfo`.
+#[repr(C)]
+#[derive(Debug)]
+struct RawAppliedAnimation {
+    name: *const c_char,
+    time: f32,
+    mix: f32,
+    layer: usize,
+}
+
+/// Mirrors the C++ `EventPropertyInfo`.
+#[repr(C)]
+#[derive(Debug)]
+struct RawEventProperty {
+    name: *const c_char,
+    property_type: i32,
+    number: f32,
+    boolean: bool,
+    string: *const c_char,
+}
+
+/// Mirrors the C++ `WatchedPropertyInfo`.
+#[repr(C)]
+#[derive(Debug)]
+struct RawWatchedProperty {
+    component: *const c_char,
+    property_key: u32,
+    value_type: i32,
+}
+
+/// Mirrors the C++ `NodeTransformInfo`.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct RawNodeTransform {
+    x: f32,
+    y: f32,
+    rotation: f32,
+    scale_x: f32,
+    scale_y: f32,
+    world: [f32; 6],
+}
+
+/// Mirrors the C++ `MissingAssetInfo`.
+#[repr(C)]
+#[derive(Debug)]
+struct RawMissingAsset {
+    asset_id: u32,
+    name: *const c_char,
+    asset_type: i32,
+    fallback: i32,
+}
+
+/// Mirrors the C++ `LayerStateInfo`.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct RawLayerStateInfo {
+    state: usize,
+    state_count: usize,
+    time: f32,
+}
+
 extern "C" {
-    fn viewer_content_new(raw_path: *const i8) -> Option<NonNull<RawViewerContent>>;
+    fn viewer_content_new_from_bytes(
+        data: *const u8,
+        len: usize,
+    ) -> Option<NonNull<RawViewerContent>>;
     fn viewer_content_release(raw_viewer_content: Option<NonNull<RawViewerContent>>);
     fn viewer_content_handle_resize(
         raw_viewer_content: Option<NonNull<RawViewerContent>>,
@@ -24,6 +123,25 @@ extern "C" {
         raw_vello_renderer: Option<NonNull<VelloRenderer>>,
         elapsed: f64,
     );
+    fn viewer_content_handle_advance(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        elapsed: f64,
+    );
+    fn viewer_content_handle_animate(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        elapsed: f64,
+    ) -> bool;
+    fn viewer_content_handle_update(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        elapsed: f64,
+    );
+    #[allow(improper_ctypes)]
+    fn viewer_content_draw(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        raw_vello_renderer: Option<NonNull<VelloRenderer>>,
+        width: i32,
+        height: i32,
+    );
     fn viewer_content_handle_pointer_move(
         raw_viewer_content: Option<NonNull<RawViewerContent>>,
         x: f32,
@@ -39,75 +157,4241 @@ extern "C" {
         x: f32,
         y: f32,
     );
+    fn viewer_content_hits_listener(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        x: f32,
+        y: f32,
+        listener_type: i32,
+    ) -> bool;
+    fn viewer_content_redraw_policy(raw_viewer_content: Option<NonNull<RawViewerContent>>) -> i32;
+    fn viewer_content_playback_state(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        state: *mut PlaybackState,
+    ) -> bool;
+    fn viewer_content_seek(raw_viewer_content: Option<NonNull<RawViewerContent>>, seconds: f32);
+    fn viewer_content_set_play_range(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        start: f32,
+        end: f32,
+    ) -> bool;
+    fn viewer_content_clear_play_range(raw_viewer_content: Option<NonNull<RawViewerContent>>);
+    fn viewer_content_offset_time(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        seconds: f32,
+    );
+    fn viewer_content_linear_animation(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> Option<NonNull<c_void>>;
+    fn viewer_content_set_number(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        value: f32,
+    ) -> bool;
+    fn viewer_content_number(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        value: *mut f32,
+    ) -> bool;
+    fn viewer_content_set_bool(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        value: bool,
+    ) -> bool;
+    fn viewer_content_input_count(raw_viewer_content: Option<NonNull<RawViewerContent>>) -> usize;
+    fn viewer_content_input(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        info: *mut RawInputInfo,
+    ) -> bool;
+    fn viewer_content_set_number_at(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        value: f32,
+    ) -> bool;
+    fn viewer_content_set_bool_at(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        value: bool,
+    ) -> bool;
+    fn viewer_content_fire_trigger_at(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> bool;
+    fn viewer_content_state_machine_name(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> *const c_char;
+    fn viewer_content_layer_count(raw_viewer_content: Option<NonNull<RawViewerContent>>) -> usize;
+    fn viewer_content_layer_state(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        layer: usize,
+        info: *mut RawLayerStateInfo,
+    ) -> bool;
+    fn viewer_content_restore_layer_state(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        layer: usize,
+        state: usize,
+        time: f32,
+    ) -> bool;
+    #[cfg(test)]
+    fn viewer_content_view_to_artboard(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        x: f32,
+        y: f32,
+        artboard_x: *mut f32,
+        artboard_y: *mut f32,
+    ) -> bool;
+    fn viewer_content_artboard_width(raw_viewer_content: Option<NonNull<RawViewerContent>>) -> f32;
+    fn viewer_content_artboard_bounds(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        bounds: *mut f32,
+    ) -> bool;
+    fn viewer_content_artboard_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_artboard_name(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_select_artboard(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> bool;
+    fn viewer_content_artboard_index(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_artboard_state_machine_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        artboard: usize,
+    ) -> usize;
+    fn viewer_content_artboard_state_machine_name(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        artboard: usize,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_artboard_animation_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        artboard: usize,
+    ) -> usize;
+    fn viewer_content_artboard_animation_name(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        artboard: usize,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_scene_count(raw_viewer_content: Option<NonNull<RawViewerContent>>) -> usize;
+    fn viewer_content_scene_name(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_select_scene(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> bool;
+    fn viewer_content_scene_index(raw_viewer_content: Option<NonNull<RawViewerContent>>) -> usize;
+    fn viewer_content_set_fit(raw_viewer_content: Option<NonNull<RawViewerContent>>, fit: i32);
+    fn viewer_content_set_pixel_snap(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        snap: bool,
+    );
+    fn viewer_content_hits_shape(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        x: f32,
+        y: f32,
+    ) -> bool;
+    fn viewer_content_draw_component(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        raw_vello_renderer: Option<NonNull<VelloRenderer>>,
+        name: *const c_char,
+        width: i32,
+        height: i32,
+        padding: f32,
+        ancestor_effects: bool,
+    ) -> bool;
+    fn viewer_content_component_bounds(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        bounds: *mut f32,
+    ) -> bool;
+    fn viewer_content_discrete_values(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        values: *mut *const f32,
+    ) -> usize;
+    fn viewer_content_applied_animation_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_applied_animation(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        info: *mut RawAppliedAnimation,
+    ) -> bool;
+    fn viewer_content_fired_event_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_fired_event_name(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_fired_event_property_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> usize;
+    fn viewer_content_fired_event_property(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        property: usize,
+        info: *mut RawEventProperty,
+    ) -> bool;
+    fn viewer_content_set_primary_color(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        color: u32,
+    ) -> usize;
+    fn viewer_content_clear_primary_color(raw_viewer_content: Option<NonNull<RawViewerContent>>);
+    fn viewer_content_watch_component(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+    ) -> usize;
+    fn viewer_content_clear_watches(raw_viewer_content: Option<NonNull<RawViewerContent>>);
+    fn viewer_content_watched_property_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_watched_property(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        info: *mut RawWatchedProperty,
+    ) -> bool;
+    fn viewer_content_read_watched(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        values: *mut u32,
+    );
+    fn viewer_content_show_watched(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        values: *const u32,
+    );
+    fn viewer_content_show_live(raw_viewer_content: Option<NonNull<RawViewerContent>>);
+    fn viewer_content_text_run_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_text_run_path(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_text_run_text(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_set_text_run(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        text: *const c_char,
+    );
+    fn viewer_content_set_text_direction(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        direction: u8,
+    ) -> usize;
+    fn viewer_content_event_count(raw_viewer_content: Option<NonNull<RawViewerContent>>) -> usize;
+    fn viewer_content_event_name(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_nested_artboard_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_nested_artboard_path(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+    ) -> *const c_char;
+    fn viewer_content_node_transform(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        info: *mut RawNodeTransform,
+    ) -> bool;
+    fn viewer_content_set_node_transform(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        name: *const c_char,
+        info: *const RawNodeTransform,
+    ) -> bool;
+    fn viewer_content_missing_asset_count(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    ) -> usize;
+    fn viewer_content_missing_asset(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        index: usize,
+        info: *mut RawMissingAsset,
+    ) -> bool;
+    fn viewer_content_rebind_asset(
+        raw_viewer_content: Option<NonNull<RawViewerContent>>,
+        asset_id: u32,
+        data: *const u8,
+        len: usize,
+    ) -> bool;
 }
 
-#[derive(Debug)]
-pub struct ViewerContent {
-    raw_viewer_content: Option<NonNull<RawViewerContent>>,
+/// How an artboard is fitted to the view. It's always centered. Mirrors `rive::Fit`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Fit {
+    /// Stretched to fill the view.
+    Fill,
+    /// As large as it fits whole.
+    #[default]
+    Contain,
+    /// As small as it covers the whole view.
+    Cover,
+    FitWidth,
+    FitHeight,
+    /// At its own size.
+    None,
+    /// Like `Contain`, but never scaled up.
+    ScaleDown,
 }
 
-impl ViewerContent {
-    pub fn new<P: AsRef<Path>>(path: P) -> Option<Self> {
-        let path = path.as_ref();
+impl FromStr for Fit {
+    type Err = String;
+
+    /// Parses the kebab-case name of a fit, e.g. `contain` or `fit-width`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "fill" => Ok(Self::Fill),
+            "contain" => Ok(Self::Contain),
+            "cover" => Ok(Self::Cover),
+            "fit-width" => Ok(Self::FitWidth),
+            "fit-height" => Ok(Self::FitHeight),
+            "none" => Ok(Self::None),
+            "scale-down" => Ok(Self::ScaleDown),
+            _ => Err(format!(
+                "expected one of fill, contain, cover, fit-width, fit-height, none or \
+                 scale-down, got `{s}`"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointerButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
 
-        let header = {
-            let mut file = File::open(path).ok()?;
-            let mut buffer = [0; 4];
+impl PointerButton {
+    /// Whether the button triggers listeners. `.riv` files don't carry a per-listener button
+    /// filter, so only the primary button does.
+    pub fn is_primary(self) -> bool {
+        self == Self::Left
+    }
+}
 
-            file.read_exact(&mut buffer).ok()?;
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointerAction {
+    Move,
+    Down(PointerButton),
+    Up(PointerButton),
+}
 
-            buffer
-        };
+/// Mirrors the `rive::ListenerType`s pointers are hit tested against.
+#[derive(Clone, Copy, Debug)]
+enum ListenerType {
+    Down = 2,
+    Up = 3,
+    Move = 4,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ViewerEvent {
+    /// The active animation reached a loop boundary and paused there. `iteration` counts the
+    /// boundaries reached since a scene was selected or the animation's time or range was
+    /// last moved, starting at 1.
+    LoopCompleted { iteration: u32 },
+    /// The content's `SceneStatus` changed since the last events were taken.
+    StatusChanged { status: SceneStatus },
+    /// The content's `RedrawPolicy` changed since the last events were taken, e.g. because its
+    /// state machine came to rest after an intro.
+    RedrawPolicyChanged { policy: RedrawPolicy },
+    /// `ViewerContent::enable_history` or a watch made the history ring likely to hold more
+    /// than `HISTORY_WARNING_BYTES` once full. `bytes` is the estimate.
+    LargeHistory { bytes: usize },
+}
+
+/// How often a host needs to redraw a content, per `ViewerContent::redraw_policy`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedrawPolicy {
+    /// Nothing ever changes: the file has no scenes, or the content isn't `SceneStatus::Ready`.
+    Static,
+    /// At rest: only inputs, the pointer or resuming playback change what's drawn, so the
+    /// content only needs advancing and drawing after those.
+    OnInteraction,
+    /// Animating: the content needs advancing and drawing every frame.
+    Continuous,
+}
+
+/// An animation the active state machine applied on its last advance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppliedAnimation {
+    pub name: String,
+    /// In animation seconds.
+    pub time: f32,
+    /// Includes the mix of the layer's transition and, for blend states, the blend weight.
+    pub mix: f32,
+    pub layer: usize,
+}
+
+/// An event the active state machine fired, as built for `ViewerContent::events_this_frame`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FiredEvent {
+    pub name: String,
+    /// The event's custom properties, in file order.
+    pub properties: Vec<(String, EventProperty)>,
+}
 
-        if &header != b"RIVE" {
+/// The value of a fired event's custom property.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventProperty {
+    Number(f32),
+    Bool(bool),
+    String(String),
+}
+
+/// A fired event as handed to handlers subscribed with `ViewerContent::subscribe_event`. It
+/// borrows the runtime's event: names, numbers and booleans are read in place, and strings are
+/// only copied by `to_fired_event`.
+#[derive(Debug)]
+pub struct EventRef<'c> {
+    raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    index: usize,
+    name: &'c str,
+}
+
+impl<'c> EventRef<'c> {
+    /// The event at `index` among those fired by the last advance, if there's one.
+    fn new(raw_viewer_content: Option<NonNull<RawViewerContent>>, index: usize) -> Option<Self> {
+        let name = unsafe { viewer_content_fired_event_name(raw_viewer_content, index) };
+        if name.is_null() {
             return None;
         }
 
-        let c_str = CString::new(path.as_os_str().as_bytes()).unwrap();
-        let raw_viewer_content = Some(unsafe { viewer_content_new(c_str.as_ptr())? });
+        Some(Self {
+            raw_viewer_content,
+            index,
+            name: unsafe { CStr::from_ptr(name) }.to_str().unwrap_or_default(),
+        })
+    }
 
-        Some(Self { raw_viewer_content })
+    pub fn name(&self) -> &'c str {
+        self.name
     }
 
-    pub fn handle_resize(&self, width: u32, height: u32) {
-        unsafe {
-            viewer_content_handle_resize(self.raw_viewer_content, width as i32, height as i32);
+    pub fn property_count(&self) -> usize {
+        unsafe { viewer_content_fired_event_property_count(self.raw_viewer_content, self.index) }
+    }
+
+    /// The first number property called `name`.
+    pub fn number(&self, name: &str) -> Option<f32> {
+        self.find(name)
+            .filter(|raw| raw.property_type == 0)
+            .map(|raw| raw.number)
+    }
+
+    /// The first boolean property called `name`.
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        self.find(name)
+            .filter(|raw| raw.property_type == 1)
+            .map(|raw| raw.boolean)
+    }
+
+    /// The first string property called `name`, borrowed from the runtime.
+    pub fn string(&self, name: &str) -> Option<&'c str> {
+        self.find(name)
+            .filter(|raw| raw.property_type == 2)
+            .and_then(|raw| unsafe { CStr::from_ptr(raw.string) }.to_str().ok())
+    }
+
+    /// Copies the event and all of its properties.
+    pub fn to_fired_event(&self) -> FiredEvent {
+        let properties = (0..self.property_count())
+            .filter_map(|property| self.property(property))
+            .map(|raw| {
+                let name = unsafe { CStr::from_ptr(raw.name) };
+                let value = match raw.property_type {
+                    0 => EventProperty::Number(raw.number),
+                    1 => EventProperty::Bool(raw.boolean),
+                    _ => EventProperty::String(
+                        unsafe { CStr::from_ptr(raw.string) }
+                            .to_string_lossy()
+                            .into_owned(),
+                    ),
+                };
+                (name.to_string_lossy().into_owned(), value)
+            })
+            .collect();
+
+        FiredEvent {
+            name: self.name.to_string(),
+            properties,
         }
     }
 
-    pub fn handle_draw(&mut self, renderer: &mut VelloRenderer, elapsed: f64) {
+    fn property(&self, property: usize) -> Option<RawEventProperty> {
+        let mut raw = RawEventProperty {
+            name: std::ptr::null(),
+            property_type: 0,
+            number: 0.0,
+            boolean: false,
+            string: std::ptr::null(),
+        };
+
         unsafe {
-            viewer_content_handle_draw(
+            viewer_content_fired_event_property(
                 self.raw_viewer_content,
-                NonNull::new(renderer as *mut VelloRenderer),
-                elapsed,
+                self.index,
+                property,
+                &mut raw,
             )
         }
+        .then_some(raw)
     }
 
-    pub fn handle_pointer_move(&self, pos: Vec2) {
-        unsafe {
-            viewer_content_handle_pointer_move(self.raw_viewer_content, pos.x as f32, pos.y as f32);
+    fn find(&self, name: &str) -> Option<RawEventProperty> {
+        (0..self.property_count())
+            .filter_map(|property| self.property(property))
+            .find(|raw| unsafe { CStr::from_ptr(raw.name) }.to_bytes() == name.as_bytes())
+    }
+}
+
+/// A handler subscribed with `ViewerContent::subscribe_event`.
+struct EventSubscription {
+    name: String,
+    handler: Box<dyn FnMut(&EventRef<'_>)>,
+}
+
+impl fmt::Debug for EventSubscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSubscription")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Points of an advance at which callbacks added with `ViewerContent::on_advance` run.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AdvancePhase {
+    /// The scene has advanced and applied its animations, but the artboard isn't updated yet:
+    /// constraints haven't run and world transforms are those of the previous frame.
+    Animated,
+    /// The artboard is updated, and its pose is the one the next draw shows.
+    Updated,
+}
+
+/// The transform of a node or bone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NodeTransform {
+    /// Relative to the parent. Bones other than root bones start at their parent's tip, so
+    /// setting their `x` and `y` does nothing.
+    pub x: f32,
+    pub y: f32,
+    /// In radians.
+    pub rotation: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    /// In artboard units, as the artboard's last update left it. Ignored when setting the
+    /// transform.
+    pub world: Affine,
+}
+
+impl From<RawNodeTransform> for NodeTransform {
+    fn from(raw: RawNodeTransform) -> Self {
+        Self {
+            x: raw.x,
+            y: raw.y,
+            rotation: raw.rotation,
+            scale_x: raw.scale_x,
+            scale_y: raw.scale_y,
+            world: Affine::new(raw.world.map(f64::from)),
         }
     }
+}
 
-    pub fn handle_pointer_down(&self, pos: Vec2) {
-        unsafe {
-            viewer_content_handle_pointer_down(self.raw_viewer_content, pos.x as f32, pos.y as f32);
+fn node_transform(
+    raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    name: &str,
+) -> Option<NodeTransform> {
+    let name = CString::new(name).ok()?;
+    let mut raw = RawNodeTransform::default();
+    unsafe { viewer_content_node_transform(raw_viewer_content, name.as_ptr(), &mut raw) }
+        .then(|| raw.into())
+}
+
+/// Mutations deferred by advance callbacks through `ArtboardView::defer`. They run in the order
+/// they were deferred, once every callback of the phase has returned.
+#[derive(Default)]
+pub struct DeferredMutations {
+    mutations: Vec<Box<dyn FnOnce(&mut ArtboardMut<'_>)>>,
+}
+
+impl DeferredMutations {
+    pub fn defer(&mut self, mutation: impl FnOnce(&mut ArtboardMut<'_>) + 'static) {
+        self.mutations.push(Box::new(mutation));
+    }
+
+    pub fn len(&self) -> usize {
+        self.mutations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mutations.is_empty()
+    }
+}
+
+impl fmt::Debug for DeferredMutations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeferredMutations")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// The artboard as handed to advance callbacks. It's read-only, apart from the state machine's
+/// inputs: those are only read when the next advance starts, so setting them mid-advance can't
+/// leave a frame half-updated. Anything else is changed through `defer`.
+#[derive(Debug)]
+pub struct ArtboardView<'c> {
+    raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    phase: AdvancePhase,
+    deferred: &'c mut DeferredMutations,
+}
+
+impl ArtboardView<'_> {
+    pub fn phase(&self) -> AdvancePhase {
+        self.phase
+    }
+
+    /// The node or bone called `name`.
+    pub fn node(&self, name: &str) -> Option<NodeTransform> {
+        node_transform(self.raw_viewer_content, name)
+    }
+
+    /// The value of a number input of the active state machine.
+    pub fn number(&self, name: &str) -> Option<f32> {
+        let name = CString::new(name).ok()?;
+        let mut value = 0.0;
+        unsafe { viewer_content_number(self.raw_viewer_content, name.as_ptr(), &mut value) }
+            .then_some(value)
+    }
+
+    /// Sets a number input of the active state machine for the next advance. Returns `false`
+    /// if there's no number input called `name`.
+    pub fn set_number(&mut self, name: &str, value: f32) -> bool {
+        let Ok(name) = CString::new(name) else {
+            return false;
+        };
+        unsafe { viewer_content_set_number(self.raw_viewer_content, name.as_ptr(), value) }
+    }
+
+    /// Sets a boolean input of the active state machine for the next advance. Returns `false`
+    /// if there's no boolean input called `name`.
+    pub fn set_bool(&mut self, name: &str, value: bool) -> bool {
+        let Ok(name) = CString::new(name) else {
+            return false;
+        };
+        unsafe { viewer_content_set_bool(self.raw_viewer_content, name.as_ptr(), value) }
+    }
+
+    /// Runs `mutation` once the phase is over, before the advance goes on. Mutations deferred
+    /// from `AdvancePhase::Animated` are seen by the artboard's update, e.g. by its constraints,
+    /// and those deferred from `AdvancePhase::Updated` are followed by another update. Either
+    /// way they're drawn in the same frame.
+    pub fn defer(&mut self, mutation: impl FnOnce(&mut ArtboardMut<'_>) + 'static) {
+        self.deferred.defer(mutation);
+    }
+}
+
+/// The artboard as handed to mutations deferred by advance callbacks, between two phases of
+/// an advance.
+#[derive(Debug)]
+pub struct ArtboardMut<'c> {
+    raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    _content: PhantomData<&'c mut ViewerContent>,
+}
+
+impl ArtboardMut<'_> {
+    /// The node or bone called `name`.
+    pub fn node(&self, name: &str) -> Option<NodeTransform> {
+        node_transform(self.raw_viewer_content, name)
+    }
+
+    /// Sets the transform of the node or bone called `name`. Returns `false` if there's none.
+    pub fn set_node(&mut self, name: &str, transform: &NodeTransform) -> bool {
+        let Ok(name) = CString::new(name) else {
+            return false;
+        };
+        let raw = RawNodeTransform {
+            x: transform.x,
+            y: transform.y,
+            rotation: transform.rotation,
+            scale_x: transform.scale_x,
+            scale_y: transform.scale_y,
+            world: [0.0; 6],
+        };
+        unsafe { viewer_content_set_node_transform(self.raw_viewer_content, name.as_ptr(), &raw) }
+    }
+
+    /// Moves the node or bone called `name` by `delta`, relative to its parent. Returns `false`
+    /// if there's none.
+    pub fn nudge(&mut self, name: &str, delta: Vec2) -> bool {
+        let Some(mut transform) = self.node(name) else {
+            return false;
+        };
+        transform.x += delta.x as f32;
+        transform.y += delta.y as f32;
+        self.set_node(name, &transform)
+    }
+}
+
+/// A callback added with `ViewerContent::on_advance`.
+struct AdvanceCallback {
+    phase: AdvancePhase,
+    callback: Box<dyn FnMut(&mut ArtboardView<'_>)>,
+}
+
+impl fmt::Debug for AdvanceCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdvanceCallback")
+            .field("phase", &self.phase)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+pub struct ViewerContent {
+    raw_viewer_content: Option<NonNull<RawViewerContent>>,
+    /// Everything but the artboard's bounds, which `transform_stack` reads as it goes.
+    transform: Cell<TransformStack>,
+    playback: Playback,
+    events: Vec<ViewerEvent>,
+    match_bare_run_names: bool,
+    scrub: Option<ScrubBinding>,
+    drag: Option<ScrubDrag>,
+    /// Whether the primary button is held after a press which started a drag.
+    captured: bool,
+    /// Last pointer position forwarded, in local pixels.
+    last_pointer: Vec2,
+    /// Hash of the `.riv` file, matched against snapshots'.
+    file_hash: String,
+    status: Status,
+    /// Status last reported through `ViewerEvent::StatusChanged`.
+    reported_status: SceneStatus,
+    /// Policy last reported through `ViewerEvent::RedrawPolicyChanged`.
+    reported_policy: RedrawPolicy,
+    /// Inputs to set back after the next advance, to the value they had before their pulse.
+    pulses: Vec<InputSnapshot>,
+    /// Events built by the last advance, those passing `event_filter`.
+    fired_events: Vec<FiredEvent>,
+    /// Names of the events to build, or `None` to build all of them.
+    event_filter: Option<Vec<String>>,
+    /// Events the last advance didn't build because of `event_filter`.
+    skipped_events: usize,
+    subscriptions: Vec<EventSubscription>,
+    advance_callbacks: Vec<AdvanceCallback>,
+    /// Why missing assets are missing, by id, for those with more to say than `NOT_IN_FILE`.
+    asset_errors: HashMap<u32, String>,
+    history: History,
+    /// Seconds of frame time advanced by since the scene started.
+    played: f64,
+    /// Indices of the active state machine's inputs, listed on the first lookup since the
+    /// scene was selected.
+    input_indices: RefCell<Option<InputIndices>>,
+    _live: Live,
+}
+
+impl ViewerContent {
+    pub fn new<P: AsRef<Path>>(path: P) -> Option<Self> {
+        Self::from_bytes(&fs::read(path).ok()?)
+    }
+
+    /// Opens a `.riv` file which is already in memory, e.g. one read by a `Loader` or bundled
+    /// with the app. Fails for anything the runtime can't import and for files without
+    /// artboards.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if !data.starts_with(b"RIVE") {
+            return None;
         }
+
+        let raw_viewer_content =
+            unsafe { viewer_content_new_from_bytes(data.as_ptr(), data.len())? };
+
+        Some(Self::from_raw(raw_viewer_content, data))
     }
 
-    pub fn handle_pointer_up(&self, pos: Vec2) {
-        unsafe {
-            viewer_content_handle_pointer_up(self.raw_viewer_content, pos.x as f32, pos.y as f32);
+    /// Opens a `.riv` file which is already in memory like `from_bytes`, failing unless its
+    /// default artboard and scene satisfy `contract`.
+    pub fn from_bytes_with_contract(
+        bytes: &[u8],
+        contract: &InteractionContract,
+    ) -> Result<Self, ContractError> {
+        let content = Self::from_bytes(bytes).ok_or(ContractError::Unloadable)?;
+        let report = content
+            .check_contract(contract)
+            .map_err(|unavailable| ContractError::Unavailable(unavailable.0))?;
+        if !report.is_satisfied() {
+            return Err(ContractError::Broken(report));
         }
+        Ok(content)
     }
-}
 
-impl Drop for ViewerContent {
-    fn drop(&mut self) {
-        unsafe {
-            viewer_content_release(self.raw_viewer_content);
+    /// Opens a `.riv` file which is already in memory like `from_bytes`, then asks `load` for
+    /// the bytes of each image and font the file doesn't embed. Assets which `load` fails for,
+    /// or whose bytes can't be decoded, are drawn with their fallback and listed with the
+    /// error by `missing_assets`, to be retried with `rebind_asset`.
+    pub fn from_bytes_with_assets(
+        bytes: &[u8],
+        mut load: impl FnMut(&MissingAsset) -> Result<Vec<u8>, String>,
+    ) -> Option<Self> {
+        let mut content = Self::from_bytes(bytes)?;
+
+        for asset in content.missing_assets() {
+            match load(&asset) {
+                Ok(bytes) => {
+                    // `rebind_asset` records why the bytes couldn't be used.
+                    let _ = content.rebind_asset(asset.id, &bytes);
+                }
+                Err(error) => {
+                    content.asset_errors.insert(asset.id, error);
+                }
+            }
         }
+
+        Some(content)
+    }
+
+    fn from_raw(raw_viewer_content: NonNull<RawViewerContent>, bytes: &[u8]) -> Self {
+        Self {
+            raw_viewer_content: Some(raw_viewer_content),
+            transform: Cell::default(),
+            playback: Playback::default(),
+            events: Vec::new(),
+            match_bare_run_names: false,
+            scrub: None,
+            drag: None,
+            captured: false,
+            last_pointer: Vec2::ZERO,
+            file_hash: snapshot::file_hash(bytes),
+            status: Status::default(),
+            reported_status: SceneStatus::Ready,
+            reported_policy: RedrawPolicy::Continuous,
+            pulses: Vec::new(),
+            fired_events: Vec::new(),
+            event_filter: None,
+            skipped_events: 0,
+            subscriptions: Vec::new(),
+            advance_callbacks: Vec::new(),
+            asset_errors: HashMap::new(),
+            history: History::default(),
+            played: 0.0,
+            input_indices: RefCell::default(),
+            _live: Live::new(Kind::Instance),
+        }
+    }
+
+    /// Contents, images, renderers and texture targets created on this thread and not dropped
+    /// yet, in debug builds. Dropping a content releases its file and images on the CPU only,
+    /// so contents can be dropped before or after the device they were drawn with.
+    pub fn debug_live_counts() -> LiveCounts {
+        live::counts()
+    }
+
+    pub fn status(&self) -> SceneStatus {
+        self.status.get()
+    }
+
+    /// Identifies the content to the image textures it draws.
+    fn owner(&self) -> usize {
+        self.raw_viewer_content
+            .map_or(0, |raw_viewer_content| raw_viewer_content.as_ptr() as usize)
+    }
+
+    /// Suspends a ready content: advancing, drawing, forwarding pointers and setting inputs fail
+    /// until `wake`. The scene keeps its state, but the GPU textures of its images are evicted,
+    /// to be uploaded again once it's drawn after `wake`.
+    pub fn hibernate(&mut self) {
+        self.status
+            .transition(SceneStatus::Ready, SceneStatus::Hibernated);
+        if self.status.get() == SceneStatus::Hibernated {
+            gpu_memory::evict_images(self.owner());
+        }
+    }
+
+    /// Makes a hibernated content ready again. Other statuses are kept.
+    pub fn wake(&mut self) {
+        self.status
+            .transition(SceneStatus::Hibernated, SceneStatus::Ready);
+    }
+
+    /// Detaches the content from its scene for good: it can't be advanced, drawn or have its
+    /// inputs set anymore, whatever its status. The scene itself is released on drop, as
+    /// usual.
+    pub fn detach(&mut self) {
+        self.status.set(SceneStatus::Detached);
+    }
+
+    pub fn placement(&self) -> Affine {
+        self.transform.get().placement
+    }
+
+    /// Places the content in world space. The placement is composed with the view transform
+    /// when drawing and inverted when handling pointers through `handle_pointer_world`.
+    pub fn set_placement(&mut self, placement: Affine) {
+        self.update_transform(|stack| stack.placement = placement);
+    }
+
+    pub fn world_to_local(&self, pos: Vec2) -> Vec2 {
+        (self.placement().inverse() * Point::new(pos.x, pos.y)).to_vec2()
+    }
+
+    /// The transforms between the surface given to `handle_resize` and the artboard, which
+    /// drawing, pointers, scrubbing and `node_to_surface` all go through.
+    pub fn transform_stack(&self) -> TransformStack {
+        let mut stack = self.transform.get();
+        let mut bounds = [0.0f32; 4];
+        if unsafe { viewer_content_artboard_bounds(self.raw_viewer_content, bounds.as_mut_ptr()) } {
+            let [x0, y0, x1, y1] = bounds.map(f64::from);
+            stack.artboard_bounds = Rect::new(x0, y0, x1, y1);
+        }
+
+        stack
+    }
+
+    fn update_transform(&self, update: impl FnOnce(&mut TransformStack)) {
+        let mut stack = self.transform.get();
+        update(&mut stack);
+        self.transform.set(stack);
+    }
+
+    /// Physical pixels per logical unit of the surface, e.g. the window's scale factor. The
+    /// artboard is fitted to the logical size, so it keeps its size across displays. Defaults
+    /// to 1.
+    pub fn set_dpi_scale(&mut self, scale: f64) {
+        self.update_transform(|stack| stack.dpi_scale = scale);
+        self.resize_view();
+    }
+
+    /// Turns the view within the surface. Sideways orientations fit the artboard to the
+    /// surface's height as the view's width.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.update_transform(|stack| stack.orientation = orientation);
+        self.resize_view();
+    }
+
+    pub fn camera(&self) -> Camera {
+        self.transform.get().camera
+    }
+
+    /// Pans and zooms the world, and with it every content placed in it.
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.update_transform(|stack| stack.camera = camera);
+    }
+
+    /// Where the node or bone called `name` is on the surface, as the last advance left it.
+    pub fn node_to_surface(&self, name: &str) -> Option<Affine> {
+        let node = self.node(name)?;
+        Some(self.transform_stack().surface_from_artboard() * node.world)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.playback.is_paused()
+    }
+
+    pub fn pause(&mut self) {
+        self.playback.pause();
+    }
+
+    /// Resumes playback. A pending `pause_at_loop_end` is cancelled.
+    pub fn resume(&mut self) {
+        self.playback.resume();
+    }
+
+    /// Keeps playing until the active animation next reaches a loop boundary, either end for
+    /// ping-pong or completion for one-shot, and pauses exactly on it. Emits
+    /// `ViewerEvent::LoopCompleted` when it does. Only content driven by a linear animation has
+    /// boundaries; anything else keeps playing.
+    pub fn pause_at_loop_end(&mut self) {
+        self.playback.pause_at_loop_end();
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.playback.speed()
+    }
+
+    /// Multiplies the elapsed time of every frame. Negative speeds are clamped to 0.
+    pub fn set_speed(&mut self, speed: f64) {
+        self.playback.set_speed(speed);
+    }
+
+    /// Drains the events emitted since the last call.
+    pub fn take_events(&mut self) -> std::vec::Drain<'_, ViewerEvent> {
+        let status = self.status.get();
+        if status != self.reported_status {
+            self.reported_status = status;
+            self.events.push(ViewerEvent::StatusChanged { status });
+        }
+        let policy = self.redraw_policy();
+        if policy != self.reported_policy {
+            self.reported_policy = policy;
+            let event = ViewerEvent::RedrawPolicyChanged { policy };
+            self.events.push(event);
+        }
+
+        self.events.drain(..)
+    }
+
+    /// Whether the host can stop redrawing the content until something happens to it. Content
+    /// is `Continuous` until its first advance, and a state machine is `OnInteraction` once
+    /// its layers come to rest, and `Continuous` again as soon as an input changes or a
+    /// pointer event reaches a listener. Paused content is `OnInteraction` too.
+    ///
+    /// The policy is re-evaluated on every call, so hosts can check it after each advance or
+    /// event, or wait for `ViewerEvent::RedrawPolicyChanged`.
+    pub fn redraw_policy(&self) -> RedrawPolicy {
+        if self.status.get() != SceneStatus::Ready {
+            return RedrawPolicy::Static;
+        }
+
+        match unsafe { viewer_content_redraw_policy(self.raw_viewer_content) } {
+            0 => RedrawPolicy::Static,
+            1 => RedrawPolicy::OnInteraction,
+            // A pending pulse still needs the advance which ends it.
+            _ if self.is_paused() && self.pulses.is_empty() => RedrawPolicy::OnInteraction,
+            _ => RedrawPolicy::Continuous,
+        }
+    }
+
+    /// Sets a number input of the active state machine. Returns `false` if there's no number
+    /// input called `name`.
+    pub fn set_number(&self, name: &str, value: f32) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        Ok(self.write_input(name, InputValue::Number(value)))
+    }
+
+    /// The value of a number input of the active state machine.
+    pub fn number(&self, name: &str) -> Option<f32> {
+        let name = CString::new(name).ok()?;
+        let mut value = 0.0;
+        unsafe { viewer_content_number(self.raw_viewer_content, name.as_ptr(), &mut value) }
+            .then_some(value)
+    }
+
+    /// Sets a boolean input of the active state machine. Returns `false` if there's no boolean
+    /// input called `name`.
+    pub fn set_bool(&self, name: &str, value: bool) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        Ok(self.write_input(name, InputValue::Bool(value)))
+    }
+
+    /// Captures the layer states, number and boolean inputs and pending pulses of the active
+    /// state machine, or returns `None` when a linear animation is playing or the content is
+    /// detached.
+    pub fn snapshot(&self) -> Option<StateSnapshot> {
+        self.raw_viewer_content?;
+
+        let state_machine = unsafe {
+            let name = viewer_content_state_machine_name(self.raw_viewer_content);
+            if name.is_null() {
+                return None;
+            }
+            CStr::from_ptr(name).to_string_lossy().into_owned()
+        };
+
+        Some(StateSnapshot {
+            schema: SNAPSHOT_SCHEMA,
+            file_hash: self.file_hash.clone(),
+            state_machine,
+            layers: self.layers(),
+            inputs: self.input_values(),
+            pulses: self.pulses.clone(),
+        })
+    }
+
+    /// States of the active state machine's layers.
+    fn layers(&self) -> Vec<LayerSnapshot> {
+        let layer_count = unsafe { viewer_content_layer_count(self.raw_viewer_content) };
+        (0..layer_count)
+            .filter_map(|layer| {
+                let mut info = RawLayerStateInfo::default();
+                unsafe { viewer_content_layer_state(self.raw_viewer_content, layer, &mut info) }
+                    .then_some(LayerSnapshot {
+                        state: info.state,
+                        state_count: info.state_count,
+                        time: info.time,
+                    })
+            })
+            .collect()
+    }
+
+    /// Number and boolean inputs of the active state machine.
+    fn input_values(&self) -> Vec<InputSnapshot> {
+        let input_count = unsafe { viewer_content_input_count(self.raw_viewer_content) };
+        (0..input_count)
+            .filter_map(|index| {
+                let mut info = RawInputInfo {
+                    name: std::ptr::null(),
+                    input_type: 0,
+                    value: 0.0,
+                };
+                if !unsafe { viewer_content_input(self.raw_viewer_content, index, &mut info) } {
+                    return None;
+                }
+
+                let value = match info.input_type {
+                    0 => InputValue::Number(info.value),
+                    1 => InputValue::Bool(info.value != 0.0),
+                    _ => return None,
+                };
+                let name = unsafe { CStr::from_ptr(info.name) };
+
+                Some(InputSnapshot {
+                    name: name.to_string_lossy().into_owned(),
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Puts the active state machine back into the state of `snapshot`. Inputs are set before
+    /// layers jump to their states, so the next advance only transitions away if the file's
+    /// conditions changed. Strict restores apply nothing unless everything matches; partial
+    /// ones apply what does and report the rest.
+    pub fn restore(
+        &mut self,
+        snapshot: &StateSnapshot,
+        mode: RestoreMode,
+    ) -> Result<RestoreReport, RestoreError> {
+        let _guard = self
+            .status
+            .enter()
+            .map_err(|SceneUnavailable(status)| RestoreError::Unavailable(status))?;
+        let current = self.snapshot().ok_or(RestoreError::NoStateMachine)?;
+        let plan = snapshot::plan_restore(snapshot, &current, mode)?;
+
+        for (name, value) in &plan.inputs {
+            self.write_input(name, *value);
+        }
+        self.pulses = plan.pulses;
+        for (index, layer) in &plan.layers {
+            unsafe {
+                viewer_content_restore_layer_state(
+                    self.raw_viewer_content,
+                    *index,
+                    layer.state,
+                    layer.time,
+                );
+            }
+        }
+
+        Ok(plan.report)
+    }
+
+    /// Sets a boolean input to `true` for the next advance only, like a momentary button, then
+    /// back to the value it had. Hosts splitting a long frame into `Substeps` only pulse the
+    /// first step. Setting the input again before that advance doesn't change the value it goes
+    /// back to. Returns `false` if there's no boolean input called `name`.
+    ///
+    /// Prefer a trigger where the file has one: triggers are reset by the state machine itself.
+    /// Pulses are for files which only react to booleans, or to hold an input for a frame.
+    pub fn set_bool_pulse(&mut self, name: &str) -> Result<bool, SceneUnavailable> {
+        self.pulse(name, InputValue::Bool(true))
+    }
+
+    /// Sets a number input to `value` for the next advance only, then back to the value it
+    /// had, like `set_bool_pulse`. Returns `false` if there's no number input called `name`.
+    pub fn set_number_for_one_frame(
+        &mut self,
+        name: &str,
+        value: f32,
+    ) -> Result<bool, SceneUnavailable> {
+        self.pulse(name, InputValue::Number(value))
+    }
+
+    fn pulse(&mut self, name: &str, value: InputValue) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let Some(previous) = self
+            .input_values()
+            .into_iter()
+            .find(|input| input.name == name && input.value.is_same_kind(value))
+        else {
+            return Ok(false);
+        };
+
+        // A second pulse before the advance still goes back to the value before the first.
+        if !self.pulses.iter().any(|pulse| pulse.name == name) {
+            self.pulses.push(previous);
+        }
+
+        Ok(self.write_input(name, value))
+    }
+
+    /// Sets the inputs pulsed before the advance which just happened back to their value.
+    fn end_pulses(&mut self) {
+        for pulse in std::mem::take(&mut self.pulses) {
+            self.write_input(&pulse.name, pulse.value);
+        }
+    }
+
+    fn write_input(&self, name: &str, value: InputValue) -> bool {
+        let input_type = match value {
+            InputValue::Number(_) => InputType::Number,
+            InputValue::Bool(_) => InputType::Bool,
+        };
+        let Some(index) = self.input_index(name, input_type) else {
+            return false;
+        };
+
+        unsafe {
+            match value {
+                InputValue::Number(value) => {
+                    viewer_content_set_number_at(self.raw_viewer_content, index, value)
+                }
+                InputValue::Bool(value) => {
+                    viewer_content_set_bool_at(self.raw_viewer_content, index, value)
+                }
+            }
+        }
+    }
+
+    /// Index of the first input of the active state machine called `name` and of type
+    /// `input_type`. Inputs are listed once per scene, so hosts setting an input every frame
+    /// don't compare names every frame.
+    fn input_index(&self, name: &str, input_type: InputType) -> Option<usize> {
+        self.input_indices
+            .borrow_mut()
+            .get_or_insert_with(|| InputIndices::new(self.indexed_inputs()))
+            .get(name, input_type)
+    }
+
+    fn raw_input(&self, index: usize) -> Option<RawInputInfo> {
+        let mut info = RawInputInfo::default();
+        unsafe { viewer_content_input(self.raw_viewer_content, index, &mut info) }.then_some(info)
+    }
+
+    /// Inputs of the active state machine with their runtime indices. Inputs of types this
+    /// crate doesn't know are skipped, since they can't be set.
+    fn indexed_inputs(&self) -> Vec<(usize, InputDescriptor)> {
+        let input_count = unsafe { viewer_content_input_count(self.raw_viewer_content) };
+        (0..input_count)
+            .filter_map(|index| Some((index, unsafe { self.raw_input(index)?.descriptor() }?)))
+            .collect()
+    }
+
+    /// Inputs of the active state machine, in file order. Empty when a linear animation is
+    /// playing.
+    pub fn inputs(&self) -> Vec<InputDescriptor> {
+        self.indexed_inputs()
+            .into_iter()
+            .map(|(_, input)| input)
+            .collect()
+    }
+
+    /// The value of a boolean input of the active state machine.
+    pub fn boolean(&self, name: &str) -> Option<bool> {
+        let index = self.input_index(name, InputType::Bool)?;
+        Some(self.raw_input(index)?.value != 0.0)
+    }
+
+    /// Fires a trigger input of the active state machine, which the next advance reacts to.
+    /// Returns `false` if there's no trigger called `name`, e.g. when a linear animation is
+    /// playing.
+    pub fn fire_trigger(&self, name: &str) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        Ok(match self.input_index(name, InputType::Trigger) {
+            Some(index) => unsafe {
+                viewer_content_fire_trigger_at(self.raw_viewer_content, index)
+            },
+            None => false,
+        })
+    }
+
+    /// Like `set_number`, but snaps `value` to the nearest of the input's `discrete_values` so
+    /// that e.g. 1.9999999 from a slider still satisfies an `== 2` condition. Inputs without
+    /// discrete values are set unchanged.
+    pub fn set_number_quantized(&self, name: &str, value: f32) -> Result<bool, SceneUnavailable> {
+        self.set_number(name, input::quantize(&self.discrete_values(name), value))
+    }
+
+    /// Values the number input `name` is compared against for equality, or placed at in 1D
+    /// blend states, sorted. Inputs which are also compared with `<`, `<=`, `>` or `>=` are
+    /// continuous and have none, so do inputs of contents without a state machine. UIs can
+    /// show a stepper instead of a slider for inputs which have some.
+    pub fn discrete_values(&self, name: &str) -> Vec<f32> {
+        let Ok(name) = CString::new(name) else {
+            return Vec::new();
+        };
+        let mut values = std::ptr::null();
+
+        unsafe {
+            let len =
+                viewer_content_discrete_values(self.raw_viewer_content, name.as_ptr(), &mut values);
+
+            if len == 0 {
+                Vec::new()
+            } else {
+                std::slice::from_raw_parts(values, len).to_vec()
+            }
+        }
+    }
+
+    /// Checks the inputs of the active state machine and the events, text runs and nested
+    /// artboards of the current artboard against what `contract` expects.
+    pub fn check_contract(
+        &self,
+        contract: &InteractionContract,
+    ) -> Result<ContractReport, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        Ok(ContractReport::check(contract, &self.interface()))
+    }
+
+    fn interface(&self) -> FileInterface {
+        let input_count = unsafe { viewer_content_input_count(self.raw_viewer_content) };
+        let inputs = (0..input_count)
+            .filter_map(|index| {
+                let info = self.raw_input(index)?;
+                let name = unsafe { CStr::from_ptr(info.name) }.to_string_lossy();
+                unsafe { info.file_input(&self.discrete_values(&name)) }
+            })
+            .collect();
+
+        FileInterface {
+            inputs,
+            events: self.events(),
+            text_runs: self.text_runs(),
+            nested_paths: self.nested_artboards(),
+        }
+    }
+
+    /// Scrubs the number input `input` while the primary pointer is dragged horizontally,
+    /// replacing any previous binding. Presses which move further than `DRAG_THRESHOLD` pixels
+    /// are scrubs and their release doesn't reach the listeners, so they don't click; shorter
+    /// ones are forwarded as usual. Returns `None` if there's no number input called `input`,
+    /// otherwise the binding, e.g. to restrict it to a `hit_area`.
+    pub fn bind_horizontal_scrub(
+        &mut self,
+        input: &str,
+        sensitivity: f32,
+        clamp: Option<(f32, f32)>,
+    ) -> Option<&mut ScrubBinding> {
+        self.number(input)?;
+        self.drag = None;
+
+        Some(
+            self.scrub
+                .insert(ScrubBinding::new(input, sensitivity, clamp)),
+        )
+    }
+
+    pub fn unbind_scrub(&mut self) {
+        self.scrub = None;
+        self.drag = None;
+    }
+
+    /// Binds the first of the `AUTO_SCRUB_INPUTS` the active state machine has, so that dragging
+    /// across the whole artboard covers `AUTO_SCRUB_RANGE`. Returns the name of the bound
+    /// input.
+    pub fn auto_bind_scrub(&mut self) -> Option<&'static str> {
+        let width = unsafe { viewer_content_artboard_width(self.raw_viewer_content) };
+        if width <= 0.0 {
+            return None;
+        }
+
+        let (min, max) = AUTO_SCRUB_RANGE;
+        let input = AUTO_SCRUB_INPUTS
+            .into_iter()
+            .find(|input| self.number(input).is_some())?;
+        self.bind_horizontal_scrub(input, (max - min) / width, Some(AUTO_SCRUB_RANGE));
+
+        Some(input)
+    }
+
+    /// What the runtime makes of the view transform, to check `TransformStack` against.
+    #[cfg(test)]
+    fn view_to_artboard(&self, pos: Vec2) -> Option<Vec2> {
+        let (mut x, mut y) = (0.0, 0.0);
+        unsafe {
+            viewer_content_view_to_artboard(
+                self.raw_viewer_content,
+                pos.x as f32,
+                pos.y as f32,
+                &mut x,
+                &mut y,
+            )
+        }
+        .then(|| Vec2::new(x as f64, y as f64))
+    }
+
+    fn start_scrub(&mut self, pos: Vec2) {
+        let Some(binding) = &self.scrub else { return };
+
+        if let Some(hit_area) = &binding.hit_area {
+            let Ok(name) = CString::new(hit_area.as_str()) else {
+                return;
+            };
+            let hit = unsafe {
+                viewer_content_hits_shape(
+                    self.raw_viewer_content,
+                    name.as_ptr(),
+                    pos.x as f32,
+                    pos.y as f32,
+                )
+            };
+            if !hit {
+                return;
+            }
+        }
+
+        let artboard = self.transform_stack().local_to_artboard(pos);
+        if let (Some(value), Some(artboard)) = (self.number(&binding.input), artboard) {
+            self.drag = Some(ScrubDrag::new(
+                (pos.x as f32, pos.y as f32),
+                artboard.x as f32,
+                value,
+            ));
+        }
+    }
+
+    fn move_scrub(&mut self, pos: Vec2) {
+        if self.drag.is_none() {
+            return;
+        }
+        let Some(artboard) = self.transform_stack().local_to_artboard(pos) else {
+            return;
+        };
+        let (Some(binding), Some(drag)) = (&self.scrub, &mut self.drag) else {
+            return;
+        };
+
+        if let Some(value) = drag.move_to(binding, (pos.x as f32, pos.y as f32), artboard.x as f32)
+        {
+            let _ = self.set_number(&binding.input, value);
+        }
+    }
+
+    /// Lets `apply_text_table` match runs of nested artboards by their bare name as well as by
+    /// their path.
+    pub fn set_match_bare_run_names(&mut self, match_bare_run_names: bool) {
+        self.match_bare_run_names = match_bare_run_names;
+    }
+
+    /// Draws every solid fill or stroke named "currentColor", the way icon sets mark the paint
+    /// the host picks, in `color`, including those of nested artboards. Icons are recolored
+    /// from the next draw on, and stay so across artboard and scene changes until cleared.
+    /// Only their paints change, so paths aren't rebuilt. Returns how many paints the current
+    /// artboard has to recolor. Animations keying those colors override `color` while they
+    /// play.
+    pub fn set_primary_color(&mut self, color: Color) -> Result<usize, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let argb = u32::from_be_bytes([color.a, color.r, color.g, color.b]);
+
+        Ok(unsafe { viewer_content_set_primary_color(self.raw_viewer_content, argb) })
+    }
+
+    /// Puts back the colors the "currentColor" paints were authored with.
+    pub fn clear_primary_color(&mut self) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        unsafe {
+            viewer_content_clear_primary_color(self.raw_viewer_content);
+        }
+
+        Ok(())
+    }
+
+    /// Names of the file's artboards, in file order.
+    pub fn artboard_names(&self) -> Vec<String> {
+        if self.raw_viewer_content.is_none() {
+            return Vec::new();
+        }
+
+        unsafe {
+            (0..viewer_content_artboard_count(self.raw_viewer_content))
+                .map(|i| {
+                    let name = viewer_content_artboard_name(self.raw_viewer_content, i);
+                    CStr::from_ptr(name).to_string_lossy().into_owned()
+                })
+                .collect()
+        }
+    }
+
+    /// Name of the artboard playing, or `None` for a detached content.
+    pub fn artboard(&self) -> Option<String> {
+        self.raw_viewer_content?;
+        let index = unsafe { viewer_content_artboard_index(self.raw_viewer_content) };
+        self.artboard_names().into_iter().nth(index)
+    }
+
+    /// Names of the state machines of the first artboard called `artboard`, whether or not
+    /// it's playing. Empty if there's no such artboard.
+    pub fn state_machine_names(&self, artboard: &str) -> Vec<String> {
+        match self
+            .artboard_names()
+            .iter()
+            .position(|name| name == artboard)
+        {
+            Some(index) => self.state_machines_at(index),
+            None => Vec::new(),
+        }
+    }
+
+    /// Names of the scenes of the first artboard called `artboard`, whether or not it's
+    /// playing: its state machines, then its animations. Empty if there's no such artboard.
+    pub fn scene_names(&self, artboard: &str) -> Vec<String> {
+        match self
+            .artboard_names()
+            .iter()
+            .position(|name| name == artboard)
+        {
+            Some(index) => self.scenes_at(index),
+            None => Vec::new(),
+        }
+    }
+
+    fn state_machines_at(&self, artboard: usize) -> Vec<String> {
+        unsafe {
+            let count =
+                viewer_content_artboard_state_machine_count(self.raw_viewer_content, artboard);
+            (0..count)
+                .map(|i| {
+                    let name = viewer_content_artboard_state_machine_name(
+                        self.raw_viewer_content,
+                        artboard,
+                        i,
+                    );
+                    CStr::from_ptr(name).to_string_lossy().into_owned()
+                })
+                .collect()
+        }
+    }
+
+    /// Scenes of the artboard at `artboard`, in the order the runtime indexes them.
+    fn scenes_at(&self, artboard: usize) -> Vec<String> {
+        let mut scenes = self.state_machines_at(artboard);
+        unsafe {
+            let count = viewer_content_artboard_animation_count(self.raw_viewer_content, artboard);
+            scenes.extend((0..count).map(|i| {
+                let name =
+                    viewer_content_artboard_animation_name(self.raw_viewer_content, artboard, i);
+                CStr::from_ptr(name).to_string_lossy().into_owned()
+            }));
+        }
+        scenes
+    }
+
+    /// Plays the first artboard called `artboard`, or the current one for `None`, from the
+    /// start with its first scene called `scene`, looking at state machines before animations,
+    /// or with its default state machine or first animation for `None`. The content's
+    /// settings, bindings and subscriptions carry over; the clock starts again and the
+    /// artboard is fitted to the last `handle_resize`. Nothing changes if either name is
+    /// unknown.
+    pub fn select(
+        &mut self,
+        artboard: Option<&str>,
+        scene: Option<&str>,
+    ) -> Result<(), SelectError> {
+        let _guard = self
+            .status
+            .enter()
+            .map_err(|unavailable| SelectError::Unavailable(unavailable.0))?;
+        let artboards = self.artboard_names();
+        let artboard_index = match artboard {
+            Some(name) => artboards
+                .iter()
+                .position(|artboard| artboard == name)
+                .ok_or_else(|| SelectError::UnknownArtboard(name.to_string()))?,
+            None => unsafe { viewer_content_artboard_index(self.raw_viewer_content) },
+        };
+        let unknown_scene = |name: &str| SelectError::UnknownScene {
+            artboard: artboards[artboard_index].clone(),
+            name: name.to_string(),
+        };
+        let scene_index = scene
+            .map(|name| {
+                self.scenes_at(artboard_index)
+                    .iter()
+                    .position(|scene| scene == name)
+                    .ok_or_else(|| unknown_scene(name))
+            })
+            .transpose()?;
+
+        let selected =
+            unsafe { viewer_content_select_artboard(self.raw_viewer_content, artboard_index) };
+        if !selected {
+            return Err(SelectError::UnknownArtboard(
+                artboards[artboard_index].clone(),
+            ));
+        }
+        // The runtime indexes the selected artboard's scenes the same way as `scenes_at`.
+        let selected = scene_index.is_none_or(|index| unsafe {
+            viewer_content_select_scene(self.raw_viewer_content, index)
+        });
+        self.reset_after_selection();
+
+        match (selected, scene) {
+            (false, Some(name)) => Err(unknown_scene(name)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts over after the runtime switched to another artboard or scene: drops any drag,
+    /// restarts the clock, forgets the old scene's inputs, reloads the watched properties and
+    /// fits the artboard, which may have bounds of its own, to the view.
+    fn reset_after_selection(&mut self) {
+        self.drag = None;
+        self.playback.restart(self.playback_state());
+        self.played = 0.0;
+        self.input_indices.take();
+        self.load_watched();
+        self.resize_view();
+    }
+
+    /// Names of the current artboard's scenes: its state machines, then its animations.
+    pub fn scenes(&self) -> Vec<String> {
+        if self.raw_viewer_content.is_none() {
+            return Vec::new();
+        }
+
+        unsafe {
+            (0..viewer_content_scene_count(self.raw_viewer_content))
+                .map(|i| {
+                    let name = viewer_content_scene_name(self.raw_viewer_content, i);
+                    CStr::from_ptr(name).to_string_lossy().into_owned()
+                })
+                .collect()
+        }
+    }
+
+    /// Name of the scene playing, or `None` for a detached content or an artboard without any.
+    pub fn scene(&self) -> Option<String> {
+        self.raw_viewer_content?;
+        let index = unsafe { viewer_content_scene_index(self.raw_viewer_content) };
+        self.scenes().into_iter().nth(index)
+    }
+
+    /// How the artboard is fitted to the size given to `handle_resize` or `draw`. Defaults to
+    /// `Fit::Contain`.
+    pub fn set_fit(&mut self, fit: Fit) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        self.update_transform(|stack| stack.fit = fit);
+        unsafe {
+            viewer_content_set_fit(self.raw_viewer_content, fit as i32);
+        }
+
+        Ok(())
+    }
+
+    /// Rounds the artboard's position in the view to whole pixels, halves up. Edges which start
+    /// on a pixel then stay on one however the view is resized, instead of blurring and
+    /// sharpening as odd and even sizes alternate. Off by default.
+    pub fn set_pixel_snap(&mut self, snap: bool) {
+        self.update_transform(|stack| stack.pixel_snap = snap);
+        unsafe {
+            viewer_content_set_pixel_snap(self.raw_viewer_content, snap);
+        }
+    }
+
+    /// Paths of the named text runs of the current artboard. Runs of nested artboards are
+    /// prefixed with the names of the nested artboards leading to them, e.g. `card/title`.
+    pub fn text_runs(&self) -> Vec<String> {
+        unsafe {
+            (0..viewer_content_text_run_count(self.raw_viewer_content))
+                .map(|i| {
+                    let path = viewer_content_text_run_path(self.raw_viewer_content, i);
+                    CStr::from_ptr(path).to_string_lossy().into_owned()
+                })
+                .collect()
+        }
+    }
+
+    /// Text of the run at `path`, as listed by `text_runs`.
+    pub fn text_run(&self, path: &str) -> Option<String> {
+        if self.raw_viewer_content.is_none() {
+            return None;
+        }
+
+        let index = self.text_runs().iter().position(|run| run == path)?;
+        unsafe {
+            let text = viewer_content_text_run_text(self.raw_viewer_content, index);
+            Some(CStr::from_ptr(text).to_string_lossy().into_owned())
+        }
+    }
+
+    /// Names of the current artboard's events, which its state machine can fire.
+    pub fn events(&self) -> Vec<String> {
+        unsafe {
+            (0..viewer_content_event_count(self.raw_viewer_content))
+                .map(|i| {
+                    let name = viewer_content_event_name(self.raw_viewer_content, i);
+                    CStr::from_ptr(name).to_string_lossy().into_owned()
+                })
+                .collect()
+        }
+    }
+
+    /// Paths of the named nested artboards of the current artboard, built like the ones of
+    /// `text_runs`, e.g. `card/icon`.
+    pub fn nested_artboards(&self) -> Vec<String> {
+        unsafe {
+            (0..viewer_content_nested_artboard_count(self.raw_viewer_content))
+                .map(|i| {
+                    let path = viewer_content_nested_artboard_path(self.raw_viewer_content, i);
+                    CStr::from_ptr(path).to_string_lossy().into_owned()
+                })
+                .collect()
+        }
+    }
+
+    /// Sets every text run matching a key of `table`. Affected texts are shaped once, on the
+    /// next draw. The runs are looked up again on every call, so re-applying the table after
+    /// loading a file or switching artboards is a single call. Values are cut at the first nul.
+    pub fn apply_text_table(
+        &mut self,
+        table: &HashMap<String, String>,
+    ) -> Result<TextApplyReport, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let paths = self.text_runs();
+        let (matches, report) = text::match_runs(&paths, table, self.match_bare_run_names);
+
+        for (index, value) in matches {
+            let value = value.split('\0').next().unwrap_or_default();
+            let c_str = CString::new(value).unwrap();
+
+            unsafe {
+                viewer_content_set_text_run(self.raw_viewer_content, index, c_str.as_ptr());
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Sets the base direction of every text of the current artboard, e.g. to preview a
+    /// right-to-left locale, and returns how many texts there are. The direction isn't stored
+    /// in files yet, so they always load with `TextDirection::Auto`, and so does an artboard
+    /// after switching to it.
+    pub fn set_text_direction(
+        &mut self,
+        direction: TextDirection,
+    ) -> Result<usize, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        Ok(unsafe { viewer_content_set_text_direction(self.raw_viewer_content, direction.raw()) })
+    }
+
+    /// Images and fonts of the file which couldn't be loaded, in file order, with what is drawn
+    /// in their place and why.
+    pub fn missing_assets(&self) -> Vec<MissingAsset> {
+        if self.raw_viewer_content.is_none() {
+            return Vec::new();
+        }
+        let count = unsafe { viewer_content_missing_asset_count(self.raw_viewer_content) };
+
+        (0..count)
+            .filter_map(|i| {
+                let mut raw = RawMissingAsset {
+                    asset_id: 0,
+                    name: std::ptr::null(),
+                    asset_type: 0,
+                    fallback: 0,
+                };
+                if !unsafe { viewer_content_missing_asset(self.raw_viewer_content, i, &mut raw) } {
+                    return None;
+                }
+
+                let name = unsafe { CStr::from_ptr(raw.name) }
+                    .to_string_lossy()
+                    .into_owned();
+                let error = self
+                    .asset_errors
+                    .get(&raw.asset_id)
+                    .map_or(NOT_IN_FILE, String::as_str);
+                MissingAsset::from_raw(
+                    raw.asset_id,
+                    name,
+                    raw.asset_type,
+                    raw.fallback,
+                    error.to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// Decodes `bytes` into the missing asset `asset_id` and swaps it in for its fallback. Images
+    /// are drawn with their new pixels and texts are shaped again on the next draw.
+    pub fn rebind_asset(&mut self, asset_id: u32, bytes: &[u8]) -> Result<(), RebindError> {
+        let _guard = self
+            .status
+            .enter()
+            .map_err(|unavailable| RebindError::Unavailable(unavailable.0))?;
+
+        if !self
+            .missing_assets()
+            .iter()
+            .any(|asset| asset.id == asset_id)
+        {
+            return Err(RebindError::NotMissing(asset_id));
+        }
+
+        let rebound = unsafe {
+            viewer_content_rebind_asset(
+                self.raw_viewer_content,
+                asset_id,
+                bytes.as_ptr(),
+                bytes.len(),
+            )
+        };
+        if !rebound {
+            self.asset_errors
+                .insert(asset_id, RebindError::Undecodable(asset_id).to_string());
+            return Err(RebindError::Undecodable(asset_id));
+        }
+
+        self.asset_errors.remove(&asset_id);
+        Ok(())
+    }
+
+    /// The authored timing of the active linear animation. `None` for content driven by a state
+    /// machine or without a scene.
+    pub fn animation_info(&self) -> Option<AnimationInfo> {
+        let animation = unsafe { viewer_content_linear_animation(self.raw_viewer_content) }?;
+        Some(unsafe { AnimationInfo::from_raw(animation) })
+    }
+
+    /// What the active state machine applied on its last advance: per layer, both sides of a
+    /// transition in progress and one entry per animation of a blend state. Empty for content
+    /// driven by a linear animation.
+    pub fn applied_animations(&self) -> Vec<AppliedAnimation> {
+        unsafe {
+            (0..viewer_content_applied_animation_count(self.raw_viewer_content))
+                .filter_map(|i| {
+                    let mut raw = RawAppliedAnimation {
+                        name: std::ptr::null(),
+                        time: 0.0,
+                        mix: 0.0,
+                        layer: 0,
+                    };
+                    viewer_content_applied_animation(self.raw_viewer_content, i, &mut raw).then(
+                        || AppliedAnimation {
+                            name: CStr::from_ptr(raw.name).to_string_lossy().into_owned(),
+                            time: raw.time,
+                            mix: raw.mix,
+                            layer: raw.layer,
+                        },
+                    )
+                })
+                .collect()
+        }
+    }
+
+    /// Events the active state machine fired on the last `advance`, in the order they fired,
+    /// except for those skipped by the event filter. Empty for content driven by a linear
+    /// animation.
+    pub fn events_this_frame(&self) -> &[FiredEvent] {
+        &self.fired_events
+    }
+
+    /// Only builds `events_this_frame` for events called one of `names`. Others are counted by
+    /// `skipped_event_count` without copying their names or properties, which adds up with
+    /// many instances firing events every frame. No names builds none, e.g. for hosts which
+    /// only subscribe. The state machine still fires and reacts to every event.
+    pub fn set_event_filter(&mut self, names: &[&str]) {
+        self.event_filter = Some(names.iter().map(|name| name.to_string()).collect());
+    }
+
+    /// Builds every event again.
+    pub fn clear_event_filter(&mut self) {
+        self.event_filter = None;
+    }
+
+    /// Events the last `advance` fired but didn't build because of the event filter.
+    pub fn skipped_event_count(&self) -> usize {
+        self.skipped_events
+    }
+
+    /// Calls `handler` from `advance` for every event called `name` the active state machine
+    /// fires, whether or not the event filter lets it through.
+    pub fn subscribe_event(&mut self, name: &str, handler: impl FnMut(&EventRef<'_>) + 'static) {
+        self.subscriptions.push(EventSubscription {
+            name: name.to_string(),
+            handler: Box::new(handler),
+        });
+    }
+
+    /// Drops the handlers subscribed to `name`.
+    pub fn unsubscribe_event(&mut self, name: &str) {
+        self.subscriptions
+            .retain(|subscription| subscription.name != name);
+    }
+
+    /// Keeps a record of each of the last `frames` advances: the values of the watched
+    /// properties, the state of each layer, the inputs and the events built. 0 stops recording
+    /// and drops the records. Advances by no time while paused aren't recorded, so stepping
+    /// through a paused content's history doesn't push frames out of it.
+    pub fn enable_history(&mut self, frames: usize) {
+        self.show_live();
+        self.history.set_capacity(frames);
+        self.warn_large_history();
+    }
+
+    /// Records of the last advances, oldest first.
+    pub fn history(&self) -> &[FrameRecord] {
+        self.history.records()
+    }
+
+    /// Watches the properties the artboard's animations key on the components called `name`
+    /// and their descendants, e.g. a character's "face". Watches carry over artboard and scene
+    /// changes, and drop the records taken so far. Returns how many properties are watched in
+    /// all.
+    pub fn watch_component(&mut self, name: &str) -> Result<usize, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let c_str = CString::new(name.replace('\0', "")).unwrap();
+
+        unsafe {
+            viewer_content_watch_component(self.raw_viewer_content, c_str.as_ptr());
+        }
+        self.load_watched();
+
+        Ok(self.history.watched().len())
+    }
+
+    /// Watches every property the artboard's animations key. Large files have thousands, and
+    /// emit `ViewerEvent::LargeHistory` if that makes the history ring too big.
+    pub fn watch_all_components(&mut self) -> Result<usize, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+
+        unsafe {
+            viewer_content_watch_component(self.raw_viewer_content, std::ptr::null());
+        }
+        self.load_watched();
+
+        Ok(self.history.watched().len())
+    }
+
+    pub fn clear_watches(&mut self) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+
+        unsafe {
+            viewer_content_clear_watches(self.raw_viewer_content);
+        }
+        self.load_watched();
+
+        Ok(())
+    }
+
+    /// Properties whose values are recorded, in the order of `FrameRecord::values`.
+    pub fn watched_properties(&self) -> &[WatchedProperty] {
+        self.history.watched()
+    }
+
+    /// Draws the watched properties with the values of `history()[index]`, e.g. to look at a
+    /// glitch frame by frame. Only what's drawn changes: nothing is simulated again, the state
+    /// machine keeps its state and unwatched properties keep their live values. The frame stays
+    /// on display through advances by no time, e.g. while paused, until `show_live_frame` or
+    /// an advance by more. Returns `false` if there's no such record.
+    pub fn show_history_frame(&mut self, index: usize) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let raw_viewer_content = self.raw_viewer_content;
+
+        Ok(self.history.show(index, |values| unsafe {
+            viewer_content_show_watched(raw_viewer_content, values.as_ptr());
+        }))
+    }
+
+    /// Index in `history` of the frame on display, or `None` while the live pose is.
+    pub fn shown_history_frame(&self) -> Option<usize> {
+        self.history.shown()
+    }
+
+    /// Puts back the pose of the last advance after `show_history_frame`.
+    pub fn show_live_frame(&mut self) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        self.show_live();
+
+        Ok(())
+    }
+
+    fn show_live(&mut self) {
+        if self.history.shown().is_none() {
+            return;
+        }
+
+        unsafe {
+            viewer_content_show_live(self.raw_viewer_content);
+        }
+        self.history.show_live();
+    }
+
+    /// Puts the live pose back before advancing by `elapsed`, if a history frame is on display.
+    /// Returns `false` when the frame stays on display instead, and the content isn't to be
+    /// advanced.
+    fn leave_history_frame(&mut self, elapsed: f64) -> bool {
+        if self.history.shown().is_some() && elapsed == 0.0 {
+            return false;
+        }
+        self.show_live();
+
+        true
+    }
+
+    fn record_frame(&mut self, elapsed: f64) {
+        if self.history.capacity() == 0 || (elapsed == 0.0 && self.is_paused()) {
+            return;
+        }
+
+        let layers = self.layers();
+        let inputs = self.input_values();
+        let raw_viewer_content = self.raw_viewer_content;
+        let read = |values: &mut [u32]| unsafe {
+            viewer_content_read_watched(raw_viewer_content, values.as_mut_ptr());
+        };
+        self.history
+            .push(elapsed, layers, inputs, &self.fired_events, read);
+    }
+
+    /// Reads back the watch list, which the runtime rebuilds for each artboard and watch.
+    fn load_watched(&mut self) {
+        let count = unsafe { viewer_content_watched_property_count(self.raw_viewer_content) };
+        let watched = (0..count)
+            .filter_map(|index| {
+                let mut info = RawWatchedProperty {
+                    component: std::ptr::null(),
+                    property_key: 0,
+                    value_type: 0,
+                };
+                if !unsafe {
+                    viewer_content_watched_property(self.raw_viewer_content, index, &mut info)
+                } {
+                    return None;
+                }
+                let component = unsafe { CStr::from_ptr(info.component) };
+
+                Some(WatchedProperty::new(
+                    component.to_string_lossy().into_owned(),
+                    info.property_key,
+                    info.value_type,
+                ))
+            })
+            .collect();
+
+        self.history.set_watched(watched);
+        self.warn_large_history();
+    }
+
+    fn warn_large_history(&mut self) {
+        let bytes = self.history.estimated_bytes();
+        if bytes > HISTORY_WARNING_BYTES {
+            self.events.push(ViewerEvent::LargeHistory { bytes });
+        }
+    }
+
+    /// Hands the events fired by the last advance to their subscribers and builds those which
+    /// pass the event filter.
+    fn dispatch_events(&mut self) {
+        self.fired_events.clear();
+        self.skipped_events = 0;
+
+        let count = unsafe { viewer_content_fired_event_count(self.raw_viewer_content) };
+        for index in 0..count {
+            let Some(event) = EventRef::new(self.raw_viewer_content, index) else {
+                continue;
+            };
+
+            for subscription in &mut self.subscriptions {
+                if subscription.name == event.name() {
+                    (subscription.handler)(&event);
+                }
+            }
+
+            let built = match &self.event_filter {
+                Some(names) => names.iter().any(|name| name == event.name()),
+                None => true,
+            };
+            if built {
+                self.fired_events.push(event.to_fired_event());
+            } else {
+                self.skipped_events += 1;
+            }
+        }
+    }
+
+    /// Moves the active linear animation to `frame`, i.e. to `AnimationInfo::time_at_frame`
+    /// seconds, keeping its direction. Frames outside the work area are brought back into it
+    /// by the next advance, the way the runtime handles any out of range time. Returns `false`
+    /// if there is no active linear animation.
+    pub fn seek_frame(&mut self, frame: u32) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let Some(info) = self.animation_info() else {
+            return Ok(false);
+        };
+
+        unsafe {
+            viewer_content_seek(self.raw_viewer_content, info.time_at_frame(frame));
+        }
+        self.playback.restart(self.playback_state());
+
+        Ok(true)
+    }
+
+    /// Loops the active linear animation between two times in seconds, e.g. to review a
+    /// stretch of it over and over. The range overrides the work area and always repeats,
+    /// ping-ponging if the animation does and looping otherwise, even if it's a one shot.
+    /// Reversed bounds are swapped, and a range narrower than a frame is widened to one.
+    /// `None` goes back to the work area.
+    ///
+    /// Returns the range which was applied, so a clamped one can be told from the one asked
+    /// for. `None` if the range was cleared or there is no active linear animation.
+    pub fn set_play_range(
+        &mut self,
+        range: Option<(f32, f32)>,
+    ) -> Result<Option<(f32, f32)>, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let Some((start, end)) = range else {
+            unsafe { viewer_content_clear_play_range(self.raw_viewer_content) };
+            self.playback.restart(self.playback_state());
+            return Ok(None);
+        };
+
+        unsafe { viewer_content_set_play_range(self.raw_viewer_content, start, end) };
+        self.playback.restart(self.playback_state());
+
+        Ok(self.playback_state().map(|state| (state.start, state.end)))
+    }
+
+    /// Starts the content `seconds` into its timeline, as if it had been advanced by that much
+    /// in one step: loops wrap and ping-pongs turn around, but events fired along the way
+    /// are dropped. Pausing and speed don't apply, so call this right after creating the
+    /// content, e.g. to keep copies of the same file from playing in sync.
+    pub fn set_time_offset(&mut self, seconds: f32) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        unsafe {
+            viewer_content_offset_time(self.raw_viewer_content, seconds);
+        }
+        self.playback.restart(self.playback_state());
+
+        Ok(())
+    }
+
+    /// Offsets the active linear animation to a random point of its cycle with
+    /// `set_time_offset`. A ping-pong's cycle covers both directions, so copies also start
+    /// playing backwards. Returns the offset, or `None` for content without a linear
+    /// animation or one which doesn't move.
+    pub fn randomize_phase<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<Option<f32>, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let Some(info) = self.animation_info() else {
+            return Ok(None);
+        };
+        let mut cycle = info.duration_seconds() / info.speed.abs();
+        if info.loop_mode == LoopMode::PingPong {
+            cycle *= 2.0;
+        }
+        if !cycle.is_finite() || cycle <= 0.0 {
+            return Ok(None);
+        }
+
+        let offset = rng.gen_range(0.0..cycle);
+        self.set_time_offset(offset)?;
+
+        Ok(Some(offset))
+    }
+
+    /// Seconds of frame time passed to `advance` and `handle_draw` since the content was
+    /// created or a scene was selected, whatever the speed or pauses made of them.
+    pub fn played(&self) -> f64 {
+        self.played
+    }
+
+    /// Where the active linear animation is in its timeline, in seconds.
+    pub fn time(&self) -> Option<f32> {
+        Some(self.playback_state()?.time)
+    }
+
+    /// The frame the active linear animation is nearest to, per `AnimationInfo::frame_at_time`.
+    pub fn current_frame(&self) -> Option<u32> {
+        let info = self.animation_info()?;
+        let state = self.playback_state()?;
+
+        Some(info.frame_at_time(state.time))
+    }
+
+    fn playback_state(&self) -> Option<PlaybackState> {
+        let mut state = PlaybackState::default();
+        unsafe { viewer_content_playback_state(self.raw_viewer_content, &mut state) }
+            .then_some(state)
+    }
+
+    /// Resizes the surface, in physical pixels. The artboard is fitted to the view this leaves,
+    /// see `TransformStack::view_size`.
+    pub fn handle_resize(&self, width: u32, height: u32) {
+        self.update_transform(|stack| stack.surface_size = (width, height));
+        self.resize_view();
+    }
+
+    fn resize_view(&self) {
+        let (width, height) = self.transform.get().view_size();
+        unsafe {
+            viewer_content_handle_resize(self.raw_viewer_content, width as i32, height as i32);
+        }
+    }
+
+    pub fn handle_draw(
+        &mut self,
+        renderer: &mut VelloRenderer,
+        elapsed: f64,
+    ) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        renderer.set_placement(self.transform.get().surface_from_local());
+        renderer.set_owner(self.owner());
+
+        let elapsed = self.step(elapsed);
+        let advanced = self.leave_history_frame(elapsed);
+
+        if self.advance_callbacks.is_empty() {
+            unsafe {
+                viewer_content_handle_draw(
+                    self.raw_viewer_content,
+                    NonNull::new(renderer as *mut VelloRenderer),
+                    elapsed,
+                )
+            }
+        } else {
+            self.advance_in_phases(elapsed);
+            let (width, height) = self.transform.get().view_size();
+            unsafe {
+                viewer_content_draw(
+                    self.raw_viewer_content,
+                    NonNull::new(renderer as *mut VelloRenderer),
+                    width as i32,
+                    height as i32,
+                )
+            }
+        }
+        if advanced {
+            self.end_pulses();
+            self.dispatch_events();
+            self.record_frame(elapsed);
+        }
+
+        Ok(())
+    }
+
+    /// Advances the content by `elapsed` seconds without drawing it. For a linear animation,
+    /// a second is `fps` frames of `AnimationInfo` at a speed of 1, and playback stays within
+    /// its work area. Together with `draw`, this lets one pose be drawn to several targets per
+    /// frame.
+    pub fn advance(&mut self, elapsed: f64) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let elapsed = self.step(elapsed);
+        if !self.leave_history_frame(elapsed) {
+            return Ok(());
+        }
+
+        if self.advance_callbacks.is_empty() {
+            unsafe {
+                viewer_content_handle_advance(self.raw_viewer_content, elapsed);
+            }
+        } else {
+            self.advance_in_phases(elapsed);
+        }
+        self.end_pulses();
+        self.dispatch_events();
+        self.record_frame(elapsed);
+
+        Ok(())
+    }
+
+    /// Draws the pose of the last `advance` fitted to a `width` by `height` target, ignoring the
+    /// size given to `handle_resize`. Drawing doesn't change the content, so it can be called
+    /// any number of times per advance, e.g. once per mirrored surface.
+    pub fn draw(
+        &self,
+        renderer: &mut VelloRenderer,
+        width: u32,
+        height: u32,
+    ) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let stack = TransformStack {
+            surface_size: (width, height),
+            ..self.transform.get()
+        };
+        renderer.set_placement(stack.surface_from_local());
+        renderer.set_owner(self.owner());
+
+        let (width, height) = stack.view_size();
+        unsafe {
+            viewer_content_draw(
+                self.raw_viewer_content,
+                NonNull::new(renderer as *mut VelloRenderer),
+                width as i32,
+                height as i32,
+            )
+        }
+
+        Ok(())
+    }
+
+    /// Draws the node or shape called `name` and what it contains alone, fitted into a `width`
+    /// by `height` target less `padding` on every side. Its own transform applies but its
+    /// ancestors' don't; their opacity and clips only apply with `ancestor_effects`. Returns
+    /// false, drawing nothing, if the artboard has no such component.
+    pub fn draw_component(
+        &mut self,
+        renderer: &mut VelloRenderer,
+        name: &str,
+        (width, height): (u32, u32),
+        padding: f32,
+        ancestor_effects: bool,
+    ) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let Ok(name) = CString::new(name) else {
+            return Ok(false);
+        };
+        renderer.set_owner(self.owner());
+
+        Ok(unsafe {
+            viewer_content_draw_component(
+                self.raw_viewer_content,
+                NonNull::new(renderer as *mut VelloRenderer),
+                name.as_ptr(),
+                width as i32,
+                height as i32,
+                padding,
+                ancestor_effects,
+            )
+        })
+    }
+
+    /// The bounds `draw_component` fits for the component called `name`: in its parent's space
+    /// with the ancestors' transforms undone, rounded out to whole units. For components at the
+    /// root of the artboard, that's where they are drawn at a scale of 1. `None` while the
+    /// scene is unavailable.
+    pub fn component_bounds(&self, name: &str) -> Option<Rect> {
+        let _guard = self.status.enter().ok()?;
+        self.raw_viewer_content?;
+        let name = CString::new(name).ok()?;
+        let mut bounds = [0.0f32; 4];
+        let found = unsafe {
+            viewer_content_component_bounds(
+                self.raw_viewer_content,
+                name.as_ptr(),
+                bounds.as_mut_ptr(),
+            )
+        };
+        let [x0, y0, x1, y1] = bounds.map(f64::from);
+        found.then(|| Rect::new(x0, y0, x1, y1))
+    }
+
+    /// Renders the component called `name` alone as `draw_component` draws it, at `size` pixels
+    /// on a transparent background, e.g. to export a part of a character.
+    pub fn rasterize_component(
+        &mut self,
+        rasterizer: &mut Rasterizer,
+        name: &str,
+        size: (u32, u32),
+        padding: f32,
+        ancestor_effects: bool,
+    ) -> Result<image::RgbaImage, ExtractError> {
+        let mut renderer = VelloRenderer::default();
+        let drawn = self
+            .draw_component(&mut renderer, name, size, padding, ancestor_effects)
+            .map_err(|unavailable| ExtractError::Unavailable(unavailable.0))?;
+        if !drawn {
+            return Err(ExtractError::UnknownComponent(name.to_string()));
+        }
+        renderer.end_frame();
+
+        let capture = rasterizer
+            .render(
+                &renderer.scene,
+                Color::TRANSPARENT,
+                size.0,
+                size.1,
+                1,
+                ScaledOutput::Downsampled,
+            )
+            .map_err(ExtractError::Capture)?;
+        Ok(capture.into_image())
+    }
+
+    /// `rasterize_component` for each of `names`, in order. Fails on the first one which
+    /// can't be rendered.
+    pub fn rasterize_components<S: AsRef<str>>(
+        &mut self,
+        rasterizer: &mut Rasterizer,
+        names: &[S],
+        size: (u32, u32),
+        padding: f32,
+        ancestor_effects: bool,
+    ) -> Result<Vec<image::RgbaImage>, ExtractError> {
+        names
+            .iter()
+            .map(|name| {
+                self.rasterize_component(rasterizer, name.as_ref(), size, padding, ancestor_effects)
+            })
+            .collect()
+    }
+
+    /// Calls `callback` during each advance once it reaches `phase`, with a view of the
+    /// artboard to read it, set inputs and defer other mutations. Callbacks only run for
+    /// contents with a scene, in the order they were added.
+    pub fn on_advance(
+        &mut self,
+        phase: AdvancePhase,
+        callback: impl FnMut(&mut ArtboardView<'_>) + 'static,
+    ) {
+        self.advance_callbacks.push(AdvanceCallback {
+            phase,
+            callback: Box::new(callback),
+        });
+    }
+
+    /// The node or bone called `name`, as the last advance left it.
+    pub fn node(&self, name: &str) -> Option<NodeTransform> {
+        node_transform(self.raw_viewer_content, name)
+    }
+
+    /// Drops the callbacks added with `on_advance`.
+    pub fn clear_advance_callbacks(&mut self) {
+        self.advance_callbacks.clear();
+    }
+
+    /// Advances the content the way `handleAdvance` does, stopping after each phase to run its
+    /// callbacks and the mutations they deferred.
+    fn advance_in_phases(&mut self, elapsed: f64) {
+        if !unsafe { viewer_content_handle_animate(self.raw_viewer_content, elapsed) } {
+            return;
+        }
+        self.run_phase(AdvancePhase::Animated);
+
+        unsafe {
+            viewer_content_handle_update(self.raw_viewer_content, elapsed);
+        }
+        if self.run_phase(AdvancePhase::Updated) {
+            // Updates by no time only apply what changed.
+            unsafe {
+                viewer_content_handle_update(self.raw_viewer_content, 0.0);
+            }
+        }
+    }
+
+    /// Runs the callbacks for `phase`, then the mutations they deferred. Returns whether there
+    /// were any mutations.
+    fn run_phase(&mut self, phase: AdvancePhase) -> bool {
+        let raw_viewer_content = self.raw_viewer_content;
+        let mut deferred = DeferredMutations::default();
+        for advance_callback in &mut self.advance_callbacks {
+            if advance_callback.phase != phase {
+                continue;
+            }
+
+            let mut view = ArtboardView {
+                raw_viewer_content,
+                phase,
+                deferred: &mut deferred,
+            };
+            (advance_callback.callback)(&mut view);
+        }
+
+        let mut artboard = ArtboardMut {
+            raw_viewer_content,
+            _content: PhantomData,
+        };
+        let mutated = !deferred.is_empty();
+        for mutation in deferred.mutations {
+            mutation(&mut artboard);
+        }
+
+        mutated
+    }
+
+    /// Applies the playback state to `elapsed` and returns what's left to advance the content
+    /// by.
+    fn step(&mut self, elapsed: f64) -> f64 {
+        self.played += elapsed;
+        match self.playback.step(elapsed, self.playback_state()) {
+            Step::Advance(elapsed) => elapsed,
+            Step::Boundary { time, iteration } => {
+                unsafe {
+                    viewer_content_seek(self.raw_viewer_content, time);
+                }
+                self.events.push(ViewerEvent::LoopCompleted { iteration });
+
+                0.0
+            }
+        }
+    }
+
+    pub fn handle_pointer_move(&mut self, pos: Vec2) -> Result<(), SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        self.last_pointer = pos;
+        unsafe {
+            viewer_content_handle_pointer_move(self.raw_viewer_content, pos.x as f32, pos.y as f32);
+        }
+
+        self.move_scrub(pos);
+
+        Ok(())
+    }
+
+    /// Whether `pos` lands on a listener of `listener_type` whose conditions are met, hit
+    /// tested the way the state machine does.
+    fn hits_listener(&self, pos: Vec2, listener_type: ListenerType) -> bool {
+        unsafe {
+            viewer_content_hits_listener(
+                self.raw_viewer_content,
+                pos.x as f32,
+                pos.y as f32,
+                listener_type as i32,
+            )
+        }
+    }
+
+    /// Returns whether the press hit a press or move listener, or started a scrub. Every button
+    /// is hit tested, but only the primary one reaches the state machine, so secondary buttons
+    /// can't disturb a click of the primary button in progress.
+    pub fn handle_pointer_down(
+        &mut self,
+        pos: Vec2,
+        button: PointerButton,
+    ) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        // Hit tested before the press, whose listeners may change the conditions.
+        let drags = self.hits_listener(pos, ListenerType::Move);
+        let hit = drags || self.hits_listener(pos, ListenerType::Down);
+        if !button.is_primary() {
+            return Ok(hit);
+        }
+
+        self.last_pointer = pos;
+        unsafe {
+            viewer_content_handle_pointer_down(self.raw_viewer_content, pos.x as f32, pos.y as f32);
+        }
+
+        self.start_scrub(pos);
+        self.captured = drags || self.drag.is_some();
+
+        Ok(hit || self.drag.is_some())
+    }
+
+    /// Whether the primary button is held after pressing a move listener or a scrub. The host
+    /// should then keep forwarding moves, even outside its window, until the button is released,
+    /// e.g. by grabbing the cursor or with a `PointerCapture`.
+    pub fn wants_pointer_capture(&self) -> bool {
+        self.captured
+    }
+
+    /// Ends a press which won't get its release, e.g. when the window loses focus in the middle
+    /// of a drag, with a release at the last position forwarded. Returns whether there was a
+    /// press to end.
+    pub fn cancel_pointer(&mut self) -> Result<bool, SceneUnavailable> {
+        if !self.captured {
+            return Ok(false);
+        }
+
+        self.handle_pointer_up(self.last_pointer, PointerButton::Left)?;
+        Ok(true)
+    }
+
+    /// Returns whether the release hit a release listener or ended a drag. Like presses, every
+    /// button is hit tested but only the primary one reaches the state machine. The release of
+    /// a scrub sets the input from its position one last time and isn't forwarded; nothing
+    /// keeps moving after it.
+    pub fn handle_pointer_up(
+        &mut self,
+        pos: Vec2,
+        button: PointerButton,
+    ) -> Result<bool, SceneUnavailable> {
+        let _guard = self.status.enter()?;
+        let hit = self.hits_listener(pos, ListenerType::Up);
+        if !button.is_primary() {
+            return Ok(hit);
+        }
+
+        self.last_pointer = pos;
+        let captured = std::mem::take(&mut self.captured);
+        self.move_scrub(pos);
+        if self.drag.take().is_some_and(|drag| drag.is_scrubbing()) {
+            return Ok(true);
+        }
+
+        unsafe {
+            viewer_content_handle_pointer_up(self.raw_viewer_content, pos.x as f32, pos.y as f32);
+        }
+
+        Ok(hit || captured)
+    }
+
+    /// Forwards a pointer given in the surface's physical pixels, e.g. as a window reports it.
+    /// Returns what `handle_pointer_world` does, or `false` outside the view.
+    pub fn handle_pointer_surface(
+        &mut self,
+        action: PointerAction,
+        pos: Vec2,
+    ) -> Result<bool, SceneUnavailable> {
+        match self.transform.get().surface_to_world(pos) {
+            Some(pos) => self.handle_pointer_world(action, pos),
+            None => Ok(false),
+        }
+    }
+
+    /// Forwards a pointer given in world space, i.e. in the same space as the placement.
+    /// Returns whether a press or release hit a listener, see `handle_pointer_down` and
+    /// `handle_pointer_up`. Moves always reach the listeners and return `true`.
+    pub fn handle_pointer_world(
+        &mut self,
+        action: PointerAction,
+        pos: Vec2,
+    ) -> Result<bool, SceneUnavailable> {
+        let pos = self.world_to_local(pos);
+
+        match action {
+            PointerAction::Move => {
+                self.handle_pointer_move(pos)?;
+                Ok(true)
+            }
+            PointerAction::Down(button) => self.handle_pointer_down(pos, button),
+            PointerAction::Up(button) => self.handle_pointer_up(pos, button),
+        }
+    }
+}
+
+impl Drop for ViewerContent {
+    fn drop(&mut self) {
+        unsafe {
+            viewer_content_release(self.raw_viewer_content);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::rc::Rc;
+
+    use rand::SeedableRng;
+
+    use crate::{
+        assets::{AssetFallback, AssetKind},
+        background::Substeps,
+        gesture::PointerCapture,
+        MissingAssetStyle, TextureTarget, Viewer,
+    };
+
+    fn placed(placement: Affine) -> ViewerContent {
+        ViewerContent {
+            raw_viewer_content: None,
+            transform: Cell::new(TransformStack {
+                placement,
+                ..Default::default()
+            }),
+            playback: Playback::default(),
+            events: Vec::new(),
+            match_bare_run_names: false,
+            scrub: None,
+            drag: None,
+            captured: false,
+            last_pointer: Vec2::ZERO,
+            file_hash: String::new(),
+            status: Status::new(SceneStatus::Detached),
+            reported_status: SceneStatus::Detached,
+            reported_policy: RedrawPolicy::Static,
+            pulses: Vec::new(),
+            fired_events: Vec::new(),
+            event_filter: None,
+            skipped_events: 0,
+            subscriptions: Vec::new(),
+            advance_callbacks: Vec::new(),
+            asset_errors: HashMap::new(),
+            history: History::default(),
+            played: 0.0,
+            input_indices: RefCell::default(),
+            _live: Live::new(Kind::Instance),
+        }
+    }
+
+    #[test]
+    fn world_pointer_only_reaches_placed_instance() {
+        // listener_conditions.riv is 500x500 with a press listener on its "back" square, which
+        // covers 0..300 and sets BackPressed.
+        let instance = |placement| {
+            let mut content = ViewerContent::new("../test/assets/listener_conditions.riv").unwrap();
+            content.handle_resize(500, 500);
+            content.advance(0.0).unwrap();
+            content.set_placement(placement);
+            content
+        };
+        let mut left = instance(Affine::scale(0.5));
+        let mut right = instance(Affine::scale(0.5).then_translate(Vec2::new(400.0, 100.0)));
+
+        // Center of the right instance's back square in world space, past the left one's edge.
+        let button = Vec2::new(400.0 + 75.0, 100.0 + 75.0);
+        let local = right.world_to_local(button);
+        assert!((local - Vec2::new(150.0, 150.0)).hypot() < 1e-9);
+
+        let press = PointerAction::Down(PointerButton::Left);
+        assert_eq!(left.handle_pointer_world(press, button), Ok(false));
+        assert_eq!(right.handle_pointer_world(press, button), Ok(true));
+        // Neither square listens to releases.
+        let release = PointerAction::Up(PointerButton::Left);
+        for content in [&mut left, &mut right] {
+            assert_eq!(content.handle_pointer_world(release, button), Ok(false));
+        }
+        assert_eq!(right.boolean("BackPressed"), Some(true));
+        assert_eq!(left.boolean("BackPressed"), Some(false));
+    }
+
+    #[test]
+    fn secondary_buttons_are_hit_tested_without_reaching_listeners() {
+        // See `world_pointer_only_reaches_placed_instance`. Nothing listens in the top right
+        // corner.
+        let mut content = ViewerContent::new("../test/assets/listener_conditions.riv").unwrap();
+        content.handle_resize(500, 500);
+        content.advance(0.0).unwrap();
+        let (back, empty) = (Vec2::new(150.0, 150.0), Vec2::new(450.0, 50.0));
+
+        for button in [
+            PointerButton::Right,
+            PointerButton::Middle,
+            PointerButton::Other(4),
+        ] {
+            assert_eq!(content.handle_pointer_down(back, button), Ok(true));
+            assert_eq!(content.handle_pointer_down(empty, button), Ok(false));
+            assert_eq!(content.handle_pointer_up(back, button), Ok(false));
+        }
+        assert_eq!(content.boolean("BackPressed"), Some(false));
+        assert!(!content.wants_pointer_capture());
+    }
+
+    #[test]
+    fn captured_positions_map_past_the_artboard() {
+        let content = placed(Affine::scale(2.0).then_translate(Vec2::new(100.0, 0.0)));
+        let mut capture = PointerCapture::new(Vec2::new(110.0, 10.0));
+
+        capture.cursor_left();
+        capture.device_moved(Vec2::new(-30.0, 0.0));
+        let world = capture.device_moved(Vec2::new(-30.0, 4.0)).unwrap();
+
+        // 60 view pixels left of the press is 30 units left of it in the content, i.e. past
+        // its left edge.
+        let local = content.world_to_local(world);
+        assert!((local - Vec2::new(-25.0, 7.0)).hypot() < 1e-9);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn releases_everything_on_drop() {
+        let mut content = ViewerContent::new("../test/assets/walle.riv").unwrap();
+        let mut renderer = VelloRenderer::default();
+        content.handle_resize(100, 100);
+        content.handle_draw(&mut renderer, 0.0).unwrap();
+
+        let live = ViewerContent::debug_live_counts();
+        assert_eq!(live.instances, 1);
+        assert_eq!(live.scenes, 1);
+        assert!(live.images > 0);
+
+        // Contents don't depend on the renderer they were drawn with.
+        drop(content);
+        assert_eq!(ViewerContent::debug_live_counts().images, 0);
+        drop(renderer);
+        assert!(ViewerContent::debug_live_counts().is_empty());
+    }
+
+    #[test]
+    fn hibernating_releases_image_textures() {
+        let gpu = crate::device::test_device();
+        let mut target =
+            TextureTarget::new(gpu.device.clone(), gpu.queue.clone(), 100, 100).unwrap();
+        let mut viewer = Viewer::new(gpu.device, gpu.queue, None).unwrap();
+        let baseline = gpu_memory::gpu_memory_report();
+        assert_eq!(baseline.targets, 100 * 100 * 4);
+
+        viewer.set_content(ViewerContent::new("../test/assets/walle.riv"));
+        viewer.resize(100, 100);
+        viewer.render_to_texture(&mut target, 0.0).unwrap();
+        let rendered = viewer.stats().gpu_memory;
+        assert!(rendered.images > 0);
+        assert!(rendered.scene > 0);
+        assert_eq!(gpu_memory::gpu_memory_report().images, rendered.images);
+
+        viewer.content_mut().unwrap().hibernate();
+        viewer.render_to_texture(&mut target, 0.0).unwrap();
+        assert_eq!(gpu_memory::gpu_memory_report(), baseline);
+    }
+
+    #[test]
+    fn time_offset_matches_advancing() {
+        // A 20 frame ping-pong, i.e. a third of a second each way.
+        let path = "../test/assets/fix_rectangle.riv";
+        let mut offset = ViewerContent::new(path).unwrap();
+        let mut advanced = ViewerContent::new(path).unwrap();
+
+        offset.set_time_offset(0.5).unwrap();
+        advanced.advance(0.5).unwrap();
+
+        let state = offset.playback_state().unwrap();
+        let expected = advanced.playback_state().unwrap();
+        assert!((state.time - expected.time).abs() < 1e-5);
+        assert!((state.time - 1.0 / 6.0).abs() < 1e-5);
+        assert_eq!(state.speed, expected.speed);
+        assert!(state.speed < 0.0);
+        assert_renders_alike(&mut offset, &mut advanced);
+    }
+
+    #[test]
+    fn time_offsets_drop_the_events_they_skip() {
+        let mut offset = ViewerContent::new(EVENTS_ON_STATES).unwrap();
+        let mut advanced = ViewerContent::new(EVENTS_ON_STATES).unwrap();
+
+        // Entering the first state fires "First" within the skipped span.
+        offset.set_time_offset(2.5).unwrap();
+        advanced.advance(2.5).unwrap();
+        assert_eq!(fired_names(&advanced), ["First"]);
+        assert_eq!(offset.snapshot(), advanced.snapshot());
+
+        offset.advance(0.0).unwrap();
+        assert!(offset.events_this_frame().is_empty());
+        advanced.advance(0.0).unwrap();
+        assert_eq!(offset.snapshot(), advanced.snapshot());
+        assert_renders_alike(&mut offset, &mut advanced);
+    }
+
+    /// Renders both contents as they are, without advancing them.
+    fn assert_renders_alike(content: &mut ViewerContent, other: &mut ViewerContent) {
+        let gpu = crate::device::test_device();
+        let mut renderer = crate::HeadlessRenderer::with_device(gpu.device, gpu.queue, gpu.tier);
+        let mut render = |content: &mut ViewerContent| {
+            let played = content.played();
+            renderer.render(content, 100, 100, played).unwrap()
+        };
+
+        assert_eq!(render(content), render(other));
+    }
+
+    #[test]
+    fn play_ranges_keep_time_away_from_the_loop_points() {
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+        assert_eq!(
+            content.set_play_range(Some((0.1, 0.2))),
+            Ok(Some((0.1, 0.2)))
+        );
+
+        for _ in 0..60 {
+            content.advance(1.0 / 60.0).unwrap();
+            let time = content.time().unwrap();
+            assert!((0.1 - 1e-5..=0.2 + 1e-5).contains(&time), "{time}");
+        }
+
+        assert_eq!(content.set_play_range(None), Ok(None));
+        let left_the_range = (0..60).any(|_| {
+            content.advance(1.0 / 60.0).unwrap();
+            !(0.1..=0.2).contains(&content.time().unwrap())
+        });
+        assert!(left_the_range);
+    }
+
+    #[test]
+    fn play_ranges_narrower_than_a_frame_are_widened() {
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+
+        let (start, end) = content.set_play_range(Some((0.105, 0.1))).unwrap().unwrap();
+        assert_eq!(start, 0.1);
+        assert!((end - start - 1.0 / 60.0).abs() < 1e-5);
+    }
+
+    /// An odd frame time, so that loop boundaries fall between frames.
+    const ODD_FRAME: f64 = 0.013;
+
+    /// Advances by `ODD_FRAME` until a pending `pause_at_loop_end` pauses the content, and
+    /// returns the iteration it reported.
+    fn play_to_loop_end(content: &mut ViewerContent) -> u32 {
+        for _ in 0..10_000 {
+            content.advance(ODD_FRAME).unwrap();
+            let iteration = content.take_events().find_map(|event| match event {
+                ViewerEvent::LoopCompleted { iteration } => Some(iteration),
+                _ => None,
+            });
+            if let Some(iteration) = iteration {
+                assert!(content.is_paused());
+                return iteration;
+            }
+        }
+
+        panic!("never reached a loop boundary");
+    }
+
+    #[test]
+    fn loops_count_from_a_seek() {
+        let mut content = ViewerContent::new("../test/assets/death_knight.riv").unwrap();
+        content
+            .select(Some("MC Main Artboard"), Some("Walk"))
+            .unwrap();
+        // Walk loops over its work area, frames 30 to 90.
+        let end = content.playback_state().unwrap().end;
+
+        for _ in 0..167 {
+            content.advance(ODD_FRAME).unwrap();
+        }
+        assert!(content.seek_frame(75).unwrap());
+        content.pause_at_loop_end();
+
+        assert_eq!(play_to_loop_end(&mut content), 1);
+        // Seeked onto the boundary, not somewhere past it.
+        assert_eq!(content.time(), Some(end));
+
+        content.resume();
+        content.pause_at_loop_end();
+        assert_eq!(play_to_loop_end(&mut content), 2);
+    }
+
+    #[test]
+    fn ping_pongs_count_from_a_new_selection() {
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+        let (start, end) = {
+            let state = content.playback_state().unwrap();
+            (state.start, state.end)
+        };
+
+        // Partway back from the end.
+        for _ in 0..38 {
+            content.advance(ODD_FRAME).unwrap();
+        }
+        assert!(content.playback_state().unwrap().speed < 0.0);
+
+        let scene = content.scenes()[0].clone();
+        content.select(None, Some(&scene)).unwrap();
+        content.pause_at_loop_end();
+        assert_eq!(play_to_loop_end(&mut content), 1);
+        assert_eq!(content.time(), Some(end));
+
+        // Resting on the end, the next boundary is the start.
+        content.resume();
+        content.pause_at_loop_end();
+        assert_eq!(play_to_loop_end(&mut content), 2);
+        assert_eq!(content.time(), Some(start));
+    }
+
+    #[test]
+    fn one_shots_stop_on_completion() {
+        let mut content = ViewerContent::new("../test/assets/death_knight.riv").unwrap();
+        content
+            .select(Some("MC Main Artboard"), Some("Attack"))
+            .unwrap();
+        let end = content.playback_state().unwrap().end;
+
+        assert!(content.seek_frame(20).unwrap());
+        content.pause_at_loop_end();
+        content.set_speed(3.7);
+
+        assert_eq!(play_to_loop_end(&mut content), 1);
+        assert_eq!(content.time(), Some(end));
+        content.advance(ODD_FRAME).unwrap();
+        assert_eq!(content.time(), Some(end));
+    }
+
+    #[test]
+    fn scenes_are_selected_by_name() {
+        let mut content = ViewerContent::new("../test/assets/rocket.riv").unwrap();
+        assert_eq!(
+            content.scenes(),
+            ["Button", "idle", "Roll_over", "Press"].map(String::from)
+        );
+
+        let artboard = content.artboard().unwrap();
+        assert_eq!(content.scene_names(&artboard), content.scenes());
+
+        assert_eq!(content.select(None, Some("Roll_over")), Ok(()));
+        assert!(content.animation_info().is_some());
+        assert_eq!(content.select(None, Some("Button")), Ok(()));
+        assert!(content.animation_info().is_none());
+        assert_eq!(
+            content.select(None, Some("missing")),
+            Err(SelectError::UnknownScene {
+                artboard,
+                name: "missing".to_string(),
+            })
+        );
+        assert_eq!(content.scene().as_deref(), Some("Button"));
+    }
+
+    #[test]
+    fn fits_parse_from_kebab_case() {
+        assert_eq!("contain".parse(), Ok(Fit::Contain));
+        assert_eq!("Fit-Width".parse(), Ok(Fit::FitWidth));
+        assert_eq!("scale-down".parse(), Ok(Fit::ScaleDown));
+        assert!("stretch".parse::<Fit>().is_err());
+    }
+
+    #[test]
+    fn files_without_scenes_are_static() {
+        let content = ViewerContent::new("../test/assets/shapetest.riv").unwrap();
+        assert_eq!(content.redraw_policy(), RedrawPolicy::Static);
+    }
+
+    #[test]
+    fn looping_animations_are_continuous() {
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+        for _ in 0..120 {
+            content.advance(1.0 / 60.0).unwrap();
+            assert_eq!(content.redraw_policy(), RedrawPolicy::Continuous);
+        }
+
+        content.pause();
+        assert_eq!(content.redraw_policy(), RedrawPolicy::OnInteraction);
+    }
+
+    #[test]
+    fn state_machines_come_to_rest_until_an_input_changes() {
+        // Enters a one second one-shot, then waits for its "On" input to change.
+        let mut content = ViewerContent::new("../test/assets/light_switch.riv").unwrap();
+        assert_eq!(content.redraw_policy(), RedrawPolicy::Continuous);
+
+        content.advance(0.5).unwrap();
+        assert_eq!(content.redraw_policy(), RedrawPolicy::Continuous);
+        content.advance(1.0).unwrap();
+        content.advance(1.0).unwrap();
+        assert_eq!(content.redraw_policy(), RedrawPolicy::OnInteraction);
+        let events = content.take_events().collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [ViewerEvent::RedrawPolicyChanged {
+                policy: RedrawPolicy::OnInteraction
+            }]
+        );
+
+        assert_eq!(content.set_bool("On", false), Ok(true));
+        assert_eq!(content.redraw_policy(), RedrawPolicy::Continuous);
+        // Mixes into the "Off" one-shot, then rests again.
+        for _ in 0..3 {
+            content.advance(1.0).unwrap();
+        }
+        assert_eq!(content.redraw_policy(), RedrawPolicy::OnInteraction);
+    }
+
+    #[test]
+    fn random_phases_stay_within_the_cycle() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+
+        for _ in 0..16 {
+            let offset = content.randomize_phase(&mut rng).unwrap().unwrap();
+            assert!((0.0..2.0 / 3.0).contains(&offset));
+        }
+    }
+
+    #[test]
+    fn snapshots_restore_into_fresh_content() {
+        let path = "../test/assets/rocket.riv";
+        let mut played = ViewerContent::new(path).unwrap();
+        played.advance(0.3).unwrap();
+
+        let snapshot = played.snapshot().unwrap();
+        let text = toml::to_string(&snapshot).unwrap();
+
+        let mut fresh = ViewerContent::new(path).unwrap();
+        let report = fresh
+            .restore(&toml::from_str(&text).unwrap(), RestoreMode::Strict)
+            .unwrap();
+        assert_eq!(report.restored_layers.len(), snapshot.layers.len());
+        assert_eq!(fresh.snapshot(), Some(snapshot.clone()));
+
+        let mut linear = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+        assert_eq!(linear.snapshot(), None);
+        assert_eq!(
+            linear.restore(&snapshot, RestoreMode::Partial),
+            Err(RestoreError::NoStateMachine)
+        );
+    }
+
+    #[test]
+    fn pulses_last_for_the_first_sub_step() {
+        let mut content = ViewerContent::new("../test/assets/rocket.riv").unwrap();
+        assert_eq!(content.boolean("Press"), Some(false));
+
+        assert_eq!(content.set_bool_pulse("Press"), Ok(true));
+        assert_eq!(content.set_bool_pulse("Missing"), Ok(false));
+        assert_eq!(content.boolean("Press"), Some(true));
+
+        let mut steps = Substeps::new(0.25);
+        content.advance(steps.next().unwrap()).unwrap();
+        assert_eq!(content.boolean("Press"), Some(false));
+
+        for step in steps {
+            content.advance(step).unwrap();
+            assert_eq!(content.boolean("Press"), Some(false));
+        }
+    }
+
+    #[test]
+    fn pulses_go_back_to_the_value_before_the_first_one() {
+        let mut content = ViewerContent::new("../test/assets/rocket.riv").unwrap();
+        content.set_bool("Hover", true).unwrap();
+        content.set_bool_pulse("Hover").unwrap();
+        content.advance(0.1).unwrap();
+        assert_eq!(content.boolean("Hover"), Some(true));
+
+        let mut content = ViewerContent::new("../test/assets/blend_test.riv").unwrap();
+        let before = content.number("verticality").unwrap();
+        assert_eq!(
+            content.set_number_for_one_frame("verticality", 100.0),
+            Ok(true)
+        );
+        assert_eq!(
+            content.set_number_for_one_frame("verticality", 50.0),
+            Ok(true)
+        );
+        assert_eq!(content.set_bool_pulse("verticality"), Ok(false));
+        assert_eq!(content.number("verticality"), Some(50.0));
+
+        content
+            .handle_draw(&mut VelloRenderer::default(), 1.0 / 60.0)
+            .unwrap();
+        assert_eq!(content.number("verticality"), Some(before));
+    }
+
+    #[test]
+    fn pending_pulses_are_snapshotted() {
+        let path = "../test/assets/rocket.riv";
+        let mut pulsed = ViewerContent::new(path).unwrap();
+        pulsed.set_bool_pulse("Press").unwrap();
+
+        let snapshot = pulsed.snapshot().unwrap();
+        assert_eq!(snapshot.pulses.len(), 1);
+        let text = toml::to_string(&snapshot).unwrap();
+
+        let mut fresh = ViewerContent::new(path).unwrap();
+        fresh
+            .restore(&toml::from_str(&text).unwrap(), RestoreMode::Strict)
+            .unwrap();
+        assert_eq!(fresh.snapshot(), Some(snapshot));
+        assert_eq!(fresh.boolean("Press"), Some(true));
+
+        fresh.advance(0.0).unwrap();
+        assert_eq!(fresh.boolean("Press"), Some(false));
+        assert!(fresh.snapshot().unwrap().pulses.is_empty());
+    }
+
+    #[test]
+    fn lists_and_selects_artboards() {
+        let mut content = ViewerContent::new("../test/assets/two_artboards.riv").unwrap();
+        let artboards = content.artboard_names();
+        assert_eq!(artboards.len(), 2);
+
+        assert_eq!(content.select(Some(&artboards[1]), None), Ok(()));
+        assert_eq!(content.artboard().as_ref(), Some(&artboards[1]));
+        assert_eq!(content.artboard_names(), artboards);
+        assert!(placed(Affine::IDENTITY).artboard_names().is_empty());
+    }
+
+    #[test]
+    fn selects_state_machines_by_name() {
+        let mut content = ViewerContent::new("../test/assets/multiple_state_machines.riv").unwrap();
+        let names = content.state_machine_names("New Artboard");
+        assert_eq!(names, ["four", "three", "two", "one"]);
+        assert!(content.state_machine_names("missing").is_empty());
+
+        assert_eq!(content.select(None, Some("two")), Ok(()));
+        assert_eq!(content.snapshot().unwrap().state_machine, "two");
+        assert_eq!(
+            content.select(Some("missing"), None),
+            Err(SelectError::UnknownArtboard("missing".to_string()))
+        );
+        assert_eq!(
+            content.select(None, Some("missing")),
+            Err(SelectError::UnknownScene {
+                artboard: "New Artboard".to_string(),
+                name: "missing".to_string(),
+            })
+        );
+        assert_eq!(content.snapshot().unwrap().state_machine, "two");
+
+        content.hibernate();
+        assert_eq!(
+            content.select(None, None),
+            Err(SelectError::Unavailable(SceneStatus::Hibernated))
+        );
+    }
+
+    #[test]
+    fn selected_artboards_are_fitted_to_the_last_resize() {
+        // A 2080x2080 artboard, then a 1698x1800 one.
+        let mut content = ViewerContent::new("../test/assets/jellyfish_test.riv").unwrap();
+        content.handle_resize(400, 300);
+
+        assert_eq!(content.select(Some("New Artboard"), None), Ok(()));
+        assert_eq!(content.artboard().as_deref(), Some("New Artboard"));
+        let center = content.view_to_artboard(Vec2::new(200.0, 150.0)).unwrap();
+        let off = center - Vec2::new(849.0, 900.0);
+        assert!(off.hypot() < 1e-2, "{center:?}");
+    }
+
+    #[test]
+    fn files_without_artboards_fail_to_open() {
+        // The header and the backboard alone.
+        let bytes = b"RIVE\x07\x00\xd0\x06\x00\x17\x00";
+        assert!(ViewerContent::from_bytes(bytes).is_none());
+    }
+
+    const STATUSES: [SceneStatus; 4] = [
+        SceneStatus::Ready,
+        SceneStatus::Hibernated,
+        SceneStatus::Poisoned,
+        SceneStatus::Detached,
+    ];
+
+    /// Panics through a call holding the scene, as a panicking FFI callback would.
+    fn poison(content: &ViewerContent) {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = content.status.enter().unwrap();
+            panic!("interrupted on purpose");
+        }));
+        assert!(result.is_err());
+    }
+
+    fn rocket_in(status: SceneStatus) -> ViewerContent {
+        let mut content = ViewerContent::new("../test/assets/rocket.riv").unwrap();
+        match status {
+            SceneStatus::Ready => {}
+            SceneStatus::Hibernated => content.hibernate(),
+            SceneStatus::Poisoned => poison(&content),
+            SceneStatus::Detached => content.detach(),
+        }
+        assert_eq!(content.status(), status);
+
+        content
+    }
+
+    fn expected<T>(status: SceneStatus, value: T) -> Result<T, SceneUnavailable> {
+        match status {
+            SceneStatus::Ready => Ok(value),
+            status => Err(SceneUnavailable(status)),
+        }
+    }
+
+    #[test]
+    fn every_call_follows_the_status() {
+        for status in STATUSES {
+            let mut content = rocket_in(status);
+            let snapshot = content.snapshot().unwrap();
+            let InputValue::Bool(value) = snapshot.inputs[0].value else {
+                panic!("expected a boolean input")
+            };
+            let input = &snapshot.inputs[0].name;
+            let mut renderer = VelloRenderer::default();
+
+            assert_eq!(content.advance(0.1), expected(status, ()), "{status}");
+            assert_eq!(
+                content.handle_draw(&mut renderer, 0.1),
+                expected(status, ()),
+                "{status}"
+            );
+            assert_eq!(
+                content.draw(&mut renderer, 100, 100),
+                expected(status, ()),
+                "{status}"
+            );
+            assert_eq!(content.set_time_offset(0.1), expected(status, ()));
+            assert_eq!(content.set_bool(input, !value), expected(status, true));
+            assert_eq!(content.set_bool("missing", true), expected(status, false));
+            assert_eq!(content.set_number("missing", 1.0), expected(status, false));
+            assert_eq!(
+                content.set_number_quantized("missing", 1.0),
+                expected(status, false)
+            );
+            assert_eq!(content.seek_frame(0), expected(status, false));
+            assert_eq!(
+                content.set_play_range(Some((0.0, 0.1))),
+                expected(status, None)
+            );
+            assert_eq!(content.set_fit(Fit::Cover), expected(status, ()));
+            assert_eq!(
+                content.apply_text_table(&HashMap::new()),
+                expected(status, TextApplyReport::default())
+            );
+            assert_eq!(
+                content.set_text_direction(TextDirection::Rtl),
+                expected(status, 0)
+            );
+            assert_eq!(
+                content.handle_pointer_move(Vec2::new(10.0, 10.0)),
+                expected(status, ())
+            );
+            for action in [
+                PointerAction::Down(PointerButton::Left),
+                PointerAction::Up(PointerButton::Left),
+            ] {
+                assert_eq!(
+                    content
+                        .handle_pointer_world(action, Vec2::new(10.0, 10.0))
+                        .map(|_| ()),
+                    expected(status, ())
+                );
+            }
+
+            let restored = content.restore(&snapshot, RestoreMode::Strict);
+            match status {
+                SceneStatus::Ready => assert!(restored.is_ok()),
+                status => assert_eq!(restored, Err(RestoreError::Unavailable(status))),
+            }
+
+            // Failed calls leave the scene as it was.
+            if status != SceneStatus::Ready {
+                assert_eq!(content.snapshot(), Some(snapshot));
+            }
+            assert_eq!(content.status(), status);
+        }
+    }
+
+    #[test]
+    fn only_hibernation_is_reversible() {
+        for status in STATUSES {
+            let mut content = rocket_in(status);
+            content.wake();
+            let woken = match status {
+                SceneStatus::Hibernated => SceneStatus::Ready,
+                status => status,
+            };
+            assert_eq!(content.status(), woken, "waking {status}");
+
+            let mut content = rocket_in(status);
+            content.hibernate();
+            let hibernated = match status {
+                SceneStatus::Ready => SceneStatus::Hibernated,
+                status => status,
+            };
+            assert_eq!(content.status(), hibernated, "hibernating {status}");
+
+            let mut content = rocket_in(status);
+            content.detach();
+            assert_eq!(
+                content.status(),
+                SceneStatus::Detached,
+                "detaching {status}"
+            );
+            content.wake();
+            assert_eq!(content.status(), SceneStatus::Detached);
+        }
+
+        let content = rocket_in(SceneStatus::Hibernated);
+        poison(&content);
+        assert_eq!(content.status(), SceneStatus::Hibernated);
+    }
+
+    #[test]
+    fn status_changes_are_reported_once() {
+        let mut content = rocket_in(SceneStatus::Ready);
+        assert_eq!(content.take_events().count(), 0);
+
+        content.hibernate();
+        content.wake();
+        assert_eq!(content.take_events().count(), 0);
+
+        content.hibernate();
+        assert_eq!(
+            content.take_events().collect::<Vec<_>>(),
+            [ViewerEvent::StatusChanged {
+                status: SceneStatus::Hibernated
+            }]
+        );
+        assert_eq!(content.take_events().count(), 0);
+
+        content.wake();
+        poison(&content);
+        assert_eq!(
+            content.take_events().collect::<Vec<_>>(),
+            [ViewerEvent::StatusChanged {
+                status: SceneStatus::Poisoned
+            }]
+        );
+    }
+
+    #[test]
+    fn text_table_without_runs_leaves_every_key_unused() {
+        let mut content = ViewerContent::new("../test/assets/rocket.riv").unwrap();
+        let table = HashMap::from([("title".to_string(), "عنوان".to_string())]);
+
+        let report = content.apply_text_table(&table).unwrap();
+
+        assert!(report.applied.is_empty());
+        assert_eq!(report.unused_keys, ["title"]);
+    }
+
+    #[test]
+    fn text_tables_reach_nested_and_right_to_left_runs() {
+        // Main has "title" and a right-to-left "price", and nests Card as "card", which has
+        // its own "title" and nests Badge, with a right-to-left "label", as "badge".
+        let mut content = ViewerContent::new("../test/assets/nested_text_runs.riv").unwrap();
+        assert_eq!(
+            content.text_runs(),
+            ["title", "price", "card/title", "card/badge/label"]
+        );
+        assert_eq!(content.text_run("price").unwrap(), "\u{200f}المجموع: ٤٢");
+
+        let price = "\u{200f}٤٢ ر.س";
+        let table = HashMap::from([
+            ("title".to_string(), "Bienvenue".to_string()),
+            ("price".to_string(), price.to_string()),
+            ("card/badge/label".to_string(), "جديد".to_string()),
+            ("footer".to_string(), "Pied".to_string()),
+        ]);
+        let report = content.apply_text_table(&table).unwrap();
+
+        assert_eq!(report.applied, ["title", "price", "card/badge/label"]);
+        assert_eq!(report.untouched_runs, ["card/title"]);
+        assert_eq!(report.unused_keys, ["footer"]);
+        assert_eq!(content.text_run("title").unwrap(), "Bienvenue");
+        assert_eq!(content.text_run("price").unwrap(), price);
+        assert_eq!(content.text_run("card/title").unwrap(), "Card");
+        assert_eq!(content.text_run("card/badge/label").unwrap(), "جديد");
+
+        // Bare names reach the nested title too.
+        content.set_match_bare_run_names(true);
+        let table = HashMap::from([("title".to_string(), "Titre".to_string())]);
+        let report = content.apply_text_table(&table).unwrap();
+        assert_eq!(report.applied, ["title", "card/title"]);
+        assert_eq!(content.text_run("card/title").unwrap(), "Titre");
+        assert_eq!(content.text_run("missing"), None);
+    }
+
+    /// Fires "First" on its first advance, "Second" and "Third" two seconds in, and "Fourth"
+    /// a second later.
+    const EVENTS_ON_STATES: &str = "../test/assets/events_on_states.riv";
+
+    fn fired_names(content: &ViewerContent) -> Vec<&str> {
+        content
+            .events_this_frame()
+            .iter()
+            .map(|event| event.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn fired_events_are_built_with_their_properties() {
+        let mut content = ViewerContent::new(EVENTS_ON_STATES).unwrap();
+
+        content.advance(0.0).unwrap();
+        assert_eq!(fired_names(&content), ["First"]);
+        content.advance(1.0).unwrap();
+        assert!(content.events_this_frame().is_empty());
+        content.advance(1.0).unwrap();
+        assert_eq!(fired_names(&content), ["Second", "Third"]);
+        assert_eq!(
+            content.events_this_frame()[1].properties,
+            [
+                ("Stereo".to_string(), EventProperty::Bool(false)),
+                (
+                    "AudioFile".to_string(),
+                    EventProperty::String("step.wav".to_string())
+                ),
+                ("Volume".to_string(), EventProperty::Number(80.0)),
+            ]
+        );
+        assert_eq!(content.skipped_event_count(), 0);
+    }
+
+    #[test]
+    fn filtered_events_are_counted_without_being_built() {
+        let mut content = ViewerContent::new(EVENTS_ON_STATES).unwrap();
+        let heard = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let sink = heard.clone();
+        content.subscribe_event("Third", move |event| {
+            sink.borrow_mut().push((
+                event.name().to_string(),
+                event.number("Volume"),
+                event.string("AudioFile").map(str::to_string),
+                event.bool("Volume"),
+            ));
+        });
+        content.set_event_filter(&["Third"]);
+
+        content.advance(0.0).unwrap();
+        assert!(content.events_this_frame().is_empty());
+        assert_eq!(content.skipped_event_count(), 1);
+
+        content.advance(1.0).unwrap();
+        assert_eq!(content.skipped_event_count(), 0);
+        content.advance(1.0).unwrap();
+        assert_eq!(fired_names(&content), ["Third"]);
+        assert_eq!(content.skipped_event_count(), 1);
+        assert_eq!(
+            *heard.borrow(),
+            [(
+                "Third".to_string(),
+                Some(80.0),
+                Some("step.wav".to_string()),
+                None
+            )]
+        );
+
+        content.set_event_filter(&[]);
+        content.unsubscribe_event("Third");
+        content.advance(1.0).unwrap();
+        assert!(content.events_this_frame().is_empty());
+        assert_eq!(content.skipped_event_count(), 1);
+        assert_eq!(heard.borrow().len(), 1);
+    }
+
+    #[test]
+    fn filtering_events_leaves_the_state_machine_alone() {
+        let mut all = ViewerContent::new(EVENTS_ON_STATES).unwrap();
+        let mut none = ViewerContent::new(EVENTS_ON_STATES).unwrap();
+        none.set_event_filter(&[]);
+
+        for elapsed in [0.0, 1.0, 1.0, 0.5, 0.5, 1.0] {
+            all.advance(elapsed).unwrap();
+            none.advance(elapsed).unwrap();
+
+            assert_eq!(all.applied_animations(), none.applied_animations());
+            assert_eq!(
+                all.events_this_frame().len(),
+                none.skipped_event_count(),
+                "{elapsed}"
+            );
+        }
+    }
+
+    #[test]
+    fn resizing_by_a_pixel_keeps_the_artboard_steady() {
+        let mut content = ViewerContent::new("../test/assets/shapetest.riv").unwrap();
+        content.set_pixel_snap(true);
+        let pixel = Vec2::new(100.0, 150.0);
+        let start_size = (401, 301);
+        content.handle_resize(start_size.0, start_size.1);
+        let start = content.view_to_artboard(pixel).unwrap();
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        // Wiggles around the start size, the way dragging a window edge back and forth does.
+        let mut step = |size: u32, start: u32| match size as i32 - start as i32 {
+            10 => size - 1,
+            -10 => size + 1,
+            _ if rng.gen::<bool>() => size + 1,
+            _ => size - 1,
+        };
+        let (mut width, mut height) = start_size;
+        for _ in 0..500 {
+            width = step(width, start_size.0);
+            height = step(height, start_size.1);
+            content.handle_resize(width, height);
+
+            let origin = content.view_to_artboard(Vec2::ZERO).unwrap();
+            let unit = content.view_to_artboard(Vec2::new(1.0, 1.0)).unwrap() - origin;
+            let snapped = content.view_to_artboard(pixel).unwrap();
+            content.set_pixel_snap(false);
+            let exact = content.view_to_artboard(pixel).unwrap();
+            content.set_pixel_snap(true);
+
+            // The artboard's corner stays on a pixel, and is never more than half a pixel away
+            // from where it'd be without snapping.
+            let corner = Vec2::new(-origin.x / unit.x, -origin.y / unit.y);
+            assert!((corner - corner.round()).hypot() < 1e-3, "{width}x{height}");
+            let off = snapped - exact;
+            assert!(
+                off.x.abs() <= unit.x * 0.501 && off.y.abs() <= unit.y * 0.501,
+                "{width}x{height}"
+            );
+            // Nothing builds up over resizes.
+            if (width, height) == start_size {
+                assert!((snapped - start).hypot() < 1e-4, "{snapped:?} {start:?}");
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn transform_stack_fits_the_artboard_like_the_runtime(
+            fit in proptest::sample::select(FITS.to_vec()),
+            pixel_snap in proptest::bool::ANY,
+            surface_size in (1u32..1500, 1u32..1500),
+            dpi_scale in proptest::sample::select(vec![1.0, 1.5, 2.0]),
+            orientation in proptest::sample::select(ORIENTATIONS.to_vec()),
+            pos in (-500.0..1500.0, -500.0..1500.0),
+        ) {
+            let mut content = ViewerContent::new("../test/assets/shapetest.riv").unwrap();
+            content.set_fit(fit).unwrap();
+            content.set_pixel_snap(pixel_snap);
+            content.set_dpi_scale(dpi_scale);
+            content.set_orientation(orientation);
+            content.handle_resize(surface_size.0, surface_size.1);
+
+            let pos = Vec2::new(pos.0, pos.1);
+            let runtime = content.view_to_artboard(pos).unwrap();
+            let stack = content.transform_stack().local_to_artboard(pos).unwrap();
+            let tolerance = 1e-3 * (1.0 + runtime.hypot());
+            assert!((runtime - stack).hypot() < tolerance, "{runtime:?} {stack:?}");
+        }
+    }
+
+    const FITS: [Fit; 7] = [
+        Fit::Fill,
+        Fit::Contain,
+        Fit::Cover,
+        Fit::FitWidth,
+        Fit::FitHeight,
+        Fit::None,
+        Fit::ScaleDown,
+    ];
+    const ORIENTATIONS: [Orientation; 4] = [
+        Orientation::Up,
+        Orientation::Right,
+        Orientation::Down,
+        Orientation::Left,
+    ];
+
+    proptest::proptest! {
+        #![proptest_config(proptest::test_runner::Config::with_cases(12))]
+
+        #[test]
+        fn artboards_are_drawn_where_the_transform_stack_puts_them(
+            fit in proptest::sample::select(FITS.to_vec()),
+            pixel_snap in proptest::bool::ANY,
+            surface_size in (8u32..96, 8u32..96),
+            dpi_scale in proptest::sample::select(vec![1.0, 1.5, 2.0]),
+            orientation in proptest::sample::select(ORIENTATIONS.to_vec()),
+            pan in (-20.0..20.0, -20.0..20.0),
+            zoom in 0.5..2.0,
+            angle in -3.2..3.2,
+        ) {
+            let gpu = crate::device::test_device();
+            let mut rasterizer = crate::Rasterizer::new(gpu.device, gpu.queue);
+            // An opaque background filling the artboard, which clips everything else to it.
+            let mut content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+            content.set_fit(fit).unwrap();
+            content.set_pixel_snap(pixel_snap);
+            content.set_dpi_scale(dpi_scale);
+            content.set_orientation(orientation);
+            content.set_camera(Camera {
+                pan: Vec2::new(pan.0, pan.1),
+                zoom,
+            });
+            let (width, height) = surface_size;
+            let center = Vec2::new(width as f64, height as f64) / (2.0 * dpi_scale);
+            content.set_placement(Affine::rotate_about(angle, center.to_point()));
+            content.handle_resize(width, height);
+            content.advance(0.0).unwrap();
+
+            let mut renderer = VelloRenderer::default();
+            content.draw(&mut renderer, width, height).unwrap();
+            renderer.end_frame();
+            let capture = rasterizer
+                .render(
+                    &renderer.scene,
+                    Color::TRANSPARENT,
+                    width,
+                    height,
+                    1,
+                    crate::ScaledOutput::Downsampled,
+                )
+                .unwrap();
+
+            // Pixels within a pixel and a half of the artboard's edge are left to antialiasing.
+            let stack = content.transform_stack();
+            let inside = |x: f64, y: f64| {
+                let artboard = stack.surface_to_artboard(Vec2::new(x, y)).unwrap();
+                stack.artboard_bounds.contains(artboard.to_point())
+            };
+            for y in 0..height {
+                for x in 0..width {
+                    let (cx, cy) = (x as f64 + 0.5, y as f64 + 0.5);
+                    let predicted = inside(cx, cy);
+                    let settled = [(-1.5, -1.5), (1.5, -1.5), (-1.5, 1.5), (1.5, 1.5)]
+                        .into_iter()
+                        .all(|(dx, dy)| inside(cx + dx, cy + dy) == predicted);
+                    if !settled {
+                        continue;
+                    }
+
+                    let alpha = capture.pixels[(y * width + x) as usize * 4 + 3];
+                    assert_eq!(alpha > 127, predicted, "({x}, {y}) of {width}x{height}");
+                }
+            }
+        }
+    }
+
+    /// shapetest.riv with its ellipse's fill renamed "currentColor", the way icon sets mark the
+    /// paint to recolor.
+    fn icon_bytes() -> Vec<u8> {
+        let bytes = fs::read("../test/assets/shapetest.riv").unwrap();
+        let name = b"\x06Fill 1";
+        let at = bytes.windows(name.len()).position(|w| w == name).unwrap();
+
+        [&bytes[..at], b"\x0ccurrentColor", &bytes[at + name.len()..]].concat()
+    }
+
+    #[test]
+    fn primary_color_recolors_current_color_paints() {
+        let mut icon = ViewerContent::from_bytes(&icon_bytes()).unwrap();
+        let mut plain = ViewerContent::new("../test/assets/shapetest.riv").unwrap();
+
+        assert_eq!(icon.set_primary_color(Color::RED), Ok(1));
+        assert_eq!(plain.set_primary_color(Color::RED), Ok(0));
+        assert_eq!(icon.clear_primary_color(), Ok(()));
+
+        icon.hibernate();
+        assert!(icon.set_primary_color(Color::RED).is_err());
+    }
+
+    #[test]
+    fn primary_color_shows_on_the_next_draw() {
+        let gpu = crate::device::test_device();
+        let mut rasterizer = crate::Rasterizer::new(gpu.device, gpu.queue);
+        let mut icon = ViewerContent::from_bytes(&icon_bytes()).unwrap();
+        icon.advance(0.0).unwrap();
+
+        // The ellipse's center and a corner of the artboard's background, whose fill keeps its
+        // color.
+        let mut pixels = |icon: &ViewerContent| {
+            let mut renderer = VelloRenderer::default();
+            icon.draw(&mut renderer, 400, 200).unwrap();
+            renderer.end_frame();
+            let capture = rasterizer
+                .render(
+                    &renderer.scene,
+                    Color::TRANSPARENT,
+                    400,
+                    200,
+                    1,
+                    crate::ScaledOutput::Downsampled,
+                )
+                .unwrap();
+            let pixel = |x: usize, y: usize| capture.pixels[(y * 400 + x) * 4..][..4].to_vec();
+            (pixel(205, 98), pixel(10, 10))
+        };
+
+        let (authored, background) = pixels(&icon);
+        assert_ne!(authored, [255, 0, 0, 255]);
+
+        icon.set_primary_color(Color::RED).unwrap();
+        assert_eq!(pixels(&icon), (vec![255, 0, 0, 255], background.clone()));
+
+        icon.clear_primary_color().unwrap();
+        assert_eq!(pixels(&icon), (authored, background));
+    }
+
+    /// Values of the watched properties as the runtime has them now.
+    fn watched_values(content: &ViewerContent) -> Vec<u32> {
+        let mut values = vec![0; content.watched_properties().len()];
+        unsafe {
+            viewer_content_read_watched(content.raw_viewer_content, values.as_mut_ptr());
+        }
+        values
+    }
+
+    fn recorded_values(record: &FrameRecord) -> Vec<u32> {
+        record.values.iter().map(|value| value.bits()).collect()
+    }
+
+    #[test]
+    fn history_keeps_the_last_frames_of_watched_components() {
+        let mut content = ViewerContent::new("../test/assets/juice.riv").unwrap();
+        content.enable_history(8);
+        let all = content.watch_all_components().unwrap();
+        content.clear_watches().unwrap();
+        let face = content.watch_component("face").unwrap();
+        assert!(0 < face && face < all, "{face} of {all}");
+
+        for _ in 0..20 {
+            content.advance(1.0 / 60.0).unwrap();
+        }
+        let history = content.history();
+        assert_eq!(history.len(), 8);
+        assert_eq!((history[0].frame, history[7].frame), (12, 19));
+        let live = watched_values(&content);
+        assert_eq!(recorded_values(&content.history()[7]), live);
+        // The face moves while the character walks.
+        assert_ne!(content.history()[0].values, content.history()[7].values);
+
+        content.pause();
+        assert_eq!(content.show_history_frame(2), Ok(true));
+        let shown = recorded_values(&content.history()[2]);
+        assert_eq!(watched_values(&content), shown);
+        assert_eq!(content.show_history_frame(8), Ok(false));
+
+        // Paused frames neither take the recorded values off display nor push records out.
+        content.advance(1.0 / 60.0).unwrap();
+        assert_eq!(content.shown_history_frame(), Some(2));
+        assert_eq!(watched_values(&content), shown);
+        assert_eq!(content.history()[7].frame, 19);
+
+        content.show_live_frame().unwrap();
+        assert_eq!(content.shown_history_frame(), None);
+        assert_eq!(watched_values(&content), live);
+
+        // Playing on goes back to the live pose and records again.
+        content.show_history_frame(0).unwrap();
+        content.resume();
+        content.advance(1.0 / 60.0).unwrap();
+        assert_eq!(content.shown_history_frame(), None);
+        assert_eq!(content.history().len(), 8);
+        assert_eq!(content.history()[7].frame, 20);
+        assert_ne!(watched_values(&content), live);
+    }
+
+    #[test]
+    fn history_frames_draw_as_they_were_recorded() {
+        let gpu = crate::device::test_device();
+        let mut rasterizer = crate::Rasterizer::new(gpu.device, gpu.queue);
+        let mut render = |content: &ViewerContent| {
+            let mut renderer = VelloRenderer::default();
+            content.draw(&mut renderer, 270, 270).unwrap();
+            renderer.end_frame();
+            rasterizer
+                .render(
+                    &renderer.scene,
+                    Color::TRANSPARENT,
+                    270,
+                    270,
+                    1,
+                    crate::ScaledOutput::Downsampled,
+                )
+                .unwrap()
+                .pixels
+        };
+
+        let mut content = ViewerContent::new("../test/assets/juice.riv").unwrap();
+        content.enable_history(30);
+        content.watch_all_components().unwrap();
+        let mut drawn = Vec::new();
+        for _ in 0..30 {
+            content.advance(1.0 / 60.0).unwrap();
+            drawn.push(render(&content));
+        }
+        assert!(drawn[0] != drawn[29]);
+
+        content.pause();
+        for index in [0, 5, 17] {
+            content.show_history_frame(index).unwrap();
+            assert!(render(&content) == drawn[index], "frame {index}");
+        }
+        content.show_live_frame().unwrap();
+        assert!(render(&content) == drawn[29]);
+    }
+
+    /// Length of bone "b" of two_bone_ik.riv, whose tip its IK constraint puts on "target".
+    const IK_TIP_BONE_LENGTH: f64 = 237.85142517089844;
+
+    #[test]
+    fn bones_nudged_after_animating_are_seen_by_constraints_and_drawing() {
+        let path = "../test/assets/two_bone_ik.riv";
+        let mut plain = ViewerContent::new(path).unwrap();
+        let mut nudged = ViewerContent::new(path).unwrap();
+
+        nudged.on_advance(AdvancePhase::Animated, |view| {
+            let before = view.node("a");
+            view.defer(|artboard| assert!(artboard.nudge("a", Vec2::new(10.0, 0.0))));
+            // Nothing changes until the phase is over.
+            assert_eq!(view.node("a"), before);
+        });
+        let drawn = Rc::new(Cell::new(None));
+        let updated = drawn.clone();
+        nudged.on_advance(AdvancePhase::Updated, move |view| {
+            updated.set(view.node("b"))
+        });
+
+        for content in [&mut plain, &mut nudged] {
+            content.handle_resize(500, 500);
+            content
+                .handle_draw(&mut VelloRenderer::default(), 0.5)
+                .unwrap();
+        }
+
+        let root = plain.node("a").unwrap();
+        let nudged_root = nudged.node("a").unwrap();
+        assert_eq!(nudged_root.x, root.x + 10.0);
+        let moved = nudged_root.world.translation() - root.world.translation();
+        assert!((moved - Vec2::new(10.0, 0.0)).hypot() < 1e-3);
+
+        // The constraint solved the chain from where the root was nudged to, so its tip still
+        // reaches the target, in the pose which was drawn.
+        let tip = nudged.node("b").unwrap();
+        assert_eq!(drawn.get(), Some(tip));
+        let target = nudged.node("target").unwrap().world.translation();
+        let reached = tip.world * Point::new(IK_TIP_BONE_LENGTH, 0.0);
+        assert!((reached.to_vec2() - target).hypot() < 1e-2);
+        assert_ne!(tip.world, plain.node("b").unwrap().world);
+    }
+
+    #[test]
+    fn mutations_deferred_after_updating_are_drawn_in_order() {
+        let mut content = ViewerContent::new("../test/assets/two_bone_ik.riv").unwrap();
+        let deferred = Rc::new(Cell::new(0));
+        let counted = deferred.clone();
+        content.on_advance(AdvancePhase::Updated, move |view| {
+            let Some(mut root) = view.node("a") else {
+                return;
+            };
+            root.x = 100.0;
+            view.defer(move |artboard| assert!(artboard.set_node("a", &root)));
+            view.defer(|artboard| assert!(artboard.nudge("a", Vec2::new(1.0, 0.0))));
+            view.defer(|artboard| assert!(!artboard.nudge("missing", Vec2::new(1.0, 0.0))));
+            counted.set(view.deferred.len());
+        });
+
+        content.advance(1.0 / 60.0).unwrap();
+
+        assert_eq!(deferred.get(), 3);
+        let root = content.node("a").unwrap();
+        assert_eq!(root.x, 101.0);
+        // Another update followed, so the world transform caught up.
+        assert!((root.world.translation().x - 101.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn inputs_set_from_advance_callbacks_are_kept_for_the_next_advance() {
+        let mut content = ViewerContent::new("../test/assets/blend_test.riv").unwrap();
+        content.on_advance(AdvancePhase::Animated, |view| {
+            assert_eq!(view.phase(), AdvancePhase::Animated);
+            assert!(view.set_number("verticality", 42.0));
+            assert!(!view.set_bool("verticality", true));
+        });
+
+        content.advance(1.0 / 60.0).unwrap();
+        assert_eq!(content.number("verticality"), Some(42.0));
+
+        content.clear_advance_callbacks();
+        content.set_number("verticality", 0.0).unwrap();
+        content.advance(1.0 / 60.0).unwrap();
+        assert_eq!(content.number("verticality"), Some(0.0));
+    }
+
+    /// Fails the first attempt at every asset, like a dropped connection, then returns `bytes`.
+    fn flaky_loader(bytes: Vec<u8>) -> impl FnMut(&MissingAsset) -> Result<Vec<u8>, String> {
+        let mut attempts = HashMap::new();
+        move |asset| {
+            let attempt = attempts.entry(asset.id).or_insert(0);
+            *attempt += 1;
+            if *attempt == 1 {
+                Err(format!("connection to {} dropped", asset.name))
+            } else {
+                Ok(bytes.clone())
+            }
+        }
+    }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        use image::{codecs::png::PngEncoder, ColorType, ImageEncoder};
+
+        let mut png = Vec::new();
+        let pixels = vec![0x80; (width * height * 4) as usize];
+        PngEncoder::new(&mut png)
+            .write_image(&pixels, width, height, ColorType::Rgba8)
+            .unwrap();
+        png
+    }
+
+    fn sorted(mut names: Vec<String>) -> Vec<String> {
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    #[test]
+    fn images_failing_to_load_draw_placeholders_until_rebound() {
+        let bytes = fs::read("../test/assets/tape_without_images.riv").unwrap();
+        let mut load = flaky_loader(png(4, 4));
+        let mut content = ViewerContent::from_bytes_with_assets(&bytes, &mut load).unwrap();
+        content.handle_resize(100, 100);
+
+        let missing = content.missing_assets();
+        assert_eq!(missing.len(), 3);
+        for asset in &missing {
+            assert_eq!(asset.kind, AssetKind::Image);
+            assert_eq!(asset.fallback, AssetFallback::Placeholder);
+            assert_eq!(asset.error, format!("connection to {} dropped", asset.name));
+        }
+
+        let mut renderer = VelloRenderer::default();
+        content.handle_draw(&mut renderer, 0.0).unwrap();
+        let placeholders = sorted(renderer.stats().missing_images.clone());
+        assert!(!placeholders.is_empty());
+        assert!(renderer.stats().images.is_empty());
+
+        for asset in &missing {
+            content
+                .rebind_asset(asset.id, &load(asset).unwrap())
+                .unwrap();
+        }
+        assert!(content.missing_assets().is_empty());
+        assert_eq!(
+            content.rebind_asset(missing[0].id, &png(4, 4)),
+            Err(RebindError::NotMissing(missing[0].id))
+        );
+
+        let mut renderer = VelloRenderer::default();
+        content.handle_draw(&mut renderer, 0.0).unwrap();
+        let stats = renderer.stats();
+        assert!(stats.missing_images.is_empty());
+        let drawn = stats
+            .images
+            .iter()
+            .map(|image| image.asset.clone())
+            .collect();
+        assert_eq!(sorted(drawn), placeholders);
+        assert!(stats.images.iter().all(|image| image.width == 4));
+    }
+
+    #[test]
+    fn undecodable_images_keep_their_placeholder() {
+        let bytes = fs::read("../test/assets/tape_without_images.riv").unwrap();
+        let mut content =
+            ViewerContent::from_bytes_with_assets(&bytes, |_| Ok(b"<html>".to_vec())).unwrap();
+        content.handle_resize(100, 100);
+
+        let missing = content.missing_assets();
+        assert_eq!(missing.len(), 3);
+        assert_eq!(
+            missing[0].error,
+            RebindError::Undecodable(missing[0].id).to_string()
+        );
+        assert_eq!(
+            content.rebind_asset(missing[0].id, b"still not an image"),
+            Err(RebindError::Undecodable(missing[0].id))
+        );
+
+        let mut renderer = VelloRenderer::default();
+        renderer.set_missing_asset_style(MissingAssetStyle::Solid(Color::rgb8(0xff, 0, 0xff)));
+        content.handle_draw(&mut renderer, 0.0).unwrap();
+        assert!(!renderer.stats().missing_images.is_empty());
+    }
+
+    #[test]
+    fn fonts_failing_to_load_box_their_texts_until_rebound() {
+        let bytes = fs::read("../test/assets/hello_world_without_font.riv").unwrap();
+        let font = fs::read("../test/assets/Montserrat.ttf").unwrap();
+        let mut load = flaky_loader(font);
+        let mut content = ViewerContent::from_bytes_with_assets(&bytes, &mut load).unwrap();
+        content.handle_resize(100, 100);
+
+        let missing = content.missing_assets();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].kind, AssetKind::Font);
+        assert_eq!(missing[0].fallback, AssetFallback::BoundingBoxes);
+        content
+            .handle_draw(&mut VelloRenderer::default(), 0.0)
+            .unwrap();
+
+        content
+            .rebind_asset(missing[0].id, &load(&missing[0]).unwrap())
+            .unwrap();
+        assert!(content.missing_assets().is_empty());
+        content
+            .handle_draw(&mut VelloRenderer::default(), 0.0)
+            .unwrap();
+    }
+
+    #[test]
+    fn embedded_assets_are_not_missing() {
+        let content = ViewerContent::new("../test/assets/walle.riv").unwrap();
+        assert!(content.missing_assets().is_empty());
+    }
+
+    #[test]
+    fn components_have_bounds_of_their_drawables() {
+        let mut content = ViewerContent::new("../test/assets/juice.riv").unwrap();
+        content.advance(0.0).unwrap();
+
+        let root = content.component_bounds("root").unwrap();
+        let face = content.component_bounds("face").unwrap();
+        assert!(root.width() > face.width() && root.height() > face.height());
+        assert_eq!(root, root.round());
+        assert!(content.component_bounds("nothing").is_none());
+
+        let mut renderer = VelloRenderer::default();
+        assert_eq!(
+            content.draw_component(&mut renderer, "face", (64, 64), 4.0, false),
+            Ok(true)
+        );
+        assert_eq!(
+            content.draw_component(&mut renderer, "nothing", (64, 64), 4.0, false),
+            Ok(false)
+        );
+
+        content.hibernate();
+        assert!(content.component_bounds("root").is_none());
+    }
+
+    #[test]
+    fn components_render_alone_as_they_composite() {
+        let gpu = crate::device::test_device();
+        let mut rasterizer = crate::Rasterizer::new(gpu.device, gpu.queue);
+        let mut content = ViewerContent::new("../test/assets/juice.riv").unwrap();
+        content.advance(0.0).unwrap();
+
+        // The artboard is 1080 units square, so it composites at a scale of 1.
+        let mut renderer = VelloRenderer::default();
+        content.draw(&mut renderer, 1080, 1080).unwrap();
+        renderer.end_frame();
+        let composite = rasterizer
+            .render(
+                &renderer.scene,
+                Color::TRANSPARENT,
+                1080,
+                1080,
+                1,
+                crate::ScaledOutput::Downsampled,
+            )
+            .unwrap()
+            .into_image();
+
+        // The root is at the root of the artboard, so its bounds are where it composites.
+        let bounds = content.component_bounds("root").unwrap();
+        assert!(bounds.x0 >= 0.0 && bounds.y0 >= 0.0);
+        assert!(bounds.x1 <= 1080.0 && bounds.y1 <= 1080.0);
+        let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+        let alone = content
+            .rasterize_component(&mut rasterizer, "root", (width, height), 0.0, false)
+            .unwrap();
+        let region = image::imageops::crop_imm(
+            &composite,
+            bounds.x0 as u32,
+            bounds.y0 as u32,
+            width,
+            height,
+        )
+        .to_image();
+
+        // The composite has the artboard's background where the root doesn't cover it, so only
+        // covered pixels compare. The glass's blended shapes blend with that background too.
+        let covered: Vec<_> = alone
+            .pixels()
+            .zip(region.pixels())
+            .filter(|(alone, _)| alone[3] == 255)
+            .collect();
+        let matching = covered
+            .iter()
+            .filter(|(alone, region)| (0..4).all(|c| alone[c].abs_diff(region[c]) <= 2))
+            .count();
+        assert!(covered.len() > 10_000, "{} covered", covered.len());
+        assert!(
+            matching * 10 >= covered.len() * 9,
+            "{matching} of {} match",
+            covered.len()
+        );
+        assert!(alone.pixels().any(|pixel| pixel[3] == 0));
+
+        let parts = content
+            .rasterize_components(&mut rasterizer, &["face", "root"], (128, 96), 8.0, false)
+            .unwrap();
+        assert_eq!(parts.len(), 2);
+        assert!(parts
+            .iter()
+            .all(|part| part.dimensions() == (128, 96) && part.pixels().any(|p| p[3] > 0)));
+        assert!(matches!(
+            content.rasterize_components(&mut rasterizer, &["face", "lid"], (128, 96), 8.0, false),
+            Err(ExtractError::UnknownComponent(name)) if name == "lid"
+        ));
+    }
+
+    #[test]
+    fn strict_loads_enforce_contracts() {
+        let bytes = fs::read("../test/assets/death_knight.riv").unwrap();
+        let text = fs::read_to_string("../test/assets/death_knight_contract.json").unwrap();
+        let broken: InteractionContract = serde_json::from_str(&text).unwrap();
+        let kept = InteractionContract {
+            inputs: broken.inputs[2..3].to_vec(),
+            nested_paths: vec!["Death Left".to_string()],
+            ..Default::default()
+        };
+
+        let content = ViewerContent::from_bytes_with_contract(&bytes, &kept).unwrap();
+        let report = content.check_contract(&kept).unwrap();
+        assert!(report.is_satisfied());
+        assert_eq!(report.extra_inputs, ["Direction", "Death", "isMoving"]);
+
+        let Err(ContractError::Broken(report)) =
+            ViewerContent::from_bytes_with_contract(&bytes, &broken)
+        else {
+            panic!("death_knight.riv satisfies a contract it breaks");
+        };
+        assert_eq!(report.missing_inputs, ["Jump"]);
+        assert_eq!(report.type_mismatches[0].found, InputType::Bool);
+        assert_eq!(report.range_mismatches[0].values, [4.0]);
+        assert_eq!(report.missing_nested_paths, ["Death Sideways"]);
+        assert!(matches!(
+            ViewerContent::from_bytes_with_contract(b"RIVE", &kept),
+            Err(ContractError::Unloadable)
+        ));
+    }
+
+    #[test]
+    fn inputs_are_listed_and_set_by_type() {
+        let mut content = ViewerContent::new("../test/assets/death_knight.riv").unwrap();
+        let descriptor = |name: &str, input_type| InputDescriptor {
+            name: name.to_string(),
+            input_type,
+        };
+
+        assert_eq!(
+            content.inputs(),
+            [
+                descriptor("Direction", InputType::Number),
+                descriptor("Attack", InputType::Trigger),
+                descriptor("Death", InputType::Trigger),
+                descriptor("isMoving", InputType::Bool),
+            ]
+        );
+
+        assert_eq!(content.fire_trigger("Attack"), Ok(true));
+        assert_eq!(content.set_bool("isMoving", true), Ok(true));
+        assert_eq!(content.boolean("isMoving"), Some(true));
+        assert_eq!(content.set_number("Direction", 3.0), Ok(true));
+        assert_eq!(content.number("Direction"), Some(3.0));
+
+        assert_eq!(content.fire_trigger("isMoving"), Ok(false));
+        assert_eq!(content.fire_trigger("Jump"), Ok(false));
+        assert_eq!(content.set_bool("Direction", true), Ok(false));
+        assert_eq!(content.set_number("Attack", 1.0), Ok(false));
+        assert_eq!(content.boolean("Direction"), None);
+
+        // The indices are listed again for the newly selected state machine.
+        content.select(Some("Smoke"), None).unwrap();
+        assert!(content.inputs().is_empty());
+        assert_eq!(content.set_bool("isMoving", false), Ok(false));
+        content.select(Some("MC Main Artboard"), None).unwrap();
+        assert_eq!(content.boolean("isMoving"), Some(false));
+    }
+
+    #[test]
+    fn quantized_numbers_take_equality_transitions() {
+        // The "Facing" layer of death_knight.riv has a transition for each `Direction == n`.
+        let path = "../test/assets/death_knight.riv";
+        let facing = |content: &ViewerContent| content.snapshot().unwrap().layers[2].state;
+
+        let mut exact = ViewerContent::new(path).unwrap();
+        exact.advance(0.0).unwrap();
+        let one = facing(&exact);
+        assert_eq!(exact.discrete_values("Direction"), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(exact.set_number("Direction", 2.0), Ok(true));
+        exact.advance(0.1).unwrap();
+        let two = facing(&exact);
+        assert_ne!(one, two);
+
+        for epsilon in [1e-6, 1e-4, 0.01, 0.3] {
+            for value in [2.0 + epsilon, 2.0 - epsilon] {
+                let mut raw = ViewerContent::new(path).unwrap();
+                raw.advance(0.0).unwrap();
+                raw.set_number("Direction", value).unwrap();
+                raw.advance(0.1).unwrap();
+                assert_eq!(facing(&raw), one, "{value}");
+
+                let mut quantized = ViewerContent::new(path).unwrap();
+                quantized.advance(0.0).unwrap();
+                quantized.set_number_quantized("Direction", value).unwrap();
+                quantized.advance(0.1).unwrap();
+                assert_eq!(facing(&quantized), two, "{value}");
+                assert_eq!(quantized.number("Direction"), Some(2.0));
+            }
+        }
+    }
+
+    #[test]
+    fn linear_animations_have_no_inputs() {
+        let content = ViewerContent::new("../test/assets/fix_rectangle.riv").unwrap();
+
+        assert!(content.inputs().is_empty());
+        assert_eq!(content.fire_trigger("Attack"), Ok(false));
+        assert_eq!(content.set_number("Direction", 1.0), Ok(false));
     }
 }
@@ -1,3 +1,4 @@
+#include "rive/math/aabb.hpp"
 #include "viewer/viewer_content.hpp"
 
 #include "vello_renderer.hpp"
@@ -6,9 +7,11 @@ extern "C"
 {
     typedef void* RawViewerContent;
 
-    const RawViewerContent viewer_content_new(const char* filename)
+    const RawViewerContent viewer_content_new_from_bytes(const uint8_t* data, size_t len)
     {
-        return static_cast<void*>(ViewerContent::findHandler(filename).release());
+        return static_cast<void*>(
+            ViewerContent::SceneFromBytes("The .riv file", rive::Span<const uint8_t>(data, len))
+                .release());
     }
 
     void viewer_content_release(const RawViewerContent viewer_content)
@@ -31,6 +34,30 @@ extern "C"
         static_cast<ViewerContent*>(viewer_content)->handleDraw(&renderer, elapsed);
     }
 
+    void viewer_content_handle_advance(const RawViewerContent viewer_content, double elapsed)
+    {
+        static_cast<ViewerContent*>(viewer_content)->handleAdvance(elapsed);
+    }
+
+    bool viewer_content_handle_animate(const RawViewerContent viewer_content, double elapsed)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->handleAnimate(elapsed);
+    }
+
+    void viewer_content_handle_update(const RawViewerContent viewer_content, double elapsed)
+    {
+        static_cast<ViewerContent*>(viewer_content)->handleUpdate(elapsed);
+    }
+
+    void viewer_content_draw(const RawViewerContent viewer_content,
+                             RawVelloRenderer raw_renderer,
+                             int32_t width,
+                             int32_t height)
+    {
+        VelloRenderer renderer = VelloRenderer(raw_renderer);
+        static_cast<const ViewerContent*>(viewer_content)->draw(&renderer, width, height);
+    }
+
     void viewer_content_handle_pointer_move(const RawViewerContent viewer_content, float x, float y)
     {
         static_cast<ViewerContent*>(viewer_content)->handlePointerMove(x, y);
@@ -45,4 +72,437 @@ extern "C"
     {
         static_cast<ViewerContent*>(viewer_content)->handlePointerUp(x, y);
     }
+
+    bool viewer_content_view_to_artboard(const RawViewerContent viewer_content,
+                                         float x,
+                                         float y,
+                                         float* artboard_x,
+                                         float* artboard_y)
+    {
+        return static_cast<ViewerContent*>(viewer_content)
+            ->viewToArtboard(x, y, artboard_x, artboard_y);
+    }
+
+    float viewer_content_artboard_width(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardWidth();
+    }
+
+    bool viewer_content_artboard_bounds(const RawViewerContent viewer_content, float* bounds)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardBounds(bounds);
+    }
+
+    size_t viewer_content_artboard_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardCount();
+    }
+
+    const char* viewer_content_artboard_name(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardName(index);
+    }
+
+    bool viewer_content_select_artboard(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->selectArtboard(index);
+    }
+
+    size_t viewer_content_scene_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->sceneCount();
+    }
+
+    size_t viewer_content_artboard_index(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardIndex();
+    }
+
+    size_t viewer_content_artboard_state_machine_count(const RawViewerContent viewer_content,
+                                                       size_t artboard)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardStateMachineCount(artboard);
+    }
+
+    const char* viewer_content_artboard_state_machine_name(const RawViewerContent viewer_content,
+                                                           size_t artboard,
+                                                           size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)
+            ->artboardStateMachineName(artboard, index);
+    }
+
+    size_t viewer_content_artboard_animation_count(const RawViewerContent viewer_content,
+                                                   size_t artboard)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardAnimationCount(artboard);
+    }
+
+    const char* viewer_content_artboard_animation_name(const RawViewerContent viewer_content,
+                                                       size_t artboard,
+                                                       size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->artboardAnimationName(artboard, index);
+    }
+
+    const char* viewer_content_scene_name(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->sceneName(index);
+    }
+
+    bool viewer_content_select_scene(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->selectScene(index);
+    }
+
+    size_t viewer_content_scene_index(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->sceneIndex();
+    }
+
+    void viewer_content_set_fit(const RawViewerContent viewer_content, int fit)
+    {
+        static_cast<ViewerContent*>(viewer_content)->setFit(fit);
+    }
+
+    void viewer_content_set_pixel_snap(const RawViewerContent viewer_content, bool snap)
+    {
+        static_cast<ViewerContent*>(viewer_content)->setPixelSnap(snap);
+    }
+
+    bool viewer_content_hits_shape(const RawViewerContent viewer_content,
+                                   const char* name,
+                                   float x,
+                                   float y)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->hitsShape(name, x, y);
+    }
+
+    bool viewer_content_draw_component(const RawViewerContent viewer_content,
+                                       RawVelloRenderer raw_renderer,
+                                       const char* name,
+                                       int32_t width,
+                                       int32_t height,
+                                       float padding,
+                                       bool ancestor_effects)
+    {
+        VelloRenderer renderer = VelloRenderer(raw_renderer);
+        return static_cast<ViewerContent*>(viewer_content)
+            ->drawComponent(&renderer, name, width, height, padding, ancestor_effects);
+    }
+
+    bool viewer_content_component_bounds(const RawViewerContent viewer_content,
+                                         const char* name,
+                                         float* bounds)
+    {
+        rive::AABB aabb;
+        if (!static_cast<const ViewerContent*>(viewer_content)->componentBounds(name, &aabb))
+        {
+            return false;
+        }
+        bounds[0] = aabb.minX;
+        bounds[1] = aabb.minY;
+        bounds[2] = aabb.maxX;
+        bounds[3] = aabb.maxY;
+        return true;
+    }
+
+    bool viewer_content_hits_listener(const RawViewerContent viewer_content,
+                                      float x,
+                                      float y,
+                                      int type)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->hitsListener(x, y, type);
+    }
+
+    int viewer_content_redraw_policy(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->redrawPolicy();
+    }
+
+    bool viewer_content_playback_state(const RawViewerContent viewer_content, PlaybackState* state)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->playbackState(state);
+    }
+
+    void viewer_content_seek(const RawViewerContent viewer_content, float seconds)
+    {
+        static_cast<ViewerContent*>(viewer_content)->seek(seconds);
+    }
+
+    bool viewer_content_set_play_range(const RawViewerContent viewer_content,
+                                       float start,
+                                       float end)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setPlayRange(start, end);
+    }
+
+    void viewer_content_clear_play_range(const RawViewerContent viewer_content)
+    {
+        static_cast<ViewerContent*>(viewer_content)->clearPlayRange();
+    }
+
+    void viewer_content_offset_time(const RawViewerContent viewer_content, float seconds)
+    {
+        static_cast<ViewerContent*>(viewer_content)->offsetTime(seconds);
+    }
+
+    const rive::LinearAnimation* viewer_content_linear_animation(
+        const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->linearAnimation();
+    }
+
+    size_t viewer_content_applied_animation_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->appliedAnimationCount();
+    }
+
+    bool viewer_content_applied_animation(const RawViewerContent viewer_content,
+                                          size_t index,
+                                          AppliedAnimationInfo* info)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->appliedAnimation(index, info);
+    }
+
+    size_t viewer_content_fired_event_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->firedEventCount();
+    }
+
+    const char* viewer_content_fired_event_name(const RawViewerContent viewer_content,
+                                                size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->firedEventName(index);
+    }
+
+    size_t viewer_content_fired_event_property_count(const RawViewerContent viewer_content,
+                                                     size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->firedEventPropertyCount(index);
+    }
+
+    bool viewer_content_fired_event_property(const RawViewerContent viewer_content,
+                                             size_t index,
+                                             size_t property,
+                                             EventPropertyInfo* info)
+    {
+        return static_cast<ViewerContent*>(viewer_content)
+            ->firedEventProperty(index, property, info);
+    }
+
+    size_t viewer_content_set_primary_color(const RawViewerContent viewer_content, uint32_t color)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setPrimaryColor(color);
+    }
+
+    void viewer_content_clear_primary_color(const RawViewerContent viewer_content)
+    {
+        static_cast<ViewerContent*>(viewer_content)->clearPrimaryColor();
+    }
+
+    size_t viewer_content_watch_component(const RawViewerContent viewer_content, const char* name)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->watchComponent(name);
+    }
+
+    void viewer_content_clear_watches(const RawViewerContent viewer_content)
+    {
+        static_cast<ViewerContent*>(viewer_content)->clearWatches();
+    }
+
+    size_t viewer_content_watched_property_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->watchedPropertyCount();
+    }
+
+    bool viewer_content_watched_property(const RawViewerContent viewer_content,
+                                         size_t index,
+                                         WatchedPropertyInfo* info)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->watchedProperty(index, info);
+    }
+
+    void viewer_content_read_watched(const RawViewerContent viewer_content, uint32_t* values)
+    {
+        static_cast<ViewerContent*>(viewer_content)->readWatched(values);
+    }
+
+    void viewer_content_show_watched(const RawViewerContent viewer_content,
+                                     const uint32_t* values)
+    {
+        static_cast<ViewerContent*>(viewer_content)->showWatched(values);
+    }
+
+    void viewer_content_show_live(const RawViewerContent viewer_content)
+    {
+        static_cast<ViewerContent*>(viewer_content)->showLive();
+    }
+
+    size_t viewer_content_text_run_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->textRunCount();
+    }
+
+    const char* viewer_content_text_run_path(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->textRunPath(index);
+    }
+
+    const char* viewer_content_text_run_text(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->textRunText(index);
+    }
+
+    void viewer_content_set_text_run(const RawViewerContent viewer_content,
+                                     size_t index,
+                                     const char* text)
+    {
+        static_cast<ViewerContent*>(viewer_content)->setTextRun(index, text);
+    }
+
+    size_t viewer_content_set_text_direction(const RawViewerContent viewer_content,
+                                             uint8_t direction)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setTextDirection(direction);
+    }
+
+    size_t viewer_content_event_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->eventCount();
+    }
+
+    const char* viewer_content_event_name(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->eventName(index);
+    }
+
+    size_t viewer_content_nested_artboard_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->nestedArtboardCount();
+    }
+
+    const char* viewer_content_nested_artboard_path(const RawViewerContent viewer_content,
+                                                    size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->nestedArtboardPath(index);
+    }
+
+    bool viewer_content_node_transform(const RawViewerContent viewer_content,
+                                       const char* name,
+                                       NodeTransformInfo* info)
+    {
+        return static_cast<const ViewerContent*>(viewer_content)->nodeTransform(name, info);
+    }
+
+    bool viewer_content_set_node_transform(const RawViewerContent viewer_content,
+                                           const char* name,
+                                           const NodeTransformInfo* info)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setNodeTransform(name, info);
+    }
+
+    size_t viewer_content_missing_asset_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->missingAssetCount();
+    }
+
+    bool viewer_content_missing_asset(const RawViewerContent viewer_content,
+                                      size_t index,
+                                      MissingAssetInfo* info)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->missingAsset(index, info);
+    }
+
+    bool viewer_content_rebind_asset(const RawViewerContent viewer_content,
+                                     uint32_t asset_id,
+                                     const uint8_t* data,
+                                     size_t len)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->rebindAsset(asset_id, data, len);
+    }
+
+    bool viewer_content_set_number(const RawViewerContent viewer_content,
+                                   const char* name,
+                                   float value)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setNumber(name, value);
+    }
+
+    bool viewer_content_number(const RawViewerContent viewer_content,
+                               const char* name,
+                               float* value)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->number(name, value);
+    }
+
+    bool viewer_content_set_bool(const RawViewerContent viewer_content,
+                                 const char* name,
+                                 bool value)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setBool(name, value);
+    }
+
+    size_t viewer_content_input_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->inputCount();
+    }
+
+    bool viewer_content_input(const RawViewerContent viewer_content,
+                              size_t index,
+                              InputInfo* info)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->input(index, info);
+    }
+
+    bool viewer_content_set_number_at(const RawViewerContent viewer_content,
+                                      size_t index,
+                                      float value)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setNumberAt(index, value);
+    }
+
+    bool viewer_content_set_bool_at(const RawViewerContent viewer_content, size_t index, bool value)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->setBoolAt(index, value);
+    }
+
+    bool viewer_content_fire_trigger_at(const RawViewerContent viewer_content, size_t index)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->fireTriggerAt(index);
+    }
+
+    const char* viewer_content_state_machine_name(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->stateMachineName();
+    }
+
+    size_t viewer_content_layer_count(const RawViewerContent viewer_content)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->layerCount();
+    }
+
+    bool viewer_content_layer_state(const RawViewerContent viewer_content,
+                                    size_t layer,
+                                    LayerStateInfo* info)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->layerState(layer, info);
+    }
+
+    bool viewer_content_restore_layer_state(const RawViewerContent viewer_content,
+                                            size_t layer,
+                                            size_t state,
+                                            float time)
+    {
+        return static_cast<ViewerContent*>(viewer_content)->restoreLayerState(layer, state, time);
+    }
+
+    size_t viewer_content_discrete_values(const RawViewerContent viewer_content,
+                                          const char* name,
+                                          const float** values)
+    {
+        auto span = static_cast<ViewerContent*>(viewer_content)->discreteValues(name);
+        *values = span.data();
+        return span.size();
+    }
 }
@@ -0,0 +1,98 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use rive_core::writer::{RivWriter, Value};
+
+/// A 64 by 48 artboard called "Main" with a one second, 60 fps looping animation called "Idle".
+fn tiny_riv() -> Vec<u8> {
+    RivWriter::new()
+        .object(
+            RivWriter::ARTBOARD,
+            &[
+                (RivWriter::COMPONENT_NAME, Value::String("Main")),
+                (RivWriter::ARTBOARD_WIDTH, Value::Double(64.0)),
+                (RivWriter::ARTBOARD_HEIGHT, Value::Double(48.0)),
+            ],
+        )
+        .object(
+            RivWriter::LINEAR_ANIMATION,
+            &[
+                (RivWriter::ANIMATION_NAME, Value::String("Idle")),
+                (RivWriter::ANIMATION_FPS, Value::Uint(60)),
+                (RivWriter::ANIMATION_DURATION, Value::Uint(60)),
+                (RivWriter::ANIMATION_LOOP, Value::Uint(1)),
+            ],
+        )
+        .finish()
+}
+
+/// A fresh directory holding the tiny file, unique to `test`.
+fn scratch(test: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("rive-render-{test}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let file = dir.join("tiny.riv");
+    fs::write(&file, tiny_riv()).unwrap();
+
+    (dir, file)
+}
+
+#[test]
+fn renders_a_frame_sequence() {
+    let (dir, file) = scratch("sequence");
+    let output = Command::new(env!("CARGO_BIN_EXE_rive-render"))
+        .arg(&file)
+        .args(["--artboard", "Main", "--scene", "Idle"])
+        .args(["--fps", "5", "--duration", "1s", "--size", "64x48"])
+        .arg("--out")
+        .arg(dir.join("frames/%04d.png"))
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for frame in 0..5 {
+        let image = image::open(dir.join(format!("frames/{frame:04}.png"))).unwrap();
+        assert_eq!((image.width(), image.height()), (64, 48));
+    }
+    assert!(!dir.join("frames/0005.png").exists());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<_> = stdout.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[0].starts_with("{\"frame\":0,\"time\":0,"));
+    assert!(lines.iter().all(|line| line.contains("\"render_ms\":")));
+
+    let _ = fs::remove_dir_all(dir);
+}
+
+#[test]
+fn unknown_names_fail_with_the_available_ones() {
+    let (dir, file) = scratch("unknown");
+
+    for (flag, name, available) in [
+        ("--artboard", "Missing", "Main"),
+        ("--scene", "Missing", "Idle"),
+    ] {
+        let output = Command::new(env!("CARGO_BIN_EXE_rive-render"))
+            .arg(&file)
+            .args([flag, name])
+            .arg("--out")
+            .arg(dir.join("unused.png"))
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        assert!(!output.status.success());
+        assert!(
+            stderr.contains("\"Missing\"") && stderr.contains(available),
+            "{stderr}"
+        );
+    }
+    assert!(!dir.join("unused.png").exists());
+
+    let _ = fs::remove_dir_all(dir);
+}
@@ -16,11 +16,100 @@
 
 namespace rive
 {
+class AABB;
 class Renderer;
 class Factory;
+class File;
 class Font;
+class LinearAnimation;
+class RenderImage;
 } // namespace rive
 
+// Where the active linear animation is in its timeline, in animation seconds. speed is signed
+// by the current playback direction.
+struct PlaybackState
+{
+    float time;
+    float start;
+    float end;
+    float speed;
+    int loop;
+};
+
+// An animation the active state machine applied on its last advance. name is owned by the
+// content's file.
+struct AppliedAnimationInfo
+{
+    const char* name;
+    float time;
+    float mix;
+    size_t layer;
+};
+
+// An animated property of a watched component. component is owned by the content's artboard
+// instance.
+struct WatchedPropertyInfo
+{
+    const char* component;
+    uint32_t propertyKey;
+    // 0 for numbers, 1 for booleans, 2 for colors and 3 for unsigned integers.
+    int type;
+};
+
+// An input of the active state machine. name is owned by the content's state machine instance.
+struct InputInfo
+{
+    const char* name;
+    // 0 for numbers, 1 for booleans and 2 for triggers, which have no value.
+    int type;
+    float value;
+};
+
+// A custom property of an event the active state machine fired. name and string are owned by
+// the content's artboard instance.
+struct EventPropertyInfo
+{
+    const char* name;
+    // 0 for numbers, 1 for booleans and 2 for strings. Only the matching value is set.
+    int type;
+    float number;
+    bool boolean;
+    const char* string;
+};
+
+// The state a layer of the active state machine is in, as an index among the layer's states.
+// time is that of the state's animation, or 0 for states without one.
+struct LayerStateInfo
+{
+    size_t state;
+    size_t stateCount;
+    float time;
+};
+
+// The transform of a node or bone. world is the rive::Mat2D the artboard's last update left it
+// with, in artboard units: xx, xy, yx, yy, tx and ty.
+struct NodeTransformInfo
+{
+    float x;
+    float y;
+    float rotation;
+    float scaleX;
+    float scaleY;
+    float world[6];
+};
+
+// An image or font of the file which wasn't embedded in it, or couldn't be decoded. assetType
+// is 0 for images and 1 for fonts. fallback tells what is drawn instead: 1 for a placeholder
+// image of the asset's authored size, 2 for the registered fallback font and 3 for boxes
+// around the texts it styles.
+struct MissingAssetInfo
+{
+    uint32_t assetId;
+    const char* name;
+    int32_t assetType;
+    int32_t fallback;
+};
+
 class ViewerContent
 {
 public:
@@ -28,6 +117,18 @@ public:
 
     virtual void handleResize(int width, int height) = 0;
     virtual void handleDraw(rive::Renderer* renderer, double elapsed) = 0;
+
+    // handleDraw split in two, so that one advanced pose can be drawn to several targets.
+    // draw doesn't change the content and fits it to the given size rather than to the one of
+    // handleResize.
+    virtual void handleAdvance(double elapsed) {}
+    virtual void draw(rive::Renderer* renderer, int width, int height) const {}
+    // handleAdvance split in two at the point the scene has applied its animations but the
+    // artboard's components, e.g. its constraints and world transforms, aren't updated yet.
+    // handleAnimate returns false when it left the content as it was, in which case there's
+    // nothing for handleUpdate to do.
+    virtual bool handleAnimate(double elapsed) { return false; }
+    virtual void handleUpdate(double elapsed) {}
 #ifndef RIVE_SKIP_IMGUI
     virtual void handleImgui() = 0;
 #endif
@@ -36,6 +137,188 @@ public:
     virtual void handlePointerDown(float x, float y) {}
     virtual void handlePointerUp(float x, float y) {}
 
+    // Maps a point of the view to artboard units, the space pointers reach listeners in.
+    virtual bool viewToArtboard(float x, float y, float* artboardX, float* artboardY) const
+    {
+        return false;
+    }
+    virtual float artboardWidth() const { return 0.0f; }
+    // The artboard's bounds as minX, minY, maxX, maxY, which the view transform fits to the view.
+    virtual bool artboardBounds(float bounds[4]) const { return false; }
+    // Artboards of the file, in file order. Selecting one restarts it with its default state
+    // machine or first animation, and returns false if the index is out of range.
+    virtual size_t artboardCount() const { return 0; }
+    virtual const char* artboardName(size_t index) const { return nullptr; }
+    virtual bool selectArtboard(size_t index) { return false; }
+    virtual size_t artboardIndex() const { return 0; }
+    // State machines of the artboard at artboardIndex, whether or not it's the current one.
+    virtual size_t artboardStateMachineCount(size_t artboardIndex) const { return 0; }
+    virtual const char* artboardStateMachineName(size_t artboardIndex, size_t index) const
+    {
+        return nullptr;
+    }
+    // Animations of the artboard at artboardIndex, whether or not it's the current one.
+    virtual size_t artboardAnimationCount(size_t artboardIndex) const { return 0; }
+    virtual const char* artboardAnimationName(size_t artboardIndex, size_t index) const
+    {
+        return nullptr;
+    }
+    // Scenes of the current artboard: its state machines, then its animations. Selecting one
+    // restarts the artboard with it, and returns false if the index is out of range.
+    virtual size_t sceneCount() const { return 0; }
+    virtual const char* sceneName(size_t index) const { return nullptr; }
+    virtual bool selectScene(size_t index) { return false; }
+    // Index of the scene playing, or sceneCount() if there's none.
+    virtual size_t sceneIndex() const { return sceneCount(); }
+    // How the artboard is fitted to the view, as a rive::Fit. It's always centered.
+    virtual void setFit(int fit) {}
+    // Rounds the artboard's position in the view to whole pixels, halves up, so that edges
+    // which start on a pixel stay crisp however the view is resized.
+    virtual void setPixelSnap(bool snap) {}
+    // Whether a point of the view lands on the shape called name, hit tested the way
+    // listeners are.
+    virtual bool hitsShape(const char name[], float x, float y) const { return false; }
+    // Whether a point of the view lands on a listener of the given rive::ListenerType whose
+    // conditions are met, e.g. a move listener which should keep receiving the pointer
+    // until it's released.
+    virtual bool hitsListener(float x, float y, int type) const { return false; }
+
+    // How the content needs to be redrawn as of its last advance: 0 when it has no scene and
+    // never changes, 1 when it's at rest and only changes in response to inputs or the
+    // pointer, and 2 while it's animating or hasn't been advanced yet.
+    virtual int redrawPolicy() const { return 2; }
+
+    // Only content driven by a single linear animation has a playback state.
+    virtual bool playbackState(PlaybackState* state) const { return false; }
+    // Moves the active linear animation to the given time, keeping its direction.
+    virtual void seek(float seconds) {}
+    // Loops the active linear animation between two times, whatever its loop mode, until
+    // cleared. The range is reported as the playback state's start and end. Returns false if
+    // it had to be clamped, e.g. to at least a frame.
+    virtual bool setPlayRange(float start, float end) { return false; }
+    virtual void clearPlayRange() {}
+    // Advances the scene by seconds in a single step, as if it had been playing since. Events
+    // fired along the way are dropped by the next advance without being reported.
+    virtual void offsetTime(float seconds) {}
+    // The active linear animation, owned by the content's file. Null for content driven by a
+    // state machine.
+    virtual const rive::LinearAnimation* linearAnimation() const { return nullptr; }
+    // Only content driven by a state machine applies animations this way.
+    virtual size_t appliedAnimationCount() const { return 0; }
+    virtual bool appliedAnimation(size_t index, AppliedAnimationInfo* info) const { return false; }
+
+    // Sets a number input of the active state machine. Returns false if there is none by that
+    // name.
+    virtual bool setNumber(const char name[], float value) { return false; }
+    virtual bool number(const char name[], float* value) const { return false; }
+    virtual bool setBool(const char name[], bool value) { return false; }
+    virtual size_t inputCount() const { return 0; }
+    virtual bool input(size_t index, InputInfo* info) const { return false; }
+    // Set the input at index, as listed by input, sparing hosts a lookup by name. They fail if
+    // the input is of another type.
+    virtual bool setNumberAt(size_t index, float value) { return false; }
+    virtual bool setBoolAt(size_t index, bool value) { return false; }
+    virtual bool fireTriggerAt(size_t index) { return false; }
+
+    // Name of the active state machine, or null when a linear animation is playing.
+    virtual const char* stateMachineName() const { return nullptr; }
+    virtual size_t layerCount() const { return 0; }
+    virtual bool layerState(size_t layer, LayerStateInfo* info) const { return false; }
+    // Puts a layer straight into one of its states, e.g. to restore a saved one. Returns false
+    // if the layer or state is out of range.
+    virtual bool restoreLayerState(size_t layer, size_t state, float time) { return false; }
+    // Values a number input is compared against for equality or blended at, sorted. Empty for
+    // inputs which are used continuously.
+    virtual rive::Span<const float> discreteValues(const char name[]) const { return {}; }
+
+    // Events the active state machine fired on its last advance, in the order they fired. Those
+    // fired by listeners are dropped by the next advance before they can be reported.
+    virtual size_t firedEventCount() const { return 0; }
+    virtual const char* firedEventName(size_t index) const { return nullptr; }
+    // Custom properties of a fired event, in file order.
+    virtual size_t firedEventPropertyCount(size_t index) const { return 0; }
+    virtual bool firedEventProperty(size_t index, size_t property, EventPropertyInfo* info) const
+    {
+        return false;
+    }
+
+    // Replaces the color of every solid fill or stroke named "currentColor", including those of
+    // nested artboards, until cleared. Returns how many there are. Animations keying those
+    // colors override it while they play.
+    virtual size_t setPrimaryColor(uint32_t color) { return 0; }
+    // Puts back the colors the paints were authored with.
+    virtual void clearPrimaryColor() {}
+
+    // Watches the properties the artboard's animations key on the components called name and
+    // their descendants, or on every component when name is null. Watches are kept across
+    // artboard and scene changes. Returns how many properties are watched in total.
+    virtual size_t watchComponent(const char name[]) { return 0; }
+    virtual void clearWatches() {}
+    virtual size_t watchedPropertyCount() const { return 0; }
+    virtual bool watchedProperty(size_t index, WatchedPropertyInfo* info) const { return false; }
+    // Writes the bits of each watched property's current value to values: floats as their
+    // bits, booleans as 0 or 1 and colors as ARGB.
+    virtual void readWatched(uint32_t values[]) const {}
+    // Shows the watched properties with values read earlier, for drawing only. The state
+    // machine doesn't see them, and the values it applied come back with showLive or the next
+    // advance by more than no time.
+    virtual void showWatched(const uint32_t values[]) {}
+    virtual void showLive() {}
+
+    // Named text runs of the artboard, including those of nested artboards which are addressed
+    // by path, e.g. "nested/run".
+    virtual size_t textRunCount() const { return 0; }
+    virtual const char* textRunPath(size_t index) const { return nullptr; }
+    virtual const char* textRunText(size_t index) const { return nullptr; }
+    // Only marks the owning text dirty; it is shaped once on the next advance.
+    virtual void setTextRun(size_t index, const char text[]) {}
+    // Sets the base direction of the artboard's texts, 0 being auto, 1 ltr and 2 rtl, like
+    // rive::TextBaseDirection. Returns how many texts were set.
+    virtual size_t setTextDirection(uint8_t direction) { return 0; }
+
+    // Names of the artboard's events, which its state machines can fire.
+    virtual size_t eventCount() const { return 0; }
+    virtual const char* eventName(size_t index) const { return nullptr; }
+    // Paths of the artboard's nested artboards, built like the ones of text runs.
+    virtual size_t nestedArtboardCount() const { return 0; }
+    virtual const char* nestedArtboardPath(size_t index) const { return nullptr; }
+
+    // The node or bone of the artboard called name. Setting its transform ignores world, and
+    // x and y for bones other than root bones, which follow their parent's tip. The world
+    // transform follows with the next update.
+    virtual bool nodeTransform(const char name[], NodeTransformInfo* info) const { return false; }
+    virtual bool setNodeTransform(const char name[], const NodeTransformInfo* info)
+    {
+        return false;
+    }
+
+    // Draws the node or shape called name and what it contains, alone, fitted into a width x
+    // height target less padding on every side. Its own transform applies but its ancestors'
+    // don't; their opacity and clips only apply with ancestorEffects. Returns false, drawing
+    // nothing, if the artboard has no such component.
+    virtual bool drawComponent(rive::Renderer* renderer,
+                               const char name[],
+                               int width,
+                               int height,
+                               float padding,
+                               bool ancestorEffects)
+    {
+        return false;
+    }
+    // The bounds drawComponent fits: in the component's parent space with its ancestors'
+    // transforms undone, rounded out to whole units.
+    virtual bool componentBounds(const char name[], rive::AABB* bounds) const { return false; }
+
+    // Assets of the file still missing, in file order.
+    virtual size_t missingAssetCount() const { return 0; }
+    virtual bool missingAsset(size_t index, MissingAssetInfo* info) const { return false; }
+    // Decodes bytes into the missing asset assetId, in place of its fallback. Returns false,
+    // keeping the fallback, if no missing asset has that id or the bytes can't be decoded.
+    virtual bool rebindAsset(uint32_t assetId, const uint8_t* bytes, size_t len)
+    {
+        return false;
+    }
+
     using Factory = std::unique_ptr<ViewerContent> (*)(const char filename[]);
 
     // Searches all handlers and returns a content if it is found.
@@ -64,12 +347,24 @@ public:
     static std::unique_ptr<ViewerContent> TextPath(const char[]);
     static std::unique_ptr<ViewerContent> TrimPath(const char[]);
 
+    // Opens a .riv file which is already in memory. name only labels the content.
+    static std::unique_ptr<ViewerContent> SceneFromBytes(const char name[],
+                                                         rive::Span<const uint8_t> bytes);
+
     static std::vector<uint8_t> LoadFile(const char path[]);
     static void DumpCounters(const char label[]);
 
     // Abstracts which rive Factory is currently used.
     static rive::Factory* RiveFactory();
 
+    // Lets renderers which support it pick between the @1x/@2x/... variants of a file's
+    // images by on-screen density.
+    static void LinkImageVariants(const rive::File*);
+
+    // An image of width x height pixels which renderers draw as their missing asset
+    // placeholder.
+    static std::unique_ptr<rive::RenderImage> MissingImage(uint32_t width, uint32_t height);
+
     // Abstracts which font backend is currently used.
     static rive::rcp<rive::Font> DecodeFont(rive::Span<const uint8_t>);
 };
@@ -8,18 +8,123 @@
 #include "rive/animation/state_machine_number.hpp"
 #include "rive/animation/state_machine_bool.hpp"
 #include "rive/animation/state_machine_trigger.hpp"
+#include "rive/animation/state_machine_layer.hpp"
+#include "rive/animation/state_machine_listener.hpp"
+#include "rive/animation/layer_state.hpp"
+#include "rive/animation/state_transition.hpp"
+#include "rive/animation/transition_number_condition.hpp"
+#include "rive/animation/blend_state_1d.hpp"
+#include "rive/animation/blend_animation_1d.hpp"
+#include "rive/animation/keyed_object.hpp"
+#include "rive/animation/keyed_property.hpp"
+#include "rive/animation/keyframe_bool.hpp"
+#include "rive/animation/keyframe_color.hpp"
+#include "rive/animation/keyframe_double.hpp"
+#include "rive/animation/keyframe_id.hpp"
 #include "rive/artboard.hpp"
+#include "rive/assets/font_asset.hpp"
+#include "rive/bones/root_bone.hpp"
+#include "rive/command_path.hpp"
+#include "rive/custom_property_boolean.hpp"
+#include "rive/custom_property_number.hpp"
+#include "rive/custom_property_string.hpp"
+#include "rive/event.hpp"
 #include "rive/file.hpp"
+#include "rive/generated/core_registry.hpp"
 #include "rive/layout.hpp"
 #include "rive/math/aabb.hpp"
+#include "rive/nested_artboard.hpp"
+#include "rive/node.hpp"
+#include "rive/shapes/clipping_shape.hpp"
+#include "rive/shapes/image.hpp"
+#include "rive/shapes/path.hpp"
+#include "rive/shapes/shape.hpp"
+#include "rive/shapes/paint/shape_paint.hpp"
+#include "rive/shapes/paint/solid_color.hpp"
+#include "rive/text/text.hpp"
+#include "rive/text/text_style.hpp"
+#include "rive/text/text_value_run.hpp"
+#include "rive/text/utf.hpp"
 #include "rive/assets/image_asset.hpp"
 #include "viewer/viewer_content.hpp"
+#include <algorithm>
+#include <cmath>
+#include <cstring>
+#include <set>
+#include <unordered_map>
+#include <unordered_set>
 #ifdef RIVE_RENDERER_TESS
 #include "viewer/sample_tools/sample_atlas_packer.hpp"
 #endif
 
 constexpr int REQUEST_DEFAULT_SCENE = -1;
 
+// Bounds of the drawables measured into it, in world space. Shapes are measured by the control
+// points of their paths, which contain the curves, but not by the width of their strokes.
+class BoundsCommandPath : public rive::CommandPath
+{
+    rive::AABB m_Bounds = rive::AABB::forExpansion();
+    rive::Mat2D m_Xform;
+    bool m_Measured = false;
+
+    void expand(float x, float y)
+    {
+        rive::AABB::expandTo(m_Bounds, m_Xform * rive::Vec2D(x, y));
+        m_Measured = true;
+    }
+
+public:
+    // Nested artboards and meshes aren't measured.
+    void measure(rive::Drawable* drawable)
+    {
+        if (drawable->is<rive::Shape>())
+        {
+            for (auto path : drawable->as<rive::Shape>()->paths())
+            {
+                m_Xform = path->pathTransform();
+                path->buildPath(*this);
+            }
+        }
+        else if (drawable->is<rive::Image>())
+        {
+            auto image = drawable->as<rive::Image>();
+            auto asset = image->imageAsset();
+            auto renderImage = asset ? asset->renderImage() : nullptr;
+            if (renderImage != nullptr && image->mesh() == nullptr)
+            {
+                float width = renderImage->width(), height = renderImage->height();
+                m_Xform = image->worldTransform();
+                addRect(-width * image->originX(), -height * image->originY(), width, height);
+            }
+        }
+        else if (drawable->is<rive::Text>())
+        {
+            auto text = drawable->as<rive::Text>();
+            auto bounds = text->localBounds();
+            m_Xform = text->worldTransform();
+            addRect(bounds.minX, bounds.minY, bounds.width(), bounds.height());
+        }
+    }
+
+    bool measured() const { return m_Measured; }
+    const rive::AABB& bounds() const { return m_Bounds; }
+
+    void rewind() override {}
+    void fillRule(rive::FillRule) override {}
+    void addPath(rive::CommandPath*, const rive::Mat2D&) override {}
+    rive::RenderPath* renderPath() override { return nullptr; }
+
+    void moveTo(float x, float y) override { expand(x, y); }
+    void lineTo(float x, float y) override { expand(x, y); }
+    void cubicTo(float ox, float oy, float ix, float iy, float x, float y) override
+    {
+        expand(ox, oy);
+        expand(ix, iy);
+        expand(x, y);
+    }
+    void close() override {}
+};
+
 class SceneContent : public ViewerContent
 {
     // ImGui wants raw pointers to names, but our public API returns
@@ -29,152 +134,1648 @@ class SceneContent : public ViewerContent
     std::vector<std::string> animationNames;
     std::vector<std::string> stateMachineNames;
 
-    void loadArtboardNames()
+    void loadArtboardNames()
+    {
+        if (m_File)
+        {
+            artboardNames.clear();
+            auto abCnt = m_File->artboardCount();
+
+            for (int i = 0; i < abCnt; i++)
+            {
+                auto abName = m_File->artboardNameAt(i);
+                artboardNames.push_back(abName);
+            }
+        }
+    }
+
+    void loadNames(const rive::Artboard* ab)
+    {
+        animationNames.clear();
+        stateMachineNames.clear();
+        if (ab)
+        {
+            for (size_t i = 0; i < ab->animationCount(); ++i)
+            {
+                animationNames.push_back(ab->animationNameAt(i));
+            }
+            for (size_t i = 0; i < ab->stateMachineCount(); ++i)
+            {
+                stateMachineNames.push_back(ab->stateMachineNameAt(i));
+            }
+        }
+    }
+
+    std::string m_Filename;
+    std::unique_ptr<rive::File> m_File;
+
+    std::unique_ptr<rive::ArtboardInstance> m_ArtboardInstance;
+    std::unique_ptr<rive::Scene> m_CurrentScene;
+    int m_ArtboardIndex = 0;
+    int m_AnimationIndex = 0;
+    int m_StateMachineIndex = -1;
+    rive::Fit m_Fit = rive::Fit::contain;
+    bool m_PixelSnap = false;
+    // Whether the current scene was advanced since it was created. Until then, there is no
+    // telling whether it comes to rest.
+    bool m_Advanced = false;
+
+    int m_width = 0, m_height = 0;
+    rive::Mat2D m_InverseViewTransform;
+
+    struct TextRun
+    {
+        std::string path;
+        rive::TextValueRun* run;
+    };
+    std::vector<TextRun> m_TextRuns;
+    std::vector<std::string> m_EventNames;
+    std::vector<std::string> m_NestedArtboardPaths;
+
+    // Solid colors of the paints named "currentColor", with the color they were authored with.
+    struct PrimaryColorSlot
+    {
+        rive::SolidColor* color;
+        uint32_t authored;
+    };
+    std::vector<PrimaryColorSlot> m_PrimaryColorSlots;
+    // Kept across artboard and scene changes, so the whole file is drawn in it.
+    bool m_HasPrimaryColor = false;
+    uint32_t m_PrimaryColor = 0;
+
+    // Images and fonts the file refers to which weren't loaded, with what stands in for them.
+    struct MissingAsset
+    {
+        rive::FileAsset* asset;
+        int fallback;
+    };
+    std::vector<MissingAsset> m_MissingAssets;
+    // Texts of the artboard with runs left without a font, boxed when drawn. Texts of nested
+    // artboards aren't boxed.
+    std::vector<rive::Text*> m_BoxedTexts;
+
+    // Animated properties of the watched components, in the order their values are read.
+    struct WatchedProperty
+    {
+        rive::Core* object;
+        // Name of the object, or of its closest named ancestor for unnamed ones like vertices.
+        const char* component;
+        uint16_t key;
+        int type;
+    };
+    std::vector<WatchedProperty> m_Watched;
+    std::vector<std::string> m_WatchedNames;
+    bool m_WatchAll = false;
+    // Values the scene applied, put back once the watched values stop being shown.
+    std::vector<uint32_t> m_LiveValues;
+    bool m_ShowingWatched = false;
+
+    // Values each number input of the active state machine is compared against for equality
+    // or blended at. Inputs also used in ordered comparisons are continuous and left out.
+    std::unordered_map<std::string, std::vector<float>> m_DiscreteValues;
+
+    void loadDiscreteValues()
+    {
+        m_DiscreteValues.clear();
+        if (m_AnimationIndex >= 0 || !m_CurrentScene)
+        {
+            return;
+        }
+
+        auto machine =
+            static_cast<rive::StateMachineInstance*>(m_CurrentScene.get())->stateMachine();
+        std::vector<std::vector<float>> values(machine->inputCount());
+        std::vector<bool> continuous(machine->inputCount(), false);
+
+        auto addCondition = [&](const rive::TransitionCondition* condition) {
+            if (condition == nullptr || !condition->is<rive::TransitionNumberCondition>() ||
+                condition->inputId() >= values.size())
+            {
+                return;
+            }
+            auto number = condition->as<rive::TransitionNumberCondition>();
+            switch (number->op())
+            {
+                case rive::TransitionConditionOp::equal:
+                case rive::TransitionConditionOp::notEqual:
+                    values[number->inputId()].push_back(number->value());
+                    break;
+                default:
+                    continuous[number->inputId()] = true;
+                    break;
+            }
+        };
+
+        for (size_t i = 0; i < machine->layerCount(); i++)
+        {
+            auto layer = machine->layer(i);
+            for (size_t j = 0; j < layer->stateCount(); j++)
+            {
+                auto state = layer->state(j);
+                for (size_t k = 0; k < state->transitionCount(); k++)
+                {
+                    auto transition = state->transition(k);
+                    for (size_t l = 0; l < transition->conditionCount(); l++)
+                    {
+                        addCondition(transition->condition(l));
+                    }
+                }
+
+                if (state->is<rive::BlendState1D>())
+                {
+                    auto blendState = state->as<rive::BlendState1D>();
+                    if (blendState->inputId() < values.size())
+                    {
+                        for (auto animation : blendState->animations())
+                        {
+                            values[blendState->inputId()].push_back(
+                                animation->as<rive::BlendAnimation1D>()->value());
+                        }
+                    }
+                }
+            }
+        }
+        for (size_t i = 0; i < machine->listenerCount(); i++)
+        {
+            auto listener = machine->listener(i);
+            for (size_t j = 0; j < listener->conditionCount(); j++)
+            {
+                addCondition(listener->condition(j));
+            }
+        }
+
+        for (size_t i = 0; i < values.size(); i++)
+        {
+            auto input = machine->input(i);
+            if (continuous[i] || values[i].empty() || !input->is<rive::StateMachineNumber>())
+            {
+                continue;
+            }
+            auto& inputValues = values[i];
+            std::sort(inputValues.begin(), inputValues.end());
+            inputValues.erase(std::unique(inputValues.begin(), inputValues.end()),
+                              inputValues.end());
+            m_DiscreteValues[input->name()] = std::move(inputValues);
+        }
+    }
+
+    void loadTextRuns(rive::ArtboardInstance* artboard, const std::string& prefix)
+    {
+        for (auto object : artboard->objects())
+        {
+            if (object == nullptr)
+            {
+                continue;
+            }
+            if (object->is<rive::TextValueRun>())
+            {
+                auto run = object->as<rive::TextValueRun>();
+                if (!run->name().empty())
+                {
+                    m_TextRuns.push_back({prefix + run->name(), run});
+                }
+            }
+            else if (object->is<rive::NestedArtboard>())
+            {
+                auto nested = object->as<rive::NestedArtboard>();
+                if (auto instance = nested->artboard())
+                {
+                    loadTextRuns(instance, prefix + nested->name() + "/");
+                }
+            }
+        }
+    }
+
+    void loadEventNames(rive::ArtboardInstance* artboard)
+    {
+        for (auto object : artboard->objects())
+        {
+            if (object != nullptr && object->is<rive::Event>() &&
+                !object->as<rive::Event>()->name().empty())
+            {
+                m_EventNames.push_back(object->as<rive::Event>()->name());
+            }
+        }
+    }
+
+    void loadNestedArtboardPaths(rive::ArtboardInstance* artboard, const std::string& prefix)
+    {
+        for (auto nested : artboard->nestedArtboards())
+        {
+            if (!nested->name().empty())
+            {
+                m_NestedArtboardPaths.push_back(prefix + nested->name());
+            }
+            if (auto instance = nested->artboard())
+            {
+                loadNestedArtboardPaths(instance, prefix + nested->name() + "/");
+            }
+        }
+    }
+
+    void loadPrimaryColorSlots(rive::ArtboardInstance* artboard)
+    {
+        for (auto object : artboard->objects())
+        {
+            if (object == nullptr)
+            {
+                continue;
+            }
+            if (object->is<rive::ShapePaint>())
+            {
+                auto paint = object->as<rive::ShapePaint>();
+                if (paint->name() != "currentColor")
+                {
+                    continue;
+                }
+                // Gradients have no single color to replace.
+                for (auto child : paint->children())
+                {
+                    if (child->is<rive::SolidColor>())
+                    {
+                        auto color = child->as<rive::SolidColor>();
+                        auto authored = static_cast<uint32_t>(color->colorValue());
+                        m_PrimaryColorSlots.push_back({color, authored});
+                    }
+                }
+            }
+            else if (object->is<rive::NestedArtboard>())
+            {
+                if (auto instance = object->as<rive::NestedArtboard>()->artboard())
+                {
+                    loadPrimaryColorSlots(instance);
+                }
+            }
+        }
+    }
+
+    static void markTextsDirty(rive::ArtboardInstance* artboard)
+    {
+        for (auto object : artboard->objects())
+        {
+            if (object == nullptr)
+            {
+                continue;
+            }
+            if (object->is<rive::Text>())
+            {
+                object->as<rive::Text>()->markShapeDirty();
+            }
+            else if (object->is<rive::NestedArtboard>())
+            {
+                if (auto instance = object->as<rive::NestedArtboard>()->artboard())
+                {
+                    markTextsDirty(instance);
+                }
+            }
+        }
+    }
+
+    // Characters of the runs left without a font, for the fallback font to cover.
+    std::vector<rive::Unichar> fontlessText() const
+    {
+        std::vector<rive::Unichar> unichars;
+        for (auto object : m_ArtboardInstance->objects())
+        {
+            if (object == nullptr || !object->is<rive::TextValueRun>())
+            {
+                continue;
+            }
+            auto run = object->as<rive::TextValueRun>();
+            if (run->style() == nullptr || run->style()->font() != nullptr)
+            {
+                continue;
+            }
+            auto ptr = reinterpret_cast<const uint8_t*>(run->text().c_str());
+            while (*ptr)
+            {
+                unichars.push_back(rive::UTF::NextUTF8(&ptr));
+            }
+        }
+        return unichars;
+    }
+
+    // Puts the fallback of a missing asset in its place: a placeholder of the authored size for
+    // images, and for fonts the registered fallback font, if it has one for their text.
+    void applyFallback(MissingAsset& missing)
+    {
+        auto asset = missing.asset;
+        if (asset->is<rive::ImageAsset>())
+        {
+            auto image = asset->as<rive::ImageAsset>();
+            image->renderImage(MissingImage(std::max(1u, static_cast<uint32_t>(image->width())),
+                                            std::max(1u, static_cast<uint32_t>(image->height()))));
+            missing.fallback = 1;
+        }
+        else if (asset->is<rive::FontAsset>())
+        {
+            missing.fallback = 3;
+            if (rive::Font::gFallbackProc != nullptr && m_ArtboardInstance)
+            {
+                auto unichars = fontlessText();
+                if (auto font = rive::Font::gFallbackProc(unichars))
+                {
+                    asset->as<rive::FontAsset>()->font(font);
+                    missing.fallback = 2;
+                }
+            }
+        }
+    }
+
+    void loadMissingAssets()
+    {
+        m_MissingAssets.clear();
+        for (auto asset : m_File->assets())
+        {
+            bool missing =
+                (asset->is<rive::ImageAsset>() &&
+                 asset->as<rive::ImageAsset>()->renderImage() == nullptr) ||
+                (asset->is<rive::FontAsset>() && asset->as<rive::FontAsset>()->font() == nullptr);
+            if (missing)
+            {
+                // The file owns its assets, and so do we.
+                m_MissingAssets.push_back({const_cast<rive::FileAsset*>(asset), 0});
+            }
+        }
+        for (auto& missing : m_MissingAssets)
+        {
+            if (missing.asset->is<rive::ImageAsset>())
+            {
+                applyFallback(missing);
+            }
+        }
+    }
+
+    void applyFontFallbacks()
+    {
+        for (auto& missing : m_MissingAssets)
+        {
+            if (missing.asset->is<rive::FontAsset>() && missing.fallback != 2)
+            {
+                applyFallback(missing);
+            }
+        }
+        markTextsDirty(m_ArtboardInstance.get());
+    }
+
+    void loadBoxedTexts()
+    {
+        m_BoxedTexts.clear();
+        for (auto object : m_ArtboardInstance->objects())
+        {
+            if (object == nullptr || !object->is<rive::TextValueRun>())
+            {
+                continue;
+            }
+            auto run = object->as<rive::TextValueRun>();
+            auto text = run->parent() == nullptr ? nullptr : run->parent()->as<rive::Text>();
+            if (text == nullptr || run->style() == nullptr || run->style()->font() != nullptr ||
+                std::find(m_BoxedTexts.begin(), m_BoxedTexts.end(), text) != m_BoxedTexts.end())
+            {
+                continue;
+            }
+            m_BoxedTexts.push_back(text);
+        }
+    }
+
+    // Boxes the texts with runs left without a font, at roughly the size their runs would have
+    // been shaped to on a single line.
+    void drawTextBoxes(rive::Renderer* renderer) const
+    {
+        if (m_BoxedTexts.empty())
+        {
+            return;
+        }
+        auto paint = RiveFactory()->makeRenderPaint();
+        paint->style(rive::RenderPaintStyle::stroke);
+        paint->thickness(1.0f);
+        paint->color(0xFFFF00FF);
+        for (auto text : m_BoxedTexts)
+        {
+            float width = 0.0f;
+            float height = 0.0f;
+            for (auto run : text->runs())
+            {
+                auto style = run->style();
+                if (style == nullptr)
+                {
+                    continue;
+                }
+                size_t count = 0;
+                for (auto c : run->text())
+                {
+                    count += (c & 0xC0) != 0x80;
+                }
+                width += style->fontSize() * 0.5f * count;
+                float lineHeight =
+                    style->lineHeight() < 0.0f ? style->fontSize() * 1.2f : style->lineHeight();
+                height = std::max(height, lineHeight);
+            }
+            rive::RawPath rawPath;
+            rawPath.addRect(rive::AABB::fromLTWH(-width * text->originX(),
+                                                 -height * text->originY(),
+                                                 width,
+                                                 height));
+            auto path = RiveFactory()->makeRenderPath(rawPath, rive::FillRule::nonZero);
+            renderer->save();
+            renderer->transform(text->worldTransform());
+            renderer->drawPath(path.get(), paint.get());
+            renderer->restore();
+        }
+    }
+
+    // Type of a keyed property's values, told by its key frames since booleans and unsigned
+    // integers share a field type. -1 for those which can't be watched, like strings.
+    static int watchedType(const rive::KeyedProperty* property)
+    {
+        if (property->keyFrameCount() == 0)
+        {
+            return -1;
+        }
+        auto keyFrame = property->keyFrame(0);
+        if (keyFrame->is<rive::KeyFrameDouble>())
+        {
+            return 0;
+        }
+        if (keyFrame->is<rive::KeyFrameBool>())
+        {
+            return 1;
+        }
+        if (keyFrame->is<rive::KeyFrameColor>())
+        {
+            return 2;
+        }
+        if (keyFrame->is<rive::KeyFrameId>())
+        {
+            return 3;
+        }
+        return -1;
+    }
+
+    bool isWatched(rive::Core* object) const
+    {
+        if (m_WatchAll)
+        {
+            return true;
+        }
+        if (!object->is<rive::Component>())
+        {
+            return false;
+        }
+        for (const rive::Component* component = object->as<rive::Component>();
+             component != nullptr;
+             component = component->parent())
+        {
+            if (std::find(m_WatchedNames.begin(), m_WatchedNames.end(), component->name()) !=
+                m_WatchedNames.end())
+            {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    static const char* componentName(rive::Core* object)
+    {
+        if (!object->is<rive::Component>())
+        {
+            return "";
+        }
+        const rive::Component* component = object->as<rive::Component>();
+        while (component->name().empty() && component->parent() != nullptr)
+        {
+            component = component->parent();
+        }
+        return component->name().c_str();
+    }
+
+    void loadWatched()
+    {
+        m_Watched.clear();
+        m_ShowingWatched = false;
+        if (!m_ArtboardInstance || (!m_WatchAll && m_WatchedNames.empty()))
+        {
+            return;
+        }
+
+        // Properties keyed by several animations are only watched once.
+        std::set<std::pair<rive::Core*, uint16_t>> seen;
+        auto artboard = m_ArtboardInstance.get();
+        for (size_t i = 0; i < artboard->animationCount(); i++)
+        {
+            auto animation = artboard->animation(i);
+            for (size_t j = 0; j < animation->keyedObjectCount(); j++)
+            {
+                auto keyedObject = animation->keyedObject(j);
+                auto object = artboard->resolve(keyedObject->objectId());
+                if (object == nullptr || !isWatched(object))
+                {
+                    continue;
+                }
+                for (size_t k = 0; k < keyedObject->keyedPropertyCount(); k++)
+                {
+                    auto property = keyedObject->keyedProperty(k);
+                    uint16_t key = property->propertyKey();
+                    int type = watchedType(property);
+                    if (type >= 0 && seen.insert({object, key}).second)
+                    {
+                        m_Watched.push_back({object, componentName(object), key, type});
+                    }
+                }
+            }
+        }
+    }
+
+    static uint32_t readValue(const WatchedProperty& property)
+    {
+        switch (property.type)
+        {
+            case 0:
+            {
+                float value = rive::CoreRegistry::getDouble(property.object, property.key);
+                uint32_t bits;
+                std::memcpy(&bits, &value, sizeof(bits));
+                return bits;
+            }
+            case 1:
+                return rive::CoreRegistry::getBool(property.object, property.key) ? 1 : 0;
+            case 2:
+                return static_cast<uint32_t>(
+                    rive::CoreRegistry::getColor(property.object, property.key));
+            default:
+                return rive::CoreRegistry::getUint(property.object, property.key);
+        }
+    }
+
+    static void writeValue(const WatchedProperty& property, uint32_t bits)
+    {
+        switch (property.type)
+        {
+            case 0:
+            {
+                float value;
+                std::memcpy(&value, &bits, sizeof(value));
+                rive::CoreRegistry::setDouble(property.object, property.key, value);
+                break;
+            }
+            case 1:
+                rive::CoreRegistry::setBool(property.object, property.key, bits != 0);
+                break;
+            case 2:
+                rive::CoreRegistry::setColor(property.object,
+                                             property.key,
+                                             static_cast<int>(bits));
+                break;
+            default:
+                rive::CoreRegistry::setUint(property.object, property.key, bits);
+                break;
+        }
+    }
+
+    void initArtboard(int index)
+    {
+        if (!m_File)
+            return;
+        loadArtboardNames();
+        m_ArtboardInstance = nullptr;
+
+        m_ArtboardIndex = (index == REQUEST_DEFAULT_SCENE) ? 0 : index;
+        m_ArtboardInstance = m_File->artboardAt(m_ArtboardIndex);
+        applyFontFallbacks();
+
+        m_ArtboardInstance->advance(0.0f);
+        loadNames(m_ArtboardInstance.get());
+
+        m_TextRuns.clear();
+        loadTextRuns(m_ArtboardInstance.get(), "");
+        m_EventNames.clear();
+        loadEventNames(m_ArtboardInstance.get());
+        m_NestedArtboardPaths.clear();
+        loadNestedArtboardPaths(m_ArtboardInstance.get(), "");
+        loadBoxedTexts();
+
+        m_PrimaryColorSlots.clear();
+        loadPrimaryColorSlots(m_ArtboardInstance.get());
+        if (m_HasPrimaryColor)
+        {
+            setPrimaryColor(m_PrimaryColor);
+        }
+
+        loadWatched();
+
+        initStateMachine(REQUEST_DEFAULT_SCENE);
+    }
+
+    void initStateMachine(int index)
+    {
+        m_StateMachineIndex = -1;
+        m_AnimationIndex = -1;
+        m_CurrentScene = nullptr;
+        m_Advanced = false;
+
+        m_ArtboardInstance->advance(0.0f);
+
+        if (index < 0)
+        {
+            m_CurrentScene = m_ArtboardInstance->defaultStateMachine();
+            index = m_ArtboardInstance->defaultStateMachineIndex();
+        }
+        if (!m_CurrentScene)
+        {
+            if (index >= m_ArtboardInstance->stateMachineCount())
+            {
+                index = 0;
+            }
+            m_CurrentScene = m_ArtboardInstance->stateMachineAt(index);
+        }
+        if (!m_CurrentScene)
+        {
+            index = -1;
+            m_CurrentScene = m_ArtboardInstance->animationAt(0);
+            m_AnimationIndex = 0;
+        }
+        m_StateMachineIndex = index;
+
+        if (m_CurrentScene)
+        {
+            m_CurrentScene->inputCount();
+        }
+        loadDiscreteValues();
+
+        DumpCounters("After loading file");
+    }
+
+    void initAnimation(int index)
+    {
+        m_StateMachineIndex = -1;
+        m_AnimationIndex = -1;
+        m_CurrentScene = nullptr;
+        m_Advanced = false;
+
+        m_ArtboardInstance->advance(0.0f);
+
+        if (index >= 0 && index < m_ArtboardInstance->animationCount())
+        {
+            m_AnimationIndex = index;
+            m_CurrentScene = m_ArtboardInstance->animationAt(index);
+            m_CurrentScene->inputCount();
+        }
+        loadDiscreteValues();
+
+        DumpCounters("After loading file");
+    }
+
+public:
+    SceneContent(const char filename[], std::unique_ptr<rive::File> file) :
+        m_Filename(filename), m_File(std::move(file))
+    {
+        loadMissingAssets();
+        LinkImageVariants(m_File.get());
+        initArtboard(REQUEST_DEFAULT_SCENE);
+    }
+
+    void handlePointerMove(float x, float y) override
+    {
+        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
+        if (m_CurrentScene)
+        {
+            m_CurrentScene->pointerMove(pointer);
+        }
+    }
+
+    void handlePointerDown(float x, float y) override
+    {
+        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
+        if (m_CurrentScene)
+        {
+            m_CurrentScene->pointerDown(pointer);
+        }
+    }
+
+    void handlePointerUp(float x, float y) override
+    {
+        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
+        if (m_CurrentScene)
+        {
+            m_CurrentScene->pointerUp(pointer);
+        }
+    }
+
+    bool viewToArtboard(float x, float y, float* artboardX, float* artboardY) const override
+    {
+        if (!m_ArtboardInstance)
+        {
+            return false;
+        }
+
+        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
+        *artboardX = pointer.x;
+        *artboardY = pointer.y;
+        return true;
+    }
+
+    float artboardWidth() const override
+    {
+        return m_ArtboardInstance ? m_ArtboardInstance->width() : 0.0f;
+    }
+
+    bool artboardBounds(float bounds[4]) const override
+    {
+        if (!m_ArtboardInstance)
+        {
+            return false;
+        }
+
+        auto aabb = m_ArtboardInstance->bounds();
+        bounds[0] = aabb.minX;
+        bounds[1] = aabb.minY;
+        bounds[2] = aabb.maxX;
+        bounds[3] = aabb.maxY;
+        return true;
+    }
+
+    bool nodeTransform(const char name[], NodeTransformInfo* info) const override
+    {
+        auto component =
+            m_ArtboardInstance ? m_ArtboardInstance->find<rive::TransformComponent>(name) : nullptr;
+        if (component == nullptr)
+        {
+            return false;
+        }
+
+        info->x = component->x();
+        info->y = component->y();
+        info->rotation = component->rotation();
+        info->scaleX = component->scaleX();
+        info->scaleY = component->scaleY();
+        const rive::Mat2D& world = component->worldTransform();
+        for (int i = 0; i < 6; i++)
+        {
+            info->world[i] = world[i];
+        }
+        return true;
+    }
+
+    bool setNodeTransform(const char name[], const NodeTransformInfo* info) override
+    {
+        auto component =
+            m_ArtboardInstance ? m_ArtboardInstance->find<rive::TransformComponent>(name) : nullptr;
+        if (component == nullptr)
+        {
+            return false;
+        }
+
+        if (component->is<rive::Node>())
+        {
+            component->as<rive::Node>()->x(info->x);
+            component->as<rive::Node>()->y(info->y);
+        }
+        else if (component->is<rive::RootBone>())
+        {
+            component->as<rive::RootBone>()->x(info->x);
+            component->as<rive::RootBone>()->y(info->y);
+        }
+        component->rotation(info->rotation);
+        component->scaleX(info->scaleX);
+        component->scaleY(info->scaleY);
+        return true;
+    }
+
+    size_t missingAssetCount() const override { return m_MissingAssets.size(); }
+
+    bool missingAsset(size_t index, MissingAssetInfo* info) const override
+    {
+        if (index >= m_MissingAssets.size())
+        {
+            return false;
+        }
+        auto& missing = m_MissingAssets[index];
+        info->assetId = missing.asset->assetId();
+        info->name = missing.asset->name().c_str();
+        info->assetType = missing.asset->is<rive::ImageAsset>() ? 0 : 1;
+        info->fallback = missing.fallback;
+        return true;
+    }
+
+    bool rebindAsset(uint32_t assetId, const uint8_t* bytes, size_t len) override
+    {
+        auto missing = std::find_if(m_MissingAssets.begin(),
+                                    m_MissingAssets.end(),
+                                    [assetId](const MissingAsset& missing) {
+                                        return missing.asset->assetId() == assetId;
+                                    });
+        if (missing == m_MissingAssets.end())
+        {
+            return false;
+        }
+
+        auto asset = missing->asset;
+        bool isFont = asset->is<rive::FontAsset>();
+        // Decoding drops the fallback whether it succeeds or not.
+        if (!asset->decode(rive::Span<const uint8_t>(bytes, len), RiveFactory()))
+        {
+            applyFallback(*missing);
+            if (isFont)
+            {
+                markTextsDirty(m_ArtboardInstance.get());
+            }
+            return false;
+        }
+
+        m_MissingAssets.erase(missing);
+        if (isFont)
+        {
+            markTextsDirty(m_ArtboardInstance.get());
+            loadBoxedTexts();
+        }
+        else
+        {
+            LinkImageVariants(m_File.get());
+        }
+        return true;
+    }
+
+    bool hitsShape(const char name[], float x, float y) const override
+    {
+        auto shape = m_ArtboardInstance ? m_ArtboardInstance->find<rive::Shape>(name) : nullptr;
+        if (shape == nullptr)
+        {
+            return false;
+        }
+
+        // Same offset and radius as StateMachineInstance::updateListeners.
+        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
+        if (m_ArtboardInstance->frameOrigin())
+        {
+            pointer -= rive::Vec2D(m_ArtboardInstance->originX() * m_ArtboardInstance->width(),
+                                   m_ArtboardInstance->originY() * m_ArtboardInstance->height());
+        }
+        const float hitRadius = 2;
+        auto hitArea = rive::AABB(pointer.x - hitRadius,
+                                  pointer.y - hitRadius,
+                                  pointer.x + hitRadius,
+                                  pointer.y + hitRadius)
+                           .round();
+        return shape->hitTest(hitArea);
+    }
+
+    // Whether component is root or one of its descendants.
+    static bool isWithin(const rive::Component* component, const rive::Component* root)
+    {
+        for (; component != nullptr; component = component->parent())
+        {
+            if (component == root)
+            {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    struct ComponentSubtree
+    {
+        rive::TransformComponent* component;
+        std::unordered_set<uint32_t> drawables;
+        // Clips of the component's ancestors which apply to its drawables.
+        std::vector<rive::ClippingShape*> ancestorClips;
+        // Undoes the ancestors' transforms, leaving the component's own.
+        rive::Mat2D own;
+        // What own maps the drawables to, rounded out to whole units so that at a scale of 1
+        // pixels line up with the artboard's.
+        rive::AABB bounds;
+    };
+
+    bool componentSubtree(const char name[], ComponentSubtree* subtree) const
+    {
+        auto component =
+            m_ArtboardInstance ? m_ArtboardInstance->find<rive::TransformComponent>(name) : nullptr;
+        if (component == nullptr)
+        {
+            return false;
+        }
+        subtree->component = component;
+
+        BoundsCommandPath measure;
+        const auto& objects = m_ArtboardInstance->objects();
+        for (size_t id = 0; id < objects.size(); id++)
+        {
+            auto object = objects[id];
+            if (object == nullptr)
+            {
+                continue;
+            }
+            if (object->is<rive::ClippingShape>())
+            {
+                auto clip = object->as<rive::ClippingShape>();
+                if (clip->isVisible() && clip->parent() != nullptr &&
+                    !isWithin(clip, component) && isWithin(component, clip->parent()))
+                {
+                    subtree->ancestorClips.push_back(clip);
+                }
+            }
+            else if (object->is<rive::Drawable>() &&
+                     isWithin(object->as<rive::Drawable>(), component))
+            {
+                auto drawable = object->as<rive::Drawable>();
+                subtree->drawables.insert((uint32_t)id);
+                if (!drawable->isHidden())
+                {
+                    measure.measure(drawable);
+                }
+            }
+        }
+
+        auto parent = component->parent();
+        if (parent != nullptr && parent->is<rive::WorldTransformComponent>())
+        {
+            subtree->own =
+                parent->as<rive::WorldTransformComponent>()->worldTransform().invertOrIdentity();
+        }
+
+        auto artboard = m_ArtboardInstance.get();
+        auto world = measure.measured()
+                         ? measure.bounds()
+                         : rive::AABB::fromLTWH(-artboard->width() * artboard->originX(),
+                                                -artboard->height() * artboard->originY(),
+                                                artboard->width(),
+                                                artboard->height());
+        rive::Vec2D corners[4] = {{world.minX, world.minY},
+                                  {world.maxX, world.minY},
+                                  {world.maxX, world.maxY},
+                                  {world.minX, world.maxY}};
+        subtree->own.mapPoints(corners, corners, 4);
+        auto bounds = rive::AABB(rive::Span<rive::Vec2D>(corners, 4));
+        subtree->bounds = rive::AABB(std::floor(bounds.minX),
+                                     std::floor(bounds.minY),
+                                     std::max(std::ceil(bounds.maxX), std::floor(bounds.minX) + 1),
+                                     std::max(std::ceil(bounds.maxY), std::floor(bounds.minY) + 1));
+        return true;
+    }
+
+    bool componentBounds(const char name[], rive::AABB* bounds) const override
+    {
+        ComponentSubtree subtree;
+        if (!componentSubtree(name, &subtree))
+        {
+            return false;
+        }
+        *bounds = subtree.bounds;
+        return true;
+    }
+
+    bool drawComponent(rive::Renderer* renderer,
+                       const char name[],
+                       int width,
+                       int height,
+                       float padding,
+                       bool ancestorEffects) override
+    {
+        ComponentSubtree subtree;
+        if (!componentSubtree(name, &subtree))
+        {
+            return false;
+        }
+
+        auto target = rive::AABB(padding, padding, width - padding, height - padding);
+        if (target.width() <= 0 || target.height() <= 0)
+        {
+            return true;
+        }
+
+        std::vector<std::pair<rive::WorldTransformComponent*, float>> opacities;
+        if (!ancestorEffects)
+        {
+            for (auto parent = subtree.component->parent(); parent != nullptr;
+                 parent = parent->parent())
+            {
+                if (!parent->is<rive::WorldTransformComponent>())
+                {
+                    continue;
+                }
+                auto node = parent->as<rive::WorldTransformComponent>();
+                if (node->opacity() != 1.0f)
+                {
+                    opacities.push_back({node, node->opacity()});
+                    node->opacity(1.0f);
+                }
+            }
+            for (auto clip : subtree.ancestorClips)
+            {
+                clip->isVisible(false);
+            }
+            if (!opacities.empty())
+            {
+                m_ArtboardInstance->updateComponents();
+            }
+        }
+
+        renderer->save();
+        renderer->transform(rive::computeAlignment(rive::Fit::contain,
+                                                   rive::Alignment::center,
+                                                   target,
+                                                   subtree.bounds) *
+                            subtree.own);
+        m_ArtboardInstance->drawOnly(renderer, subtree.drawables);
+        renderer->restore();
+
+        if (!ancestorEffects)
+        {
+            for (auto clip : subtree.ancestorClips)
+            {
+                clip->isVisible(true);
+            }
+            for (auto [node, opacity] : opacities)
+            {
+                node->opacity(opacity);
+            }
+            if (!opacities.empty())
+            {
+                m_ArtboardInstance->updateComponents();
+            }
+        }
+        return true;
+    }
+
+    bool hitsListener(float x, float y, int type) const override
+    {
+        auto stateMachine = stateMachineInstance();
+        return stateMachine != nullptr &&
+               stateMachine->hasListenerAt(m_InverseViewTransform * rive::Vec2D(x, y),
+                                           static_cast<rive::ListenerType>(type));
+    }
+
+    bool playbackState(PlaybackState* state) const override
+    {
+        if (m_AnimationIndex < 0 || !m_CurrentScene)
+        {
+            return false;
+        }
+
+        auto animation = static_cast<const rive::LinearAnimationInstance*>(m_CurrentScene.get());
+        state->time = animation->time();
+        state->start = animation->playStartSeconds();
+        state->end = animation->playEndSeconds();
+        state->speed = animation->speed() * animation->direction();
+        state->loop = animation->loopValue();
+        return true;
+    }
+
+    void seek(float seconds) override
+    {
+        if (m_AnimationIndex < 0 || !m_CurrentScene)
+        {
+            return;
+        }
+
+        auto animation = static_cast<rive::LinearAnimationInstance*>(m_CurrentScene.get());
+        // Setting the time resets the direction, which would turn a ping-pong around.
+        auto direction = static_cast<int>(animation->direction());
+        animation->time(seconds);
+        animation->direction(direction);
+    }
+
+    bool setPlayRange(float start, float end) override
+    {
+        if (m_AnimationIndex < 0 || !m_CurrentScene)
+        {
+            return false;
+        }
+
+        return static_cast<rive::LinearAnimationInstance*>(m_CurrentScene.get())
+            ->playRange(start, end);
+    }
+
+    void clearPlayRange() override
+    {
+        if (m_AnimationIndex < 0 || !m_CurrentScene)
+        {
+            return;
+        }
+
+        static_cast<rive::LinearAnimationInstance*>(m_CurrentScene.get())->clearPlayRange();
+    }
+
+    void offsetTime(float seconds) override
+    {
+        if (m_CurrentScene)
+        {
+            m_CurrentScene->advanceAndApply(seconds);
+        }
+    }
+
+    const rive::LinearAnimation* linearAnimation() const override
+    {
+        if (m_AnimationIndex < 0 || !m_CurrentScene)
+        {
+            return nullptr;
+        }
+        return static_cast<const rive::LinearAnimationInstance*>(m_CurrentScene.get())
+            ->animation();
+    }
+
+    bool number(const char name[], float* value) const override
+    {
+        auto number = m_CurrentScene ? m_CurrentScene->getNumber(name) : nullptr;
+        if (number == nullptr)
+        {
+            return false;
+        }
+        *value = number->value();
+        return true;
+    }
+
+    bool setBool(const char name[], bool value) override
+    {
+        auto input = m_CurrentScene ? m_CurrentScene->getBool(name) : nullptr;
+        if (input == nullptr)
+        {
+            return false;
+        }
+        input->value(value);
+        return true;
+    }
+
+    size_t inputCount() const override { return m_CurrentScene ? m_CurrentScene->inputCount() : 0; }
+
+    bool input(size_t index, InputInfo* info) const override
+    {
+        if (index >= inputCount())
+        {
+            return false;
+        }
+
+        auto input = m_CurrentScene->input(index);
+        info->name = input->name().c_str();
+        if (input->input()->is<rive::StateMachineNumber>())
+        {
+            info->type = 0;
+            info->value = static_cast<rive::SMINumber*>(input)->value();
+        }
+        else if (input->input()->is<rive::StateMachineBool>())
+        {
+            info->type = 1;
+            info->value = static_cast<rive::SMIBool*>(input)->value() ? 1.0f : 0.0f;
+        }
+        else
+        {
+            info->type = 2;
+            info->value = 0.0f;
+        }
+        return true;
+    }
+
+    rive::SMIInput* inputAt(size_t index) const
+    {
+        return index < inputCount() ? m_CurrentScene->input(index) : nullptr;
+    }
+
+    bool setNumberAt(size_t index, float value) override
+    {
+        auto input = inputAt(index);
+        if (input == nullptr || !input->input()->is<rive::StateMachineNumber>())
+        {
+            return false;
+        }
+        static_cast<rive::SMINumber*>(input)->value(value);
+        return true;
+    }
+
+    bool setBoolAt(size_t index, bool value) override
+    {
+        auto input = inputAt(index);
+        if (input == nullptr || !input->input()->is<rive::StateMachineBool>())
+        {
+            return false;
+        }
+        static_cast<rive::SMIBool*>(input)->value(value);
+        return true;
+    }
+
+    bool fireTriggerAt(size_t index) override
+    {
+        auto input = inputAt(index);
+        if (input == nullptr || !input->input()->is<rive::StateMachineTrigger>())
+        {
+            return false;
+        }
+        static_cast<rive::SMITrigger*>(input)->fire();
+        return true;
+    }
+
+    size_t artboardCount() const override { return artboardNames.size(); }
+
+    const char* artboardName(size_t index) const override
+    {
+        return index < artboardNames.size() ? artboardNames[index].c_str() : nullptr;
+    }
+
+    bool selectArtboard(size_t index) override
+    {
+        if (index >= artboardNames.size())
+        {
+            return false;
+        }
+        initArtboard(static_cast<int>(index));
+        return true;
+    }
+
+    size_t artboardIndex() const override { return m_ArtboardIndex; }
+
+    size_t artboardStateMachineCount(size_t artboardIndex) const override
+    {
+        auto artboard = m_File ? m_File->artboard(artboardIndex) : nullptr;
+        return artboard ? artboard->stateMachineCount() : 0;
+    }
+
+    const char* artboardStateMachineName(size_t artboardIndex, size_t index) const override
+    {
+        auto artboard = m_File ? m_File->artboard(artboardIndex) : nullptr;
+        auto stateMachine = artboard ? artboard->stateMachine(index) : nullptr;
+        return stateMachine ? stateMachine->name().c_str() : nullptr;
+    }
+
+    size_t artboardAnimationCount(size_t artboardIndex) const override
+    {
+        auto artboard = m_File ? m_File->artboard(artboardIndex) : nullptr;
+        return artboard ? artboard->animationCount() : 0;
+    }
+
+    const char* artboardAnimationName(size_t artboardIndex, size_t index) const override
+    {
+        auto artboard = m_File ? m_File->artboard(artboardIndex) : nullptr;
+        auto animation = artboard ? artboard->animation(index) : nullptr;
+        return animation ? animation->name().c_str() : nullptr;
+    }
+
+    size_t sceneCount() const override
     {
-        if (m_File)
+        return stateMachineNames.size() + animationNames.size();
+    }
+
+    const char* sceneName(size_t index) const override
+    {
+        if (index < stateMachineNames.size())
         {
-            artboardNames.clear();
-            auto abCnt = m_File->artboardCount();
+            return stateMachineNames[index].c_str();
+        }
+        index -= stateMachineNames.size();
+        return index < animationNames.size() ? animationNames[index].c_str() : nullptr;
+    }
 
-            for (int i = 0; i < abCnt; i++)
-            {
-                auto abName = m_File->artboardNameAt(i);
-                artboardNames.push_back(abName);
-            }
+    size_t sceneIndex() const override
+    {
+        if (m_StateMachineIndex >= 0)
+        {
+            return static_cast<size_t>(m_StateMachineIndex);
+        }
+        if (m_AnimationIndex >= 0)
+        {
+            return stateMachineNames.size() + static_cast<size_t>(m_AnimationIndex);
         }
+        return sceneCount();
     }
 
-    void loadNames(const rive::Artboard* ab)
+    bool selectScene(size_t index) override
     {
-        animationNames.clear();
-        stateMachineNames.clear();
-        if (ab)
+        if (index >= sceneCount())
         {
-            for (size_t i = 0; i < ab->animationCount(); ++i)
-            {
-                animationNames.push_back(ab->animationNameAt(i));
-            }
-            for (size_t i = 0; i < ab->stateMachineCount(); ++i)
+            return false;
+        }
+        size_t stateMachineCount = stateMachineNames.size();
+        initArtboard(m_ArtboardIndex);
+        if (index < stateMachineCount)
+        {
+            initStateMachine(static_cast<int>(index));
+        }
+        else
+        {
+            initAnimation(static_cast<int>(index - stateMachineCount));
+        }
+        return true;
+    }
+
+    void setFit(int fit) override
+    {
+        m_Fit = static_cast<rive::Fit>(fit);
+        m_InverseViewTransform = viewTransform(m_width, m_height).invertOrIdentity();
+    }
+
+    void setPixelSnap(bool snap) override
+    {
+        m_PixelSnap = snap;
+        m_InverseViewTransform = viewTransform(m_width, m_height).invertOrIdentity();
+    }
+
+    const char* stateMachineName() const override
+    {
+        auto stateMachine = stateMachineInstance();
+        return stateMachine ? stateMachine->stateMachine()->name().c_str() : nullptr;
+    }
+
+    size_t layerCount() const override
+    {
+        auto stateMachine = stateMachineInstance();
+        return stateMachine ? stateMachine->layerCount() : 0;
+    }
+
+    bool layerState(size_t layer, LayerStateInfo* info) const override
+    {
+        auto stateMachine = stateMachineInstance();
+        auto state = stateMachine ? stateMachine->layerState(layer) : nullptr;
+        if (state == nullptr)
+        {
+            return false;
+        }
+
+        auto machineLayer = stateMachine->stateMachine()->layer(layer);
+        info->stateCount = machineLayer->stateCount();
+        info->state = info->stateCount;
+        for (size_t i = 0; i < machineLayer->stateCount(); i++)
+        {
+            if (machineLayer->state(i) == state)
             {
-                stateMachineNames.push_back(ab->stateMachineNameAt(i));
+                info->state = i;
             }
         }
+        auto animation = stateMachine->layerAnimation(layer);
+        info->time = animation ? animation->time() : 0.0f;
+        return true;
     }
 
-    std::string m_Filename;
-    std::unique_ptr<rive::File> m_File;
+    bool restoreLayerState(size_t layer, size_t state, float time) override
+    {
+        if (m_AnimationIndex >= 0 || !m_CurrentScene)
+        {
+            return false;
+        }
 
-    std::unique_ptr<rive::ArtboardInstance> m_ArtboardInstance;
-    std::unique_ptr<rive::Scene> m_CurrentScene;
-    int m_ArtboardIndex = 0;
-    int m_AnimationIndex = 0;
-    int m_StateMachineIndex = -1;
+        auto stateMachine = static_cast<rive::StateMachineInstance*>(m_CurrentScene.get());
+        if (layer >= stateMachine->layerCount())
+        {
+            return false;
+        }
+        return stateMachine->restoreLayerState(
+            layer,
+            stateMachine->stateMachine()->layer(layer)->state(state),
+            time);
+    }
 
-    int m_width = 0, m_height = 0;
-    rive::Mat2D m_InverseViewTransform;
+    const rive::StateMachineInstance* stateMachineInstance() const
+    {
+        if (m_AnimationIndex >= 0 || !m_CurrentScene)
+        {
+            return nullptr;
+        }
+        return static_cast<const rive::StateMachineInstance*>(m_CurrentScene.get());
+    }
 
-    void initArtboard(int index)
+    size_t appliedAnimationCount() const override
     {
-        if (!m_File)
-            return;
-        loadArtboardNames();
-        m_ArtboardInstance = nullptr;
+        auto machine = stateMachineInstance();
+        return machine ? machine->appliedAnimations().size() : 0;
+    }
 
-        m_ArtboardIndex = (index == REQUEST_DEFAULT_SCENE) ? 0 : index;
-        m_ArtboardInstance = m_File->artboardAt(m_ArtboardIndex);
+    bool appliedAnimation(size_t index, AppliedAnimationInfo* info) const override
+    {
+        if (index >= appliedAnimationCount())
+        {
+            return false;
+        }
 
-        m_ArtboardInstance->advance(0.0f);
-        loadNames(m_ArtboardInstance.get());
+        auto& applied = stateMachineInstance()->appliedAnimations()[index];
+        info->name = applied.animation->name().c_str();
+        info->time = applied.time;
+        info->mix = applied.mix;
+        info->layer = applied.layer;
+        return true;
+    }
 
-        initStateMachine(REQUEST_DEFAULT_SCENE);
+    const rive::Event* firedEvent(size_t index) const
+    {
+        auto machine = stateMachineInstance();
+        return machine ? machine->firedEventAt(index) : nullptr;
     }
 
-    void initStateMachine(int index)
+    size_t firedEventCount() const override
     {
-        m_StateMachineIndex = -1;
-        m_AnimationIndex = -1;
-        m_CurrentScene = nullptr;
+        auto machine = stateMachineInstance();
+        return machine ? machine->firedEventCount() : 0;
+    }
 
-        m_ArtboardInstance->advance(0.0f);
+    const char* firedEventName(size_t index) const override
+    {
+        auto event = firedEvent(index);
+        return event ? event->name().c_str() : nullptr;
+    }
 
-        if (index < 0)
+    size_t firedEventPropertyCount(size_t index) const override
+    {
+        auto event = firedEvent(index);
+        if (event == nullptr)
         {
-            m_CurrentScene = m_ArtboardInstance->defaultStateMachine();
-            index = m_ArtboardInstance->defaultStateMachineIndex();
+            return 0;
         }
-        if (!m_CurrentScene)
+        return std::count_if(event->children().begin(),
+                             event->children().end(),
+                             [](const rive::Component* child) {
+                                 return child->is<rive::CustomProperty>();
+                             });
+    }
+
+    bool firedEventProperty(size_t index,
+                            size_t property,
+                            EventPropertyInfo* info) const override
+    {
+        auto event = firedEvent(index);
+        if (event == nullptr)
         {
-            if (index >= m_ArtboardInstance->stateMachineCount())
+            return false;
+        }
+
+        size_t seen = 0;
+        for (auto child : event->children())
+        {
+            if (!child->is<rive::CustomProperty>() || seen++ != property)
             {
-                index = 0;
+                continue;
             }
-            m_CurrentScene = m_ArtboardInstance->stateMachineAt(index);
+
+            info->name = child->name().c_str();
+            if (child->is<rive::CustomPropertyNumber>())
+            {
+                info->type = 0;
+                info->number = child->as<rive::CustomPropertyNumber>()->propertyValue();
+            }
+            else if (child->is<rive::CustomPropertyBoolean>())
+            {
+                info->type = 1;
+                info->boolean = child->as<rive::CustomPropertyBoolean>()->propertyValue();
+            }
+            else
+            {
+                info->type = 2;
+                info->string = child->as<rive::CustomPropertyString>()->propertyValue().c_str();
+            }
+            return true;
         }
-        if (!m_CurrentScene)
+        return false;
+    }
+
+    bool setNumber(const char name[], float value) override
+    {
+        auto number = m_CurrentScene ? m_CurrentScene->getNumber(name) : nullptr;
+        if (number == nullptr)
         {
-            index = -1;
-            m_CurrentScene = m_ArtboardInstance->animationAt(0);
-            m_AnimationIndex = 0;
+            return false;
         }
-        m_StateMachineIndex = index;
+        number->value(value);
+        return true;
+    }
 
-        if (m_CurrentScene)
+    rive::Span<const float> discreteValues(const char name[]) const override
+    {
+        auto values = m_DiscreteValues.find(name);
+        if (values == m_DiscreteValues.end())
         {
-            m_CurrentScene->inputCount();
+            return {};
         }
+        return values->second;
+    }
 
-        DumpCounters("After loading file");
+    size_t textRunCount() const override { return m_TextRuns.size(); }
+
+    const char* textRunPath(size_t index) const override
+    {
+        return m_TextRuns[index].path.c_str();
     }
 
-    void initAnimation(int index)
+    const char* textRunText(size_t index) const override
     {
-        m_StateMachineIndex = -1;
-        m_AnimationIndex = -1;
-        m_CurrentScene = nullptr;
+        return m_TextRuns[index].run->text().c_str();
+    }
 
-        m_ArtboardInstance->advance(0.0f);
+    void setTextRun(size_t index, const char text[]) override
+    {
+        m_TextRuns[index].run->text(text);
+    }
 
-        if (index >= 0 && index < m_ArtboardInstance->animationCount())
+    size_t setTextDirection(uint8_t direction) override
+    {
+        if (m_ArtboardInstance == nullptr || direction > 2)
         {
-            m_AnimationIndex = index;
-            m_CurrentScene = m_ArtboardInstance->animationAt(index);
-            m_CurrentScene->inputCount();
+            return 0;
+        }
+        auto texts = m_ArtboardInstance->find<rive::Text>();
+        for (auto text : texts)
+        {
+            text->direction(static_cast<rive::TextBaseDirection>(direction));
         }
+        return texts.size();
+    }
 
-        DumpCounters("After loading file");
+    size_t eventCount() const override { return m_EventNames.size(); }
+
+    const char* eventName(size_t index) const override { return m_EventNames[index].c_str(); }
+
+    size_t nestedArtboardCount() const override { return m_NestedArtboardPaths.size(); }
+
+    const char* nestedArtboardPath(size_t index) const override
+    {
+        return m_NestedArtboardPaths[index].c_str();
     }
 
-public:
-    SceneContent(const char filename[], std::unique_ptr<rive::File> file) :
-        m_Filename(filename), m_File(std::move(file))
+    size_t setPrimaryColor(uint32_t color) override
     {
-        initArtboard(REQUEST_DEFAULT_SCENE);
+        m_HasPrimaryColor = true;
+        m_PrimaryColor = color;
+        // Only the paints change: their paths are left alone.
+        for (auto& slot : m_PrimaryColorSlots)
+        {
+            slot.color->colorValue(color);
+        }
+        return m_PrimaryColorSlots.size();
     }
 
-    void handlePointerMove(float x, float y) override
+    size_t watchComponent(const char name[]) override
     {
-        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
-        if (m_CurrentScene)
+        showLive();
+        if (name == nullptr)
         {
-            m_CurrentScene->pointerMove(pointer);
+            m_WatchAll = true;
+        }
+        else if (std::find(m_WatchedNames.begin(), m_WatchedNames.end(), name) ==
+                 m_WatchedNames.end())
+        {
+            m_WatchedNames.push_back(name);
         }
+        loadWatched();
+        return m_Watched.size();
     }
 
-    void handlePointerDown(float x, float y) override
+    void clearWatches() override
     {
-        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
-        if (m_CurrentScene)
+        showLive();
+        m_WatchAll = false;
+        m_WatchedNames.clear();
+        loadWatched();
+    }
+
+    size_t watchedPropertyCount() const override { return m_Watched.size(); }
+
+    bool watchedProperty(size_t index, WatchedPropertyInfo* info) const override
+    {
+        if (index >= m_Watched.size())
         {
-            m_CurrentScene->pointerDown(pointer);
+            return false;
         }
+        const auto& property = m_Watched[index];
+        *info = {property.component, property.key, property.type};
+        return true;
     }
 
-    void handlePointerUp(float x, float y) override
+    void readWatched(uint32_t values[]) const override
     {
-        auto pointer = m_InverseViewTransform * rive::Vec2D(x, y);
-        if (m_CurrentScene)
+        for (size_t i = 0; i < m_Watched.size(); i++)
         {
-            m_CurrentScene->pointerUp(pointer);
+            values[i] = readValue(m_Watched[i]);
+        }
+    }
+
+    void showWatched(const uint32_t values[]) override
+    {
+        if (!m_ShowingWatched)
+        {
+            m_LiveValues.resize(m_Watched.size());
+            readWatched(m_LiveValues.data());
+            m_ShowingWatched = true;
+        }
+        for (size_t i = 0; i < m_Watched.size(); i++)
+        {
+            writeValue(m_Watched[i], values[i]);
+        }
+        m_ArtboardInstance->updateComponents();
+    }
+
+    void showLive() override
+    {
+        if (!m_ShowingWatched)
+        {
+            return;
+        }
+        m_ShowingWatched = false;
+        for (size_t i = 0; i < m_Watched.size(); i++)
+        {
+            writeValue(m_Watched[i], m_LiveValues[i]);
+        }
+        m_ArtboardInstance->updateComponents();
+    }
+
+    void clearPrimaryColor() override
+    {
+        m_HasPrimaryColor = false;
+        for (auto& slot : m_PrimaryColorSlots)
+        {
+            slot.color->colorValue(slot.authored);
         }
     }
 
@@ -182,33 +1783,113 @@ public:
     {
         m_width = width;
         m_height = height;
+        // Derived from the size alone rather than updated, so no error builds up over resizes.
+        m_InverseViewTransform = viewTransform(m_width, m_height).invertOrIdentity();
     }
 
-    void handleDraw(rive::Renderer* renderer, double elapsed) override
+    rive::Mat2D viewTransform(int width, int height) const
     {
-        renderer->save();
+        return rive::computeAlignment(m_Fit,
+                                      rive::Alignment::center,
+                                      rive::AABB(0, 0, width, height),
+                                      m_ArtboardInstance->bounds(),
+                                      m_PixelSnap);
+    }
+
+    void handleAdvance(double elapsed) override
+    {
+        if (handleAnimate(elapsed))
+        {
+            handleUpdate(elapsed);
+        }
+    }
 
-        auto viewTransform = rive::computeAlignment(rive::Fit::contain,
-                                                    rive::Alignment::center,
-                                                    rive::AABB(0, 0, m_width, m_height),
-                                                    m_ArtboardInstance->bounds());
-        renderer->transform(viewTransform);
+    // Scene::advanceAndApply, without the artboard's advance.
+    bool handleAnimate(double elapsed) override
+    {
         // Store the inverse view so we can later go from screen to world.
-        m_InverseViewTransform = viewTransform.invertOrIdentity();
+        m_InverseViewTransform = viewTransform(m_width, m_height).invertOrIdentity();
+
+        // Recorded values stay on display while paused.
+        if (m_ShowingWatched)
+        {
+            if (elapsed == 0.0)
+            {
+                return false;
+            }
+            showLive();
+        }
+
+        if (!m_CurrentScene)
+        {
+            return false;
+        }
+
+        if (m_AnimationIndex < 0)
+        {
+            static_cast<rive::StateMachineInstance*>(m_CurrentScene.get())->advance(elapsed);
+        }
+        else
+        {
+            auto animation = static_cast<rive::LinearAnimationInstance*>(m_CurrentScene.get());
+            animation->advance(elapsed);
+            animation->apply();
+        }
+        m_Advanced = true;
+        return true;
+    }
+
+    void handleUpdate(double elapsed) override
+    {
+        if (m_ArtboardInstance)
+        {
+            m_ArtboardInstance->advance(elapsed);
+        }
+    }
+
+    int redrawPolicy() const override
+    {
+        if (!m_CurrentScene)
+        {
+            return 0;
+        }
+        if (!m_Advanced)
+        {
+            return 2;
+        }
+        if (auto stateMachine = stateMachineInstance())
+        {
+            // Input changes and listeners mark the machine as needing an advance too.
+            return stateMachine->needsAdvance() ? 2 : 1;
+        }
+        auto animation = static_cast<const rive::LinearAnimationInstance*>(m_CurrentScene.get());
+        return animation->keepGoing() ? 2 : 1;
+    }
+
+    void draw(rive::Renderer* renderer, int width, int height) const override
+    {
+        renderer->save();
+        renderer->transform(viewTransform(width, height));
 
         if (m_CurrentScene)
         {
-            m_CurrentScene->advanceAndApply(elapsed);
             m_CurrentScene->draw(renderer);
         }
         else
         {
             m_ArtboardInstance->draw(renderer); // we're just a still-frame file/artboard
         }
+        drawTextBoxes(renderer);
 
         renderer->restore();
     }
 
+    void handleDraw(rive::Renderer* renderer, double elapsed) override
+    {
+        handleAdvance(elapsed);
+        draw(renderer, m_width, m_height);
+    }
+
 #ifndef RIVE_SKIP_IMGUI
     void handleImgui() override
     {
@@ -263,6 +1944,8 @@ public:
                                                        &resolver))
                     {
                         m_File = std::move(file);
+                        loadMissingAssets();
+                        LinkImageVariants(m_File.get());
                         initArtboard(REQUEST_DEFAULT_SCENE);
                     }
                 }
@@ -372,6 +2055,16 @@ public:
                 }
 
                 ImGui::Columns(1);
+
+                AppliedAnimationInfo applied;
+                for (size_t i = 0; appliedAnimation(i, &applied); i++)
+                {
+                    ImGui::Text("layer %zu: %s at %.3fs, mix %.3f",
+                                applied.layer,
+                                applied.name,
+                                applied.time,
+                                applied.mix);
+                }
             }
             ImGui::End();
         }
@@ -386,9 +2079,33 @@ public:
 std::unique_ptr<ViewerContent> ViewerContent::Scene(const char filename[])
 {
     auto bytes = LoadFile(filename);
-    if (auto file = rive::File::import(bytes, RiveFactory()))
+    return SceneFromBytes(filename, bytes);
+}
+
+std::unique_ptr<ViewerContent> ViewerContent::SceneFromBytes(const char name[],
+                                                             rive::Span<const uint8_t> bytes)
+{
+    // The default limits are generous enough for anything an editor exports; files exceeding
+    // them are reported by name.
+    rive::ImportResult result;
+    rive::LoadLimitReport report;
+    if (auto file = rive::File::import(bytes, RiveFactory(), rive::LoadLimits(), &result, &report))
+    {
+        // There's nothing to show, and the content assumes an artboard throughout.
+        if (file->artboardCount() == 0)
+        {
+            fprintf(stderr, "%s has no artboards\n", name);
+            return nullptr;
+        }
+        return rivestd::make_unique<SceneContent>(name, std::move(file));
+    }
+    if (result == rive::ImportResult::limitExceeded)
     {
-        return rivestd::make_unique<SceneContent>(filename, std::move(file));
+        fprintf(stderr,
+                "%s was rejected by the %s load limit (%llu)\n",
+                name,
+                rive::LoadLimits::name(report.limit),
+                (unsigned long long)report.observed);
     }
     return nullptr;
 }
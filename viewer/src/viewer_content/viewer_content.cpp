@@ -67,6 +67,8 @@ void ViewerContent::DumpCounters(const char label[])
 #include "viewer/viewer_host.hpp"
 
 rive::Factory* ViewerContent::RiveFactory() { return ViewerHost::Factory(); }
+
+void ViewerContent::LinkImageVariants(const rive::File*) {}
 #endif
 
 #include "rive/text/font_hb.hpp"